@@ -0,0 +1,134 @@
+//! `cargo bench` suite for the hot paths: converting one record, ranking
+//! it everywhere, scoring a cohort, the parquet round-trip and loading a
+//! directory of records. The inputs are generated deterministically, so
+//! runs are comparable across commits.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use suneung_calc::calculator::calc_matrix;
+use suneung_calc::cohort::Cohort;
+use suneung_calc::score::{
+    Grade, Percentile, ReadOptions, Record, Score, StandardScore, Subject, University,
+};
+
+const YEAR: usize = 2024;
+
+fn student(i: usize) -> Record {
+    let grade = |g: u8| Grade::try_from(g).unwrap();
+    let standard = |x: f64| StandardScore::new(x).unwrap();
+    let percentile = |x: f64| Percentile::new(x).unwrap();
+    let spread = (i % 40) as f64;
+    let mut record = Record::new(&format!("학생{:03}", i + 1));
+    record.record_typed(
+        Subject::Korean,
+        standard(100f64 + spread),
+        percentile(60f64 + spread),
+        grade(2),
+    );
+    record.record_typed(
+        Subject::Math,
+        standard(140f64 - spread),
+        percentile(98f64 - spread),
+        grade(2),
+    );
+    record.record_score(
+        Subject::English,
+        Score::grade_only(grade(1 + (i % 4) as u8)),
+    );
+    record.record_typed(
+        Subject::Chemistry,
+        standard(50f64 + spread / 2f64),
+        percentile(70f64),
+        grade(3),
+    );
+    record.record_typed(
+        Subject::EarthScience,
+        standard(70f64 - spread / 2f64),
+        percentile(80f64),
+        grade(3),
+    );
+    record
+}
+
+fn cohort(n: usize) -> Vec<Record> {
+    (0..n).map(student).collect()
+}
+
+fn calc(c: &mut Criterion) {
+    let record = student(0);
+    c.bench_function("calc_with_university", |b| {
+        b.iter(|| black_box(&record).calc_with_university(University::KONKUK, YEAR))
+    });
+    c.bench_function("calc_all", |b| {
+        b.iter(|| black_box(&record).calc_all(YEAR).unwrap())
+    });
+}
+
+fn matrix(c: &mut Criterion) {
+    let records = cohort(500);
+    let universities = University::supported(YEAR);
+    let columns: Vec<(University, u16)> = universities.iter().map(|&u| (u, YEAR as u16)).collect();
+
+    let mut group = c.benchmark_group("matrix_500");
+    group.bench_function("nested_calc_with_university", |b| {
+        b.iter(|| {
+            records
+                .iter()
+                .map(|r| {
+                    universities
+                        .iter()
+                        .map(|&u| r.try_calc_with_university(u, YEAR).ok())
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("calc_matrix", |b| {
+        b.iter(|| calc_matrix(black_box(&records), &columns))
+    });
+    let cohort = Cohort::new(records.clone());
+    group.bench_function("report_matrix", |b| {
+        b.iter(|| suneung_calc::report::matrix(black_box(cohort.records()), &universities, YEAR))
+    });
+    #[cfg(feature = "parallel")]
+    group.bench_function("calc_matrix_par", |b| {
+        b.iter(|| black_box(&cohort).calc_matrix_par(YEAR))
+    });
+    group.finish();
+}
+
+fn parquet(c: &mut Criterion) {
+    let record = student(0);
+    c.bench_function("to_dataframe", |b| {
+        b.iter(|| black_box(&record).to_dataframe())
+    });
+
+    let dir = std::env::temp_dir().join(format!("suneung-bench-{}", std::process::id()));
+    c.bench_function("parquet_round_trip", |b| {
+        b.iter(|| {
+            record.write_parquet_in(&dir).unwrap();
+            Record::read_parquet_in(&dir, record.name(), &ReadOptions::default()).unwrap()
+        })
+    });
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+fn load(c: &mut Criterion) {
+    let dir = std::env::temp_dir().join(format!("suneung-bench-load-{}", std::process::id()));
+    for record in cohort(300) {
+        record.write_parquet_in(&dir).unwrap();
+    }
+
+    let mut group = c.benchmark_group("load_300");
+    group.sample_size(10);
+    group.bench_function("load_all_in", |b| {
+        b.iter(|| Cohort::load_all_in(&dir).unwrap())
+    });
+    group.bench_function("load_all_subjects_english", |b| {
+        b.iter(|| Cohort::load_all_subjects(&dir, &[Subject::English]).unwrap())
+    });
+    group.finish();
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+criterion_group!(benches, calc, matrix, parquet, load);
+criterion_main!(benches);
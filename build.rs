@@ -0,0 +1,388 @@
+//! Generates the embedded weight data from `data_src/weights.csv` and
+//! `data_src/english_tables.csv` into `OUT_DIR`:
+//!
+//! - `university_weight.rs`: the `<UNIV>_<YEAR>_WEIGHT`, `_ENG`, `_SCI_REQ`,
+//!   `_SCI_AGG`, `_ENG_REQ` and, when a source is given, `_SOURCE`, `_SECTION` and
+//!   `_ENTERED` constants, included by `src/university_weight.rs`
+//! - `embedded_weights.rs`: the `weight_data!` list behind
+//!   `score::EMBEDDED_DATA`
+//!
+//! Both files follow the row order of `weights.csv`. Malformed rows fail
+//! the build with one line per problem, naming the file and line.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+const WEIGHTS: &str = "data_src/weights.csv";
+const ENGLISH_TABLES: &str = "data_src/english_tables.csv";
+// Most values per English table, 1등급 first. A shorter row covers only the
+// grades it lists, see `EnglishTable::from_legacy`
+const ENGLISH_LEN: usize = 9;
+// Variants of `formula::ScienceAggregation`, as `science_aggregation` names
+// them, with the number of 탐구 subjects each needs
+const AGGREGATIONS: [(&str, usize); 3] = [("Sum", 2), ("Average", 2), ("BestDoubled", 1)];
+
+struct Weight {
+    // Line in `WEIGHTS`
+    line: u64,
+    university: String,
+    year: u16,
+    weight: [usize; 4],
+    science_required: usize,
+    science_aggregation: String,
+    english_required: usize,
+    source: Option<String>,
+    // Page or section of `source`, and the date the row was entered
+    section: Option<String>,
+    entered: Option<String>,
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", WEIGHTS);
+    println!("cargo:rerun-if-changed={}", ENGLISH_TABLES);
+
+    let mut errors = vec![];
+    let weights = read_weights(&mut errors);
+    let tables = read_english_tables(&mut errors);
+    check(&weights, &tables, &mut errors);
+    if !errors.is_empty() {
+        eprintln!("invalid weight data:");
+        for error in &errors {
+            eprintln!("  {}", error);
+        }
+        std::process::exit(1);
+    }
+
+    let out = std::env::var("OUT_DIR").unwrap();
+    let out = Path::new(&out);
+    std::fs::write(
+        out.join("university_weight.rs"),
+        constants(&weights, &tables),
+    )
+    .unwrap();
+    std::fs::write(out.join("embedded_weights.rs"), embedded_list(&weights)).unwrap();
+}
+
+// Records of `path` with their 1-based line numbers, header excluded
+fn records(path: &str, errors: &mut Vec<String>) -> Vec<(u64, csv::StringRecord)> {
+    let mut rdr = match csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_path(path)
+    {
+        Ok(rdr) => rdr,
+        Err(e) => {
+            errors.push(format!("{}: {}", path, e));
+            return vec![];
+        }
+    };
+    let mut records = vec![];
+    for record in rdr.records() {
+        match record {
+            Ok(record) => {
+                let line = record.position().map_or(0, |p| p.line());
+                records.push((line, record));
+            }
+            Err(e) => errors.push(format!("{}: {}", path, e)),
+        }
+    }
+    records
+}
+
+// Field `i` of a record as a number, or an error naming `column`
+fn number<T: std::str::FromStr>(
+    record: &csv::StringRecord,
+    i: usize,
+    column: &str,
+) -> Result<T, String> {
+    let field = record.get(i).unwrap_or_default();
+    field
+        .parse()
+        .map_err(|_| format!("{} is not a whole number: {:?}", column, field))
+}
+
+fn key(record: &csv::StringRecord) -> Result<(String, u16), String> {
+    let university = record.get(0).unwrap_or_default();
+    let valid = university.starts_with(|c: char| c.is_ascii_uppercase())
+        && university
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+    if !valid {
+        return Err(format!(
+            "university must be a University variant such as SOGANG, got {:?}",
+            university
+        ));
+    }
+    Ok((university.to_string(), number(record, 1, "year")?))
+}
+
+fn read_weights(errors: &mut Vec<String>) -> Vec<Weight> {
+    let mut weights = vec![];
+    for (line, record) in records(WEIGHTS, errors) {
+        let parse = || -> Result<Weight, String> {
+            let (university, year) = key(&record)?;
+            if !(9..=12).contains(&record.len()) {
+                return Err(format!(
+                    "{} {}: expected 9 fields and an optional source, section and entered date, got {}",
+                    university,
+                    year,
+                    record.len()
+                ));
+            }
+            let mut weight = [0; 4];
+            for (i, column) in ["korean", "math", "english", "science"].iter().enumerate() {
+                weight[i] = number(&record, 2 + i, column)?;
+            }
+            let optional = |i: usize| record.get(i).filter(|s| !s.is_empty()).map(str::to_string);
+            let (source, section, entered) = (optional(9), optional(10), optional(11));
+            if source.is_none() && (section.is_some() || entered.is_some()) {
+                return Err(format!(
+                    "{} {}: section and entered date need a source",
+                    university, year
+                ));
+            }
+            if let Some(date) = &entered {
+                if !is_date(date) {
+                    return Err(format!(
+                        "entered must be a date such as 2023-11-20, got {:?}",
+                        date
+                    ));
+                }
+            }
+            Ok(Weight {
+                line,
+                university,
+                year,
+                weight,
+                science_required: number(&record, 6, "science_required")?,
+                science_aggregation: record.get(7).unwrap_or_default().to_string(),
+                english_required: number(&record, 8, "english_required")?,
+                source,
+                section,
+                entered,
+            })
+        };
+        match parse() {
+            Ok(weight) => weights.push(weight),
+            Err(e) => errors.push(format!("{}:{}: {}", WEIGHTS, line, e)),
+        }
+    }
+    weights
+}
+
+// `YYYY-MM-DD`, digits only; the calendar is not checked
+fn is_date(text: &str) -> bool {
+    let parts: Vec<&str> = text.split('-').collect();
+    parts.len() == 3
+        && [4, 2, 2]
+            .iter()
+            .zip(&parts)
+            .all(|(&len, part)| part.len() == len && part.bytes().all(|b| b.is_ascii_digit()))
+}
+
+fn read_english_tables(errors: &mut Vec<String>) -> BTreeMap<(String, u16), Vec<usize>> {
+    let mut tables = BTreeMap::new();
+    for (line, record) in records(ENGLISH_TABLES, errors) {
+        let parse = || -> Result<((String, u16), Vec<usize>), String> {
+            let key = key(&record)?;
+            if !(3..=2 + ENGLISH_LEN).contains(&record.len()) {
+                return Err(format!(
+                    "{} {}: expected 1 to {} table values, got {}",
+                    key.0,
+                    key.1,
+                    ENGLISH_LEN,
+                    record.len().saturating_sub(2)
+                ));
+            }
+            let mut table = vec![];
+            for i in 0..record.len() - 2 {
+                table.push(number(&record, 2 + i, &format!("v{}", i))?);
+            }
+            Ok((key, table))
+        };
+        match parse() {
+            Ok((key, table)) => {
+                if tables.insert(key.clone(), table).is_some() {
+                    errors.push(format!(
+                        "{}:{}: {} {} appears twice",
+                        ENGLISH_TABLES, line, key.0, key.1
+                    ));
+                }
+            }
+            Err(e) => errors.push(format!("{}:{}: {}", ENGLISH_TABLES, line, e)),
+        }
+    }
+    tables
+}
+
+fn check(
+    weights: &[Weight],
+    tables: &BTreeMap<(String, u16), Vec<usize>>,
+    errors: &mut Vec<String>,
+) {
+    let mut seen = BTreeMap::new();
+    for w in weights {
+        let key = (w.university.clone(), w.year);
+        let name = format!("{}:{}: {} {}", WEIGHTS, w.line, w.university, w.year);
+        if seen.insert(key.clone(), ()).is_some() {
+            errors.push(format!("{} appears twice", name));
+        }
+
+        // Shares of 100 or, as at 서강대, of 1000; up to a tenth may go to
+        // 한국사 or other subjects outside the four
+        let sum: usize = w.weight.iter().sum();
+        let scale = if sum > 100 { 1000 } else { 100 };
+        if sum > scale || sum * 10 < scale * 9 {
+            errors.push(format!(
+                "{}: weights {:?} sum to {}, expected 90-100 out of 100 (or 900-1000 out of 1000)",
+                name, w.weight, sum
+            ));
+        }
+        if !(1..=2).contains(&w.science_required) {
+            errors.push(format!(
+                "{}: science_required must be 1 or 2, got {}",
+                name, w.science_required
+            ));
+        }
+        match AGGREGATIONS
+            .iter()
+            .find(|(variant, _)| *variant == w.science_aggregation)
+        {
+            None => errors.push(format!(
+                "{}: science_aggregation must be one of Sum, Average and BestDoubled, got {:?}",
+                name, w.science_aggregation
+            )),
+            Some(&(_, subjects)) if subjects != w.science_required => errors.push(format!(
+                "{}: science_aggregation {} needs science_required {}, got {}",
+                name, w.science_aggregation, subjects, w.science_required
+            )),
+            Some(_) => (),
+        }
+
+        match tables.get(&key) {
+            None => errors.push(format!("{} has no row in {}", name, ENGLISH_TABLES)),
+            Some(table) => {
+                if table.windows(2).any(|pair| pair[0] < pair[1]) {
+                    errors.push(format!(
+                        "{}: English table {:?} rises for a worse grade (transposed?)",
+                        name, table
+                    ));
+                }
+                if !(1..=9).contains(&w.english_required) {
+                    errors.push(format!(
+                        "{}: english_required must be a grade from 1 to 9, got {}",
+                        name, w.english_required
+                    ));
+                }
+            }
+        }
+    }
+    for (university, year) in tables.keys() {
+        if !seen.contains_key(&(university.clone(), *year)) {
+            errors.push(format!(
+                "{}: {} {} has no row in {}",
+                ENGLISH_TABLES, university, year, WEIGHTS
+            ));
+        }
+    }
+}
+
+fn constants(weights: &[Weight], tables: &BTreeMap<(String, u16), Vec<usize>>) -> String {
+    let join = |values: &[usize]| {
+        values
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut out = String::new();
+    let mut year = None;
+    for w in weights {
+        if year != Some(w.year) {
+            if year.is_some() {
+                out.push('\n');
+            }
+            writeln!(
+                out,
+                "// ┌──────────────────────────────────────────────────────────┐"
+            )
+            .unwrap();
+            writeln!(out, "//  {}", w.year).unwrap();
+            writeln!(
+                out,
+                "// └──────────────────────────────────────────────────────────┘"
+            )
+            .unwrap();
+            year = Some(w.year);
+        } else {
+            out.push('\n');
+        }
+        let prefix = format!("{}_{}", w.university, w.year);
+        let table = &tables[&(w.university.clone(), w.year)];
+        writeln!(
+            out,
+            "pub const {}_WEIGHT: [usize; 4] = [{}];",
+            prefix,
+            join(&w.weight)
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "pub const {}_ENG: [usize; {}] = [{}];",
+            prefix,
+            table.len(),
+            join(table)
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "pub const {}_SCI_REQ: usize = {};",
+            prefix, w.science_required
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "pub const {}_SCI_AGG: ScienceAggregation = ScienceAggregation::{};",
+            prefix, w.science_aggregation
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "pub const {}_ENG_REQ: usize = {};",
+            prefix, w.english_required
+        )
+        .unwrap();
+        if let Some(source) = &w.source {
+            writeln!(out, "pub const {}_SOURCE: &str = {:?};", prefix, source).unwrap();
+            writeln!(
+                out,
+                "pub const {}_SECTION: Option<&str> = {:?};",
+                prefix, w.section
+            )
+            .unwrap();
+            writeln!(
+                out,
+                "pub const {}_ENTERED: Option<&str> = {:?};",
+                prefix, w.entered
+            )
+            .unwrap();
+        }
+    }
+    out
+}
+
+fn embedded_list(weights: &[Weight]) -> String {
+    let mut out = String::from("[\n");
+    for w in weights {
+        let sourced = if w.source.is_some() { ", sourced" } else { "" };
+        writeln!(
+            out,
+            "    weight_data!({}, {}{}),",
+            w.university, w.year, sourced
+        )
+        .unwrap();
+    }
+    out.push(']');
+    out
+}
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use suneung_calc::record_set::RecordSet;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = RecordSet::anonymization_mapping_from_str(&String::from_utf8_lossy(data));
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use suneung_calc::config::Config;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Config::from_toml_str(&String::from_utf8_lossy(data));
+});
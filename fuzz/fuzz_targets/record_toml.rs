@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use suneung_calc::score::Record;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(record) = Record::from_toml_str(&String::from_utf8_lossy(data)) {
+        assert!(record.validate().is_ok());
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use suneung_calc::input::normalize;
+use suneung_calc::score::Record;
+
+fuzz_target!(|data: &[u8]| {
+    let text = String::from_utf8_lossy(data);
+    let normalized = normalize(&text);
+    assert_eq!(normalize(&normalized), normalized);
+    if let Ok(record) = Record::parse_report_text("fuzz", &text) {
+        assert!(record.validate().is_ok());
+    }
+});
@@ -0,0 +1,101 @@
+#ifndef SUNEUNG_CALC_H
+#define SUNEUNG_CALC_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define SUNEUNG_OK 0
+
+#define SUNEUNG_ERR_INVALID_ARGUMENT -1
+
+#define SUNEUNG_ERR_PANIC -2
+
+#define SUNEUNG_ERR_IO 1
+
+#define SUNEUNG_ERR_PARQUET 2
+
+#define SUNEUNG_ERR_CHECKSUM 3
+
+#define SUNEUNG_ERR_WEIGHT_DATA 4
+
+#define SUNEUNG_ERR_UNSUPPORTED_YEAR 5
+
+#define SUNEUNG_ERR_MISSING_SUBJECT 6
+
+#define SUNEUNG_ERR_INVALID_SCORE 7
+
+#define SUNEUNG_ERR_REPORT_PARSE 8
+
+#define SUNEUNG_ERR_UNKNOWN_NAME 9
+
+#define SUNEUNG_ERR_PLOT 10
+
+#define SUNEUNG_ERR_CONFIG 11
+
+#define SUNEUNG_ERR_PDF 12
+
+#define SUNEUNG_ERR_INVALID_INPUT 13
+
+#define SUNEUNG_ERR_INSUFFICIENT_DATA 14
+
+#define SUNEUNG_ERR_FORMULA_PARSE 15
+
+#define SUNEUNG_ERR_STRICT 16
+
+/**
+ * Opaque handle to a `Record`
+ */
+typedef struct SuneungRecord SuneungRecord;
+
+/**
+ * New empty record, or NULL if `name` is NULL or not UTF-8.
+ *
+ * # Safety
+ * `name` must be NULL or a NUL-terminated string.
+ */
+struct SuneungRecord *suneung_record_new(const char *name);
+
+/**
+ * Record one subject. English only uses `grade`.
+ *
+ * # Safety
+ * `record` must be NULL or a pointer from `suneung_record_new`.
+ */
+int32_t suneung_record_set(struct SuneungRecord *record,
+                           uint32_t subject_code,
+                           double standard_score,
+                           double percentile,
+                           uint32_t grade);
+
+/**
+ * Converted score at a university, written to `out_score`.
+ *
+ * # Safety
+ * `record` must be NULL or a pointer from `suneung_record_new`, and
+ * `out_score` must be NULL or point to writable memory for an f64.
+ */
+int32_t suneung_calc(const struct SuneungRecord *record,
+                     uint32_t university_code,
+                     uint32_t year,
+                     double *out_score);
+
+/**
+ * Free a record. NULL is ignored.
+ *
+ * # Safety
+ * `record` must be NULL or a pointer from `suneung_record_new` that has not
+ * been freed yet.
+ */
+void suneung_record_free(struct SuneungRecord *record);
+
+/**
+ * Message of the last error on this thread, or NULL if there was none. The
+ * string stays valid until the next failing call on the same thread.
+ */
+const char *suneung_last_error_message(void);
+
+#endif  /* SUNEUNG_CALC_H */
@@ -0,0 +1,224 @@
+use crate::score::{Record, University};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// 정시 모집군. An applicant picks at most one 모집단위 per group.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum AdmissionGroup {
+    Ga,
+    Na,
+    Da,
+}
+
+impl AdmissionGroup {
+    pub fn all() -> &'static [AdmissionGroup] {
+        &[AdmissionGroup::Ga, AdmissionGroup::Na, AdmissionGroup::Da]
+    }
+
+    /// "가군", "나군", "다군"
+    pub fn label(&self) -> &'static str {
+        match self {
+            AdmissionGroup::Ga => "가군",
+            AdmissionGroup::Na => "나군",
+            AdmissionGroup::Da => "다군",
+        }
+    }
+}
+
+impl fmt::Display for AdmissionGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// Likelihood of admission from how a converted score compares to a
+/// university's cut line (e.g. last year's 70% cut)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Chance {
+    /// 안정: at least `Chance::MARGIN` percent above the cut
+    Safe,
+    /// 적정: at or just above the cut
+    Likely,
+    /// 소신: less than `Chance::MARGIN` percent below the cut
+    Reach,
+    /// 상향: further below
+    Unlikely,
+}
+
+impl Chance {
+    /// Distance from the cut, in percent of the cut, that separates 안정
+    /// from 적정 and 소신 from 상향
+    pub const MARGIN: f64 = 1f64;
+
+    pub fn all() -> &'static [Chance] {
+        &[
+            Chance::Safe,
+            Chance::Likely,
+            Chance::Reach,
+            Chance::Unlikely,
+        ]
+    }
+
+    pub fn classify(score: f64, cut: f64) -> Self {
+        let gap = (score - cut) / cut * 100f64;
+        if gap >= Self::MARGIN {
+            Chance::Safe
+        } else if gap >= 0f64 {
+            Chance::Likely
+        } else if gap > -Self::MARGIN {
+            Chance::Reach
+        } else {
+            Chance::Unlikely
+        }
+    }
+
+    /// "안정", "적정", "소신", "상향"
+    pub fn label(&self) -> &'static str {
+        match self {
+            Chance::Safe => "안정",
+            Chance::Likely => "적정",
+            Chance::Reach => "소신",
+            Chance::Unlikely => "상향",
+        }
+    }
+}
+
+impl fmt::Display for Chance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// Highlight of a converted score in terminal tables, from how it compares
+/// to a cut line
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ColorClass {
+    /// Green: at least `ColorThresholds::clear` percent above the cut
+    Clear,
+    /// Yellow: between `borderline` percent below and `clear` percent above
+    Borderline,
+    /// Red: further below
+    Reach,
+    /// No score or no cut to compare with
+    Plain,
+}
+
+/// Margins, in percent of the cut, between the `ColorClass`es. Both default
+/// to `Chance::MARGIN`.
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColorThresholds {
+    pub clear: f64,
+    pub borderline: f64,
+}
+
+impl Default for ColorThresholds {
+    fn default() -> Self {
+        Self {
+            clear: Chance::MARGIN,
+            borderline: Chance::MARGIN,
+        }
+    }
+}
+
+impl ColorClass {
+    /// ```
+    /// use suneung_calc::admission::{ColorClass, ColorThresholds};
+    ///
+    /// let t = ColorThresholds::default();
+    /// assert_eq!(ColorClass::classify(Some(390.0), Some(385.0), &t), ColorClass::Clear);
+    /// assert_eq!(ColorClass::classify(Some(384.0), Some(385.0), &t), ColorClass::Borderline);
+    /// assert_eq!(ColorClass::classify(Some(370.0), Some(385.0), &t), ColorClass::Reach);
+    /// assert_eq!(ColorClass::classify(Some(370.0), None, &t), ColorClass::Plain);
+    /// ```
+    pub fn classify(score: Option<f64>, cut: Option<f64>, thresholds: &ColorThresholds) -> Self {
+        let (Some(score), Some(cut)) = (score, cut) else {
+            return ColorClass::Plain;
+        };
+        let gap = (score - cut) / cut * 100f64;
+        if gap >= thresholds.clear {
+            ColorClass::Clear
+        } else if gap >= -thresholds.borderline {
+            ColorClass::Borderline
+        } else {
+            ColorClass::Reach
+        }
+    }
+}
+
+impl University {
+    /// 모집군 of the 자연계열 모집단위 the weight data describes for `year`.
+    /// Groups change between years and some universities split their
+    /// 모집단위 over several groups, so only confirmed entries are listed;
+    /// everything else is `None`.
+    pub fn admission_group(&self, year: usize) -> Option<AdmissionGroup> {
+        match (self, year) {
+            (University::KYUNGHEE, 2022..=2025) => Some(AdmissionGroup::Na),
+            (University::SOGANG, 2024..=2025) => Some(AdmissionGroup::Ga),
+            _ => None,
+        }
+    }
+}
+
+/// 모집군 of each university for one year
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdmissionGroupData {
+    groups: HashMap<University, AdmissionGroup>,
+}
+
+impl AdmissionGroupData {
+    /// No university has a group
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `University::admission_group` of every university for `year`
+    pub fn embedded(year: usize) -> Self {
+        let groups = University::iter()
+            .filter_map(|u| Some((u, u.admission_group(year)?)))
+            .collect();
+        Self { groups }
+    }
+
+    pub fn with(mut self, university: University, group: AdmissionGroup) -> Self {
+        self.groups.insert(university, group);
+        self
+    }
+
+    pub fn get(&self, university: University) -> Option<AdmissionGroup> {
+        self.groups.get(&university).copied()
+    }
+}
+
+/// Ranked scores split by 모집군
+pub type GroupedScores = Vec<(Option<AdmissionGroup>, Vec<(University, f64)>)>;
+
+/// Split ranked scores (e.g. from `Record::calc_all`) by 모집군, keeping
+/// their order within each group. Groups come in 가/나/다 order, followed by
+/// the universities whose group is unknown for `year`; empty groups are left
+/// out.
+pub fn group_by_admission(scores: &[(University, f64)], year: usize) -> GroupedScores {
+    AdmissionGroup::all()
+        .iter()
+        .map(|&g| Some(g))
+        .chain(std::iter::once(None))
+        .filter_map(|group| {
+            let members: Vec<(University, f64)> = scores
+                .iter()
+                .filter(|(univ, _)| univ.admission_group(year) == group)
+                .copied()
+                .collect();
+            (!members.is_empty()).then_some((group, members))
+        })
+        .collect()
+}
+
+impl Record {
+    /// `calc_all` split by 모집군 (see `group_by_admission`)
+    pub fn calc_all_grouped(&self, year: usize) -> crate::error::Result<GroupedScores> {
+        Ok(group_by_admission(&self.calc_all(year)?, year))
+    }
+}
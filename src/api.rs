@@ -0,0 +1,326 @@
+//! Request and response models of the JSON API and the calculator page
+//! (see the `server` module), kept free of any HTTP dependency so other frontends can share them.
+
+use crate::admission::AdmissionGroup;
+use crate::error::SuneungError;
+use crate::score::{
+    CalcBreakdown, Grade, Locale, Percentile, Record, Score, StandardScore, Subject, University,
+    YEARS,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CalcRequest {
+    pub record: Record,
+    pub university: University,
+    pub year: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CalcResponse {
+    pub name: String,
+    pub university: University,
+    pub university_name: String,
+    pub year: usize,
+    pub score: f64,
+    pub breakdown: CalcBreakdown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CalcAllRequest {
+    pub record: Record,
+    pub year: usize,
+}
+
+/// One university of `server::calc_all`: a `ComparisonRow` with its 모집군
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RankedScore {
+    /// 1-based rank among the eligible universities
+    pub rank: Option<usize>,
+    pub university: University,
+    pub university_name: String,
+    /// 모집군, when known for the requested year
+    #[serde(default)]
+    pub admission_group: Option<AdmissionGroup>,
+    pub score: Option<f64>,
+    #[serde(flatten)]
+    pub eligibility: Eligibility,
+}
+
+/// Whether a record could be scored at a university
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Eligibility {
+    Eligible,
+    /// The weight data exists but the record cannot be scored, e.g. a
+    /// required science is missing
+    Ineligible {
+        /// `SuneungError::code` of the failure
+        #[serde(default)]
+        code: String,
+        /// The subject a `missing_subject` failure names
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        subject: Option<Subject>,
+        reason: String,
+    },
+    /// No weight data for the requested year
+    NoData,
+}
+
+impl Eligibility {
+    /// `NoData` for `UnsupportedYear`, `Ineligible` for any other failure
+    pub fn of_error(e: &SuneungError) -> Self {
+        match e {
+            SuneungError::UnsupportedYear { .. } => Eligibility::NoData,
+            _ => Eligibility::Ineligible {
+                code: e.code().to_string(),
+                subject: match e {
+                    SuneungError::MissingSubject(subject) => Some(*subject),
+                    _ => None,
+                },
+                reason: e.to_string(),
+            },
+        }
+    }
+
+    /// Why the record is `Ineligible`, in `locale`. Korean names the
+    /// common failures by their code and falls back to the English message.
+    ///
+    /// ```
+    /// use suneung_calc::api::Eligibility;
+    /// use suneung_calc::error::SuneungError;
+    /// use suneung_calc::score::{Locale, Subject};
+    ///
+    /// let missing = Eligibility::of_error(&SuneungError::MissingSubject(Subject::EarthScience));
+    /// assert_eq!(missing.reason(Locale::Korean).unwrap(), "지구과학Ⅰ 성적 없음");
+    /// assert_eq!(
+    ///     missing.reason(Locale::English).unwrap(),
+    ///     "Record has no EarthScience score"
+    /// );
+    /// assert_eq!(Eligibility::Eligible.reason(Locale::Korean), None);
+    /// ```
+    pub fn reason(&self, locale: Locale) -> Option<String> {
+        let Eligibility::Ineligible {
+            code,
+            subject,
+            reason,
+        } = self
+        else {
+            return None;
+        };
+        if locale == Locale::English {
+            return Some(reason.clone());
+        }
+        Some(match (code.as_str(), subject) {
+            ("missing_subject", Some(subject)) => {
+                format!("{} 성적 없음", subject.localized_name(locale))
+            }
+            ("insufficient_data", _) => "환산에 필요한 자료 없음".to_string(),
+            ("invalid_score" | "score_out_of_range", _) => "성적 값 오류".to_string(),
+            ("weight_data", _) => "반영 비율 자료 오류".to_string(),
+            ("strict", _) => "엄격 모드에서 거부".to_string(),
+            _ => reason.clone(),
+        })
+    }
+}
+
+/// One university of a single-student comparison (see `compare`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ComparisonRow {
+    /// 1-based rank among the eligible universities
+    pub rank: Option<usize>,
+    pub university: University,
+    pub university_name: String,
+    pub score: Option<f64>,
+    /// `score` as a percentage of the best score in the comparison
+    pub percent_of_max: Option<f64>,
+    #[serde(flatten)]
+    pub eligibility: Eligibility,
+}
+
+/// Score `record` at `universities` for `year`. Universities lacking data
+/// or failing to calculate keep their row instead of aborting the run. With
+/// `sort` the rows are ranked best first, otherwise they keep the order of
+/// `universities`.
+pub fn compare(
+    record: &Record,
+    universities: &[University],
+    year: usize,
+    sort: bool,
+) -> Vec<ComparisonRow> {
+    let mut rows: Vec<ComparisonRow> = universities
+        .iter()
+        .map(|&university| {
+            let (score, eligibility) = match record.try_calc_with_university(university, year) {
+                Ok(score) => (Some(score), Eligibility::Eligible),
+                Err(e) => (None, Eligibility::of_error(&e)),
+            };
+            ComparisonRow {
+                rank: None,
+                university,
+                university_name: university.name().to_string(),
+                score,
+                percent_of_max: None,
+                eligibility,
+            }
+        })
+        .collect();
+
+    let best = rows
+        .iter()
+        .filter_map(|r| r.score)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let mut ranked: Vec<usize> = (0..rows.len())
+        .filter(|&i| rows[i].score.is_some())
+        .collect();
+    ranked.sort_by(|&a, &b| rows[b].score.unwrap().total_cmp(&rows[a].score.unwrap()));
+    for (rank, i) in ranked.into_iter().enumerate() {
+        rows[i].rank = Some(rank + 1);
+        rows[i].percent_of_max = rows[i].score.map(|s| s / best * 100f64);
+    }
+
+    if sort {
+        // Unranked rows keep their relative order after the ranked ones
+        rows.sort_by_key(|r| r.rank.unwrap_or(usize::MAX));
+    }
+    rows
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct UniversityInfo {
+    pub id: University,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct YearQuery {
+    pub year: usize,
+}
+
+/// Fields of the calculator page of the `server` module as they were
+/// typed, so that the page can show them again with what is wrong with
+/// them. A subject left blank is not recorded; English takes a grade only.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CalculatorForm {
+    pub name: String,
+    pub year: String,
+    pub korean_standard: String,
+    pub korean_percentile: String,
+    pub korean_grade: String,
+    pub math_standard: String,
+    pub math_percentile: String,
+    pub math_grade: String,
+    pub english_grade: String,
+    pub chemistry_standard: String,
+    pub chemistry_percentile: String,
+    pub chemistry_grade: String,
+    pub earth_science_standard: String,
+    pub earth_science_percentile: String,
+    pub earth_science_grade: String,
+}
+
+/// Name of the record of a calculator form with the name left blank
+pub const CALCULATOR_NAME: &str = "학생";
+
+impl CalculatorForm {
+    /// The 표준점수, 백분위 and 등급 fields of `subject`; English has only
+    /// the last
+    pub fn fields(&self, subject: Subject) -> [&str; 3] {
+        match subject {
+            Subject::Korean => [
+                &self.korean_standard,
+                &self.korean_percentile,
+                &self.korean_grade,
+            ],
+            Subject::Math => [&self.math_standard, &self.math_percentile, &self.math_grade],
+            Subject::English => ["", "", &self.english_grade],
+            Subject::Chemistry => [
+                &self.chemistry_standard,
+                &self.chemistry_percentile,
+                &self.chemistry_grade,
+            ],
+            Subject::EarthScience => [
+                &self.earth_science_standard,
+                &self.earth_science_percentile,
+                &self.earth_science_grade,
+            ],
+        }
+    }
+
+    /// Names of the `fields` of `subject` in the form
+    pub fn field_names(subject: Subject) -> [&'static str; 3] {
+        match subject {
+            Subject::Korean => ["korean_standard", "korean_percentile", "korean_grade"],
+            Subject::Math => ["math_standard", "math_percentile", "math_grade"],
+            Subject::English => ["", "", "english_grade"],
+            Subject::Chemistry => [
+                "chemistry_standard",
+                "chemistry_percentile",
+                "chemistry_grade",
+            ],
+            Subject::EarthScience => [
+                "earth_science_standard",
+                "earth_science_percentile",
+                "earth_science_grade",
+            ],
+        }
+    }
+
+    /// The record and year the form describes, checked as
+    /// `Record::validate` checks stored records: `InvalidScore` for a
+    /// subject filled in only in part, with something that is not a number
+    /// or out of range; `InvalidInput` for a year without weight data.
+    pub fn record(&self) -> Result<(Record, usize), SuneungError> {
+        let year = self.year.trim();
+        let year = year
+            .parse::<usize>()
+            .ok()
+            .filter(|y| YEARS.contains(y))
+            .ok_or_else(|| {
+                SuneungError::InvalidInput(format!("no weight data for year {:?}", year))
+            })?;
+        let name = match self.name.trim() {
+            "" => CALCULATOR_NAME,
+            name => name,
+        };
+        let mut record = Record::new(name);
+        for &subject in Subject::all() {
+            let [standard, percentile, grade] = self.fields(subject).map(str::trim);
+            let blank = match subject {
+                Subject::English => grade.is_empty(),
+                _ => [standard, percentile, grade].iter().all(|f| f.is_empty()),
+            };
+            if blank {
+                continue;
+            }
+            // The messages of `Record::validate`, with the subject
+            let invalid = |e: SuneungError| match e {
+                SuneungError::ScoreOutOfRange(reason) => {
+                    SuneungError::InvalidScore { subject, reason }
+                }
+                e => e,
+            };
+            let grade = grade.parse::<Grade>().map_err(invalid)?;
+            if subject == Subject::English {
+                record.record_score(subject, Score::grade_only(grade));
+                continue;
+            }
+            record.record_typed(
+                subject,
+                standard.parse::<StandardScore>().map_err(invalid)?,
+                percentile.parse::<Percentile>().map_err(invalid)?,
+                grade,
+            );
+        }
+        record.validate()?;
+        Ok((record, year))
+    }
+}
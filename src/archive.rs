@@ -0,0 +1,227 @@
+use crate::atomic::write_all_atomic;
+use crate::checksum::sha256_hex;
+use crate::exam::ExamMeta;
+use crate::export::embedded_weights;
+use crate::input::{check_name, MAX_INPUT_BYTES};
+use crate::score::list_records;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+
+const MANIFEST: &str = "manifest.json";
+const WEIGHTS: &str = "weights.json";
+const RECORDS_DIR: &str = "records";
+
+/// Layout of the archive, raised whenever the manifest or the file layout
+/// changes
+pub const ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+/// Largest record file `import_archive` reads, in bytes (64 MiB)
+pub const MAX_ENTRY_BYTES: u64 = 64 << 20;
+/// Largest sum of the record files `import_archive` reads, in bytes (1 GiB)
+pub const MAX_ARCHIVE_BYTES: u64 = 1 << 30;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    /// `ARCHIVE_FORMAT_VERSION` of the writer
+    pub format_version: u32,
+    pub crate_version: String,
+    pub students: Vec<String>,
+    /// Exam of each student's record, in the order of `students`
+    pub exams: Vec<ArchiveExam>,
+    pub files: Vec<ArchiveEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchiveExam {
+    pub student: String,
+    /// `None` when the record has no exam meta or cannot be read
+    pub exam: Option<ExamMeta>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    /// Path inside the archive, relative to its `records/` directory
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// Bundle every student directory under `root` into the zip file `out`.
+///
+/// Besides the record files, the archive carries `manifest.json` (format and
+/// crate version, students and their exams, per-file checksums) and
+/// `weights.json` with the embedded weight tables the scores were computed
+/// with.
+pub fn export_archive(root: &Path, out: &Path) -> Result<ArchiveManifest, Box<dyn Error>> {
+    let mut students = vec![];
+    for entry in std::fs::read_dir(root)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                students.push(name.to_string());
+            }
+        }
+    }
+    students.sort();
+
+    let mut zip = zip::ZipWriter::new(File::create(out)?);
+    let options = SimpleFileOptions::default();
+    let mut files = vec![];
+
+    for student in students.iter() {
+        check_name(student)?;
+        let mut paths = vec![];
+        for entry in std::fs::read_dir(root.join(student))? {
+            let path = entry?.path();
+            // Skip leftovers of interrupted atomic writes
+            if path.is_file() && path.extension().is_none_or(|e| e != "tmp" && e != "bak") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        for path in paths {
+            let bytes = std::fs::read(&path)?;
+            let file_name = path.file_name().unwrap().to_string_lossy();
+            let rel = format!("{}/{}", student, file_name);
+            zip.start_file(format!("{}/{}", RECORDS_DIR, rel), options)?;
+            zip.write_all(&bytes)?;
+            files.push(ArchiveEntry {
+                path: rel,
+                size: bytes.len() as u64,
+                sha256: sha256_hex(&bytes),
+            });
+        }
+    }
+
+    let mut exams: Vec<ArchiveExam> = list_records(root)?
+        .into_iter()
+        .map(|stored| ArchiveExam {
+            student: stored.name,
+            exam: stored.exam,
+        })
+        .collect();
+    exams.retain(|e| students.contains(&e.student));
+
+    let manifest = ArchiveManifest {
+        format_version: ARCHIVE_FORMAT_VERSION,
+        crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        students,
+        exams,
+        files,
+    };
+    zip.start_file(MANIFEST, options)?;
+    zip.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+
+    zip.start_file(WEIGHTS, options)?;
+    zip.write_all(serde_json::to_string_pretty(&embedded_weights())?.as_bytes())?;
+
+    zip.finish()?;
+    Ok(manifest)
+}
+
+/// Restore the record files of an archive written by `export_archive` into
+/// `dest_root`, verifying every file against its manifest size and checksum
+/// first. Files over `MAX_ENTRY_BYTES`, or together over
+/// `MAX_ARCHIVE_BYTES`, are refused before anything is read. The files are
+/// then written together by `write_all_atomic`: when one cannot be
+/// written, none of the files under `dest_root` is replaced.
+pub fn import_archive(path: &Path, dest_root: &Path) -> Result<ArchiveManifest, Box<dyn Error>> {
+    let mut zip = zip::ZipArchive::new(File::open(path)?)?;
+
+    let manifest: ArchiveManifest = {
+        let bytes = read_limited(&mut zip.by_name(MANIFEST)?, MAX_INPUT_BYTES as u64)
+            .map_err(|e| format!("{}: {}", MANIFEST, e))?;
+        serde_json::from_slice(&bytes)?
+    };
+    if manifest.format_version > ARCHIVE_FORMAT_VERSION {
+        return Err(format!(
+            "unsupported archive format version: {}",
+            manifest.format_version
+        )
+        .into());
+    }
+    if let Some(entry) = manifest.files.iter().find(|e| e.size > MAX_ENTRY_BYTES) {
+        return Err(format!(
+            "{} is {} bytes, more than the limit of {}",
+            entry.path, entry.size, MAX_ENTRY_BYTES
+        )
+        .into());
+    }
+    let total = manifest.files.iter().map(|e| e.size).sum::<u64>();
+    if total > MAX_ARCHIVE_BYTES {
+        return Err(format!(
+            "records are {} bytes, more than the limit of {}",
+            total, MAX_ARCHIVE_BYTES
+        )
+        .into());
+    }
+
+    for student in &manifest.students {
+        check_name(student).map_err(|e| format!("unsafe student name in archive: {}", e))?;
+    }
+
+    // Verify everything before touching dest_root so a corrupted archive
+    // does not leave a half-restored data directory.
+    let mut contents = vec![];
+    for entry in manifest.files.iter() {
+        let rel = safe_relative(&entry.path)
+            .ok_or_else(|| format!("unsafe path in archive: {}", entry.path))?;
+        let mut file = zip.by_name(&format!("{}/{}", RECORDS_DIR, entry.path))?;
+        let bytes =
+            read_limited(&mut file, entry.size).map_err(|e| format!("{}: {}", entry.path, e))?;
+        if bytes.len() as u64 != entry.size {
+            return Err(format!(
+                "{} is {} bytes, the manifest says {}",
+                entry.path,
+                bytes.len(),
+                entry.size
+            )
+            .into());
+        }
+        let found = sha256_hex(&bytes);
+        if found != entry.sha256 {
+            return Err(format!(
+                "checksum mismatch for {}: expected {}, found {}",
+                entry.path, entry.sha256, found
+            )
+            .into());
+        }
+        contents.push((rel, bytes));
+    }
+
+    let files: Vec<(PathBuf, Vec<u8>)> = contents
+        .into_iter()
+        .map(|(rel, bytes)| (dest_root.join(rel), bytes))
+        .collect();
+    write_all_atomic(&files)?;
+
+    Ok(manifest)
+}
+
+// At most `limit` bytes of `reader`; an error when there are more
+fn read_limited(reader: &mut impl Read, limit: u64) -> std::io::Result<Vec<u8>> {
+    let mut bytes = vec![];
+    reader.take(limit + 1).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > limit {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("larger than the limit of {} bytes", limit),
+        ));
+    }
+    Ok(bytes)
+}
+
+// `student/file` as written by `export_archive`, both parts passing
+// `check_name`
+fn safe_relative(path: &str) -> Option<PathBuf> {
+    let (student, file) = path.split_once('/')?;
+    if check_name(student).is_err() || check_name(file).is_err() {
+        return None;
+    }
+    Some(Path::new(student).join(file))
+}
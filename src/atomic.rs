@@ -0,0 +1,245 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+/// Write `target` through a sibling `<target>.tmp` file.
+///
+/// `write` receives the temporary path. The temporary file is renamed over
+/// `target` only when `write` succeeds, so an interrupted write never leaves a
+/// truncated `target` behind. On failure the temporary file is removed.
+pub(crate) fn write_atomic<F>(target: &Path, write: F) -> Result<(), Box<dyn Error>>
+where
+    F: FnOnce(&Path) -> Result<(), Box<dyn Error>>,
+{
+    let tmp = tmp_path(target);
+
+    if let Err(e) = write(&tmp) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e);
+    }
+
+    if let Err(e) = replace(&tmp, target) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e.into());
+    }
+
+    Ok(())
+}
+
+/// Write several files so that either all of them are replaced or none is.
+///
+/// Every file is first written to its `<target>.tmp` sibling, so a failed
+/// write leaves every target as it was and removes the temporaries. Only
+/// once all of them are on disk is each existing target moved to
+/// `<target>.bak` and its temporary renamed over it. When a rename fails,
+/// the targets already replaced are put back from their backups and the
+/// new ones removed; the backups are deleted once every file is in place.
+/// A crash during the renames can still leave some targets replaced, with
+/// the originals in the `.bak` files.
+#[cfg(feature = "archive")]
+pub(crate) fn write_all_atomic(files: &[(PathBuf, Vec<u8>)]) -> Result<(), Box<dyn Error>> {
+    let targets: Vec<&Path> = files.iter().map(|(target, _)| target.as_path()).collect();
+    write_all_atomic_with(&targets, |i, tmp| {
+        std::fs::write(tmp, &files[i].1)?;
+        Ok(())
+    })
+}
+
+/// `write_all_atomic` with each file written by `write`, which receives the
+/// index of its target and the temporary path.
+#[cfg(feature = "parquet")]
+pub(crate) fn write_all_atomic_with<F>(
+    targets: &[&Path],
+    mut write: F,
+) -> Result<(), Box<dyn Error>>
+where
+    F: FnMut(usize, &Path) -> Result<(), Box<dyn Error>>,
+{
+    let mut staged = vec![];
+    for (i, target) in targets.iter().enumerate() {
+        let tmp = tmp_path(target);
+        let written = target
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .map_err(Box::<dyn Error>::from)
+            .and_then(|_| write(i, &tmp));
+        staged.push((tmp, *target));
+        if let Err(e) = written {
+            for (tmp, _) in &staged {
+                let _ = std::fs::remove_file(tmp);
+            }
+            return Err(e);
+        }
+    }
+
+    // Targets renamed so far, with the backup of what they replaced
+    let mut replaced: Vec<(&Path, Option<PathBuf>)> = vec![];
+    for (i, (tmp, target)) in staged.iter().enumerate() {
+        let result = backup(target).and_then(|bak| {
+            replaced.push((target, bak));
+            replace(tmp, target)
+        });
+        if let Err(e) = result {
+            for (target, bak) in replaced.iter().rev() {
+                let _ = match bak {
+                    Some(bak) => replace(bak, target),
+                    None => std::fs::remove_file(target),
+                };
+            }
+            for (tmp, _) in &staged[i..] {
+                let _ = std::fs::remove_file(tmp);
+            }
+            return Err(e.into());
+        }
+    }
+    for bak in replaced.into_iter().filter_map(|(_, bak)| bak) {
+        let _ = std::fs::remove_file(bak);
+    }
+    Ok(())
+}
+
+// Move an existing `target` aside to `<target>.bak`
+#[cfg(feature = "parquet")]
+fn backup(target: &Path) -> std::io::Result<Option<PathBuf>> {
+    if !target.exists() {
+        return Ok(None);
+    }
+    let mut name = target.as_os_str().to_os_string();
+    name.push(".bak");
+    let bak = PathBuf::from(name);
+    replace(target, &bak)?;
+    Ok(Some(bak))
+}
+
+fn tmp_path(target: &Path) -> PathBuf {
+    let mut name = target.as_os_str().to_os_string();
+    name.push(".tmp");
+    PathBuf::from(name)
+}
+
+// `std::fs::rename` already replaces an existing target on every platform we
+// build for (`MoveFileExW` with `MOVEFILE_REPLACE_EXISTING` on Windows), so no
+// remove-then-rename fallback is needed.
+fn replace(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::rename(from, to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_write_keeps_the_original() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("record.parquet");
+        std::fs::write(&target, "original").unwrap();
+
+        // The temporary file is written in part, then the writer fails
+        let result = write_atomic(&target, |tmp| {
+            std::fs::write(tmp, "trunc")?;
+            Err("killed after the temp write".into())
+        });
+
+        assert!(result.is_err());
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "original");
+        assert!(!tmp_path(&target).exists());
+    }
+
+    #[test]
+    fn successful_write_replaces_the_original() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("record.parquet");
+        std::fs::write(&target, "original").unwrap();
+
+        write_atomic(&target, |tmp| {
+            std::fs::write(tmp, "updated")?;
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "updated");
+        assert!(!tmp_path(&target).exists());
+    }
+
+    #[test]
+    fn first_write_creates_the_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("new.csv");
+
+        write_atomic(&target, |tmp| {
+            std::fs::write(tmp, "a,b\n")?;
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "a,b\n");
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn failed_batch_replaces_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("a").join("record.parquet");
+        std::fs::create_dir_all(first.parent().unwrap()).unwrap();
+        std::fs::write(&first, "original").unwrap();
+        // A file where the second target needs a directory
+        std::fs::write(dir.path().join("b"), "not a directory").unwrap();
+        let second = dir.path().join("b").join("record.parquet");
+
+        let files = vec![
+            (first.clone(), b"updated".to_vec()),
+            (second.clone(), b"new".to_vec()),
+        ];
+        assert!(write_all_atomic(&files).is_err());
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "original");
+        assert!(!tmp_path(&first).exists());
+        assert!(!second.exists());
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn failed_rename_restores_the_replaced_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("a.parquet");
+        std::fs::write(&first, "original").unwrap();
+        // The second target can't be moved aside: its backup would replace
+        // a directory that is not empty
+        let second = dir.path().join("b.parquet");
+        std::fs::write(&second, "original").unwrap();
+        let blocker = dir.path().join("b.parquet.bak");
+        std::fs::create_dir_all(blocker.join("inside")).unwrap();
+
+        let files = vec![
+            (first.clone(), b"updated".to_vec()),
+            (second.clone(), b"updated".to_vec()),
+        ];
+        assert!(write_all_atomic(&files).is_err());
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "original");
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), "original");
+        assert!(!dir.path().join("a.parquet.bak").exists());
+        assert!(!tmp_path(&first).exists());
+        assert!(!tmp_path(&second).exists());
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn batch_replaces_every_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let files: Vec<(PathBuf, Vec<u8>)> = ["a", "b"]
+            .iter()
+            .map(|name| {
+                (
+                    dir.path().join(name).join("record.parquet"),
+                    name.as_bytes().to_vec(),
+                )
+            })
+            .collect();
+        write_all_atomic(&files).unwrap();
+        // Again, over the files the first batch wrote
+        write_all_atomic(&files).unwrap();
+        for (path, bytes) in &files {
+            assert_eq!(&std::fs::read(path).unwrap(), bytes);
+            assert!(!tmp_path(path).exists());
+            assert!(!path.with_extension("parquet.bak").exists());
+        }
+    }
+}
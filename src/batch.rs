@@ -0,0 +1,118 @@
+//! Converted scores of every stored student at once, the core of
+//! `suneung batch`.
+
+use crate::calculator::score_columns;
+use crate::error::Result;
+use crate::exam::ExamMeta;
+use crate::score::{list_records, ReadOptions, Record, University};
+use serde::Serialize;
+use std::path::Path;
+
+/// Scores of one student, aligned with `Batch::universities`; `None` where
+/// the record cannot be scored
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BatchRow {
+    pub name: String,
+    pub exam: Option<ExamMeta>,
+    pub scores: Vec<Option<f64>>,
+}
+
+/// A student left out of the batch, with the reason
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BatchFailure {
+    pub name: String,
+    pub error: String,
+}
+
+/// Students × universities matrix for one year
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Batch {
+    pub year: usize,
+    /// Every university with weight data for `year`
+    pub universities: Vec<University>,
+    /// Sorted by name
+    pub rows: Vec<BatchRow>,
+    /// Records that could not be read or scored anywhere
+    pub failures: Vec<BatchFailure>,
+}
+
+impl Batch {
+    /// Score every student stored under `dir` with `calc_matrix`, which
+    /// loads each weight once. A record that cannot be read, or scored at
+    /// no university, becomes a failure instead of stopping the run.
+    /// `progress` is called before reading each student with the 1-based
+    /// position, the total and the name.
+    pub fn run_in<P, F>(dir: P, year: usize, mut progress: F) -> Result<Self>
+    where
+        P: AsRef<Path>,
+        F: FnMut(usize, usize, &str),
+    {
+        let dir = dir.as_ref();
+        let universities = University::supported(year);
+        let students = list_records(dir)?;
+        // Keep the position of each student so that failures stay in the
+        // order of `students` whether reading or scoring failed
+        let mut positions = vec![];
+        let mut records = vec![];
+        let mut failures = vec![];
+
+        for (i, stored) in students.iter().enumerate() {
+            progress(i + 1, students.len(), &stored.name);
+            match Record::read_parquet_in(dir, &stored.name, &ReadOptions::default()) {
+                Ok(record) => {
+                    positions.push(i);
+                    records.push(record);
+                }
+                Err(e) => failures.push((i, e.to_string())),
+            }
+        }
+
+        let scores = score_columns(&records, universities.iter().map(|&u| (u, year)));
+        let mut rows = vec![];
+        for (k, (&i, record)) in positions.iter().zip(&records).enumerate() {
+            let results: Vec<&Result<f64>> = (0..universities.len())
+                .map(|j| &scores[j * records.len() + k])
+                .collect();
+            if results.iter().all(|r| r.is_err()) {
+                if let Some(Err(e)) = results.first() {
+                    failures.push((i, e.to_string()));
+                    continue;
+                }
+            }
+            rows.push(BatchRow {
+                name: students[i].name.clone(),
+                exam: record.exam().cloned(),
+                scores: results
+                    .into_iter()
+                    .map(|r| r.as_ref().ok().copied())
+                    .collect(),
+            });
+        }
+        failures.sort_by_key(|&(i, _)| i);
+        Ok(Self {
+            year,
+            universities,
+            rows,
+            failures: failures
+                .into_iter()
+                .map(|(i, error)| BatchFailure {
+                    name: students[i].name.clone(),
+                    error,
+                })
+                .collect(),
+        })
+    }
+
+    /// Mean score at each university over the students scored there
+    pub fn means(&self) -> Vec<Option<f64>> {
+        (0..self.universities.len())
+            .map(|j| {
+                let scores: Vec<f64> = self.rows.iter().filter_map(|r| r.scores[j]).collect();
+                (!scores.is_empty()).then(|| scores.iter().sum::<f64>() / scores.len() as f64)
+            })
+            .collect()
+    }
+}
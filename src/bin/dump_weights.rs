@@ -0,0 +1,6 @@
+//! Print every embedded weight as one JSON document, see
+//! `export::dump_weights_json`. `cargo run --bin dump-weights > weights.json`
+
+fn main() {
+    print!("{}", suneung_calc::export::dump_weights_json());
+}
@@ -0,0 +1,201 @@
+use crate::resolve_year;
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use suneung_calc::batch::Batch;
+use suneung_calc::config::Config;
+use suneung_calc::score::Locale;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Csv,
+    Json,
+    /// Needs the `xlsx` feature and `--out`
+    Xlsx,
+}
+
+#[derive(Debug, Args)]
+pub struct BatchArgs {
+    /// 학년도 of the weight data; `default_year` from the config by default
+    #[arg(long)]
+    year: Option<usize>,
+
+    /// Output file; stdout when omitted
+    #[arg(long, value_name = "FILE")]
+    out: Option<PathBuf>,
+
+    /// Taken from the extension of `--out` when omitted, else csv;
+    /// `--json` means json
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+}
+
+/// Label of the cohort mean row, in Korean and English
+const MEAN_LABEL: (&str, &str) = ("평균", "Mean");
+
+/// Header of the name column, in Korean and English
+const NAME_LABEL: (&str, &str) = ("이름", "Name");
+
+const XLSX_MISSING: &str = "suneung was built without the xlsx feature";
+
+pub fn run(
+    args: BatchArgs,
+    data_dir: &Path,
+    config: &Config,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    let year = resolve_year(args.year, config)?;
+    let format = if json {
+        Some(Format::Json)
+    } else {
+        args.format
+    };
+    let format = format.unwrap_or_else(|| {
+        let ext = args.out.as_ref().and_then(|p| p.extension()?.to_str());
+        match ext {
+            Some("json") => Format::Json,
+            Some("xlsx") => Format::Xlsx,
+            _ => Format::Csv,
+        }
+    });
+    if format == Format::Xlsx {
+        if !cfg!(feature = "xlsx") {
+            return Err(XLSX_MISSING.into());
+        }
+        if args.out.is_none() {
+            return Err("--format xlsx needs --out".into());
+        }
+    }
+    let batch = Batch::run_in(data_dir, year, |i, n, name| {
+        eprintln!("[{}/{}] {}", i, n, name)
+    })?;
+    let locale = config.locale.unwrap_or_default();
+
+    match format {
+        Format::Csv => {
+            let bytes = to_csv(&batch, locale)?;
+            match &args.out {
+                Some(path) => std::fs::write(path, bytes)?,
+                None => print!("{}", String::from_utf8(bytes)?),
+            }
+        }
+        Format::Json => {
+            let json = serde_json::to_string_pretty(&BatchJson {
+                batch: &batch,
+                means: batch.means(),
+            })?;
+            match &args.out {
+                Some(path) => std::fs::write(path, json + "\n")?,
+                None => println!("{}", json),
+            }
+        }
+        Format::Xlsx => {
+            // `--out` was checked above
+            if let Some(path) = &args.out {
+                write_xlsx(&batch, locale, path)?;
+            }
+        }
+    }
+    if let Some(path) = &args.out {
+        eprintln!("wrote {}", path.display());
+    }
+
+    eprintln!(
+        "{} scored, {} failed",
+        batch.rows.len(),
+        batch.failures.len()
+    );
+    for failure in &batch.failures {
+        eprintln!("  {}: {}", failure.name, failure.error);
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct BatchJson<'a> {
+    #[serde(flatten)]
+    batch: &'a Batch,
+    /// Cohort mean at each university, aligned with `universities`
+    means: Vec<Option<f64>>,
+}
+
+// Header, one line per student and the mean row, as cells
+fn cells(batch: &Batch, locale: Locale) -> Vec<Vec<Cell>> {
+    let pick = |(korean, english): (&str, &str)| match locale {
+        Locale::Korean => korean.to_string(),
+        Locale::English => english.to_string(),
+    };
+    let mut header = vec![Cell::Text(pick(NAME_LABEL))];
+    header.extend(
+        batch
+            .universities
+            .iter()
+            .map(|u| Cell::Text(u.localized_name(locale).to_string())),
+    );
+    let mut lines = vec![header];
+    for row in &batch.rows {
+        let mut line = vec![Cell::Text(row.name.clone())];
+        line.extend(row.scores.iter().map(|&s| Cell::Number(s)));
+        lines.push(line);
+    }
+    let mut means = vec![Cell::Text(pick(MEAN_LABEL))];
+    means.extend(batch.means().into_iter().map(Cell::Number));
+    lines.push(means);
+    lines
+}
+
+enum Cell {
+    Text(String),
+    /// Blank where a student cannot be scored
+    Number(Option<f64>),
+}
+
+fn to_csv(batch: &Batch, locale: Locale) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    for line in cells(batch, locale) {
+        wtr.write_record(line.iter().map(|cell| match cell {
+            Cell::Text(s) => s.clone(),
+            Cell::Number(Some(x)) => format!("{:.2}", x),
+            Cell::Number(None) => String::new(),
+        }))?;
+    }
+    Ok(wtr.into_inner()?)
+}
+
+#[cfg(feature = "xlsx")]
+fn write_xlsx(batch: &Batch, locale: Locale, path: &Path) -> Result<(), Box<dyn Error>> {
+    use rust_xlsxwriter::{Format as CellFormat, Workbook};
+
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    sheet.set_name(match locale {
+        Locale::Korean => format!("{}학년도", batch.year),
+        Locale::English => format!("{} admission", batch.year),
+    })?;
+    let bold = CellFormat::new().set_bold();
+    let number = CellFormat::new().set_num_format("0.00");
+    let lines = cells(batch, locale);
+    let last = lines.len() - 1;
+    for (i, line) in lines.iter().enumerate() {
+        for (j, cell) in line.iter().enumerate() {
+            let (row, col) = (i as u32, j as u16);
+            match cell {
+                Cell::Text(s) if i == 0 || i == last => {
+                    sheet.write_string_with_format(row, col, s, &bold)?
+                }
+                Cell::Text(s) => sheet.write_string(row, col, s)?,
+                Cell::Number(Some(x)) => sheet.write_number_with_format(row, col, *x, &number)?,
+                Cell::Number(None) => sheet,
+            };
+        }
+    }
+    sheet.set_freeze_panes(1, 1)?;
+    workbook.save(path)?;
+    Ok(())
+}
+
+#[cfg(not(feature = "xlsx"))]
+fn write_xlsx(_: &Batch, _: Locale, _: &Path) -> Result<(), Box<dyn Error>> {
+    Err(XLSX_MISSING.into())
+}
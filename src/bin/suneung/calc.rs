@@ -0,0 +1,88 @@
+use crate::completions::{record_names, UniversityParser};
+use crate::{load_record, resolve_year};
+use clap::Args;
+use clap_complete::ArgValueCandidates;
+use std::error::Error;
+use std::path::Path;
+use suneung_calc::api::CalcResponse;
+use suneung_calc::config::Config;
+use suneung_calc::history::History;
+use suneung_calc::render::render_breakdown;
+use suneung_calc::score::University;
+
+#[derive(Debug, Args)]
+pub struct CalcArgs {
+    /// Student name
+    #[arg(long, add = ArgValueCandidates::new(record_names))]
+    name: String,
+
+    /// University id, name or alias (e.g. KYUNGHEE, 경희대, 경희)
+    #[arg(long, value_parser = UniversityParser, hide_possible_values = true)]
+    university: University,
+
+    /// 학년도 of the weight data; `default_year` from the config by default
+    #[arg(long)]
+    year: Option<usize>,
+
+    /// Also print the contribution of each subject
+    #[arg(long)]
+    breakdown: bool,
+
+    /// Convert the standard scores to the scale of `--year` through their
+    /// percentiles before calculating (see `History::eval_all`)
+    #[arg(long)]
+    scaled: bool,
+}
+
+pub fn run(
+    args: CalcArgs,
+    data_dir: &Path,
+    config: &Config,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    let year = resolve_year(args.year, config)?;
+    let supported = args.university.supported_years();
+    if !supported.contains(&year) {
+        let years: Vec<String> = supported.iter().map(|y| y.to_string()).collect();
+        return Err(format!(
+            "{} has no weight data for {} (supported years: {})",
+            args.university,
+            year,
+            years.join(", ")
+        )
+        .into());
+    }
+
+    let mut record = load_record(data_dir, &args.name)?;
+    if args.scaled {
+        record = History::load(year)?.eval_all(&record);
+    }
+    let breakdown = record.calc_breakdown(args.university, year)?;
+    let university_name = args
+        .university
+        .localized_name(config.locale.unwrap_or_default());
+
+    if json {
+        let response = CalcResponse {
+            name: record.name().to_string(),
+            university: args.university,
+            university_name: university_name.to_string(),
+            year,
+            score: breakdown.total,
+            breakdown,
+        };
+        println!("{}", serde_json::to_string_pretty(&response)?);
+    } else {
+        println!(
+            "{} {} {}: {:.2}",
+            record.name(),
+            university_name,
+            year,
+            breakdown.total
+        );
+        if args.breakdown {
+            println!("{}", render_breakdown(&breakdown));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use crate::completions::{record_names, UniversityParser};
+use crate::report::read_cuts;
+use crate::{load_record, resolve_year};
+use clap::{Args, ValueEnum};
+use clap_complete::ArgValueCandidates;
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use suneung_calc::api::{compare, ComparisonRow, Eligibility};
+use suneung_calc::config::Config;
+use suneung_calc::render::render_ranking;
+use suneung_calc::score::University;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Output {
+    Table,
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal and NO_COLOR is unset
+    Auto,
+    /// Needs the `color` feature
+    Always,
+    Never,
+}
+
+#[derive(Debug, Args)]
+pub struct CompareArgs {
+    /// Student name
+    #[arg(long, add = ArgValueCandidates::new(record_names))]
+    name: String,
+
+    /// 학년도 of the weight data; `default_year` from the config by default
+    #[arg(long)]
+    year: Option<usize>,
+
+    /// Comma-separated universities (id, name or alias); `universities`
+    /// from the config, or all, by default
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_parser = UniversityParser,
+        hide_possible_values = true
+    )]
+    universities: Vec<University>,
+
+    /// Rank best first instead of keeping the listed order
+    #[arg(long)]
+    sort: bool,
+
+    #[arg(long, value_enum, default_value_t = Output::Table)]
+    output: Output,
+
+    /// TOML file of cut lines per university, e.g. `KONKUK = 385.0`;
+    /// colors the table by the margins in the `[color]` config table
+    #[arg(long, value_name = "FILE")]
+    cuts: Option<PathBuf>,
+
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+}
+
+const COLOR_MISSING: &str = "suneung was built without the color feature";
+
+pub fn run(
+    args: CompareArgs,
+    data_dir: &Path,
+    config: &Config,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    let year = resolve_year(args.year, config)?;
+    let record = load_record(data_dir, &args.name)?;
+    let universities = if !args.universities.is_empty() {
+        args.universities
+    } else if let Some(universities) = &config.universities {
+        universities.clone()
+    } else {
+        University::all().to_vec()
+    };
+    let mut rows = compare(&record, &universities, year, args.sort);
+    let locale = config.locale.unwrap_or_default();
+    for row in &mut rows {
+        row.university_name = row.university.localized_name(locale).to_string();
+    }
+
+    let output = if json { Output::Json } else { args.output };
+    let color = match args.color {
+        ColorChoice::Always if !cfg!(feature = "color") => return Err(COLOR_MISSING.into()),
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            cfg!(feature = "color")
+                && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+                && std::io::stdout().is_terminal()
+        }
+    };
+    match output {
+        Output::Table => match &args.cuts {
+            Some(path) if color => println!(
+                "{}",
+                colored_ranking(&rows, year, &read_cuts(path)?, config)
+            ),
+            _ => println!("{}", render_ranking(&rows, year)),
+        },
+        Output::Json => println!("{}", serde_json::to_string_pretty(&rows)?),
+        Output::Csv => {
+            let mut wtr = csv::Writer::from_writer(std::io::stdout());
+            wtr.write_record([
+                "rank",
+                "university",
+                "university_name",
+                "score",
+                "percent_of_max",
+                "status",
+                "reason",
+            ])?;
+            for row in &rows {
+                let (status, reason) = match &row.eligibility {
+                    Eligibility::Eligible => ("eligible", ""),
+                    Eligibility::Ineligible { reason, .. } => ("ineligible", reason.as_str()),
+                    Eligibility::NoData => ("no_data", ""),
+                };
+                wtr.write_record([
+                    row.rank.map(|r| r.to_string()).unwrap_or_default(),
+                    format!("{:?}", row.university),
+                    row.university_name.clone(),
+                    row.score.map(|s| s.to_string()).unwrap_or_default(),
+                    row.percent_of_max
+                        .map(|p| p.to_string())
+                        .unwrap_or_default(),
+                    status.to_string(),
+                    reason.to_string(),
+                ])?;
+            }
+            wtr.flush()?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "color")]
+fn colored_ranking(
+    rows: &[ComparisonRow],
+    year: usize,
+    cuts: &HashMap<University, f64>,
+    config: &Config,
+) -> String {
+    let thresholds = config.color.unwrap_or_default();
+    suneung_calc::render::render_ranking_colored(rows, year, cuts, &thresholds)
+}
+
+// Unreachable: `color` is false without the feature
+#[cfg(not(feature = "color"))]
+fn colored_ranking(
+    rows: &[ComparisonRow],
+    year: usize,
+    _: &HashMap<University, f64>,
+    _: &Config,
+) -> String {
+    render_ranking(rows, year)
+}
@@ -0,0 +1,120 @@
+//! `suneung completions`, plus the value parsers and candidate lists that
+//! feed both the generated scripts and `COMPLETE=<shell> suneung` requests.
+
+use crate::Cli;
+use clap::builder::{PossibleValue, TypedValueParser};
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory};
+use clap_complete::env::Shells;
+use clap_complete::{CompletionCandidate, Shell};
+use std::error::Error;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+use suneung_calc::config::Config;
+use suneung_calc::score::{list_records, University, DATA_DIR};
+
+/// Environment variable `CompleteEnv` answers completion requests on
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+#[derive(Debug, Args)]
+pub struct CompletionsArgs {
+    /// Shell to generate the script for
+    shell: Shell,
+
+    /// Print a script that asks `suneung` for candidates at completion time
+    /// instead of a fixed one, which also completes `--name` from the
+    /// stored records
+    #[arg(long)]
+    dynamic: bool,
+}
+
+pub fn run(args: CompletionsArgs) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Cli::command();
+    let name = cmd.get_name().to_string();
+    let mut out = std::io::stdout();
+    if args.dynamic {
+        let shells = Shells::builtins();
+        let shell = shells
+            .completer(&args.shell.to_string())
+            .ok_or_else(|| format!("no dynamic completions for {}", args.shell))?;
+        // The script calls this binary back, so point it at the one running
+        let bin = std::env::current_exe()
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_else(|_| name.clone());
+        shell.write_registration(COMPLETE_VAR, &name, &name, &bin, &mut out)?;
+    } else {
+        clap_complete::generate(args.shell, &mut cmd, name, &mut out);
+    }
+    Ok(())
+}
+
+/// Parses `University` with its `FromStr` impl, listing ids, names and
+/// aliases as candidates without restricting input to them.
+#[derive(Debug, Copy, Clone)]
+pub struct UniversityParser;
+
+impl TypedValueParser for UniversityParser {
+    type Value = University;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<University, clap::Error> {
+        let invalid = |reason: String| {
+            let arg = arg.map_or_else(|| "...".to_string(), |a| a.to_string());
+            clap::Error::raw(
+                ErrorKind::ValueValidation,
+                format!("invalid value for '{}': {}\n", arg, reason),
+            )
+            .with_cmd(cmd)
+        };
+        let value = value
+            .to_str()
+            .ok_or_else(|| invalid("not valid UTF-8".to_string()))?;
+        value.parse().map_err(|e| invalid(format!("{}", e)))
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        let values = University::iter().flat_map(|u| {
+            // Display names like `경희대(서울)` would need quoting at the
+            // shell; an alias always covers them
+            let name = Some(u.name()).filter(|n| !n.contains('('));
+            std::iter::once(format!("{:?}", u))
+                .chain(name.map(str::to_string))
+                .chain(u.aliases().iter().map(|a| a.to_string()))
+                .map(PossibleValue::new)
+        });
+        Some(Box::new(values))
+    }
+}
+
+/// Names of the stored records, for `--name`. The data root is taken from
+/// `--data-dir` on the command line being completed, then from the default
+/// config file.
+pub fn record_names() -> Vec<CompletionCandidate> {
+    let args: Vec<String> = std::env::args().collect();
+    let data_dir = args
+        .iter()
+        .enumerate()
+        .find_map(|(i, arg)| match arg.strip_prefix("--data-dir") {
+            Some("") => args.get(i + 1).cloned(),
+            Some(rest) => rest.strip_prefix('=').map(str::to_string),
+            None => None,
+        })
+        .map(PathBuf::from)
+        .or_else(|| Config::load_default().ok()?.data_dir)
+        .unwrap_or_else(|| PathBuf::from(DATA_DIR));
+    list_records(data_dir)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|stored| {
+            let candidate = CompletionCandidate::new(stored.name);
+            match stored.exam {
+                Some(exam) => candidate.help(Some(exam.to_string().into())),
+                None => candidate,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,134 @@
+use clap::{Args, Subcommand};
+use comfy_table::Table;
+use serde::Serialize;
+use std::error::Error;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+use suneung_calc::config::Config;
+use suneung_calc::score::{list_records, University};
+
+#[derive(Debug, Args)]
+pub struct ListArgs {
+    #[command(subcommand)]
+    what: What,
+}
+
+#[derive(Debug, Subcommand)]
+enum What {
+    /// Supported universities with their available years
+    Universities {
+        /// Only universities with data for this 학년도
+        #[arg(long)]
+        year: Option<usize>,
+    },
+    /// Students stored under the data root
+    Records,
+}
+
+#[derive(Debug, Serialize)]
+struct UniversityEntry {
+    id: University,
+    name: &'static str,
+    years: Vec<usize>,
+}
+
+#[derive(Debug, Serialize)]
+struct RecordEntry {
+    name: String,
+    /// e.g. `2025 9월 모의평가`
+    exam: Option<String>,
+    /// Seconds since the Unix epoch
+    modified: Option<u64>,
+}
+
+pub fn run(
+    args: ListArgs,
+    data_dir: &Path,
+    config: &Config,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    match args.what {
+        What::Universities { year } => {
+            let universities = match year {
+                Some(year) => University::supported(year),
+                None => University::all().to_vec(),
+            };
+            let entries: Vec<UniversityEntry> = universities
+                .into_iter()
+                .map(|u| UniversityEntry {
+                    id: u,
+                    name: u.localized_name(config.locale.unwrap_or_default()),
+                    years: u.supported_years(),
+                })
+                .collect();
+            if json {
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+                return Ok(());
+            }
+            let mut table = Table::new();
+            table.set_header(vec!["대학", "ID", "연도"]);
+            for e in entries {
+                let years: Vec<String> = e.years.iter().map(|y| y.to_string()).collect();
+                table.add_row(vec![
+                    e.name.to_string(),
+                    format!("{:?}", e.id),
+                    years.join(", "),
+                ]);
+            }
+            println!("{}", table);
+        }
+        What::Records => {
+            let entries: Vec<RecordEntry> = list_records(data_dir)?
+                .into_iter()
+                .map(|r| RecordEntry {
+                    name: r.name,
+                    exam: r.exam.map(|e| e.to_string()),
+                    modified: r
+                        .modified
+                        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                        .map(|d| d.as_secs()),
+                })
+                .collect();
+            if json {
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+                return Ok(());
+            }
+            let mut table = Table::new();
+            table.set_header(vec!["이름", "시험", "수정 시각 (UTC)"]);
+            for e in entries {
+                table.add_row(vec![
+                    e.name,
+                    e.exam.unwrap_or_else(|| "-".to_string()),
+                    e.modified.map_or("-".to_string(), format_utc),
+                ]);
+            }
+            println!("{}", table);
+        }
+    }
+    Ok(())
+}
+
+// `2024-06-20 13:05:00`, from days-since-epoch to the civil calendar
+// (Howard Hinnant's algorithm), so no date crate is needed
+fn format_utc(secs: u64) -> String {
+    let days = (secs / 86400) as i64;
+    let rem = secs % 86400;
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
@@ -0,0 +1,241 @@
+//! `suneung`: command line front end of the calculator.
+//!
+//! Records live under the data root (`data` by default, see `--data-dir`),
+//! one directory per student, exactly as written by `Record::write_parquet`.
+//! Defaults for the data root, year, universities and locale can be set in
+//! a config file (see `suneung_calc::config`); flags always win over it.
+//!
+//! With the global `--json` flag every command prints one JSON document on
+//! stdout instead of tables and status lines. The shapes are stable:
+//!
+//! | command | stdout |
+//! |---------|--------|
+//! | `record` | `{name, path, record}` |
+//! | `calc` | `{name, university, university_name, year, score, breakdown}` |
+//! | `compare` | `[{university, university_name, score, ...}]` |
+//! | `list universities` / `list records` | arrays of objects |
+//! | `report` | `{path, markdown}`, `{path, html}` or `{path, latex}`, `path` null without `--out` |
+//! | `plot` | `{path}` |
+//! | `radar` | `{axes, series: [{name, values}]}` |
+//! | `batch` | `{year, universities, rows, failures, means}` |
+//!
+//! Failures print `{"error": {"code", "message"}}` on stderr and exit
+//! non-zero; `code` is `SuneungError::code`, `usage` for bad arguments and
+//! `io` or `cli` for anything else. `completions`, `serve`, `tui` and `watch`
+//! have no JSON form and refuse the flag.
+
+mod batch;
+mod calc;
+mod compare;
+mod completions;
+mod list;
+mod plot;
+mod radar;
+mod record;
+mod report;
+#[cfg(feature = "server")]
+mod serve;
+#[cfg(feature = "tui")]
+mod tui;
+#[cfg(feature = "watch")]
+mod watch;
+
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::CompleteEnv;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use suneung_calc::config::Config;
+use suneung_calc::error::SuneungError;
+use suneung_calc::score::{list_records, Locale, ReadOptions, Record, DATA_DIR};
+
+#[derive(Debug, Parser)]
+#[command(name = "suneung", version, about = "수능 성적 대학별 환산 계산기")]
+struct Cli {
+    /// Directory holding one sub-directory per student
+    #[arg(long, global = true, value_name = "PATH")]
+    data_dir: Option<PathBuf>,
+
+    /// Config file to use instead of ~/.config/suneung/config.toml
+    #[arg(long, global = true, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Language of university names and report labels (ko or en)
+    #[arg(long, global = true)]
+    locale: Option<Locale>,
+
+    /// Print JSON instead of tables, and errors as a JSON envelope
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Enter or update a student's scores
+    Record(record::RecordArgs),
+    /// Converted score of a stored record at one university
+    Calc(calc::CalcArgs),
+    /// Rank a stored record across universities
+    Compare(compare::CompareArgs),
+    /// List supported universities or stored records
+    List(list::ListArgs),
+    /// Write a Markdown report for a student or the whole cohort
+    Report(report::ReportArgs),
+    /// Chart a student's converted scores or standard-score trend
+    Plot(plot::PlotArgs),
+    /// Radar-chart values of one or more records as JSON or CSV
+    Radar(radar::RadarArgs),
+    /// Score every stored student and write the matrix as CSV, JSON or xlsx
+    Batch(batch::BatchArgs),
+    /// Print a shell completion script
+    Completions(completions::CompletionsArgs),
+    /// Serve the calculator page and the JSON API over HTTP
+    #[cfg(feature = "server")]
+    Serve(serve::ServeArgs),
+    /// Browse students and their rankings in a terminal dashboard
+    #[cfg(feature = "tui")]
+    Tui(tui::TuiArgs),
+    /// Keep the cohort comparison on screen, updating it as records change
+    #[cfg(feature = "watch")]
+    Watch(watch::WatchArgs),
+}
+
+fn main() -> ExitCode {
+    CompleteEnv::with_factory(Cli::command)
+        .var(completions::COMPLETE_VAR)
+        .complete();
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        // Help and version are not errors, so only usage errors are wrapped
+        Err(e) if e.use_stderr() && std::env::args_os().any(|arg| arg == "--json") => {
+            // First line of clap's message, without the `error: ` prefix
+            let rendered = e.render().to_string();
+            let line = rendered.lines().next().unwrap_or_default();
+            let message = line.strip_prefix("error: ").unwrap_or(line);
+            eprintln!("{}", error_envelope("usage", message));
+            return ExitCode::from(2);
+        }
+        Err(e) => e.exit(),
+    };
+    let json = cli.json;
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) if json => {
+            eprintln!("{}", error_envelope(error_code(&*e), &e.to_string()));
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn error_code(e: &(dyn Error + 'static)) -> &'static str {
+    if let Some(e) = e.downcast_ref::<SuneungError>() {
+        e.code()
+    } else if e.is::<std::io::Error>() {
+        "io"
+    } else {
+        "cli"
+    }
+}
+
+/// `{"error": {"code": ..., "message": ...}}`
+fn error_envelope(code: &str, message: &str) -> String {
+    serde_json::json!({ "error": { "code": code, "message": message } }).to_string()
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let file = match &cli.config {
+        Some(path) => Config::load(path)?,
+        None => Config::load_default()?,
+    };
+    let config = file.merge(Config {
+        data_dir: cli.data_dir,
+        locale: cli.locale,
+        ..Config::default()
+    });
+    let data_dir = config
+        .data_dir
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DATA_DIR));
+
+    let json = cli.json;
+    let no_json = |command: &str| -> Result<(), Box<dyn Error>> {
+        Err(format!("{} has no JSON output", command).into())
+    };
+    match cli.command {
+        Command::Record(args) => record::run(args, &data_dir, json),
+        Command::Calc(args) => calc::run(args, &data_dir, &config, json),
+        Command::Compare(args) => compare::run(args, &data_dir, &config, json),
+        Command::List(args) => list::run(args, &data_dir, &config, json),
+        Command::Report(args) => report::run(args, &data_dir, &config, json),
+        Command::Plot(args) => plot::run(args, &data_dir, &config, json),
+        Command::Radar(args) => radar::run(args, &data_dir, &config, json),
+        Command::Batch(args) => batch::run(args, &data_dir, &config, json),
+        Command::Completions(_) if json => no_json("completions"),
+        Command::Completions(args) => completions::run(args),
+        #[cfg(feature = "server")]
+        Command::Serve(_) if json => no_json("serve"),
+        #[cfg(feature = "server")]
+        Command::Serve(args) => serve::run(args, &config),
+        #[cfg(feature = "tui")]
+        Command::Tui(_) if json => no_json("tui"),
+        #[cfg(feature = "tui")]
+        Command::Tui(args) => tui::run(args, &data_dir, &config),
+        #[cfg(feature = "watch")]
+        Command::Watch(_) if json => no_json("watch"),
+        #[cfg(feature = "watch")]
+        Command::Watch(args) => watch::run(args, &data_dir, &config),
+    }
+}
+
+/// `--year` if given, else `default_year` from the config
+pub fn resolve_year(flag: Option<usize>, config: &Config) -> Result<usize, Box<dyn Error>> {
+    flag.or(config.default_year)
+        .ok_or_else(|| "--year is required unless default_year is set in the config file".into())
+}
+
+/// Stored record of `name`, with the data root in the error message
+pub fn load_record(data_dir: &Path, name: &str) -> Result<Record, Box<dyn Error>> {
+    suneung_calc::input::check_name(name)?;
+    if !data_dir.join(name).join("record.parquet").exists() {
+        return Err(format!("no record for {} under {}", name, data_dir.display()).into());
+    }
+    Ok(Record::read_parquet_in(
+        data_dir,
+        name,
+        &ReadOptions::default(),
+    )?)
+}
+
+/// Stored exams of `name`, oldest first: the records named `<name>` or
+/// `<name>_<anything>` that carry exam information
+pub fn load_exams(data_dir: &Path, name: &str) -> Result<Vec<Record>, Box<dyn Error>> {
+    let prefix = format!("{}_", name);
+    let mut exams = vec![];
+    for stored in list_records(data_dir)? {
+        if stored.name != name && !stored.name.starts_with(&prefix) {
+            continue;
+        }
+        let Some(exam) = stored.exam else {
+            continue;
+        };
+        let record = Record::read_parquet_in(data_dir, &stored.name, &ReadOptions::default())?;
+        exams.push(((exam.year, exam.kind), record));
+    }
+    if exams.is_empty() {
+        return Err(format!(
+            "no records of {} with exam information under {}",
+            name,
+            data_dir.display()
+        )
+        .into());
+    }
+    exams.sort_by_key(|(key, _)| *key);
+    Ok(exams.into_iter().map(|(_, record)| record).collect())
+}
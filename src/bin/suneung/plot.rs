@@ -0,0 +1,144 @@
+use crate::completions::{record_names, UniversityParser};
+use crate::report::read_cuts;
+use crate::{load_exams, load_record, resolve_year};
+use clap::{Args, ValueEnum};
+use clap_complete::ArgValueCandidates;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use suneung_calc::api::compare;
+use suneung_calc::config::Config;
+use suneung_calc::normalize::ExamStatsTable;
+use suneung_calc::plot::{trend, university_bars, PlotOptions};
+use suneung_calc::score::{Subject, University};
+
+/// Subject that `--subjects` can choose; English has no standard score or
+/// percentile to plot
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum TrendSubject {
+    Korean,
+    Math,
+    Chemistry,
+    Earth,
+}
+
+impl From<TrendSubject> for Subject {
+    fn from(subject: TrendSubject) -> Self {
+        match subject {
+            TrendSubject::Korean => Subject::Korean,
+            TrendSubject::Math => Subject::Math,
+            TrendSubject::Chemistry => Subject::Chemistry,
+            TrendSubject::Earth => Subject::EarthScience,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct PlotArgs {
+    /// Student name
+    #[arg(long, add = ArgValueCandidates::new(record_names))]
+    name: String,
+
+    /// 학년도 of the weight data; `default_year` from the config by default
+    #[arg(long)]
+    year: Option<usize>,
+
+    /// Standard scores across the student's stored exams instead of
+    /// converted scores. Exams are the records named `<name>` or
+    /// `<name>_<anything>` that carry exam information.
+    #[arg(long, conflicts_with_all = ["year", "percent", "cuts"])]
+    trend: bool,
+
+    /// Subjects of the trend, comma separated; all by default
+    #[arg(long, value_delimiter = ',', requires = "trend")]
+    subjects: Vec<TrendSubject>,
+
+    /// Trend of percentiles instead of standard scores
+    #[arg(long, requires = "trend")]
+    percentile: bool,
+
+    /// Trend of z-scores against each exam's national mean and σ, so
+    /// that exams of different difficulty compare; the 수능 of 2022-2025
+    /// have embedded statistics, other exams use the nominal 표준점수
+    /// scale
+    #[arg(long, requires = "trend", conflicts_with = "percentile")]
+    normalized: bool,
+
+    /// Add the converted score at this university, for the 학년도 of each
+    /// exam, to the trend
+    #[arg(
+        long,
+        requires = "trend",
+        value_parser = UniversityParser,
+        hide_possible_values = true
+    )]
+    overlay: Option<University>,
+
+    /// Bars show the percentage of the best score
+    #[arg(long)]
+    percent: bool,
+
+    /// TOML file of converted-score cut lines, e.g. `KONKUK = 380.5`;
+    /// colors the bars by 안정/적정/소신/상향
+    #[arg(long, value_name = "FILE")]
+    cuts: Option<PathBuf>,
+
+    /// Output image, `.png` or `.svg`
+    #[arg(long, value_name = "FILE")]
+    out: PathBuf,
+
+    /// Font with Hangul glyphs; a system Korean font is looked up otherwise
+    #[arg(long, value_name = "FILE")]
+    font: Option<PathBuf>,
+}
+
+pub fn run(
+    args: PlotArgs,
+    data_dir: &Path,
+    config: &Config,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    let mut opts = PlotOptions {
+        font: args.font,
+        percent_of_max: args.percent,
+        percentile: args.percentile,
+        normalized: args.normalized.then(ExamStatsTable::embedded),
+        overlay: args.overlay,
+        ..PlotOptions::default()
+    };
+    if let Some(path) = &args.cuts {
+        opts.cuts = read_cuts(path)?;
+    }
+    let path = if args.trend {
+        let metric = if args.normalized {
+            "z 점수"
+        } else if args.percentile {
+            "백분위"
+        } else {
+            "표준점수"
+        };
+        opts.title = Some(format!("{} {} 추이", args.name, metric));
+        let subjects: Vec<Subject> = if args.subjects.is_empty() {
+            Subject::all().to_vec()
+        } else {
+            args.subjects.into_iter().map(Subject::from).collect()
+        };
+        let exams = load_exams(data_dir, &args.name)?;
+        trend(&exams, &subjects, &args.out, &opts)?
+    } else {
+        let record = load_record(data_dir, &args.name)?;
+        let year = resolve_year(args.year, config)?;
+        let locale = config.locale.unwrap_or_default();
+        let mut rows = compare(&record, &University::supported(year), year, true);
+        for row in &mut rows {
+            row.university_name = row.university.localized_name(locale).to_string();
+        }
+        opts.title = Some(format!("{} {}학년도 환산 점수", args.name, year));
+        university_bars(&record, &rows, &args.out, &opts)?
+    };
+    if json {
+        println!("{}", serde_json::json!({ "path": path }));
+    } else {
+        eprintln!("wrote {}", path.display());
+    }
+    Ok(())
+}
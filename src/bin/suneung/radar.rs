@@ -0,0 +1,83 @@
+use crate::completions::record_names;
+use crate::load_record;
+use clap::{Args, ValueEnum};
+use clap_complete::ArgValueCandidates;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use suneung_calc::config::Config;
+use suneung_calc::report::{radar_series_localized, RadarNorm};
+use suneung_calc::score::Subject;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Norm {
+    /// Percentile of each subject
+    Percentile,
+    /// Percentage of the 표준점수 최고점 given with `--max`
+    Max,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Output {
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Args)]
+pub struct RadarArgs {
+    /// Student (or exam record) name; repeat to overlay several
+    #[arg(long, required = true, add = ArgValueCandidates::new(record_names))]
+    name: Vec<String>,
+
+    #[arg(long, value_enum, default_value_t = Norm::Percentile)]
+    norm: Norm,
+
+    /// TOML file of 표준점수 최고점 per subject, e.g. `Korean = 150`
+    #[arg(long, value_name = "FILE", required_if_eq("norm", "max"))]
+    max: Option<PathBuf>,
+
+    #[arg(long, value_enum, default_value_t = Output::Json)]
+    output: Output,
+}
+
+pub fn run(
+    args: RadarArgs,
+    data_dir: &Path,
+    config: &Config,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    let norm = match (args.norm, &args.max) {
+        (Norm::Max, Some(path)) => RadarNorm::FractionOfMax(read_max(path)?),
+        _ => RadarNorm::Percentile,
+    };
+    let records = args
+        .name
+        .iter()
+        .map(|name| load_record(data_dir, name))
+        .collect::<Result<Vec<_>, _>>()?;
+    let series = radar_series_localized(&records, &norm, config.locale.unwrap_or_default());
+
+    let output = if json { Output::Json } else { args.output };
+    match output {
+        Output::Json => println!("{}", serde_json::to_string_pretty(&series)?),
+        Output::Csv => series.write_csv(std::io::stdout())?,
+    }
+    Ok(())
+}
+
+fn read_max(path: &Path) -> Result<HashMap<Subject, f64>, Box<dyn Error>> {
+    let text = std::fs::read_to_string(path)?;
+    let table: HashMap<String, f64> =
+        toml::from_str(&text).map_err(|e| format!("--max {}: {}", path.display(), e))?;
+    table
+        .into_iter()
+        .map(|(key, max)| {
+            let subject = Subject::all()
+                .iter()
+                .copied()
+                .find(|s| s.name().eq_ignore_ascii_case(&key))
+                .ok_or_else(|| format!("--max {}: unknown subject {:?}", path.display(), key))?;
+            Ok((subject, max))
+        })
+        .collect()
+}
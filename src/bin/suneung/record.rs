@@ -0,0 +1,193 @@
+use crate::completions::record_names;
+use clap::Args;
+use clap_complete::ArgValueCandidates;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use suneung_calc::convert::Track;
+use suneung_calc::exam::{ExamKind, ExamMeta};
+use suneung_calc::input;
+use suneung_calc::score::{
+    Grade, Percentile, ReadOptions, Record, Score, StandardScore, Subject, University,
+};
+use suneung_calc::targets::Targets;
+
+#[derive(Debug, Args)]
+pub struct RecordArgs {
+    /// Student name, also the directory name under the data root
+    #[arg(
+        long,
+        required_unless_present = "from_toml",
+        add = ArgValueCandidates::new(record_names)
+    )]
+    name: Option<String>,
+
+    /// 국어 as 표준점수,백분위,등급
+    #[arg(long, value_name = "STD,PCT,GRADE", value_parser = parse_score)]
+    korean: Option<Score>,
+
+    /// 수학 as 표준점수,백분위,등급
+    #[arg(long, value_name = "STD,PCT,GRADE", value_parser = parse_score)]
+    math: Option<Score>,
+
+    /// 영어 등급
+    #[arg(long, value_name = "GRADE")]
+    english: Option<Grade>,
+
+    /// 화학Ⅰ as 표준점수,백분위,등급
+    #[arg(long, value_name = "STD,PCT,GRADE", value_parser = parse_score)]
+    chemistry: Option<Score>,
+
+    /// 지구과학Ⅰ as 표준점수,백분위,등급
+    #[arg(long, value_name = "STD,PCT,GRADE", value_parser = parse_score)]
+    earth: Option<Score>,
+
+    /// Month the exam was taken, e.g. 2024-09 for the 2025학년도 9월 모의평가
+    #[arg(long, value_name = "YYYY-MM", value_parser = parse_exam)]
+    exam: Option<ExamMeta>,
+
+    /// Label for slicing the cohort, e.g. 재수생; repeatable, and added to
+    /// the stored tags of the student
+    #[arg(long, value_name = "TAG")]
+    tag: Vec<String>,
+
+    /// 선택과목 of 국어 or 수학, e.g. 미적분 or calculus; repeatable, and
+    /// shown next to the subject as on the 성적통지표
+    #[arg(long, value_name = "TRACK")]
+    elective: Vec<Track>,
+
+    /// Start from a record in TOML (the serde form of `Record`)
+    #[arg(long, value_name = "FILE", conflicts_with = "interactive")]
+    from_toml: Option<PathBuf>,
+
+    /// Prompt for every subject on the terminal
+    #[arg(long)]
+    interactive: bool,
+
+    /// Target converted score, e.g. KONKUK:2025:400; repeatable, and
+    /// replaces the stored targets of the student
+    #[arg(long, value_name = "UNIV:YEAR:SCORE", value_parser = parse_target)]
+    target: Vec<(University, u16, f64)>,
+}
+
+pub fn run(args: RecordArgs, data_dir: &Path, json: bool) -> Result<(), Box<dyn Error>> {
+    let mut record = if let Some(path) = &args.from_toml {
+        let text = input::read_to_string(path)
+            .map_err(|e| format!("--from-toml {}: {}", path.display(), e))?;
+        let mut record = Record::from_toml_str(&text)
+            .map_err(|e| format!("--from-toml {}: {}", path.display(), e))?;
+        if let Some(name) = &args.name {
+            record.rename(name);
+        }
+        record
+    } else {
+        // `name` is required unless --from-toml is given
+        let name = args.name.as_deref().unwrap_or_default();
+        suneung_calc::input::check_name(name)?;
+        if args.interactive {
+            Record::from_interactive(name, std::io::stdin().lock(), std::io::stderr())?
+        } else if data_dir.join(name).join("record.parquet").exists() {
+            Record::read_parquet_in(data_dir, name, &ReadOptions::default())?
+        } else {
+            Record::new(name)
+        }
+    };
+
+    let scores = [
+        (Subject::Korean, args.korean),
+        (Subject::Math, args.math),
+        (Subject::English, args.english.map(Score::grade_only)),
+        (Subject::Chemistry, args.chemistry),
+        (Subject::EarthScience, args.earth),
+    ];
+    for (subject, score) in scores {
+        if let Some(score) = score {
+            record.record_score(subject, score);
+        }
+    }
+    if args.exam.is_some() {
+        record.set_exam(args.exam);
+    }
+    for tag in &args.tag {
+        record.add_tag(tag);
+    }
+    for &elective in &args.elective {
+        record.set_elective(elective)?;
+    }
+
+    if record.is_empty() {
+        return Err("no scores given (use --korean, --math, ... or --interactive)".into());
+    }
+    record.validate()?;
+    record.write_parquet_in(data_dir)?;
+    if !args.target.is_empty() {
+        let targets = Targets {
+            entries: args.target,
+        };
+        targets.write_in(data_dir, record.name())?;
+    }
+
+    let path = data_dir.join(record.name());
+    if json {
+        let out = serde_json::json!({ "name": record.name(), "path": path, "record": record });
+        println!("{}", serde_json::to_string_pretty(&out)?);
+    } else {
+        print!("{}", record);
+        println!();
+        eprintln!("saved to {}", path.display());
+    }
+    Ok(())
+}
+
+fn parse_score(s: &str) -> Result<Score, String> {
+    let normalized = input::normalize(s);
+    let parts: Vec<&str> = normalized.split(',').map(str::trim).collect();
+    let [standard_score, percentile, grade] = parts.as_slice() else {
+        return Err(format!("expected 표준점수,백분위,등급, got {:?}", s));
+    };
+    let standard_score: f64 = standard_score
+        .parse()
+        .map_err(|_| format!("invalid 표준점수 {:?}", standard_score))?;
+    let percentile: f64 = percentile
+        .parse()
+        .map_err(|_| format!("invalid 백분위 {:?}", percentile))?;
+    let grade: Grade = grade.parse().map_err(|e| format!("{}", e))?;
+    let standard_score = StandardScore::new(standard_score).map_err(|e| e.to_string())?;
+    let percentile = Percentile::new(percentile).map_err(|e| e.to_string())?;
+    Ok(Score::new_typed(standard_score, percentile, grade))
+}
+
+fn parse_target(s: &str) -> Result<(University, u16, f64), String> {
+    let normalized = input::normalize(s);
+    let parts: Vec<&str> = normalized.split(':').map(str::trim).collect();
+    let [university, year, target] = parts.as_slice() else {
+        return Err(format!("expected UNIV:YEAR:SCORE, got {:?}", s));
+    };
+    let university: University = university.parse().map_err(|e| format!("{}", e))?;
+    let year: u16 = year
+        .parse()
+        .map_err(|_| format!("invalid 학년도 {:?}", year))?;
+    let target: f64 = target
+        .parse()
+        .ok()
+        .filter(|t: &f64| t.is_finite())
+        .ok_or_else(|| format!("invalid target {:?}", target))?;
+    Ok((university, year, target))
+}
+
+// `2024-09` is the 9월 모의평가 of the 2025학년도
+pub fn parse_exam(s: &str) -> Result<ExamMeta, String> {
+    let invalid = || format!("expected YYYY-MM, got {:?}", s);
+    let normalized = input::normalize(s);
+    let (year, month) = normalized.trim().split_once('-').ok_or_else(invalid)?;
+    let year: u16 = year.parse().map_err(|_| invalid())?;
+    let month: u8 = month.parse().map_err(|_| invalid())?;
+    let kind = match month {
+        3 => ExamKind::Mock3,
+        6 => ExamKind::Mock6,
+        9 => ExamKind::Mock9,
+        11 => ExamKind::Csat,
+        1..=12 => ExamKind::Other,
+        _ => return Err(invalid()),
+    };
+    Ok(ExamMeta::new(kind, year + 1))
+}
@@ -0,0 +1,342 @@
+use crate::completions::{record_names, UniversityParser};
+use crate::record::parse_exam;
+use crate::{load_exams, load_record, resolve_year};
+use clap::{Args, ValueEnum};
+use clap_complete::ArgValueCandidates;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use suneung_calc::api::compare;
+use suneung_calc::cohort::Cohort;
+use suneung_calc::config::Config;
+use suneung_calc::exam::ExamMeta;
+use suneung_calc::report::latex::{self, Layout};
+use suneung_calc::report::{
+    cohort_summary, html, markdown, ReportOptions, Sections, SummaryOptions,
+};
+use suneung_calc::score::{Record, University};
+use suneung_calc::targets::{progress, Targets};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Markdown,
+    /// Single self-contained file, for printing
+    Html,
+    /// Score and comparison tables (booktabs) to `\input` into a
+    /// document; student reports only
+    Latex,
+    /// Needs the `pdf` feature and `--out`; student reports only
+    Pdf,
+}
+
+/// Report section that `--skip` can leave out
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Section {
+    Scores,
+    Comparison,
+    EnglishSweep,
+    Matrix,
+    Statistics,
+    Correlation,
+    GradeCuts,
+    Ranking,
+    Top,
+    Progress,
+    Plan,
+}
+
+#[derive(Debug, Args)]
+pub struct ReportArgs {
+    /// Student name
+    #[arg(
+        long,
+        required_unless_present_any = ["cohort", "summary"],
+        add = ArgValueCandidates::new(record_names)
+    )]
+    name: Option<String>,
+
+    /// 학년도 of the weight data; `default_year` from the config by default
+    #[arg(long)]
+    year: Option<usize>,
+
+    /// Summarize every stored student instead of one
+    #[arg(long, conflicts_with = "name")]
+    cohort: bool,
+
+    /// One-page overview of the stored students: statistics, grade
+    /// counts, top lists and how many clear the `--cuts`
+    #[arg(long, conflicts_with_all = ["name", "cohort"])]
+    summary: bool,
+
+    /// With `--summary`, only the students of the exam taken in this
+    /// month, e.g. 2024-06 for the 2025학년도 6월 모의평가
+    #[arg(long, value_name = "YYYY-MM", value_parser = parse_exam, requires = "summary")]
+    exam: Option<ExamMeta>,
+
+    /// With `--summary`, universities with a top list; the first three
+    /// with data by default
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_parser = UniversityParser,
+        hide_possible_values = true,
+        requires = "summary"
+    )]
+    top_universities: Vec<University>,
+
+    /// With `--summary` or `--cohort`, length of each top list
+    #[arg(long, default_value_t = 10)]
+    top: usize,
+
+    /// TOML file of converted-score cut lines, e.g. `KONKUK = 380.5`
+    #[arg(long, value_name = "FILE")]
+    cuts: Option<PathBuf>,
+
+    /// Sections to leave out, comma separated
+    #[arg(long, value_delimiter = ',', value_name = "SECTION")]
+    skip: Vec<Section>,
+
+    /// Add the subject to improve at each university by up to this many
+    /// 표준점수 points (English 등급 steps); student reports only
+    #[arg(long, value_name = "POINTS", conflicts_with_all = ["cohort", "summary"])]
+    plan: Option<f64>,
+
+    /// Estimate the 백분위 of subjects stored without one from their
+    /// 표준점수, marked as estimated in the report; student reports only
+    #[arg(long, conflicts_with_all = ["cohort", "summary"])]
+    fill_percentiles: bool,
+
+    /// Output file; stdout when omitted
+    #[arg(long, value_name = "FILE")]
+    out: Option<PathBuf>,
+
+    /// Taken from the extension of `--out` when omitted, else markdown
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+
+    /// Directory of fonts (e.g. Noto Sans KR) searched before the system
+    /// fonts for PDF output; repeatable
+    #[arg(long, value_name = "DIR")]
+    font_dir: Vec<PathBuf>,
+}
+
+const PDF_MISSING: &str = "suneung was built without the pdf feature";
+
+const STUDENT_ONLY: &str = "LaTeX and PDF output cover student reports only";
+
+pub fn run(
+    args: ReportArgs,
+    data_dir: &Path,
+    config: &Config,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    let year = resolve_year(args.year, config)?;
+    let mut opts = ReportOptions {
+        locale: config.locale.unwrap_or_default(),
+        sections: sections(&args.skip),
+        font_dirs: args.font_dir,
+        top_count: args.top,
+        ..ReportOptions::default()
+    };
+    if let Some(path) = &args.cuts {
+        opts.cuts = read_cuts(path)?;
+    }
+    if let Some(name) = args
+        .name
+        .as_deref()
+        .filter(|_| !args.cohort && !args.summary)
+    {
+        let targets = Targets::read_in(data_dir, name)?;
+        if !targets.is_empty() {
+            // A student without exam information is one exam
+            let history = match load_exams(data_dir, name) {
+                Ok(history) => history,
+                Err(_) => vec![load_record(data_dir, name)?],
+            };
+            opts.progress = progress(&history, &targets);
+        }
+        if let Some(budget) = args.plan {
+            opts.plan = load_record(data_dir, name)?.improvement_plan(year, budget);
+        }
+    }
+
+    let format = args.format.unwrap_or_else(|| {
+        let ext = args.out.as_ref().and_then(|p| p.extension()?.to_str());
+        match ext {
+            Some("html" | "htm") => Format::Html,
+            Some("tex") => Format::Latex,
+            Some("pdf") => Format::Pdf,
+            _ => Format::Markdown,
+        }
+    });
+    if format == Format::Pdf {
+        if !cfg!(feature = "pdf") {
+            return Err(PDF_MISSING.into());
+        }
+        if args.cohort || args.summary {
+            return Err(STUDENT_ONLY.into());
+        }
+        let Some(out) = &args.out else {
+            return Err("--format pdf needs --out".into());
+        };
+        let record = load_student(data_dir, args.name.as_deref(), args.fill_percentiles, year)?;
+        write_pdf(&record, year, &opts, out)?;
+        if json {
+            let out = serde_json::json!({ "path": out });
+            println!("{}", serde_json::to_string_pretty(&out)?);
+        } else {
+            eprintln!("wrote {}", out.display());
+        }
+        return Ok(());
+    }
+
+    let report = if args.summary {
+        let mut cohort = Cohort::load_all_in(data_dir)?;
+        if let Some(exam) = &args.exam {
+            let records = cohort
+                .records()
+                .iter()
+                .filter(|r| {
+                    r.exam()
+                        .is_some_and(|e| e.kind == exam.kind && e.year == exam.year)
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            if records.is_empty() {
+                return Err(format!("no stored record is from the {}", exam).into());
+            }
+            cohort = Cohort::new(records);
+        }
+        let summary = cohort_summary(
+            &cohort,
+            year,
+            &SummaryOptions {
+                universities: args.top_universities,
+                top: args.top,
+                cuts: opts.cuts.clone(),
+            },
+        );
+        match format {
+            Format::Markdown => markdown::cohort_summary(&summary, opts.locale),
+            Format::Html => html::cohort_summary(&summary, opts.locale),
+            Format::Latex | Format::Pdf => return Err(STUDENT_ONLY.into()),
+        }
+    } else if args.cohort {
+        let cohort = Cohort::load_all_in(data_dir)?;
+        match format {
+            Format::Markdown => markdown::cohort_report(cohort.records(), year, &opts),
+            Format::Html => html::cohort_report(cohort.records(), year, &opts),
+            Format::Latex | Format::Pdf => return Err(STUDENT_ONLY.into()),
+        }
+    } else {
+        let record = load_student(data_dir, args.name.as_deref(), args.fill_percentiles, year)?;
+        match format {
+            Format::Markdown => markdown::student_report(&record, year, &opts),
+            Format::Html => html::student_report(&record, year, &opts),
+            Format::Latex => latex_tables(&record, year, &opts),
+            Format::Pdf => unreachable!("PDF is written above"),
+        }
+    };
+
+    if let Some(path) = &args.out {
+        std::fs::write(path, &report)?;
+    }
+    if json {
+        let key = match format {
+            Format::Markdown => "markdown",
+            Format::Html => "html",
+            Format::Latex => "latex",
+            Format::Pdf => unreachable!("PDF is written above"),
+        };
+        let out = serde_json::json!({ "path": args.out, key: report });
+        println!("{}", serde_json::to_string_pretty(&out)?);
+    } else {
+        match &args.out {
+            Some(path) => eprintln!("wrote {}", path.display()),
+            None => print!("{}", report),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "pdf")]
+fn write_pdf(
+    record: &Record,
+    year: usize,
+    opts: &ReportOptions,
+    out: &Path,
+) -> Result<(), Box<dyn Error>> {
+    Ok(suneung_calc::report::pdf::student_report(
+        record, year, opts, out,
+    )?)
+}
+
+#[cfg(not(feature = "pdf"))]
+fn write_pdf(_: &Record, _: usize, _: &ReportOptions, _: &Path) -> Result<(), Box<dyn Error>> {
+    Err(PDF_MISSING.into())
+}
+
+// The sections of `markdown::student_report` that have a LaTeX table
+fn latex_tables(record: &Record, year: usize, opts: &ReportOptions) -> String {
+    let mut tables = vec![];
+    if opts.sections.scores {
+        tables.push(latex::score_table(record, opts));
+    }
+    if opts.sections.comparison {
+        let rows = compare(record, &University::supported(year), year, true);
+        tables.push(latex::comparison_table(&rows, opts, Layout::Auto));
+    }
+    tables.join("\n")
+}
+
+fn sections(skip: &[Section]) -> Sections {
+    let mut sections = Sections::default();
+    for section in skip {
+        let shown = match section {
+            Section::Scores => &mut sections.scores,
+            Section::Comparison => &mut sections.comparison,
+            Section::EnglishSweep => &mut sections.english_sweep,
+            Section::Matrix => &mut sections.matrix,
+            Section::Statistics => &mut sections.statistics,
+            Section::Correlation => &mut sections.correlation,
+            Section::GradeCuts => &mut sections.grade_cuts,
+            Section::Ranking => &mut sections.ranking,
+            Section::Top => &mut sections.top,
+            Section::Progress => &mut sections.progress,
+            Section::Plan => &mut sections.plan,
+        };
+        *shown = false;
+    }
+    sections
+}
+
+// `--name`, with `--fill-percentiles` applied
+fn load_student(
+    data_dir: &Path,
+    name: Option<&str>,
+    fill_percentiles: bool,
+    year: usize,
+) -> Result<Record, Box<dyn Error>> {
+    // `name` is required unless --cohort or --summary is given
+    let mut record = load_record(data_dir, name.unwrap_or_default())?;
+    if fill_percentiles {
+        record.fill_missing_percentiles(year);
+    }
+    Ok(record)
+}
+
+/// `--cuts` file: a TOML table of university to converted-score cut line
+pub fn read_cuts(path: &Path) -> Result<HashMap<University, f64>, Box<dyn Error>> {
+    let text = std::fs::read_to_string(path)?;
+    let table: HashMap<String, f64> =
+        toml::from_str(&text).map_err(|e| format!("--cuts {}: {}", path.display(), e))?;
+    table
+        .into_iter()
+        .map(|(key, cut)| {
+            let university: University = key
+                .parse()
+                .map_err(|e| format!("--cuts {}: {}", path.display(), e))?;
+            Ok((university, cut))
+        })
+        .collect()
+}
@@ -0,0 +1,24 @@
+use clap::Args;
+use std::error::Error;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use suneung_calc::config::Config;
+
+#[derive(Debug, Args)]
+pub struct ServeArgs {
+    /// Port to listen on
+    #[arg(long, default_value_t = 8080)]
+    port: u16,
+
+    /// Address to listen on; the default only accepts local connections
+    #[arg(long, default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    host: IpAddr,
+}
+
+pub fn run(args: ServeArgs, config: &Config) -> Result<(), Box<dyn Error>> {
+    let addr = SocketAddr::new(args.host, args.port);
+    let locale = config.locale.unwrap_or_default();
+    println!("http://{} 에서 계산기를 제공합니다 (Ctrl-C로 종료)", addr);
+    // Runs until the process is stopped
+    tokio::runtime::Runtime::new()?.block_on(suneung_calc::server::serve_in(addr, locale))?;
+    Ok(())
+}
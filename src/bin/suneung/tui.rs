@@ -0,0 +1,178 @@
+//! `suneung tui`: terminal front end over `app::App`. Everything but key
+//! mapping and drawing lives in `app`.
+
+mod app;
+
+use crate::{load_exams, load_record};
+use app::{Action, App, Student};
+use clap::Args;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Row, Table};
+use ratatui::{DefaultTerminal, Frame};
+use std::error::Error;
+use std::path::Path;
+use suneung_calc::config::Config;
+use suneung_calc::score::list_records;
+
+#[derive(Debug, Args)]
+pub struct TuiArgs {
+    /// 학년도 to start at; `default_year` from the config, or the latest
+    /// with weight data, by default
+    #[arg(long)]
+    year: Option<usize>,
+}
+
+const HELP: &str = "↑↓ 학생  ←→ 학년도  Tab/Shift+Tab 시험  +/- 영어 등급  0 영어 원래대로  q 종료";
+
+pub fn run(args: TuiArgs, data_dir: &Path, config: &Config) -> Result<(), Box<dyn Error>> {
+    let app = App::new(load_students(data_dir)?, args.year.or(config.default_year));
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, app);
+    ratatui::restore();
+    Ok(result?)
+}
+
+/// Every stored student with their exams. Records named `<student>_<...>`
+/// are exams of `<student>`, as in `load_exams`.
+fn load_students(data_dir: &Path) -> Result<Vec<Student>, Box<dyn Error>> {
+    let names: Vec<String> = list_records(data_dir)?
+        .into_iter()
+        .map(|stored| stored.name)
+        .collect();
+    let is_exam_of_other = |name: &str| {
+        names
+            .iter()
+            .any(|other| name != other && name.starts_with(&format!("{}_", other)))
+    };
+    let mut students = vec![];
+    for name in names.iter().filter(|name| !is_exam_of_other(name)) {
+        // Records without exam information still show on their own
+        let exams = match load_exams(data_dir, name) {
+            Ok(exams) => exams,
+            Err(_) => vec![load_record(data_dir, name)?],
+        };
+        students.push(Student {
+            name: name.clone(),
+            exams,
+        });
+    }
+    Ok(students)
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, mut app: App) -> std::io::Result<()> {
+    while !app.should_quit() {
+        terminal.draw(|frame| draw(frame, &app))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                if let Some(action) = action_for(key.code) {
+                    app.update(action);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn action_for(code: KeyCode) -> Option<Action> {
+    let action = match code {
+        KeyCode::Down | KeyCode::Char('j') => Action::NextStudent,
+        KeyCode::Up | KeyCode::Char('k') => Action::PrevStudent,
+        KeyCode::Tab => Action::NextExam,
+        KeyCode::BackTab => Action::PrevExam,
+        KeyCode::Right | KeyCode::Char('l') => Action::NextYear,
+        KeyCode::Left | KeyCode::Char('h') => Action::PrevYear,
+        KeyCode::Char('+') | KeyCode::Char('=') => Action::EnglishUp,
+        KeyCode::Char('-') => Action::EnglishDown,
+        KeyCode::Char('0') => Action::EnglishReset,
+        KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+        _ => return None,
+    };
+    Some(action)
+}
+
+fn draw(frame: &mut Frame, app: &App) {
+    let [body, help] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let [students, main] =
+        Layout::horizontal([Constraint::Length(20), Constraint::Min(0)]).areas(body);
+
+    let items: Vec<ListItem> = app
+        .students()
+        .iter()
+        .map(|s| ListItem::new(s.name.as_str()))
+        .collect();
+    let mut state = ListState::default().with_selected(Some(app.selected()));
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(Block::bordered().title("학생"))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED)),
+        students,
+        &mut state,
+    );
+
+    let Some(record) = app.record() else {
+        frame.render_widget(
+            Paragraph::new("저장된 성적이 없습니다").block(Block::bordered()),
+            main,
+        );
+        frame.render_widget(Line::from(HELP), help);
+        return;
+    };
+    let report = record.to_string();
+    let [scores, ranking] = Layout::vertical([
+        Constraint::Length(report.lines().count() as u16 + 2),
+        Constraint::Min(0),
+    ])
+    .areas(main);
+
+    let (exam, exams) = app.exam_position();
+    let mut title = format!("성적 ({}/{})", exam + 1, exams);
+    if let Some(grade) = app.english_override() {
+        title.push_str(&format!(" · 영어 {}등급 가정", grade.get()));
+    }
+    frame.render_widget(
+        Paragraph::new(report).block(Block::bordered().title(title)),
+        scores,
+    );
+
+    let mut rank = 0;
+    let rows: Vec<Row> = app
+        .ranking()
+        .into_iter()
+        .map(|row| match row.score {
+            Ok(score) => {
+                rank += 1;
+                Row::new(vec![
+                    rank.to_string(),
+                    row.university.name().to_string(),
+                    format!("{:.2}", score),
+                ])
+            }
+            Err(reason) => Row::new(vec![
+                "-".to_string(),
+                row.university.name().to_string(),
+                format!("불가 ({})", reason),
+            ]),
+        })
+        .collect();
+    frame.render_widget(
+        Table::new(
+            rows,
+            [
+                Constraint::Length(4),
+                Constraint::Length(16),
+                Constraint::Min(0),
+            ],
+        )
+        .header(
+            Row::new(["순위", "대학", "환산 점수"])
+                .style(Style::new().add_modifier(Modifier::BOLD)),
+        )
+        .block(Block::bordered().title(format!("{}학년도 환산 점수", app.year()))),
+        ranking,
+    );
+    frame.render_widget(Line::from(HELP), help);
+}
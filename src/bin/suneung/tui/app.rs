@@ -0,0 +1,207 @@
+//! State and update logic of `suneung tui`, kept free of terminal types so
+//! it can be driven without a terminal.
+
+use suneung_calc::calculator::ScoreCalculator;
+use suneung_calc::score::{Grade, Record, Subject, University};
+
+/// A student and their stored exams, oldest first
+#[derive(Debug, Clone)]
+pub struct Student {
+    pub name: String,
+    pub exams: Vec<Record>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Action {
+    NextStudent,
+    PrevStudent,
+    NextExam,
+    PrevExam,
+    NextYear,
+    PrevYear,
+    /// One English grade better (a lower number)
+    EnglishUp,
+    /// One English grade worse
+    EnglishDown,
+    /// Back to the recorded English grade
+    EnglishReset,
+    Quit,
+}
+
+/// One line of the ranked university table
+#[derive(Debug, Clone)]
+pub struct RankRow {
+    pub university: University,
+    /// Converted score, or why the record cannot be scored
+    pub score: Result<f64, String>,
+}
+
+#[derive(Debug)]
+pub struct App {
+    students: Vec<Student>,
+    selected: usize,
+    exam: usize,
+    years: Vec<usize>,
+    year: usize,
+    english: Option<Grade>,
+    calculator: ScoreCalculator,
+    quit: bool,
+}
+
+impl App {
+    /// Starts at the first student's latest exam and at `year`, or the
+    /// latest year with weight data if `year` is not one of them
+    pub fn new(students: Vec<Student>, year: Option<usize>) -> Self {
+        let mut years: Vec<usize> = University::iter()
+            .flat_map(|u| u.supported_years())
+            .collect();
+        years.sort_unstable();
+        years.dedup();
+        let year = year
+            .and_then(|y| years.iter().position(|&x| x == y))
+            .unwrap_or(years.len().saturating_sub(1));
+        let mut app = Self {
+            students,
+            selected: 0,
+            exam: 0,
+            calculator: ScoreCalculator::new(years.get(year).copied().unwrap_or_default()),
+            years,
+            year,
+            english: None,
+            quit: false,
+        };
+        app.select(0);
+        app
+    }
+
+    pub fn update(&mut self, action: Action) {
+        let n = self.students.len();
+        match action {
+            Action::NextStudent if n > 0 => self.select((self.selected + 1) % n),
+            Action::PrevStudent if n > 0 => self.select((self.selected + n - 1) % n),
+            Action::NextExam => {
+                if self.exam + 1 < self.exam_count() {
+                    self.exam += 1;
+                }
+            }
+            Action::PrevExam => self.exam = self.exam.saturating_sub(1),
+            Action::NextYear => {
+                if self.year + 1 < self.years.len() {
+                    self.set_year(self.year + 1);
+                }
+            }
+            Action::PrevYear => {
+                if self.year > 0 {
+                    self.set_year(self.year - 1);
+                }
+            }
+            Action::EnglishUp => self.shift_english(-1),
+            Action::EnglishDown => self.shift_english(1),
+            Action::EnglishReset => self.english = None,
+            Action::Quit => self.quit = true,
+            Action::NextStudent | Action::PrevStudent => {}
+        }
+    }
+
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    pub fn students(&self) -> &[Student] {
+        &self.students
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn year(&self) -> usize {
+        self.calculator.year()
+    }
+
+    /// 0-based index of the shown exam and the number of exams
+    pub fn exam_position(&self) -> (usize, usize) {
+        (self.exam, self.exam_count())
+    }
+
+    /// Hypothetical English grade replacing the recorded one, if any
+    pub fn english_override(&self) -> Option<Grade> {
+        self.english
+    }
+
+    /// Shown exam with the hypothetical English grade applied
+    pub fn record(&self) -> Option<Record> {
+        let mut record = self
+            .students
+            .get(self.selected)?
+            .exams
+            .get(self.exam)?
+            .clone();
+        if let Some(grade) = self.english {
+            // A record without English keeps showing as such
+            let _ = record.set_grade(Subject::English, grade);
+        }
+        Some(record)
+    }
+
+    /// Every university with data for the year, best first, followed by the
+    /// ones the record cannot be scored at
+    pub fn ranking(&self) -> Vec<RankRow> {
+        let Some(record) = self.record() else {
+            return vec![];
+        };
+        let mut rows: Vec<RankRow> = self
+            .calculator
+            .universities()
+            .map(|university| RankRow {
+                university,
+                score: self
+                    .calculator
+                    .score(&record, university)
+                    .map_err(|e| e.to_string()),
+            })
+            .collect();
+        rows.sort_by(|a, b| match (&a.score, &b.score) {
+            (Ok(x), Ok(y)) => y.total_cmp(x),
+            (Ok(_), Err(_)) => std::cmp::Ordering::Less,
+            (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
+            (Err(_), Err(_)) => std::cmp::Ordering::Equal,
+        });
+        rows
+    }
+
+    fn exam_count(&self) -> usize {
+        self.students
+            .get(self.selected)
+            .map_or(0, |s| s.exams.len())
+    }
+
+    // Switching students shows their latest exam with the recorded English
+    fn select(&mut self, index: usize) {
+        self.selected = index;
+        self.exam = self.exam_count().saturating_sub(1);
+        self.english = None;
+    }
+
+    fn set_year(&mut self, index: usize) {
+        self.year = index;
+        self.calculator = ScoreCalculator::new(self.years[index]);
+    }
+
+    fn shift_english(&mut self, delta: i8) {
+        let current = self
+            .english
+            .or_else(|| {
+                self.students
+                    .get(self.selected)?
+                    .exams
+                    .get(self.exam)?
+                    .try_score(Subject::English)
+                    .map(|s| s.rank())
+            })
+            .map_or(1, |g| g.get() as i8);
+        if let Ok(grade) = Grade::try_from((current + delta).clamp(1, 9) as u8) {
+            self.english = Some(grade);
+        }
+    }
+}
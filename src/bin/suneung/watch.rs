@@ -0,0 +1,80 @@
+use crate::resolve_year;
+use clap::Args;
+use notify::{RecursiveMode, Watcher};
+use std::error::Error;
+use std::io::Write;
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::Duration;
+use suneung_calc::config::Config;
+use suneung_calc::render::render_comparison_grouped;
+use suneung_calc::watch::{Changes, WatchedCohort};
+
+/// Quiet period after the last file event before reloading, so a record
+/// and its checksum sidecar written back to back trigger a single reload
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+#[derive(Debug, Args)]
+pub struct WatchArgs {
+    /// 학년도 of the weight data; `default_year` from the config by default
+    #[arg(long)]
+    year: Option<usize>,
+}
+
+pub fn run(args: WatchArgs, data_dir: &Path, config: &Config) -> Result<(), Box<dyn Error>> {
+    let mut cohort = WatchedCohort::new(data_dir, resolve_year(args.year, config)?);
+    let changes = cohort.refresh()?;
+    redraw(&cohort, &changes)?;
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher.watch(data_dir, RecursiveMode::Recursive)?;
+    // Ends when the watcher goes away, which it does not before Ctrl-C
+    while let Ok(event) = rx.recv() {
+        event?;
+        while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+            event?;
+        }
+        let changes = cohort.refresh()?;
+        if !changes.is_empty() {
+            redraw(&cohort, &changes)?;
+        }
+    }
+    Ok(())
+}
+
+fn redraw(cohort: &WatchedCohort, changes: &Changes) -> std::io::Result<()> {
+    let records = cohort.records();
+    let mut out = std::io::stdout().lock();
+    // Clear the screen and move to the top left
+    write!(out, "\x1b[2J\x1b[H")?;
+    writeln!(
+        out,
+        "{} · {}학년도 · {}명",
+        cohort.dir().display(),
+        cohort.year(),
+        records.len()
+    )?;
+    if records.is_empty() {
+        writeln!(out, "저장된 성적이 없습니다")?;
+    } else {
+        writeln!(
+            out,
+            "{}",
+            render_comparison_grouped(&records, &cohort.results(), cohort.year())
+        )?;
+    }
+    for (label, names) in [
+        ("추가", &changes.added),
+        ("갱신", &changes.updated),
+        ("삭제", &changes.removed),
+    ] {
+        if !names.is_empty() {
+            writeln!(out, "{}: {}", label, names.join(", "))?;
+        }
+    }
+    for (name, error) in &changes.failed {
+        writeln!(out, "읽기 실패: {} ({})", name, error)?;
+    }
+    out.flush()
+}
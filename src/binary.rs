@@ -0,0 +1,32 @@
+use bincode::Options;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::error::Error;
+
+// Every blob starts with MAGIC followed by a format version byte, so a future
+// change of the payload layout can be detected instead of misread.
+const MAGIC: &[u8; 4] = b"SNCR";
+const VERSION: u8 = 5;
+const LIMIT: u64 = 16 * 1024 * 1024;
+
+fn options() -> impl Options {
+    bincode::DefaultOptions::new().with_limit(LIMIT)
+}
+
+pub(crate) fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.push(VERSION);
+    options().serialize_into(&mut bytes, value)?;
+    Ok(bytes)
+}
+
+pub(crate) fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, Box<dyn Error>> {
+    if bytes.len() < MAGIC.len() + 1 || &bytes[..MAGIC.len()] != MAGIC {
+        return Err("Not a suneung_calc binary snapshot".into());
+    }
+    let version = bytes[MAGIC.len()];
+    if version != VERSION {
+        return Err(format!("Unsupported binary snapshot version: {}", version).into());
+    }
+    Ok(options().deserialize(&bytes[MAGIC.len() + 1..])?)
+}
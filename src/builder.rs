@@ -0,0 +1,304 @@
+use crate::error::{Result, SuneungError};
+use crate::formula::{FormulaKind, ScienceAggregation};
+use crate::score::{
+    EnglishTable, Grade, Percentile, Provenance, Record, Score, StandardScore, Subject,
+    UniversityWeight,
+};
+use std::sync::OnceLock;
+
+/// Fluent alternative to a series of `Record::record_typed` calls, taking
+/// plain numbers and reporting the first one out of range from `build`.
+///
+/// ```
+/// use suneung_calc::builder::RecordBuilder;
+/// use suneung_calc::score::Subject;
+///
+/// let record = RecordBuilder::new("홍길동")
+///     .korean(131.0, 93.0, 2)
+///     .math(137.0, 96.0, 1)
+///     .english_grade(2)
+///     .science(Subject::Chemistry, 65.0, 91.0, 2)
+///     .science(Subject::EarthScience, 68.0, 95.0, 1)
+///     .build()
+///     .unwrap();
+/// assert_eq!(record.math().rank().get(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RecordBuilder {
+    record: Record,
+    // First value out of range, reported by `build`
+    invalid: Option<(Subject, String)>,
+}
+
+impl RecordBuilder {
+    pub fn new(name: &str) -> Self {
+        Self {
+            record: Record::new(name),
+            invalid: None,
+        }
+    }
+
+    pub fn korean(self, standard_score: f64, percentile: f64, grade: usize) -> Self {
+        self.subject(Subject::Korean, standard_score, percentile, grade)
+    }
+
+    pub fn math(self, standard_score: f64, percentile: f64, grade: usize) -> Self {
+        self.subject(Subject::Math, standard_score, percentile, grade)
+    }
+
+    /// English is absolute-graded, so only the grade is stored
+    pub fn english_grade(self, grade: usize) -> Self {
+        self.subject(Subject::English, 0f64, 0f64, grade)
+    }
+
+    pub fn science(
+        self,
+        subject: Subject,
+        standard_score: f64,
+        percentile: f64,
+        grade: usize,
+    ) -> Self {
+        self.subject(subject, standard_score, percentile, grade)
+    }
+
+    fn subject(
+        mut self,
+        subject: Subject,
+        standard_score: f64,
+        percentile: f64,
+        grade: usize,
+    ) -> Self {
+        let score = Grade::try_from(grade).and_then(|grade| {
+            Ok(Score::new_typed(
+                StandardScore::new(standard_score)?,
+                Percentile::new(percentile)?,
+                grade,
+            ))
+        });
+        match score {
+            Ok(score) => self.record.record_score(subject, score),
+            Err(e) => {
+                if self.invalid.is_none() {
+                    let reason = match e {
+                        SuneungError::ScoreOutOfRange(reason) => reason,
+                        e => e.to_string(),
+                    };
+                    self.invalid = Some((subject, reason));
+                }
+            }
+        }
+        self
+    }
+
+    /// Validate and return the record, which must hold Korean, Math, English
+    /// and at least one science subject.
+    ///
+    /// A missing science is reported as `MissingSubject(Subject::Chemistry)`.
+    pub fn build(self) -> Result<Record> {
+        if let Some((subject, reason)) = self.invalid {
+            return Err(SuneungError::InvalidScore { subject, reason });
+        }
+        for subject in [Subject::Korean, Subject::Math, Subject::English] {
+            if self.record.try_score(subject).is_none() {
+                return Err(SuneungError::MissingSubject(subject));
+            }
+        }
+        let has_science = [Subject::Chemistry, Subject::EarthScience]
+            .iter()
+            .any(|&s| self.record.try_score(s).is_some());
+        if !has_science {
+            return Err(SuneungError::MissingSubject(Subject::Chemistry));
+        }
+        self.build_partial()
+    }
+
+    /// Validate score ranges only, for intentionally incomplete records.
+    pub fn build_partial(self) -> Result<Record> {
+        if let Some((subject, reason)) = self.invalid {
+            return Err(SuneungError::InvalidScore { subject, reason });
+        }
+        self.record.validate()?;
+        Ok(self.record)
+    }
+}
+
+/// A `UniversityWeight` assembled by hand, e.g. for a hypothetical ratio
+/// change or a university without embedded data.
+///
+/// ```
+/// use suneung_calc::builder::{RecordBuilder, UniversityWeightBuilder};
+/// use suneung_calc::score::Subject;
+///
+/// let weight = UniversityWeightBuilder::new()
+///     .korean(25.0)
+///     .math(40.0)
+///     .science(35.0)
+///     .english_table([100.0, 98.0, 95.0, 92.0, 86.0, 75.0, 60.0, 40.0, 20.0])
+///     .build()
+///     .unwrap();
+/// let record = RecordBuilder::new("홍길동")
+///     .korean(131.0, 93.0, 2)
+///     .math(137.0, 96.0, 1)
+///     .english_grade(2)
+///     .science(Subject::Chemistry, 65.0, 91.0, 2)
+///     .science(Subject::EarthScience, 68.0, 95.0, 1)
+///     .build()
+///     .unwrap();
+/// assert!(record.calc_with_weight(&weight).is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct UniversityWeightBuilder {
+    korean: f64,
+    math: f64,
+    english: f64,
+    science: f64,
+    science_required: usize,
+    science_aggregation: Option<ScienceAggregation>,
+    english_required: usize,
+    english_table: Option<Vec<f64>>,
+    // Table of the weight passed to `from_weight`
+    base_table: Option<EnglishTable>,
+    formula: FormulaKind,
+    provenance: Option<Provenance>,
+}
+
+impl Default for UniversityWeightBuilder {
+    fn default() -> Self {
+        Self {
+            korean: 0f64,
+            math: 0f64,
+            english: 0f64,
+            science: 0f64,
+            science_required: 2,
+            science_aggregation: None,
+            english_required: 1,
+            english_table: None,
+            base_table: None,
+            formula: FormulaKind::Standard,
+            provenance: None,
+        }
+    }
+}
+
+impl UniversityWeightBuilder {
+    /// No weights, two required sciences, default English grade 1
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from an existing weight, e.g. `UniversityWeight::load`. Its
+    /// provenance is not carried over, since a changed weight no longer
+    /// matches the source.
+    pub fn from_weight(weight: &UniversityWeight) -> Self {
+        Self {
+            korean: weight.korean(),
+            math: weight.math(),
+            english: weight.english(),
+            science: weight.science(),
+            science_required: weight.science_required(),
+            science_aggregation: weight.science_aggregation,
+            english_required: weight.english_required(),
+            english_table: None,
+            base_table: Some(*weight.english_table()),
+            formula: weight.formula(),
+            provenance: None,
+        }
+    }
+
+    pub fn korean(mut self, weight: f64) -> Self {
+        self.korean = weight;
+        self
+    }
+
+    pub fn math(mut self, weight: f64) -> Self {
+        self.math = weight;
+        self
+    }
+
+    /// 0 turns English into a bonus relative to the default grade
+    pub fn english(mut self, weight: f64) -> Self {
+        self.english = weight;
+        self
+    }
+
+    pub fn science(mut self, weight: f64) -> Self {
+        self.science = weight;
+        self
+    }
+
+    /// 1 (better of the two, doubled) or 2 (the sum of both), replacing
+    /// any `science_aggregation` set before
+    pub fn science_required(mut self, count: usize) -> Self {
+        self.science_required = count;
+        self.science_aggregation = None;
+        self
+    }
+
+    /// How the 탐구 scores are combined; also sets `science_required` to
+    /// the subjects it needs
+    pub fn science_aggregation(mut self, aggregation: ScienceAggregation) -> Self {
+        self.science_aggregation = Some(aggregation);
+        self.science_required = aggregation.subjects();
+        self
+    }
+
+    /// English grade scored as the baseline
+    pub fn english_required(mut self, grade: usize) -> Self {
+        self.english_required = grade;
+        self
+    }
+
+    /// Points for English grades 1 to 9, in that order, or the points
+    /// deducted for them with `FormulaKind::DeductionEnglish`
+    pub fn english_table<T: AsRef<[f64]>>(mut self, table: T) -> Self {
+        self.english_table = Some(table.as_ref().to_vec());
+        self
+    }
+
+    pub fn formula(mut self, formula: FormulaKind) -> Self {
+        self.formula = formula;
+        self
+    }
+
+    pub fn provenance(mut self, provenance: Provenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
+
+    /// Validate and return the weight, see `UniversityWeight::validate`.
+    /// Errors are `WeightData`.
+    pub fn build(self) -> Result<UniversityWeight> {
+        let invalid = |reason: String| Err(SuneungError::WeightData(reason));
+        let english_table = match (self.english_table, self.base_table) {
+            (Some(table), _) => {
+                let Ok(points) = <[f64; 9]>::try_from(table.as_slice()) else {
+                    return invalid(format!(
+                        "english table must list 9 grades, got {}",
+                        table.len()
+                    ));
+                };
+                match self.formula {
+                    FormulaKind::DeductionEnglish => EnglishTable::deductions(points)?,
+                    _ => EnglishTable::new(points)?,
+                }
+            }
+            (None, Some(table)) => table,
+            (None, None) => return invalid("english table is missing".to_string()),
+        };
+        let weight = UniversityWeight {
+            korean: self.korean,
+            math: self.math,
+            english: self.english,
+            science: self.science,
+            science_required: self.science_required,
+            science_aggregation: self.science_aggregation,
+            english_required: self.english_required,
+            english_table,
+            formula: self.formula,
+            provenance: self.provenance,
+            max_score: OnceLock::new(),
+        };
+        weight.validate()?;
+        Ok(weight)
+    }
+}
@@ -0,0 +1,203 @@
+use crate::error::{Result, SuneungError};
+use crate::formula::guard;
+use crate::score::{CalcBreakdown, Record, University, UniversityWeight};
+use crate::weight_source::{EmbeddedWeights, WeightSource};
+
+/// Weights for one year loaded once and reused for every record. Gives the
+/// same results as `Record::calc_with_university`, which loads the weight
+/// again on each call.
+#[derive(Debug, Clone)]
+pub struct ScoreCalculator {
+    year: usize,
+    weights: Vec<(University, UniversityWeight)>,
+}
+
+impl ScoreCalculator {
+    /// Every university with embedded weight data for `year`
+    pub fn new(year: usize) -> Self {
+        Self {
+            year,
+            weights: University::iter()
+                .filter_map(|univ| UniversityWeight::try_load(univ, year).map(|w| (univ, w)))
+                .collect(),
+        }
+    }
+
+    /// Only `universities`, or `UnsupportedYear` if one has no data for `year`
+    pub fn for_universities(universities: &[University], year: usize) -> Result<Self> {
+        Self::with_source(&EmbeddedWeights, universities, year)
+    }
+
+    /// `for_universities` with weights from `source`
+    pub fn with_source(
+        source: &dyn WeightSource,
+        universities: &[University],
+        year: usize,
+    ) -> Result<Self> {
+        let weights = universities
+            .iter()
+            .map(|&univ| source.get(univ, year).map(|w| (univ, w)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { year, weights })
+    }
+
+    pub fn year(&self) -> usize {
+        self.year
+    }
+
+    pub fn universities(&self) -> impl Iterator<Item = University> + '_ {
+        self.weights.iter().map(|&(univ, _)| univ)
+    }
+
+    /// Weight of `university`, or `UnsupportedYear` if it was not loaded
+    pub fn weight(&self, university: University) -> Result<&UniversityWeight> {
+        self.weights
+            .iter()
+            .find(|(univ, _)| *univ == university)
+            .map(|(_, w)| w)
+            .ok_or(SuneungError::UnsupportedYear {
+                university,
+                year: self.year,
+            })
+    }
+
+    pub fn breakdown(&self, record: &Record, university: University) -> Result<CalcBreakdown> {
+        let weight = self.weight(university)?;
+        weight.convert(record)
+    }
+
+    pub fn score(&self, record: &Record, university: University) -> Result<f64> {
+        self.breakdown(record, university).map(|b| b.total)
+    }
+
+    /// Converted scores at every loaded university, best first
+    pub fn score_all(&self, record: &Record) -> Result<Vec<(University, f64)>> {
+        let mut scores = self
+            .universities()
+            .map(|univ| self.score(record, univ).map(|s| (univ, s)))
+            .collect::<Result<Vec<_>>>()?;
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+        Ok(scores)
+    }
+}
+
+/// Scores of a list of records at a list of (university, year) columns,
+/// from `calc_matrix`. Every cell has its own result, so a student who
+/// cannot be scored somewhere leaves the other cells alone.
+#[derive(Debug)]
+pub struct CalcMatrix {
+    columns: Vec<(University, u16)>,
+    records: usize,
+    // Column by column: `cells[j * records + i]` is record `i` at column `j`
+    cells: Vec<Result<f64>>,
+}
+
+impl CalcMatrix {
+    pub fn columns(&self) -> &[(University, u16)] {
+        &self.columns
+    }
+
+    /// Number of records, i.e. of rows
+    pub fn len(&self) -> usize {
+        self.records
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records == 0
+    }
+
+    /// Score of record `record` at column `column`, `None` out of range
+    pub fn get(&self, record: usize, column: usize) -> Option<&Result<f64>> {
+        if record >= self.records {
+            return None;
+        }
+        self.cells.get(column * self.records + record)
+    }
+
+    /// Scores of one record, aligned with `columns`; empty out of range
+    pub fn row(&self, record: usize) -> impl Iterator<Item = &Result<f64>> + '_ {
+        let columns = if record < self.records {
+            self.columns.len()
+        } else {
+            0
+        };
+        self.cells
+            .iter()
+            .skip(record)
+            .step_by(self.records.max(1))
+            .take(columns)
+    }
+
+    /// Scores of every record at one column, in the order of the records
+    pub fn column(&self, column: usize) -> &[Result<f64>] {
+        &self.cells[column * self.records..(column + 1) * self.records]
+    }
+}
+
+/// Score every record at every column, loading each weight once and going
+/// through the records column by column. Gives the same results as calling
+/// `Record::calc_with_university` for every pair; a column without weight
+/// data is `UnsupportedYear` all the way down.
+///
+/// ```
+/// use suneung_calc::calculator::calc_matrix;
+/// use suneung_calc::score::{Record, University};
+///
+/// let records = Record::samples();
+/// let columns = [(University::KONKUK, 2024), (University::SOGANG, 2025), (University::AJU, 2025)];
+///
+/// let m = calc_matrix(&records, &columns);
+/// for (i, record) in records.iter().enumerate() {
+///     for (j, &(univ, year)) in columns.iter().enumerate() {
+///         let naive = record.try_calc_with_university(univ, year as usize).ok();
+///         assert_eq!(m.get(i, j).unwrap().as_ref().ok().copied(), naive);
+///     }
+/// }
+/// // The last sample has only one science
+/// assert!(m.get(4, 0).unwrap().is_err());
+/// assert!(m.column(2).iter().all(|cell| cell.is_err()));
+/// ```
+pub fn calc_matrix(records: &[Record], universities: &[(University, u16)]) -> CalcMatrix {
+    let columns = universities
+        .iter()
+        .map(|&(university, year)| (university, usize::from(year)));
+    CalcMatrix {
+        columns: universities.to_vec(),
+        records: records.len(),
+        cells: score_columns(records, columns),
+    }
+}
+
+/// Cells of `calc_matrix`, column by column, for columns with any year
+pub(crate) fn score_columns(
+    records: &[Record],
+    columns: impl ExactSizeIterator<Item = (University, usize)>,
+) -> Vec<Result<f64>> {
+    let mut cells = Vec::with_capacity(records.len() * columns.len());
+    for (university, year) in columns {
+        match UniversityWeight::load_cached(university, year) {
+            Some(weight) => {
+                // The maximum is the same for every record of the column;
+                // when it can't be found, neither can any converted score
+                let formula = weight.formula().formula();
+                let max = weight.max_score();
+                cells.extend(records.iter().map(|record| {
+                    let max = match &max {
+                        Ok(max) => *max,
+                        Err(_) => return weight.convert(record).map(|b| b.total),
+                    };
+                    formula
+                        .convert(record, &weight)
+                        .and_then(|b| guard(b, max))
+                        .map(|b| b.total)
+                }));
+            }
+            None => cells.extend(
+                records
+                    .iter()
+                    .map(|_| Err(SuneungError::UnsupportedYear { university, year })),
+            ),
+        }
+    }
+    cells
+}
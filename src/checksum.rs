@@ -0,0 +1,114 @@
+#[cfg(feature = "parquet")]
+use peroxide::fuga::{DTypeArray, DataFrame};
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "archive")]
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    to_hex(&Sha256::digest(bytes))
+}
+
+#[cfg(feature = "parquet")]
+/// Checksum of what a DataFrame holds rather than of its file encoding.
+///
+/// Column names and values are hashed in header order: each f64 by its bits
+/// and each string by its length and bytes, so the checksum does not change
+/// with how any crate formats them. Columns of other types, which records
+/// never write, are hashed by their type and length only.
+pub(crate) fn dataframe_checksum(df: &DataFrame) -> String {
+    let mut hasher = Sha256::new();
+    for h in df.header() {
+        hasher.update((h.len() as u64).to_le_bytes());
+        hasher.update(h.as_bytes());
+        match &df[h.as_str()].values {
+            DTypeArray::F64(values) => {
+                hasher.update([1u8]);
+                hasher.update((values.len() as u64).to_le_bytes());
+                for x in values {
+                    hasher.update(x.to_bits().to_le_bytes());
+                }
+            }
+            DTypeArray::Str(values) => {
+                hasher.update([2u8]);
+                hasher.update((values.len() as u64).to_le_bytes());
+                for s in values {
+                    hasher.update((s.len() as u64).to_le_bytes());
+                    hasher.update(s.as_bytes());
+                }
+            }
+            _ => {
+                hasher.update([0u8]);
+                hasher.update((df[h.as_str()].len() as u64).to_le_bytes());
+            }
+        }
+    }
+    to_hex(&hasher.finalize())
+}
+
+#[cfg(feature = "parquet")]
+/// Check `df` against the checksum in its sidecar. `Err((expected, found))`
+/// on a mismatch.
+pub(crate) fn verify_dataframe(sidecar: &str, df: &DataFrame) -> Result<(), (String, String)> {
+    let expected = sidecar.trim();
+    let found = dataframe_checksum(df);
+    if expected == found {
+        Ok(())
+    } else {
+        Err((expected.to_string(), found))
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(all(test, feature = "parquet"))]
+mod tests {
+    use super::*;
+    use peroxide::fuga::*;
+
+    fn frame(columns: Vec<(&str, Series)>) -> DataFrame {
+        let mut df = DataFrame::new(vec![]);
+        for (name, series) in columns {
+            df.push(name, series);
+        }
+        df
+    }
+
+    fn strings(values: &[&str]) -> Series {
+        Series::new(values.iter().map(|s| s.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn values_are_hashed_by_their_bits() {
+        let positive = frame(vec![("Korean", Series::new(vec![0f64, 93.0, 2.0]))]);
+        let negative = frame(vec![("Korean", Series::new(vec![-0f64, 93.0, 2.0]))]);
+        assert_ne!(dataframe_checksum(&positive), dataframe_checksum(&negative));
+        assert_eq!(dataframe_checksum(&positive), dataframe_checksum(&positive));
+    }
+
+    #[test]
+    fn string_boundaries_are_hashed() {
+        let a = frame(vec![("Tags", strings(&["ab", "c"]))]);
+        let b = frame(vec![("Tags", strings(&["a", "bc"]))]);
+        assert_ne!(dataframe_checksum(&a), dataframe_checksum(&b));
+    }
+
+    #[test]
+    fn column_names_are_hashed() {
+        let a = frame(vec![("Korean", Series::new(vec![131f64]))]);
+        let b = frame(vec![("Math", Series::new(vec![131f64]))]);
+        assert_ne!(dataframe_checksum(&a), dataframe_checksum(&b));
+    }
+
+    #[test]
+    fn sidecars_are_verified() {
+        let df = frame(vec![("Korean", Series::new(vec![131f64, 93.0, 2.0]))]);
+        let sidecar = format!("{}\n", dataframe_checksum(&df));
+        assert_eq!(verify_dataframe(&sidecar, &df), Ok(()));
+
+        let tampered = frame(vec![("Korean", Series::new(vec![141f64, 93.0, 2.0]))]);
+        let (expected, found) = verify_dataframe(&sidecar, &tampered).unwrap_err();
+        assert_eq!(expected, dataframe_checksum(&df));
+        assert_eq!(found, dataframe_checksum(&tampered));
+    }
+}
@@ -0,0 +1,245 @@
+use crate::record_set::RecordSet;
+use crate::score::{Record, Subject};
+use peroxide::fuga::*;
+
+mod correlation;
+mod cuts;
+mod rank;
+mod synthetic;
+
+pub use correlation::{Correlation, CorrelationMatrix};
+pub use cuts::{GradeCutEstimate, MIN_TOP_GRADE};
+pub use rank::{RankTable, RankedEntry};
+pub use synthetic::{CohortProfile, SubjectProfile, GRADE_PERCENTILES};
+
+/// A value every record in a cohort may or may not have
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Metric {
+    StandardScore(Subject),
+    Percentile(Subject),
+    Grade(Subject),
+    /// 탐구 백분위 합 over the recorded science subjects
+    SciencePercentileSum,
+}
+
+impl Metric {
+    pub fn value(&self, record: &Record) -> Option<f64> {
+        match *self {
+            Metric::StandardScore(subject) => record.try_score(subject).map(|s| s.standard_score()),
+            Metric::Percentile(subject) => record.try_score(subject).map(|s| s.percentile()),
+            Metric::Grade(subject) => record.try_score(subject).map(|s| s.rank().into()),
+            Metric::SciencePercentileSum => {
+                let sciences: Vec<f64> = [Subject::Chemistry, Subject::EarthScience]
+                    .iter()
+                    .filter_map(|&s| record.try_score(s))
+                    .map(|s| s.percentile())
+                    .collect();
+                (!sciences.is_empty()).then(|| sciences.iter().sum())
+            }
+        }
+    }
+
+    // A lower grade is a better result
+    fn higher_is_better(&self) -> bool {
+        !matches!(self, Metric::Grade(_))
+    }
+}
+
+/// Summary of one metric over a cohort
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SubjectStats {
+    pub mean: f64,
+    /// Sample standard deviation, 0 for fewer than two records
+    pub std: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    /// Records that have the metric
+    pub n: usize,
+    /// Records left out because they lack the metric
+    pub skipped: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Cohort {
+    records: Vec<Record>,
+}
+
+impl Cohort {
+    pub fn new(records: Vec<Record>) -> Self {
+        Self { records }
+    }
+
+    /// Every record stored under `data`, sorted by name
+    #[cfg(feature = "parquet")]
+    pub fn load_all() -> crate::error::Result<Self> {
+        Self::load_all_in(crate::score::DATA_DIR)
+    }
+
+    /// `load_all` under `dir` instead of `data`
+    #[cfg(feature = "parquet")]
+    pub fn load_all_in<P: AsRef<std::path::Path>>(dir: P) -> crate::error::Result<Self> {
+        let dir = dir.as_ref();
+        let records = crate::score::list_records(dir)?
+            .iter()
+            .map(|stored| Record::read_parquet_in(dir, &stored.name, &Default::default()))
+            .collect::<crate::error::Result<Vec<_>>>()?;
+        Ok(Self { records })
+    }
+
+    /// `load_all_in` reading only `subjects` (and the exam and tags) of each record,
+    /// see `Record::read_parquet_subjects_in`
+    #[cfg(feature = "parquet")]
+    pub fn load_all_subjects<P: AsRef<std::path::Path>>(
+        dir: P,
+        subjects: &[Subject],
+    ) -> crate::error::Result<Self> {
+        let dir = dir.as_ref();
+        let records = crate::score::list_records(dir)?
+            .iter()
+            .map(|stored| Record::read_parquet_subjects_in(dir, &stored.name, subjects))
+            .collect::<crate::error::Result<Vec<_>>>()?;
+        Ok(Self { records })
+    }
+
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Every record scored at every university with data for `year`, with
+    /// the records spread over the rayon thread pool. Same result as
+    /// `report::matrix(self.records(), &University::supported(year), year)`:
+    /// rows in the order of the records, columns in that of
+    /// `University::supported`.
+    ///
+    /// ```
+    /// use suneung_calc::cohort::Cohort;
+    /// use suneung_calc::report::matrix;
+    /// use suneung_calc::score::{Record, University};
+    ///
+    /// let records = Record::samples();
+    /// let parallel = Cohort::new(records.clone()).calc_matrix_par(2024);
+    /// assert_eq!(parallel, matrix(&records, &University::supported(2024), 2024));
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn calc_matrix_par(&self, year: usize) -> crate::report::Matrix {
+        let universities = crate::score::University::supported(year);
+        crate::report::matrix_par(&self.records, &universities, year)
+    }
+
+    /// Statistics of the standard score of `subject` (the grade for English,
+    /// which has no standard score).
+    pub fn stats(&self, subject: Subject) -> Option<SubjectStats> {
+        match subject {
+            Subject::English => self.stats_of(Metric::Grade(subject)),
+            _ => self.stats_of(Metric::StandardScore(subject)),
+        }
+    }
+
+    /// Statistics of `metric` over the records that have it, or `None` if
+    /// no record does.
+    pub fn stats_of(&self, metric: Metric) -> Option<SubjectStats> {
+        let values: Vec<f64> = self
+            .records
+            .iter()
+            .filter_map(|r| metric.value(r))
+            .collect();
+        if values.is_empty() {
+            return None;
+        }
+        let n = values.len();
+        Some(SubjectStats {
+            mean: values.mean(),
+            std: if n > 1 { values.sd() } else { 0f64 },
+            median: values.median(),
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            n,
+            skipped: self.records.len() - n,
+        })
+    }
+
+    /// The records accepted by `predicate`, in their order, as a cohort of
+    /// their own for statistics and reports
+    pub fn filter<F: Fn(&Record) -> bool>(&self, predicate: F) -> Cohort {
+        Cohort::new(
+            self.records
+                .iter()
+                .filter(|r| predicate(r))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// `filter` by `Record::has_tag`
+    ///
+    /// ```
+    /// use suneung_calc::cohort::Cohort;
+    /// use suneung_calc::score::{Record, Subject};
+    ///
+    /// let mut records = Record::samples();
+    /// records[0].add_tag("재수생");
+    /// records[2].add_tag("재수생");
+    /// let cohort = Cohort::new(records.clone());
+    ///
+    /// let repeaters = cohort.filter_tag(" 재수생");
+    /// assert_eq!(repeaters.len(), 2);
+    /// assert_eq!(repeaters.records()[1].name(), records[2].name());
+    /// assert!(repeaters.stats(Subject::Math).is_some());
+    /// assert!(cohort.filter_tag("A반").is_empty());
+    /// ```
+    pub fn filter_tag(&self, tag: &str) -> Cohort {
+        self.filter(|r| r.has_tag(tag))
+    }
+
+    pub fn count_where<F: Fn(&Record) -> bool>(&self, predicate: F) -> usize {
+        self.records.iter().filter(|r| predicate(r)).count()
+    }
+
+    /// Share (0-100) of the cohort that `record` does at least as well as on
+    /// `metric`, counting only records that have the metric.
+    pub fn percentile_of(&self, record: &Record, metric: Metric) -> Option<f64> {
+        let x = metric.value(record)?;
+        let values: Vec<f64> = self
+            .records
+            .iter()
+            .filter_map(|r| metric.value(r))
+            .collect();
+        if values.is_empty() {
+            return None;
+        }
+        let at_or_below = values
+            .iter()
+            .filter(|&&v| {
+                if metric.higher_is_better() {
+                    v <= x
+                } else {
+                    v >= x
+                }
+            })
+            .count();
+        Some(at_or_below as f64 / values.len() as f64 * 100f64)
+    }
+}
+
+impl From<Vec<Record>> for Cohort {
+    fn from(records: Vec<Record>) -> Self {
+        Self { records }
+    }
+}
+
+impl From<RecordSet> for Cohort {
+    fn from(set: RecordSet) -> Self {
+        Self {
+            records: set.records().to_vec(),
+        }
+    }
+}
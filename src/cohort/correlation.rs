@@ -0,0 +1,126 @@
+//! Pearson correlation between subjects over the records of a cohort
+
+use super::{Cohort, Metric};
+use crate::error::{Result, SuneungError};
+use crate::score::Subject;
+use peroxide::fuga::*;
+
+/// Subjects with a 표준점수, in the order of `Subject::all`
+const SCORED_SUBJECTS: [Subject; 4] = [
+    Subject::Korean,
+    Subject::Math,
+    Subject::Chemistry,
+    Subject::EarthScience,
+];
+
+/// Pearson correlation coefficient and the number of records behind it
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Correlation {
+    pub r: f64,
+    /// Records that have both subjects
+    pub n: usize,
+}
+
+/// Correlation of every pair of subjects with a 표준점수
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorrelationMatrix {
+    pub subjects: Vec<Subject>,
+    /// `cells[i][j]` for `subjects[i]` and `subjects[j]`, `None` where
+    /// `Cohort::correlation` fails
+    pub cells: Vec<Vec<Option<Correlation>>>,
+}
+
+impl Cohort {
+    /// Pearson correlation of `metric` (`Metric::StandardScore` or
+    /// `Metric::Percentile`) between `a` and `b`, over the records that have
+    /// both. `InsufficientData` with fewer than two such records or when
+    /// either subject has the same value in all of them.
+    ///
+    /// ```
+    /// use suneung_calc::cohort::{Cohort, Metric};
+    /// use suneung_calc::score::{Grade, Percentile, Record, StandardScore, Subject};
+    ///
+    /// let grade = Grade::try_from(3u8).unwrap();
+    /// let records: Vec<Record> = (0..5)
+    ///     .map(|i| {
+    ///         let x = i as f64;
+    ///         let mut record = Record::new(&format!("학생{}", i));
+    ///         record.record_typed(
+    ///             Subject::Korean,
+    ///             StandardScore::new(100f64 + x).unwrap(),
+    ///             Percentile::new(50f64 + x).unwrap(),
+    ///             grade,
+    ///         );
+    ///         record.record_typed(
+    ///             Subject::Math,
+    ///             StandardScore::new(90f64 + 2f64 * x).unwrap(),
+    ///             Percentile::new(80f64 - x).unwrap(),
+    ///             grade,
+    ///         );
+    ///         record
+    ///     })
+    ///     .collect();
+    /// let mut cohort = records.clone();
+    /// cohort.push(Record::new("국어 없음"));
+    /// let cohort = Cohort::new(cohort);
+    ///
+    /// let standard = cohort.correlation(Subject::Korean, Subject::Math, Metric::StandardScore).unwrap();
+    /// assert!((standard.r - 1f64).abs() < 1e-12);
+    /// assert_eq!(standard.n, 5);
+    /// let percentile = cohort.correlation(Subject::Korean, Subject::Math, Metric::Percentile).unwrap();
+    /// assert!((percentile.r + 1f64).abs() < 1e-12);
+    /// assert!(cohort.correlation(Subject::Korean, Subject::Chemistry, Metric::StandardScore).is_err());
+    /// ```
+    pub fn correlation(
+        &self,
+        a: Subject,
+        b: Subject,
+        metric: fn(Subject) -> Metric,
+    ) -> Result<Correlation> {
+        let (xs, ys): (Vec<f64>, Vec<f64>) = self
+            .records()
+            .iter()
+            .filter_map(|r| Some((metric(a).value(r)?, metric(b).value(r)?)))
+            .unzip();
+        let n = xs.len();
+        let insufficient = |reason: String| {
+            Err(SuneungError::InsufficientData(format!(
+                "correlation of {} and {}: {}",
+                a.name(),
+                b.name(),
+                reason
+            )))
+        };
+        if n < 2 {
+            return insufficient(format!("{} records have both subjects", n));
+        }
+        for (subject, values) in [(a, &xs), (b, &ys)] {
+            if values.iter().all(|&v| v == values[0]) {
+                return insufficient(format!("every {} value is {}", subject.name(), values[0]));
+            }
+        }
+        Ok(Correlation {
+            r: cor(&xs, &ys),
+            n,
+        })
+    }
+
+    /// `correlation` of every pair of 국어, 수학, 화학Ⅰ and 지구과학Ⅰ,
+    /// leaving out subjects no record has
+    pub fn correlation_matrix(&self, metric: fn(Subject) -> Metric) -> CorrelationMatrix {
+        let subjects: Vec<Subject> = SCORED_SUBJECTS
+            .into_iter()
+            .filter(|&s| self.records().iter().any(|r| r.contains(s)))
+            .collect();
+        let cells = subjects
+            .iter()
+            .map(|&a| {
+                subjects
+                    .iter()
+                    .map(|&b| self.correlation(a, b, metric).ok())
+                    .collect()
+            })
+            .collect();
+        CorrelationMatrix { subjects, cells }
+    }
+}
@@ -0,0 +1,100 @@
+//! 등급컷 of a cohort: the scores at which the national grade shares (4%,
+//! 11%, 23%, ...) are reached within the cohort, e.g. for an academy's own
+//! estimate after an internal 모의고사
+
+use super::{Cohort, Metric, GRADE_PERCENTILES};
+use crate::score::{Grade, Subject};
+
+/// Fewer students than this in 1등급 make the upper cuts unstable
+pub const MIN_TOP_GRADE: usize = 10;
+
+/// `Cohort::estimate_grade_cuts` of one subject
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradeCutEstimate {
+    /// Lowest score of 1등급 to 8등급, best grade first
+    pub cuts: Vec<(Grade, f64)>,
+    /// Records with the subject
+    pub n: usize,
+    /// Fewer than `MIN_TOP_GRADE` records fall in 1등급, so the upper cuts
+    /// rest on a handful of students
+    pub small_sample: bool,
+}
+
+impl Cohort {
+    /// Lowest `metric` (`Metric::StandardScore` or `Metric::Percentile`) of
+    /// `subject` within the top 4%, 11%, 23%, ... of the records that have
+    /// it, i.e. the `⌈share × n⌉`th best. No cuts for a cohort without the
+    /// subject.
+    ///
+    /// ```
+    /// use suneung_calc::cohort::{Cohort, Metric};
+    /// use suneung_calc::score::{Grade, Percentile, Record, StandardScore, Subject};
+    ///
+    /// // 표준점수 1 to 100, one student each: the top 4% are 97-100
+    /// let grade = Grade::try_from(5u8).unwrap();
+    /// let records = (1..=100)
+    ///     .map(|s| {
+    ///         let mut record = Record::new(&format!("학생{}", s));
+    ///         record.record_typed(
+    ///             Subject::Math,
+    ///             StandardScore::new(s as f64).unwrap(),
+    ///             Percentile::new(50.0).unwrap(),
+    ///             grade,
+    ///         );
+    ///         record
+    ///     })
+    ///     .collect();
+    /// let estimate = Cohort::new(records).estimate_grade_cuts(Subject::Math, Metric::StandardScore);
+    ///
+    /// let cuts: Vec<f64> = estimate.cuts.iter().map(|&(_, cut)| cut).collect();
+    /// assert_eq!(cuts, [97.0, 90.0, 78.0, 61.0, 41.0, 24.0, 12.0, 5.0]);
+    /// assert_eq!(estimate.cuts[0].0.get(), 1);
+    /// // Four students in 1등급
+    /// assert!(estimate.small_sample);
+    /// ```
+    pub fn estimate_grade_cuts(
+        &self,
+        subject: Subject,
+        metric: fn(Subject) -> Metric,
+    ) -> GradeCutEstimate {
+        let mut values: Vec<f64> = self
+            .records()
+            .iter()
+            .filter_map(|r| metric(subject).value(r))
+            .collect();
+        values.sort_by(|a, b| b.total_cmp(a));
+        let n = values.len();
+        // Multiplying first keeps whole shares exact
+        let within = |percentile: f64| ((100f64 - percentile) * n as f64 / 100f64).ceil() as usize;
+        let cuts = if n == 0 {
+            vec![]
+        } else {
+            GRADE_PERCENTILES
+                .iter()
+                .enumerate()
+                .map(|(k, &p)| {
+                    let grade = Grade::try_from(k as u8 + 1).expect("8 cuts");
+                    (grade, values[within(p).clamp(1, n) - 1])
+                })
+                .collect()
+        };
+        GradeCutEstimate {
+            cuts,
+            n,
+            small_sample: within(GRADE_PERCENTILES[0]) < MIN_TOP_GRADE,
+        }
+    }
+
+    /// `estimate_grade_cuts` of every subject with a 표준점수 that some
+    /// record has, in the order of `Subject::all`
+    pub fn estimate_all_grade_cuts(
+        &self,
+        metric: fn(Subject) -> Metric,
+    ) -> Vec<(Subject, GradeCutEstimate)> {
+        Subject::all()
+            .iter()
+            .filter(|&&s| s != Subject::English && self.records().iter().any(|r| r.contains(s)))
+            .map(|&s| (s, self.estimate_grade_cuts(s, metric)))
+            .collect()
+    }
+}
@@ -0,0 +1,128 @@
+//! Students of a cohort ordered by their converted score at one university
+
+use super::Cohort;
+use crate::report::{matrix, Matrix, MatrixCell};
+use crate::score::University;
+use serde::Serialize;
+
+/// Place of one student in a `RankTable`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RankedEntry {
+    /// Standard competition rank (1, 2, 2, 4); `None` for ineligible
+    /// students
+    pub rank: Option<usize>,
+    pub name: String,
+    pub score: Option<f64>,
+    /// Whether the student could be scored at the university
+    pub eligible: bool,
+    /// `SuneungError::code` of why an ineligible student could not be
+    /// scored, e.g. `missing_subject`
+    pub reason: Option<&'static str>,
+}
+
+/// Every student of a cohort at one university: the eligible ones best
+/// first, then the ineligible ones in the order of the cohort
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RankTable {
+    pub university: University,
+    pub year: usize,
+    pub entries: Vec<RankedEntry>,
+}
+
+impl RankTable {
+    /// Eligible entries with a rank of at most `n`; more than `n` entries
+    /// when students tie at the `n`th place
+    pub fn top(&self, n: usize) -> &[RankedEntry] {
+        let end = self
+            .entries
+            .iter()
+            .position(|e| e.rank.is_none_or(|rank| rank > n))
+            .unwrap_or(self.entries.len());
+        &self.entries[..end]
+    }
+
+    /// One table per column of `matrix`, without scoring anything again
+    pub fn from_matrix(matrix: &Matrix) -> Vec<RankTable> {
+        matrix
+            .universities
+            .iter()
+            .enumerate()
+            .map(|(j, &university)| {
+                let mut scored = vec![];
+                let mut ineligible = vec![];
+                for row in &matrix.rows {
+                    match &row.cells[j] {
+                        MatrixCell::Score { value } => scored.push((row.name.clone(), *value)),
+                        MatrixCell::Blank { code, .. } => ineligible.push(RankedEntry {
+                            rank: None,
+                            name: row.name.clone(),
+                            score: None,
+                            eligible: false,
+                            reason: Some(*code),
+                        }),
+                    }
+                }
+                // Stable, so ties keep the order of the cohort
+                scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+                let mut entries: Vec<RankedEntry> = vec![];
+                for (i, (name, score)) in scored.into_iter().enumerate() {
+                    let rank = match entries.last() {
+                        Some(last) if last.score == Some(score) => last.rank,
+                        _ => Some(i + 1),
+                    };
+                    entries.push(RankedEntry {
+                        rank,
+                        name,
+                        score: Some(score),
+                        eligible: true,
+                        reason: None,
+                    });
+                }
+                entries.extend(ineligible);
+                RankTable {
+                    university,
+                    year: matrix.year,
+                    entries,
+                }
+            })
+            .collect()
+    }
+
+    pub fn ineligible(&self) -> &[RankedEntry] {
+        let start = self.entries.iter().position(|e| !e.eligible);
+        &self.entries[start.unwrap_or(self.entries.len())..]
+    }
+}
+
+impl Cohort {
+    /// The students ranked by their converted score at `univ` for `year`,
+    /// see `RankTable`. Ties share a rank and keep the order of the
+    /// cohort.
+    ///
+    /// ```
+    /// use suneung_calc::cohort::Cohort;
+    /// use suneung_calc::score::{Record, University};
+    ///
+    /// let mut records = Record::samples();
+    /// records.push(records[1].clone());
+    /// let ranked = Cohort::new(records).rank_by(University::KONKUK, 2024);
+    ///
+    /// let ranks: Vec<_> = ranked.iter().map(|e| e.rank).collect();
+    /// assert_eq!(ranks, [Some(1), Some(2), Some(2), Some(4), Some(5), None]);
+    /// assert_eq!(ranked[5].name, "샘플 과탐 1과목");
+    /// assert_eq!(ranked[5].reason, Some("missing_subject"));
+    /// ```
+    pub fn rank_by(&self, univ: University, year: usize) -> Vec<RankedEntry> {
+        self.rank_table(&[univ], year)
+            .pop()
+            .map(|table| table.entries)
+            .unwrap_or_default()
+    }
+
+    /// `rank_by` for each of `universities`, scoring the cohort once
+    pub fn rank_table(&self, universities: &[University], year: usize) -> Vec<RankTable> {
+        RankTable::from_matrix(&matrix(self.records(), universities, year))
+    }
+}
@@ -0,0 +1,313 @@
+//! Synthetic cohorts for demos and load testing: records drawn from normal
+//! distributions fitted to the published 등급컷 of a 수능, so that the
+//! scores fall where those of real takers would.
+
+use super::Cohort;
+use crate::error::{Result, SuneungError};
+use crate::score::{Grade, Percentile, Record, Score, StandardScore, Subject};
+use crate::suneung_data::*;
+use peroxide::fuga::{stdrng_from_seed, TPDist, RNG};
+
+/// 백분위 at the lower end of 1등급 to 8등급, as used in `suneung_data`
+pub const GRADE_PERCENTILES: [f64; 8] = [96f64, 89f64, 77f64, 60f64, 40f64, 23f64, 11f64, 4f64];
+/// Standard normal quantiles of `GRADE_PERCENTILES`
+const GRADE_Z: [f64; 8] = [
+    1.7507, 1.2265, 0.7388, 0.2533, -0.2533, -0.7388, -1.2265, -1.7507,
+];
+
+/// Normal distribution of the 표준점수 of one subject
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SubjectProfile {
+    pub mean: f64,
+    pub std: f64,
+}
+
+impl SubjectProfile {
+    pub fn new(mean: f64, std: f64) -> Self {
+        Self { mean, std }
+    }
+
+    /// Distribution fitted by least squares to the 등급컷 `cuts` (lowest
+    /// 표준점수 of 1등급 to 8등급), read as the quantiles of
+    /// `GRADE_PERCENTILES`
+    ///
+    /// ```
+    /// use suneung_calc::cohort::SubjectProfile;
+    ///
+    /// let cuts = [131f64, 125f64, 117f64, 107f64, 96f64, 84f64, 72f64, 63f64];
+    /// let korean = SubjectProfile::from_cuts(cuts);
+    /// assert!((korean.mean - 99.375).abs() < 1e-9);
+    /// assert!((19f64..22f64).contains(&korean.std));
+    /// ```
+    pub fn from_cuts(cuts: [f64; 8]) -> Self {
+        // The quantiles are symmetric around 0, so the intercept is the
+        // mean of the cuts
+        let mean = cuts.iter().sum::<f64>() / cuts.len() as f64;
+        let zz: f64 = GRADE_Z.iter().map(|z| z * z).sum();
+        let zs: f64 = GRADE_Z.iter().zip(&cuts).map(|(z, s)| z * s).sum();
+        Self::new(mean, zs / zz)
+    }
+
+    fn from_usize(cuts: [usize; 8]) -> Self {
+        Self::from_cuts(cuts.map(|c| c as f64))
+    }
+
+    /// Reject a profile that cannot be drawn from: a mean that is not a
+    /// finite number, or a standard deviation that is not positive
+    ///
+    /// ```
+    /// use suneung_calc::cohort::SubjectProfile;
+    ///
+    /// assert!(SubjectProfile::new(100f64, 20f64).validate().is_ok());
+    /// assert!(SubjectProfile::new(100f64, 0f64).validate().is_err());
+    /// assert!(SubjectProfile::new(f64::NAN, 20f64).validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        if !self.mean.is_finite() {
+            return Err(SuneungError::InvalidInput(format!(
+                "mean of {} is not a finite number",
+                self.mean
+            )));
+        }
+        if !(self.std.is_finite() && self.std > 0f64) {
+            return Err(SuneungError::InvalidInput(format!(
+                "standard deviation of {} is not a positive number",
+                self.std
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// What `Cohort::synthesize` draws from
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CohortProfile {
+    pub korean: SubjectProfile,
+    pub math: SubjectProfile,
+    pub chemistry: SubjectProfile,
+    pub earth_science: SubjectProfile,
+    /// Correlation of the Korean and Math 표준점수, clamped to -1..=1. The
+    /// default of 0.7 is an assumption, not a published figure; the other
+    /// subjects are drawn independently.
+    pub korean_math_correlation: f64,
+    /// 백분위 at the lower end of 1등급 to 8등급, `GRADE_PERCENTILES` by
+    /// default
+    pub grade_cuts: [f64; 8],
+    /// Share of English takers, in percent, at 1등급 to 8등급 or better.
+    /// The default is a rough shape of recent 절대평가 results (1등급
+    /// about 5%), not the figures of any one year.
+    pub english_shares: [f64; 8],
+}
+
+impl CohortProfile {
+    /// Profile fitted to the 등급컷 of the 수능 for `year` in
+    /// `suneung_data`, or `None` for a year without them
+    ///
+    /// ```
+    /// use suneung_calc::cohort::CohortProfile;
+    ///
+    /// let profile = CohortProfile::for_year(2024).unwrap();
+    /// assert!((90f64..110f64).contains(&profile.korean.mean));
+    /// assert!(CohortProfile::for_year(2019).is_none());
+    /// ```
+    pub fn for_year(year: usize) -> Option<Self> {
+        let (korean, math, chemistry, earth_science) = match year {
+            2025 => (KOREAN_2025, MATH_2025, CHEM_2025, EARSCI_2025),
+            2024 => (KOREAN_2024, MATH_2024, CHEM_2024, EARSCI_2024),
+            2023 => (KOREAN_2023, MATH_2023, CHEM_2023, EARSCI_2023),
+            2022 => (KOREAN_2022, MATH_2022, CHEM_2022, EARSCI_2022),
+            _ => return None,
+        };
+        Some(Self {
+            korean: SubjectProfile::from_usize(korean),
+            math: SubjectProfile::from_usize(math),
+            chemistry: SubjectProfile::from_usize(chemistry),
+            earth_science: SubjectProfile::from_usize(earth_science),
+            korean_math_correlation: 0.7,
+            grade_cuts: GRADE_PERCENTILES,
+            english_shares: [5f64, 20f64, 45f64, 65f64, 80f64, 90f64, 95f64, 98f64],
+        })
+    }
+
+    /// Reject a profile that `Cohort::synthesize` cannot draw from: a
+    /// subject failing `SubjectProfile::validate`, or a correlation, 등급컷
+    /// or English share that is not a finite number
+    pub fn validate(&self) -> Result<()> {
+        for (subject, p) in [
+            (Subject::Korean, &self.korean),
+            (Subject::Math, &self.math),
+            (Subject::Chemistry, &self.chemistry),
+            (Subject::EarthScience, &self.earth_science),
+        ] {
+            if let Err(SuneungError::InvalidInput(m)) = p.validate() {
+                return Err(SuneungError::InvalidInput(format!(
+                    "{}: {}",
+                    subject.name(),
+                    m
+                )));
+            }
+        }
+        let invalid = |what: &str| {
+            Err(SuneungError::InvalidInput(format!(
+                "{} is not a finite number",
+                what
+            )))
+        };
+        if !self.korean_math_correlation.is_finite() {
+            return invalid("korean_math_correlation");
+        }
+        if !self.grade_cuts.iter().all(|c| c.is_finite()) {
+            return invalid("a grade cut");
+        }
+        if !self.english_shares.iter().all(|s| s.is_finite()) {
+            return invalid("an English share");
+        }
+        Ok(())
+    }
+
+    fn subject(&self, subject: Subject) -> Option<&SubjectProfile> {
+        match subject {
+            Subject::Korean => Some(&self.korean),
+            Subject::Math => Some(&self.math),
+            Subject::Chemistry => Some(&self.chemistry),
+            Subject::EarthScience => Some(&self.earth_science),
+            Subject::English => None,
+        }
+    }
+
+    /// `standard` rounded and kept within 0-200, with the 백분위 it has
+    /// under the subject's distribution and the 등급 of that 백분위;
+    /// `None` for English
+    pub(crate) fn score_at(&self, subject: Subject, standard: f64) -> Option<Score> {
+        let p = self.subject(subject)?;
+        let standard = standard.round().clamp(0f64, 200f64);
+        let percentile = (100f64 * standard_normal().cdf((standard - p.mean) / p.std))
+            .round()
+            .clamp(0f64, 100f64);
+        Some(Score::new_typed(
+            StandardScore::new(standard).ok()?,
+            Percentile::new(percentile).ok()?,
+            grade(self.grade(percentile)),
+        ))
+    }
+
+    /// Lowest 표준점수 with a 백분위 of 100 under the subject's
+    /// distribution, an estimate of its 최고점; `None` for English
+    pub(crate) fn ceiling(&self, subject: Subject) -> Option<f64> {
+        (0..=200)
+            .map(f64::from)
+            .find(|&s| {
+                self.score_at(subject, s)
+                    .is_some_and(|score| score.percentile() == 100f64)
+            })
+            .or(self.subject(subject).map(|_| 200f64))
+    }
+
+    // 1등급 at or above the first cut, 9등급 below the last
+    fn grade(&self, percentile: f64) -> u8 {
+        1 + self.grade_cuts.iter().filter(|&&c| percentile < c).count() as u8
+    }
+
+    // Grade of an English taker `z` standard deviations above the mean
+    fn english_grade(&self, z: f64) -> u8 {
+        let above = 100f64 * (1f64 - standard_normal().cdf(z));
+        1 + self.english_shares.iter().filter(|&&s| above > s).count() as u8
+    }
+}
+
+/// The profile of the latest year in `suneung_data`
+impl Default for CohortProfile {
+    fn default() -> Self {
+        Self::for_year(2025).expect("2025 has 등급컷")
+    }
+}
+
+impl Cohort {
+    /// `n` synthetic records with every subject, drawn from `profile` with
+    /// a generator seeded by `seed`: the same arguments give the same
+    /// cohort. The 표준점수 is rounded and kept within 0-200, the 백분위
+    /// is that of the 표준점수 under the subject's normal distribution and
+    /// the 등급 follows from the 백분위 by `CohortProfile::grade_cuts`, so a
+    /// higher 표준점수 never has a lower 백분위 or a worse 등급. A profile
+    /// failing `CohortProfile::validate` is `SuneungError::InvalidInput`.
+    ///
+    /// ```
+    /// use suneung_calc::cohort::{Cohort, CohortProfile};
+    /// use suneung_calc::score::Subject;
+    ///
+    /// let profile = CohortProfile::default();
+    /// let cohort = Cohort::synthesize(500, &profile, 42).unwrap();
+    /// assert_eq!(cohort.len(), 500);
+    /// assert_eq!(
+    ///     format!("{:?}", cohort),
+    ///     format!("{:?}", Cohort::synthesize(500, &profile, 42).unwrap())
+    /// );
+    ///
+    /// let korean = cohort.stats(Subject::Korean).unwrap();
+    /// assert!((korean.mean - profile.korean.mean).abs() < 3f64);
+    /// // About 4% of takers are in 1등급
+    /// let first = cohort.count_where(|r| r.try_score(Subject::Korean).unwrap().rank().get() == 1);
+    /// assert!((5..=40).contains(&first));
+    ///
+    /// let mut scores: Vec<_> = cohort
+    ///     .records()
+    ///     .iter()
+    ///     .map(|r| r.try_score(Subject::Math).unwrap())
+    ///     .collect();
+    /// scores.sort_by(|a, b| a.standard_score().total_cmp(&b.standard_score()));
+    /// for pair in scores.windows(2) {
+    ///     assert!((0f64..=100f64).contains(&pair[0].percentile()));
+    ///     assert!(pair[0].percentile() <= pair[1].percentile());
+    ///     assert!(pair[0].rank() >= pair[1].rank());
+    /// }
+    ///
+    /// let mut flat = profile;
+    /// flat.math.std = 0f64;
+    /// assert!(Cohort::synthesize(500, &flat, 42).is_err());
+    /// ```
+    pub fn synthesize(n: usize, profile: &CohortProfile, seed: u64) -> Result<Cohort> {
+        profile.validate()?;
+        let mut rng = stdrng_from_seed(seed);
+        let rho = profile.korean_math_correlation.clamp(-1f64, 1f64);
+        let width = n.to_string().len();
+        let records = (0..n)
+            .map(|i| {
+                let z = standard_normal().sample_with_rng(&mut rng, 5);
+                // Cholesky factor of the 2x2 correlation matrix
+                let korean = z[0];
+                let math = rho * z[0] + (1f64 - rho * rho).sqrt() * z[1];
+
+                let mut record = Record::new(&format!("가상 {:0width$}", i + 1));
+                for (subject, p, z) in [
+                    (Subject::Korean, &profile.korean, korean),
+                    (Subject::Math, &profile.math, math),
+                    (Subject::Chemistry, &profile.chemistry, z[2]),
+                    (Subject::EarthScience, &profile.earth_science, z[3]),
+                ] {
+                    let standard = p.mean + p.std * z;
+                    let score = profile.score_at(subject, standard).ok_or_else(|| {
+                        SuneungError::InvalidInput(format!(
+                            "{} drew 표준점수 {} from {:?}",
+                            subject.name(),
+                            standard,
+                            p
+                        ))
+                    })?;
+                    record.record_score(subject, score);
+                }
+                let english = grade(profile.english_grade(z[4]));
+                record.record_score(Subject::English, Score::grade_only(english));
+                Ok(record)
+            })
+            .collect::<Result<_>>()?;
+        Ok(Cohort { records })
+    }
+}
+
+fn standard_normal() -> TPDist<f64> {
+    TPDist::Normal(0f64, 1f64)
+}
+
+fn grade(grade: u8) -> Grade {
+    Grade::try_from(grade).expect("grades from 8 cuts are 1-9")
+}
@@ -0,0 +1,217 @@
+//! Settings shared by every `suneung` invocation, read from
+//! `~/.config/suneung/config.toml` or a file given with `--config`.
+//!
+//! Precedence, highest first: command line flags, the config file, then the
+//! built-in defaults (`DATA_DIR`, every university, Korean names). `merge`
+//! applies the first rule field by field; a field left unset at every level
+//! means the built-in default.
+//!
+//! ```toml
+//! data_dir = "~/students"
+//! default_year = 2025
+//! universities = ["경희대", "KONKUK", "동국"]
+//! locale = "ko"
+//!
+//! # percent of the cut above which a score is green, and below which it
+//! # turns from yellow to red, in colored `compare` tables
+//! [color]
+//! clear = 1.0
+//! borderline = 1.0
+//! ```
+
+use crate::admission::ColorThresholds;
+use crate::error::{Result, SuneungError};
+use crate::score::{Locale, University};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Keys a config file may contain
+pub const KEYS: [&str; 5] = [
+    "data_dir",
+    "default_year",
+    "universities",
+    "locale",
+    "color",
+];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+    /// Data root; `~/` is expanded, and a relative path in a file is taken
+    /// relative to the directory of that file
+    pub data_dir: Option<PathBuf>,
+    /// 학년도 used when a command is run without `--year`
+    pub default_year: Option<usize>,
+    /// Universities compared when none are listed on the command line
+    pub universities: Option<Vec<University>>,
+    /// Language of university names and report labels
+    pub locale: Option<Locale>,
+    /// Cut margins of colored tables; `ColorThresholds::default` if unset
+    pub color: Option<ColorThresholds>,
+}
+
+// On-disk form, checked against `KEYS` before it is deserialized
+#[derive(Debug, Deserialize)]
+struct ConfigFile {
+    data_dir: Option<PathBuf>,
+    default_year: Option<usize>,
+    universities: Option<Vec<String>>,
+    locale: Option<String>,
+    color: Option<ColorThresholds>,
+}
+
+impl Config {
+    /// `$XDG_CONFIG_HOME/suneung/config.toml`, falling back to
+    /// `~/.config/suneung/config.toml`
+    pub fn default_path() -> Option<PathBuf> {
+        let base = match std::env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => home_dir()?.join(".config"),
+        };
+        Some(base.join("suneung").join("config.toml"))
+    }
+
+    /// The file at `default_path`, or an empty config if there is none
+    pub fn load_default() -> Result<Self> {
+        match Self::default_path() {
+            Some(path) if path.exists() => Self::load(path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// Read `path`, which must exist. Errors are `Config` and name the file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = crate::input::read_to_string(path).map_err(|e| SuneungError::Config {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })?;
+        let mut config = Self::from_toml_str(&text).map_err(|e| match e {
+            SuneungError::Config { reason, .. } => SuneungError::Config {
+                path: path.to_path_buf(),
+                reason,
+            },
+            e => e,
+        })?;
+        if let Some(dir) = &config.data_dir {
+            if dir.is_relative() {
+                let base = path.parent().unwrap_or(Path::new(""));
+                config.data_dir = Some(base.join(dir));
+            }
+        }
+        Ok(config)
+    }
+
+    /// Parse a config file's contents. Unknown keys are rejected with the
+    /// closest known one as a suggestion. Paths are not resolved. Text over
+    /// `input::MAX_INPUT_BYTES` is rejected before it is parsed.
+    pub fn from_toml_str(text: &str) -> Result<Self> {
+        let invalid = |reason: String| SuneungError::Config {
+            path: PathBuf::new(),
+            reason,
+        };
+        if text.len() > crate::input::MAX_INPUT_BYTES {
+            return Err(invalid(format!(
+                "{} bytes, more than the limit of {}",
+                text.len(),
+                crate::input::MAX_INPUT_BYTES
+            )));
+        }
+        let table: toml::Table = text
+            .parse()
+            .map_err(|e: toml::de::Error| invalid(e.message().to_string()))?;
+        if let Some(key) = table.keys().find(|k| !KEYS.contains(&k.as_str())) {
+            let reason = match closest_key(key) {
+                Some(known) => format!("unknown key `{}` (did you mean `{}`?)", key, known),
+                None => format!("unknown key `{}`, expected one of {}", key, KEYS.join(", ")),
+            };
+            return Err(invalid(reason));
+        }
+        let file: ConfigFile = table
+            .try_into()
+            .map_err(|e: toml::de::Error| invalid(e.message().trim().to_string()))?;
+
+        let universities = file
+            .universities
+            .map(|names| {
+                names
+                    .iter()
+                    .map(|name| name.parse::<University>())
+                    .collect::<Result<Vec<_>>>()
+            })
+            .transpose()
+            .map_err(|e| invalid(format!("universities: {}", e)))?;
+        let locale = file
+            .locale
+            .map(|s| s.parse::<Locale>())
+            .transpose()
+            .map_err(|e| invalid(format!("locale: {}", e)))?;
+        if let Some(color) = &file.color {
+            if !(color.clear >= 0f64 && color.borderline >= 0f64) {
+                return Err(invalid(
+                    "color: margins must be zero or positive".to_string(),
+                ));
+            }
+        }
+        Ok(Self {
+            data_dir: file.data_dir.map(|dir| expand_home(&dir)),
+            default_year: file.default_year,
+            universities,
+            locale,
+            color: file.color,
+        })
+    }
+
+    /// `self` with every field that `overrides` sets replaced, e.g. a file
+    /// config merged with the flags given on the command line.
+    pub fn merge(self, overrides: Config) -> Config {
+        Config {
+            data_dir: overrides.data_dir.or(self.data_dir),
+            default_year: overrides.default_year.or(self.default_year),
+            universities: overrides.universities.or(self.universities),
+            locale: overrides.locale.or(self.locale),
+            color: overrides.color.or(self.color),
+        }
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .filter(|h| !h.is_empty())
+        .map(PathBuf::from)
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+// Known key within two edits of `key`, for typos like `dafault_year`
+fn closest_key(key: &str) -> Option<&'static str> {
+    KEYS.iter()
+        .map(|&known| (edit_distance(key, known), known))
+        .filter(|&(d, _)| d <= 2)
+        .min_by_key(|&(d, _)| d)
+        .map(|(_, known)| known)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = if ca == cb {
+                prev
+            } else {
+                1 + prev.min(row[j]).min(cur)
+            };
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
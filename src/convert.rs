@@ -0,0 +1,456 @@
+//! Estimated 표준점수 from 원점수. Right after an exam students only know
+//! their 원점수; the 표준점수, 백분위 and 등급 are published weeks later, and
+//! until then 입시 사이트 publish estimated conversion tables.
+//!
+//! Missing: conversion tables of the latest 수능 embedded in the crate.
+//! The crate has no sourced 원점수 data for any 수능 and does not make
+//! tables up, so until one is added with its source every table is
+//! supplied by the caller, with `RawConversionTable::from_csv_str` or
+//! `read_csv`. English is graded absolutely (절대평가, 1등급 from 90점
+//! in steps of 10) and converts without a table.
+
+#[cfg(feature = "csv")]
+use crate::display::label;
+use crate::error::{Result, SuneungError};
+use crate::score::{Grade, Locale, Percentile, Record, Score, StandardScore, Subject};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+#[cfg(feature = "csv")]
+use std::path::Path;
+
+/// 국어 선택과목
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum KoreanTrack {
+    /// 화법과 작문
+    SpeechWriting,
+    /// 언어와 매체
+    LanguageMedia,
+}
+
+/// 수학 선택과목
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum MathTrack {
+    /// 확률과 통계
+    Statistics,
+    /// 미적분
+    Calculus,
+    /// 기하
+    Geometry,
+}
+
+/// 선택과목 of 국어 or 수학, whose 원점수 convert differently
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Track {
+    Korean(KoreanTrack),
+    Math(MathTrack),
+}
+
+impl Track {
+    pub fn all() -> &'static [Track] {
+        &[
+            Track::Korean(KoreanTrack::SpeechWriting),
+            Track::Korean(KoreanTrack::LanguageMedia),
+            Track::Math(MathTrack::Statistics),
+            Track::Math(MathTrack::Calculus),
+            Track::Math(MathTrack::Geometry),
+        ]
+    }
+
+    pub fn subject(&self) -> Subject {
+        match self {
+            Track::Korean(_) => Subject::Korean,
+            Track::Math(_) => Subject::Math,
+        }
+    }
+
+    /// "화법과 작문", "언어와 매체", "확률과 통계", "미적분", "기하"
+    pub fn label(&self) -> &'static str {
+        match self {
+            Track::Korean(KoreanTrack::SpeechWriting) => "화법과 작문",
+            Track::Korean(KoreanTrack::LanguageMedia) => "언어와 매체",
+            Track::Math(MathTrack::Statistics) => "확률과 통계",
+            Track::Math(MathTrack::Calculus) => "미적분",
+            Track::Math(MathTrack::Geometry) => "기하",
+        }
+    }
+
+    /// `label` in `locale`
+    pub fn localized_label(&self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::Korean => self.label(),
+            Locale::English => match self {
+                Track::Korean(KoreanTrack::SpeechWriting) => "Speech and Writing",
+                Track::Korean(KoreanTrack::LanguageMedia) => "Language and Media",
+                Track::Math(MathTrack::Statistics) => "Probability and Statistics",
+                Track::Math(MathTrack::Calculus) => "Calculus",
+                Track::Math(MathTrack::Geometry) => "Geometry",
+            },
+        }
+    }
+
+    pub(crate) fn id(&self) -> &'static str {
+        match self {
+            Track::Korean(KoreanTrack::SpeechWriting) => "speech_writing",
+            Track::Korean(KoreanTrack::LanguageMedia) => "language_media",
+            Track::Math(MathTrack::Statistics) => "statistics",
+            Track::Math(MathTrack::Calculus) => "calculus",
+            Track::Math(MathTrack::Geometry) => "geometry",
+        }
+    }
+}
+
+impl fmt::Display for Track {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// Accepts the label (`미적분`, `언어와 매체`, also without spaces as on the
+/// 성적통지표) or the snake_case identifier (`calculus`)
+impl std::str::FromStr for Track {
+    type Err = SuneungError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Track::all()
+            .iter()
+            .copied()
+            .find(|t| {
+                t.label() == s || t.label().replace(' ', "") == s || t.id().eq_ignore_ascii_case(s)
+            })
+            .ok_or_else(|| SuneungError::UnknownName {
+                kind: "track",
+                name: s.to_string(),
+            })
+    }
+}
+
+/// One row of a conversion table
+#[derive(Debug, Copy, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ConversionRow {
+    pub raw: f64,
+    pub standard_score: f64,
+    pub percentile: f64,
+    pub grade: Grade,
+}
+
+// Subject and 선택과목 of a set of rows
+type TableKey = (Subject, Option<Track>);
+
+/// 원점수 to 표준점수/백분위/등급 rows of one exam, per subject and 선택과목
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RawConversionTable {
+    rows: HashMap<TableKey, Vec<ConversionRow>>,
+}
+
+impl RawConversionTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `rows` for `subject` and `track`, replacing what it had. The rows
+    /// may come in any order but must be consistent once sorted by 원점수:
+    /// no 원점수 twice, none outside the subject's range, and a higher
+    /// 원점수 never with a lower 표준점수 or 백분위 or a worse 등급.
+    pub fn insert(
+        &mut self,
+        subject: Subject,
+        track: Option<Track>,
+        mut rows: Vec<ConversionRow>,
+    ) -> Result<()> {
+        let invalid = |reason: String| Err(SuneungError::InvalidInput(reason));
+        let name = table_name(subject, track);
+        if subject == Subject::English {
+            return invalid("English is graded absolutely and has no conversion table".into());
+        }
+        if track.is_some_and(|t| t.subject() != subject) {
+            return invalid(format!(
+                "{} is not a track of {}",
+                track.unwrap().id(),
+                subject.name()
+            ));
+        }
+        for row in &rows {
+            check_raw(subject, row.raw)?;
+            StandardScore::new(row.standard_score)?;
+            Percentile::new(row.percentile)?;
+        }
+        rows.sort_by(|a, b| a.raw.total_cmp(&b.raw));
+        for pair in rows.windows(2) {
+            let (lower, upper) = (pair[0], pair[1]);
+            if lower.raw == upper.raw {
+                return invalid(format!("{}: raw score {} is given twice", name, lower.raw));
+            }
+            if upper.standard_score < lower.standard_score
+                || upper.percentile < lower.percentile
+                || upper.grade > lower.grade
+            {
+                return invalid(format!(
+                    "{}: raw score {} converts lower than {}",
+                    name, upper.raw, lower.raw
+                ));
+            }
+        }
+        self.rows.insert((subject, track), rows);
+        Ok(())
+    }
+
+    /// Rows of `subject` and `track`, by 원점수; `track` `None` also finds
+    /// the rows of the subject's only 선택과목
+    pub fn rows(&self, subject: Subject, track: Option<Track>) -> Option<&[ConversionRow]> {
+        if let Some(rows) = self.rows.get(&(subject, track)) {
+            return Some(rows);
+        }
+        if track.is_some() {
+            return None;
+        }
+        let mut tracks = self.rows.iter().filter(|((s, _), _)| *s == subject);
+        match (tracks.next(), tracks.next()) {
+            (Some((_, rows)), None) => Some(rows),
+            _ => None,
+        }
+    }
+
+    /// Read a table from a CSV file, see `from_csv_str`
+    #[cfg(feature = "csv")]
+    pub fn read_csv<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_csv_str(&crate::input::read_to_string(path.as_ref())?)
+    }
+
+    /// Parse a table with a `subject,track,raw,standard,percentile,grade`
+    /// header and one row per line. `subject` is the identifier or the
+    /// Korean name (`Math`, `수학`), `track` a `Track` or empty.
+    ///
+    /// ```
+    /// use suneung_calc::convert::{RawConversionTable, Track};
+    /// use suneung_calc::score::Subject;
+    ///
+    /// let table = RawConversionTable::from_csv_str(
+    ///     "subject,track,raw,standard,percentile,grade\n\
+    ///      수학,미적분,100,140,100,1\n\
+    ///      수학,미적분,84,131,96,1\n\
+    ///      Chemistry,,50,70,99,1\n",
+    /// )
+    /// .unwrap();
+    /// let calculus: Track = "calculus".parse().unwrap();
+    /// assert_eq!(table.rows(Subject::Math, Some(calculus)).unwrap()[0].raw, 84.0);
+    /// assert!(table.rows(Subject::Chemistry, None).is_some());
+    ///
+    /// let bad = "subject,track,raw,standard,percentile,grade\n수학,,90,120,80,3\n수학,,95,118,85,2\n";
+    /// assert!(RawConversionTable::from_csv_str(bad).is_err());
+    /// ```
+    #[cfg(feature = "csv")]
+    pub fn from_csv_str(text: &str) -> Result<Self> {
+        crate::input::check_len("conversion table", text)?;
+        let invalid = |line: usize, reason: String| {
+            SuneungError::InvalidInput(format!("conversion table line {}: {}", line, reason))
+        };
+        let mut rdr = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(text.as_bytes());
+        let mut grouped: Vec<(TableKey, Vec<ConversionRow>)> = vec![];
+        for (i, row) in rdr.records().enumerate() {
+            // +2: 1-based, after the header
+            let line = i + 2;
+            let row = row.map_err(|e| invalid(line, e.to_string()))?;
+            let field = |j: usize| row.get(j).unwrap_or_default();
+            let subject = Subject::all()
+                .iter()
+                .copied()
+                .find(|s| s.name().eq_ignore_ascii_case(field(0)) || label(*s) == field(0))
+                .ok_or_else(|| invalid(line, format!("unknown subject {:?}", field(0))))?;
+            let track = match field(1) {
+                "" => None,
+                track => Some(track.parse().map_err(|e| invalid(line, format!("{}", e)))?),
+            };
+            let number = |j: usize, what: &str| {
+                field(j)
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|x| x.is_finite())
+                    .ok_or_else(|| {
+                        invalid(line, format!("{} {:?} is not a number", what, field(j)))
+                    })
+            };
+            let parsed = ConversionRow {
+                raw: number(2, "raw")?,
+                standard_score: number(3, "standard")?,
+                percentile: number(4, "percentile")?,
+                grade: field(5)
+                    .parse()
+                    .map_err(|e| invalid(line, format!("{}", e)))?,
+            };
+            match grouped.iter_mut().find(|(key, _)| *key == (subject, track)) {
+                Some((_, rows)) => rows.push(parsed),
+                None => grouped.push(((subject, track), vec![parsed])),
+            }
+        }
+        let mut table = Self::new();
+        for ((subject, track), rows) in grouped {
+            table.insert(subject, track, rows)?;
+        }
+        Ok(table)
+    }
+}
+
+/// 표준점수, 백분위 and 등급 estimated from a 원점수
+#[derive(Debug, Copy, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EstimatedScore {
+    pub standard_score: f64,
+    pub percentile: f64,
+    pub grade: Grade,
+}
+
+impl EstimatedScore {
+    pub fn score(&self) -> Score {
+        let valid = "conversion rows are valid scores";
+        Score::new_typed(
+            StandardScore::new(self.standard_score).expect(valid),
+            Percentile::new(self.percentile).expect(valid),
+            self.grade,
+        )
+    }
+}
+
+/// Estimated scores of `raw` in `subject` (and `track`, see
+/// `RawConversionTable::rows`). Between two rows the 표준점수 and 백분위
+/// are interpolated linearly and rounded to whole points, and the 등급 is
+/// that of the lower row; outside the rows the nearest one is used. English
+/// needs no rows.
+///
+/// ```
+/// use suneung_calc::convert::{raw_to_standard, ConversionRow, RawConversionTable};
+/// use suneung_calc::score::{Grade, Subject};
+///
+/// let row = |raw, standard_score, percentile, grade: u8| ConversionRow {
+///     raw,
+///     standard_score,
+///     percentile,
+///     grade: Grade::try_from(grade).unwrap(),
+/// };
+/// let mut table = RawConversionTable::new();
+/// table
+///     .insert(
+///         Subject::Korean,
+///         None,
+///         vec![row(60.0, 110.0, 70.0, 4), row(80.0, 124.0, 89.0, 2), row(90.0, 132.0, 96.0, 1)],
+///     )
+///     .unwrap();
+/// let estimate = |raw| raw_to_standard(Subject::Korean, None, raw, &table).unwrap();
+///
+/// // On a row, and a quarter of the way from 80 to 90
+/// assert_eq!(estimate(90.0).standard_score, 132.0);
+/// let between = estimate(82.5);
+/// assert_eq!((between.standard_score, between.percentile), (126.0, 91.0));
+/// assert_eq!(between.grade.get(), 2);
+/// // Clamped to the first and last rows
+/// assert_eq!(estimate(40.0), estimate(60.0));
+/// assert_eq!(estimate(100.0), estimate(90.0));
+///
+/// assert!(raw_to_standard(Subject::Korean, None, 101.0, &table).is_err());
+/// assert!(raw_to_standard(Subject::Math, None, 80.0, &table).is_err());
+/// let english = raw_to_standard(Subject::English, None, 89.0, &table).unwrap();
+/// assert_eq!(english.grade.get(), 2);
+/// ```
+pub fn raw_to_standard(
+    subject: Subject,
+    track: Option<Track>,
+    raw: f64,
+    table: &RawConversionTable,
+) -> Result<EstimatedScore> {
+    check_raw(subject, raw)?;
+    if subject == Subject::English {
+        let grade = (10 - (raw as u8 / 10)).clamp(1, 9);
+        return Ok(EstimatedScore {
+            standard_score: 0f64,
+            percentile: 0f64,
+            grade: Grade::try_from(grade)?,
+        });
+    }
+    let rows = table
+        .rows(subject, track)
+        .filter(|rows| !rows.is_empty())
+        .ok_or_else(|| {
+            SuneungError::InsufficientData(format!(
+                "no conversion rows for {}",
+                table_name(subject, track)
+            ))
+        })?;
+    let first = rows[0];
+    let last = rows[rows.len() - 1];
+    if raw <= first.raw {
+        return Ok(estimated(first));
+    }
+    if raw >= last.raw {
+        return Ok(estimated(last));
+    }
+    // `raw` is strictly inside, so some row is above it
+    let upper = rows
+        .iter()
+        .position(|r| r.raw >= raw)
+        .expect("raw < last.raw");
+    let (lower, upper) = (rows[upper - 1], rows[upper]);
+    if raw == upper.raw {
+        return Ok(estimated(upper));
+    }
+    let t = (raw - lower.raw) / (upper.raw - lower.raw);
+    let lerp = |a: f64, b: f64| (a + t * (b - a)).round();
+    Ok(EstimatedScore {
+        standard_score: lerp(lower.standard_score, upper.standard_score),
+        percentile: lerp(lower.percentile, upper.percentile),
+        grade: lower.grade,
+    })
+}
+
+impl Record {
+    /// Record `subject` from its 원점수, estimated with `table` (see
+    /// `raw_to_standard`; the subject's only 선택과목 in the table is used)
+    pub fn record_raw(
+        &mut self,
+        subject: Subject,
+        raw: f64,
+        table: &RawConversionTable,
+    ) -> Result<()> {
+        let estimate = raw_to_standard(subject, None, raw, table)?;
+        self.record_score(subject, estimate.score());
+        Ok(())
+    }
+}
+
+fn estimated(row: ConversionRow) -> EstimatedScore {
+    EstimatedScore {
+        standard_score: row.standard_score,
+        percentile: row.percentile,
+        grade: row.grade,
+    }
+}
+
+// 원점수 are out of 100, or 50 for 탐구
+fn check_raw(subject: Subject, raw: f64) -> Result<()> {
+    let max = match subject {
+        Subject::Chemistry | Subject::EarthScience => 50f64,
+        _ => 100f64,
+    };
+    if (0f64..=max).contains(&raw) {
+        Ok(())
+    } else {
+        Err(SuneungError::ScoreOutOfRange(format!(
+            "raw score of {} must be 0-{}, got {}",
+            subject.name(),
+            max,
+            raw
+        )))
+    }
+}
+
+fn table_name(subject: Subject, track: Option<Track>) -> String {
+    match track {
+        Some(track) => format!("{} ({})", subject.name(), track.id()),
+        None => subject.name().to_string(),
+    }
+}
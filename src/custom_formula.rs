@@ -0,0 +1,360 @@
+//! Converted scores from a formula typed in by hand, for schools without
+//! weight data, e.g. `(KOR*20 + MAT*35 + SCI*30) / 85 * 3 + ENGT`.
+//!
+//! Formulas combine numbers and the variables below with `+`, `-`, `*`,
+//! `/`, unary minus and parentheses, with the usual precedence:
+//!
+//! | variable | value |
+//! |----------|-------|
+//! | `KOR`, `MAT` | 표준점수 of 국어, 수학 |
+//! | `SCI1`, `SCI2` | 표준점수 of 화학Ⅰ, 지구과학Ⅰ |
+//! | `SCI` | `SCI1 + SCI2` |
+//! | `ENGT` | English table value of the English grade |
+//! | `PCT_KOR`, `PCT_MAT`, `PCT_SCI1`, `PCT_SCI2`, `PCT_SCI` | the same as 백분위 |
+//!
+//! Only the subjects a formula uses need to be recorded. Formulas may nest
+//! at most `MAX_DEPTH` levels deep.
+
+use crate::error::{Result, SuneungError};
+use crate::score::{EnglishTable, Record, Subject};
+use std::fmt;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Variable {
+    StandardScore(Subject),
+    StandardScoreSum,
+    EnglishTable,
+    Percentile(Subject),
+    PercentileSum,
+}
+
+// Every variable by name
+const VARIABLES: [(&str, Variable); 11] = [
+    ("KOR", Variable::StandardScore(Subject::Korean)),
+    ("MAT", Variable::StandardScore(Subject::Math)),
+    ("SCI", Variable::StandardScoreSum),
+    ("SCI1", Variable::StandardScore(Subject::Chemistry)),
+    ("SCI2", Variable::StandardScore(Subject::EarthScience)),
+    ("ENGT", Variable::EnglishTable),
+    ("PCT_KOR", Variable::Percentile(Subject::Korean)),
+    ("PCT_MAT", Variable::Percentile(Subject::Math)),
+    ("PCT_SCI", Variable::PercentileSum),
+    ("PCT_SCI1", Variable::Percentile(Subject::Chemistry)),
+    ("PCT_SCI2", Variable::Percentile(Subject::EarthScience)),
+];
+
+/// Deepest nesting `CustomFormula::parse` accepts, counting parentheses,
+/// unary minus and each operator applied to the result of another. Typed
+/// formulas stay far below it; evaluating recurses once per level.
+pub const MAX_DEPTH: usize = 256;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Number(f64),
+    Variable(Variable),
+    Neg(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+/// A parsed formula, see the module documentation. Displays as the text it
+/// was parsed from.
+///
+/// ```
+/// use suneung_calc::custom_formula::CustomFormula;
+/// use suneung_calc::error::SuneungError;
+/// use suneung_calc::score::{Grade, Record, Subject, University, UniversityWeight};
+///
+/// let record = Record::sample();
+/// let table = *UniversityWeight::try_load(University::KYUNGHEE, 2024).unwrap().english_table();
+/// let calc = |expr: &str| {
+///     let formula = CustomFormula::parse(expr).unwrap();
+///     record.calc_with_formula(&formula, &table).unwrap()
+/// };
+/// assert_eq!(calc("1 + 2 * 3 - 4 / 2"), 5.0);
+/// assert_eq!(calc("(1 + 2) * -3"), -9.0);
+/// assert_eq!(calc("SCI - SCI1 - SCI2"), 0.0);
+///
+/// // The offending position, counted in characters from 1
+/// match CustomFormula::parse("KOR + HIST * 2") {
+///     Err(SuneungError::FormulaParse { position, .. }) => assert_eq!(position, 7),
+///     other => panic!("{:?}", other),
+/// }
+/// assert!(CustomFormula::parse("(KOR + MAT").is_err());
+///
+/// // 경희대 2024: 국어 20, 수학 35, 탐구 30 of 85 scaled by 3, and English
+/// // relative to 2등급 (196) at 15 of 100
+/// let mut record = record;
+/// record.set_grade(Subject::English, Grade::try_from(3u8).unwrap()).unwrap();
+/// let kyunghee = CustomFormula::parse(
+///     "(KOR*20 + MAT*35 + SCI*30) / 85 * 3 + (ENGT - 196) * 15 / 100",
+/// )
+/// .unwrap();
+/// let custom = record.calc_with_formula(&kyunghee, &table).unwrap();
+/// let builtin = record.try_calc_with_university(University::KYUNGHEE, 2024).unwrap();
+/// assert!((custom - builtin).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomFormula {
+    source: String,
+    expr: Expr,
+}
+
+impl CustomFormula {
+    /// Parse `expr`. Errors are `FormulaParse` with the 1-based character
+    /// position of the problem, nesting deeper than `MAX_DEPTH` included,
+    /// or `InvalidInput` for text over `input::MAX_INPUT_BYTES`.
+    pub fn parse(expr: &str) -> Result<Self> {
+        crate::input::check_len("formula", expr)?;
+        let mut parser = Parser {
+            chars: expr.chars().collect(),
+            pos: 0,
+            nesting: 0,
+        };
+        let (parsed, _) = parser.expr()?;
+        parser.skip_space();
+        if let Some(c) = parser.peek() {
+            return Err(parser.error(format!("unexpected {:?}", c)));
+        }
+        Ok(Self {
+            source: expr.to_string(),
+            expr: parsed,
+        })
+    }
+}
+
+impl fmt::Display for CustomFormula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl std::str::FromStr for CustomFormula {
+    type Err = SuneungError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl Record {
+    /// Value of `formula` for the record, with `english_table` behind
+    /// `ENGT`. `MissingSubject` for a subject the formula uses but the
+    /// record lacks, `InsufficientData` for an English grade the table does
+    /// not cover, `InvalidInput` when the value is not finite, e.g. after a
+    /// division by zero.
+    pub fn calc_with_formula(
+        &self,
+        formula: &CustomFormula,
+        english_table: &EnglishTable,
+    ) -> Result<f64> {
+        let value = eval(&formula.expr, self, english_table)?;
+        if !value.is_finite() {
+            return Err(SuneungError::InvalidInput(format!(
+                "formula {:?} gives {}",
+                formula.source, value
+            )));
+        }
+        Ok(value)
+    }
+}
+
+fn eval(expr: &Expr, record: &Record, english_table: &EnglishTable) -> Result<f64> {
+    Ok(match expr {
+        Expr::Number(x) => *x,
+        Expr::Variable(v) => variable(*v, record, english_table)?,
+        Expr::Neg(e) => -eval(e, record, english_table)?,
+        Expr::Binary(op, a, b) => {
+            let a = eval(a, record, english_table)?;
+            let b = eval(b, record, english_table)?;
+            match op {
+                Op::Add => a + b,
+                Op::Sub => a - b,
+                Op::Mul => a * b,
+                Op::Div => a / b,
+            }
+        }
+    })
+}
+
+fn variable(v: Variable, record: &Record, english_table: &EnglishTable) -> Result<f64> {
+    let sciences = [Subject::Chemistry, Subject::EarthScience];
+    Ok(match v {
+        Variable::StandardScore(s) => record.require(s)?.standard_score(),
+        Variable::Percentile(s) => record.require(s)?.percentile(),
+        Variable::StandardScoreSum => sciences
+            .iter()
+            .map(|&s| Ok(record.require(s)?.standard_score()))
+            .sum::<Result<f64>>()?,
+        Variable::PercentileSum => sciences
+            .iter()
+            .map(|&s| Ok(record.require(s)?.percentile()))
+            .sum::<Result<f64>>()?,
+        Variable::EnglishTable => {
+            english_table.require(record.require(Subject::English)?.rank())?
+        }
+    })
+}
+
+// Recursive descent over `expr := term (("+" | "-") term)*`,
+// `term := factor (("*" | "/") factor)*`,
+// `factor := "-" factor | number | variable | "(" expr ")"`.
+// Each rule returns the expression with the depth of its tree.
+struct Parser {
+    chars: Vec<char>,
+    // Index of the next character
+    pos: usize,
+    // Factors being parsed inside one another, bounding the recursion
+    nesting: usize,
+}
+
+impl Parser {
+    fn error(&self, reason: String) -> SuneungError {
+        SuneungError::FormulaParse {
+            position: self.pos + 1,
+            reason,
+        }
+    }
+
+    // At the last character read, the one that went a level too deep
+    fn too_deep(&self) -> SuneungError {
+        SuneungError::FormulaParse {
+            position: self.pos,
+            reason: format!("formula nests deeper than {} levels", MAX_DEPTH),
+        }
+    }
+
+    // Depth of a node over children as deep as `depth`
+    fn deeper(&self, depth: usize) -> Result<usize> {
+        if depth >= MAX_DEPTH {
+            return Err(self.too_deep());
+        }
+        Ok(depth + 1)
+    }
+
+    // `f` one level further in, refused before recursing past `MAX_DEPTH`
+    fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        if self.nesting >= MAX_DEPTH {
+            return Err(self.too_deep());
+        }
+        self.nesting += 1;
+        let result = f(self);
+        self.nesting -= 1;
+        result
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_space(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    // Skip whitespace, then consume `c` if it comes next
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_space();
+        let found = self.peek() == Some(c);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expr(&mut self) -> Result<(Expr, usize)> {
+        let (mut lhs, mut depth) = self.term()?;
+        loop {
+            let op = if self.eat('+') {
+                Op::Add
+            } else if self.eat('-') {
+                Op::Sub
+            } else {
+                return Ok((lhs, depth));
+            };
+            let (rhs, rhs_depth) = self.term()?;
+            depth = self.deeper(depth.max(rhs_depth))?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+    }
+
+    fn term(&mut self) -> Result<(Expr, usize)> {
+        let (mut lhs, mut depth) = self.factor()?;
+        loop {
+            let op = if self.eat('*') {
+                Op::Mul
+            } else if self.eat('/') {
+                Op::Div
+            } else {
+                return Ok((lhs, depth));
+            };
+            let (rhs, rhs_depth) = self.factor()?;
+            depth = self.deeper(depth.max(rhs_depth))?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+    }
+
+    fn factor(&mut self) -> Result<(Expr, usize)> {
+        if self.eat('-') {
+            let (inner, depth) = self.nested(Self::factor)?;
+            return Ok((Expr::Neg(Box::new(inner)), self.deeper(depth)?));
+        }
+        if self.eat('(') {
+            let inner = self.nested(Self::expr)?;
+            if !self.eat(')') {
+                return Err(self.error("expected ')'".to_string()));
+            }
+            return Ok(inner);
+        }
+        let leaf = match self.peek() {
+            Some(c) if c.is_ascii_digit() || c == '.' => self.number(),
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => self.variable(),
+            Some(c) => Err(self.error(format!("unexpected {:?}", c))),
+            None => Err(self.error("unexpected end of formula".to_string())),
+        };
+        Ok((leaf?, 0))
+    }
+
+    fn number(&mut self) -> Result<Expr> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        text.parse().map(Expr::Number).map_err(|_| {
+            self.pos = start;
+            self.error(format!("invalid number {:?}", text))
+        })
+    }
+
+    fn variable(&mut self) -> Result<Expr> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            self.pos += 1;
+        }
+        let name: String = self.chars[start..self.pos].iter().collect();
+        match VARIABLES.iter().find(|(n, _)| *n == name) {
+            Some(&(_, v)) => Ok(Expr::Variable(v)),
+            None => {
+                self.pos = start;
+                let known: Vec<&str> = VARIABLES.iter().map(|(n, _)| *n).collect();
+                Err(self.error(format!(
+                    "unknown variable {:?}, expected one of {}",
+                    name,
+                    known.join(", ")
+                )))
+            }
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use crate::display::{fmt_number, label};
+use crate::score::{Grade, Record, Score, Subject, University};
+use std::fmt;
+
+/// How one subject changed between two records
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SubjectDiff {
+    Changed {
+        subject: Subject,
+        before: Score,
+        after: Score,
+    },
+    /// Recorded only in the newer record
+    Added { subject: Subject, score: Score },
+    /// Recorded only in the older record
+    Removed { subject: Subject, score: Score },
+}
+
+impl SubjectDiff {
+    pub fn subject(&self) -> Subject {
+        match *self {
+            SubjectDiff::Changed { subject, .. }
+            | SubjectDiff::Added { subject, .. }
+            | SubjectDiff::Removed { subject, .. } => subject,
+        }
+    }
+
+    /// `(standard score, percentile, grade)` deltas, for subjects in both
+    /// records. A negative grade delta is an improvement.
+    pub fn deltas(&self) -> Option<(f64, f64, i32)> {
+        match self {
+            SubjectDiff::Changed { before, after, .. } => Some((
+                after.standard_score() - before.standard_score(),
+                after.percentile() - before.percentile(),
+                grade_delta(before.rank(), after.rank()),
+            )),
+            _ => None,
+        }
+    }
+}
+
+fn grade_delta(before: Grade, after: Grade) -> i32 {
+    after.get() as i32 - before.get() as i32
+}
+
+/// Change of the converted score at one university. `None` when either
+/// record cannot be calculated there.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct UniversityDelta {
+    pub university: University,
+    pub year: usize,
+    pub before: Option<f64>,
+    pub after: Option<f64>,
+}
+
+impl UniversityDelta {
+    pub fn delta(&self) -> Option<f64> {
+        Some(self.after? - self.before?)
+    }
+}
+
+/// What changed between two records of the same student (see `Record::diff`)
+#[derive(Debug, Clone)]
+pub struct RecordDiff {
+    before: Record,
+    after: Record,
+    subjects: Vec<SubjectDiff>,
+    universities: Vec<UniversityDelta>,
+}
+
+impl Record {
+    /// Per-subject changes from `self` to `other`, in `Subject::all()` order
+    pub fn diff(&self, other: &Record) -> RecordDiff {
+        let subjects = Subject::all()
+            .iter()
+            .filter_map(
+                |&subject| match (self.try_score(subject), other.try_score(subject)) {
+                    (Some(before), Some(after)) => Some(SubjectDiff::Changed {
+                        subject,
+                        before,
+                        after,
+                    }),
+                    (None, Some(score)) => Some(SubjectDiff::Added { subject, score }),
+                    (Some(score), None) => Some(SubjectDiff::Removed { subject, score }),
+                    (None, None) => None,
+                },
+            )
+            .collect();
+        RecordDiff {
+            before: self.clone(),
+            after: other.clone(),
+            subjects,
+            universities: vec![],
+        }
+    }
+}
+
+impl RecordDiff {
+    /// Add converted-score deltas at `universities` for `year`
+    pub fn with_universities(mut self, universities: &[University], year: usize) -> Self {
+        for &university in universities {
+            self.universities.push(UniversityDelta {
+                university,
+                year,
+                before: self.before.try_calc_with_university(university, year).ok(),
+                after: self.after.try_calc_with_university(university, year).ok(),
+            });
+        }
+        self
+    }
+
+    pub fn subjects(&self) -> &[SubjectDiff] {
+        &self.subjects
+    }
+
+    pub fn universities(&self) -> &[UniversityDelta] {
+        &self.universities
+    }
+}
+
+// ↑ marks an improvement, so a lower grade gets ↑
+fn arrow(delta: f64) -> &'static str {
+    if delta > 0f64 {
+        "↑"
+    } else if delta < 0f64 {
+        "↓"
+    } else {
+        "-"
+    }
+}
+
+fn fmt_delta(before: f64, after: f64, precision: Option<usize>) -> String {
+    format!(
+        "{} → {} {}",
+        fmt_number(before, precision),
+        fmt_number(after, precision),
+        arrow(after - before)
+    )
+}
+
+/// One line per subject, e.g. `수학: 표준점수 128 → 135 ↑, 백분위 89 → 95 ↑,
+/// 등급 2 → 1 ↑`, followed by the university deltas. ↑ always means better.
+impl fmt::Display for RecordDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} → {}", self.before.name(), self.after.name())?;
+        for diff in &self.subjects {
+            let subject = label(diff.subject());
+            match diff {
+                SubjectDiff::Changed { before, after, .. } => {
+                    write!(f, "{}: ", subject)?;
+                    if diff.subject() != Subject::English {
+                        write!(
+                            f,
+                            "표준점수 {}, 백분위 {}, ",
+                            fmt_delta(before.standard_score(), after.standard_score(), None),
+                            fmt_delta(before.percentile(), after.percentile(), None)
+                        )?;
+                    }
+                    writeln!(
+                        f,
+                        "등급 {} → {} {}",
+                        before.rank(),
+                        after.rank(),
+                        arrow(-grade_delta(before.rank(), after.rank()) as f64)
+                    )?;
+                }
+                SubjectDiff::Added { score, .. } => writeln!(f, "{}: 추가 ({})", subject, score)?,
+                SubjectDiff::Removed { score, .. } => writeln!(f, "{}: 삭제 ({})", subject, score)?,
+            }
+        }
+        for delta in &self.universities {
+            match (delta.before, delta.after) {
+                (Some(before), Some(after)) => writeln!(
+                    f,
+                    "{} {}: {}",
+                    delta.university,
+                    delta.year,
+                    fmt_delta(before, after, Some(2))
+                )?,
+                _ => writeln!(f, "{} {}: 계산 불가", delta.university, delta.year)?,
+            }
+        }
+        Ok(())
+    }
+}
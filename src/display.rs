@@ -0,0 +1,221 @@
+use crate::exam::ExamId;
+use crate::score::{Locale, Record, Score, Subject};
+use std::fmt;
+use unicode_width::UnicodeWidthStr;
+
+// Wide enough for `국어(언어와매체)` with a source marker
+const LABEL_WIDTH: usize = 18;
+const COLUMNS: [(&str, usize); 3] = [("표준점수", 10), ("백분위", 8), ("등급", 8)];
+
+pub(crate) fn label(subject: Subject) -> &'static str {
+    subject.localized_name(Locale::Korean)
+}
+
+/// `2등급`, or `Grade 2` in English
+pub(crate) fn grade_label(grade: impl fmt::Display, locale: Locale) -> String {
+    match locale {
+        Locale::Korean => format!("{}등급", grade),
+        Locale::English => format!("Grade {}", grade),
+    }
+}
+
+/// Pad `s` on the left to `width` terminal columns (Hangul counts as two).
+pub(crate) fn pad_left(s: &str, width: usize) -> String {
+    let w = UnicodeWidthStr::width(s);
+    format!("{}{}", " ".repeat(width.saturating_sub(w)), s)
+}
+
+/// Pad `s` on the right to `width` terminal columns (Hangul counts as two).
+pub(crate) fn pad_right(s: &str, width: usize) -> String {
+    let w = UnicodeWidthStr::width(s);
+    format!("{}{}", s, " ".repeat(width.saturating_sub(w)))
+}
+
+/// Shortest exact form (`93`, `92.5`) unless a precision is given.
+pub(crate) fn fmt_number(x: f64, precision: Option<usize>) -> String {
+    match precision {
+        Some(p) => format!("{:.*}", p, x),
+        None => x.to_string(),
+    }
+}
+
+// Exams the scores of `record` were taken from, in subject order without
+// repeats
+fn score_sources(record: &Record) -> Vec<ExamId> {
+    let mut sources = vec![];
+    for (_, score) in record.iter() {
+        if let Some(source) = score.source() {
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+    }
+    sources
+}
+
+fn superscript(n: usize) -> String {
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+    n.to_string()
+        .chars()
+        .map(|d| DIGITS[d.to_digit(10).unwrap_or_default() as usize])
+        .collect()
+}
+
+/// `Record::display_subject` of `score` in `locale` with the marker of its
+/// source, `수학(미적분)²`, where it has one
+pub(crate) fn source_label(
+    record: &Record,
+    subject: Subject,
+    score: &Score,
+    locale: Locale,
+) -> String {
+    let sources = score_sources(record);
+    let name = record.localized_subject(subject, locale);
+    match score
+        .source()
+        .and_then(|s| sources.iter().position(|&t| t == s))
+    {
+        Some(i) => format!("{}{}", name, superscript(i + 1)),
+        None => name,
+    }
+}
+
+/// `² 2024 수능` for each marker of `source_label`
+pub(crate) fn source_notes(record: &Record, locale: Locale) -> Vec<String> {
+    score_sources(record)
+        .iter()
+        .enumerate()
+        .map(|(i, source)| format!("{} {}", superscript(i + 1), source.localized_label(locale)))
+        .collect()
+}
+
+impl Score {
+    /// Standard score with a fixed number of decimals
+    pub fn fmt_standard(&self, precision: usize) -> String {
+        fmt_number(self.standard_score(), Some(precision))
+    }
+
+    /// 백분위 for fixed-width terminal tables, `93*` when estimated
+    pub(crate) fn fmt_percentile_short(&self) -> String {
+        let mark = if self.percentile_estimated() { "*" } else { "" };
+        format!("{}{}", fmt_number(self.percentile(), None), mark)
+    }
+
+    /// 백분위 for reports, `93 (추정)` when estimated, `93 (est.)` in
+    /// English
+    #[cfg(feature = "stats")]
+    pub(crate) fn fmt_percentile(&self, locale: Locale) -> String {
+        format!(
+            "{}{}",
+            fmt_number(self.percentile(), None),
+            self.estimate_mark(locale)
+        )
+    }
+
+    fn estimate_mark(&self, locale: Locale) -> &'static str {
+        if self.percentile_estimated() {
+            locale.pick(" (추정)", " (est.)")
+        } else {
+            ""
+        }
+    }
+
+    /// "1등급" .. "9등급"
+    pub fn grade_label(&self) -> String {
+        self.localized_grade_label(Locale::Korean)
+    }
+
+    /// `grade_label` in `locale`, "Grade 1" .. "Grade 9" in English
+    pub fn localized_grade_label(&self, locale: Locale) -> String {
+        grade_label(self.rank(), locale)
+    }
+
+    // English (and 한국사) are stored with zero standard score and percentile
+    fn is_grade_only(&self) -> bool {
+        self.standard_score() == 0f64 && self.percentile() == 0f64
+    }
+}
+
+/// `131 / 93% / 2등급`, or just `2등급` for grade-only scores. A precision
+/// (`{:.1}`) applies to both numbers: `131.0 / 93.0% / 2등급`. An estimated
+/// 백분위 is marked: `131 / 93% (추정) / 2등급`.
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_grade_only() {
+            return write!(f, "{}", self.grade_label());
+        }
+        write!(
+            f,
+            "{} / {}%{} / {}",
+            fmt_number(self.standard_score(), f.precision()),
+            fmt_number(self.percentile(), f.precision()),
+            self.estimate_mark(Locale::Korean),
+            self.grade_label()
+        )
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.exam() {
+            Some(exam) => writeln!(f, "{} ({})", self.name(), exam)?,
+            None => writeln!(f, "{}", self.name())?,
+        }
+        if !self.tags().is_empty() {
+            writeln!(f, "태그: {}", self.tags().join(", "))?;
+        }
+
+        write!(f, "{}", pad_right("과목", LABEL_WIDTH))?;
+        for (title, width) in COLUMNS {
+            write!(f, "{}", pad_left(title, width))?;
+        }
+        writeln!(f)?;
+
+        let mut standard_sum = 0f64;
+        let mut percentile_sum = 0f64;
+        // Record::iter() follows the order of the official 성적통지표
+        for (subject, score) in self.iter() {
+            let (standard, percentile) = if subject == Subject::English {
+                ("-".to_string(), "-".to_string())
+            } else {
+                standard_sum += score.standard_score();
+                percentile_sum += score.percentile();
+                (
+                    fmt_number(score.standard_score(), None),
+                    score.fmt_percentile_short(),
+                )
+            };
+            writeln!(
+                f,
+                "{}{}{}{}",
+                pad_right(
+                    &source_label(self, subject, score, Locale::Korean),
+                    LABEL_WIDTH
+                ),
+                pad_left(&standard, COLUMNS[0].1),
+                pad_left(&percentile, COLUMNS[1].1),
+                pad_left(&score.grade_label(), COLUMNS[2].1),
+            )?;
+        }
+
+        writeln!(
+            f,
+            "{}{}",
+            pad_right("표점합", LABEL_WIDTH),
+            pad_left(&fmt_number(standard_sum, None), COLUMNS[0].1)
+        )?;
+        write!(
+            f,
+            "{}{}",
+            pad_right("백분위합", LABEL_WIDTH),
+            pad_left(
+                &fmt_number(percentile_sum, None),
+                COLUMNS[0].1 + COLUMNS[1].1
+            )
+        )?;
+        for note in source_notes(self, Locale::Korean) {
+            write!(f, "\n{}", note)?;
+        }
+        Ok(())
+    }
+}
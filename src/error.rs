@@ -0,0 +1,168 @@
+use crate::formula::ScienceAggregation;
+use crate::score::{CalcWarning, Subject, University};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SuneungError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Parquet error: {0}")]
+    Parquet(String),
+    #[error("Checksum mismatch for {name}: expected {expected}, found {found}")]
+    ChecksumMismatch {
+        name: String,
+        expected: String,
+        found: String,
+    },
+    #[error("Invalid weight data: {0}")]
+    WeightData(String),
+    #[error("{} has no weight data for {year}", .university.name())]
+    UnsupportedYear { university: University, year: usize },
+    #[error("Record has no {} score", .0.name())]
+    MissingSubject(Subject),
+    #[error("Invalid {} score: {reason}", .subject.name())]
+    InvalidScore { subject: Subject, reason: String },
+    #[error("Invalid score: {0}")]
+    ScoreOutOfRange(String),
+    #[error("Plot error: {0}")]
+    Plot(String),
+    #[error("PDF error: {0}")]
+    Pdf(String),
+    #[error("Invalid config{}: {reason}", display_path(.path))]
+    Config { path: PathBuf, reason: String },
+    #[error("Unknown {kind} {name:?}")]
+    UnknownName { kind: &'static str, name: String },
+    #[error("Could not parse score report:\n{}", display_lines(.0))]
+    ReportParse(Vec<LineError>),
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("Not enough data: {0}")]
+    InsufficientData(String),
+    #[error("Invalid formula at position {position}: {reason}")]
+    FormulaParse { position: usize, reason: String },
+    #[error("Refused in strict mode: {}", .0.reason())]
+    Strict(CalcWarning),
+}
+
+pub type Result<T> = std::result::Result<T, SuneungError>;
+
+impl SuneungError {
+    /// Stable snake_case name of the variant, e.g. for JSON error output
+    pub fn code(&self) -> &'static str {
+        match self {
+            SuneungError::Io(_) => "io",
+            SuneungError::Parquet(_) => "parquet",
+            SuneungError::ChecksumMismatch { .. } => "checksum_mismatch",
+            SuneungError::WeightData(_) => "weight_data",
+            SuneungError::UnsupportedYear { .. } => "unsupported_year",
+            SuneungError::MissingSubject(_) => "missing_subject",
+            SuneungError::InvalidScore { .. } => "invalid_score",
+            SuneungError::ScoreOutOfRange(_) => "score_out_of_range",
+            SuneungError::Plot(_) => "plot",
+            SuneungError::Pdf(_) => "pdf",
+            SuneungError::Config { .. } => "config",
+            SuneungError::UnknownName { .. } => "unknown_name",
+            SuneungError::ReportParse(_) => "report_parse",
+            SuneungError::InvalidInput(_) => "invalid_input",
+            SuneungError::InsufficientData(_) => "insufficient_data",
+            SuneungError::FormulaParse { .. } => "formula_parse",
+            SuneungError::Strict(_) => "strict",
+        }
+    }
+}
+
+/// Why `UniversityWeight::validate` rejected a weight. Converts into
+/// `SuneungError::WeightData` with the same message.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum WeightValidationError {
+    #[error("{subject} weight must be non-negative, got {weight}")]
+    NegativeWeight { subject: &'static str, weight: f64 },
+    #[error("korean, math and science weights are all zero")]
+    NoWeight,
+    #[error("science_required must be 1 or 2, got {0}")]
+    ScienceRequired(usize),
+    #[error(
+        "{aggregation:?} science aggregation needs science_required {}, got {science_required}",
+        aggregation.subjects()
+    )]
+    ScienceAggregation {
+        aggregation: ScienceAggregation,
+        science_required: usize,
+    },
+    #[error("english_required must be a grade from 1 to 9, got {0}")]
+    EnglishRequired(usize),
+    #[error("english table contains {0}")]
+    EnglishTableValue(f64),
+    #[error(
+        "english table has {0} entries, expected 1 to 9 grades or a padding slot and 9 grades"
+    )]
+    EnglishTableLength(usize),
+    #[error("english table gives grade {worse} more points than grade {better}")]
+    EnglishTableRises { better: usize, worse: usize },
+    #[error("english deduction table deducts less for grade {worse} than for grade {better}")]
+    EnglishDeductionFalls { better: usize, worse: usize },
+}
+
+impl From<WeightValidationError> for SuneungError {
+    fn from(e: WeightValidationError) -> Self {
+        SuneungError::WeightData(e.to_string())
+    }
+}
+
+/// Why `Record::merge` could not combine two records. Converts into
+/// `SuneungError::InvalidInput` with the same message.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum MergeConflict {
+    #[error("cannot merge records of {ours:?} and {theirs:?}")]
+    NameMismatch { ours: String, theirs: String },
+    #[error("records disagree on {}", display_subjects(.0))]
+    Subjects(Vec<Subject>),
+}
+
+impl From<MergeConflict> for SuneungError {
+    fn from(e: MergeConflict) -> Self {
+        SuneungError::InvalidInput(e.to_string())
+    }
+}
+
+fn display_subjects(subjects: &[Subject]) -> String {
+    subjects
+        .iter()
+        .map(|s| s.name())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A line of pasted input that could not be understood
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineError {
+    /// 1-based line number in the input
+    pub line: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {} ({:?})", self.line, self.reason, self.text)
+    }
+}
+
+// ` <path>`, or nothing for text that did not come from a file
+fn display_path(path: &Path) -> String {
+    if path.as_os_str().is_empty() {
+        String::new()
+    } else {
+        format!(" {}", path.display())
+    }
+}
+
+fn display_lines(errors: &[LineError]) -> String {
+    errors
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,77 @@
+//! 백분위 estimated from the 표준점수, for scores typed in without one.
+//!
+//! The 표준점수 of a subject is taken as normally distributed with the
+//! mean and σ fitted to the 수능 등급컷 of the year (`ExamStats::csat`), or
+//! the nominal scale for years without them. The real distributions are
+//! skewed near the top, so estimates can be a point or two off there.
+
+use crate::normalize::ExamStats;
+use crate::score::{Record, Subject};
+use peroxide::fuga::{TPDist, RNG};
+
+/// Whole 백분위 of `standard_score` in `subject` for the 수능 of `year`; 0
+/// for English, which has no 백분위
+///
+/// ```
+/// use suneung_calc::cohort::CohortProfile;
+/// use suneung_calc::estimate::percentile_from_standard;
+/// use suneung_calc::score::Subject;
+///
+/// let mean = CohortProfile::for_year(2024).unwrap().math.mean;
+/// assert_eq!(percentile_from_standard(Subject::Math, 2024, mean), 50.0);
+/// // 최고점 (148) is near the top; the scale ends at 100 and 0
+/// assert!(percentile_from_standard(Subject::Math, 2024, 148.0) >= 98.0);
+/// assert_eq!(percentile_from_standard(Subject::Math, 2024, 200.0), 100.0);
+/// assert_eq!(percentile_from_standard(Subject::Math, 2024, 0.0), 0.0);
+/// ```
+pub fn percentile_from_standard(subject: Subject, year: usize, standard_score: f64) -> f64 {
+    let stats = ExamStats::csat(year).unwrap_or_else(ExamStats::nominal);
+    match stats.z_score(subject, standard_score) {
+        Some(z) => (100f64 * TPDist::Normal(0f64, 1f64).cdf(z))
+            .round()
+            .clamp(0f64, 100f64),
+        None => 0f64,
+    }
+}
+
+impl Record {
+    /// Fill in the 백분위 of every subject recorded with a 표준점수 but a
+    /// 백분위 of 0 by `percentile_from_standard`, marking it as estimated
+    /// (`Score::percentile_estimated`). Returns the subjects filled in.
+    ///
+    /// ```
+    /// use suneung_calc::score::{Grade, Percentile, Record, StandardScore, Subject};
+    ///
+    /// let mut record = Record::sample();
+    /// record.record_typed(
+    ///     Subject::Math,
+    ///     StandardScore::new(128.0).unwrap(),
+    ///     Percentile::new(0.0).unwrap(),
+    ///     Grade::try_from(2u8).unwrap(),
+    /// );
+    /// assert_eq!(record.fill_missing_percentiles(2025), [Subject::Math]);
+    ///
+    /// let math = record.try_score(Subject::Math).unwrap();
+    /// assert!(math.percentile_estimated());
+    /// assert!((85.0..=95.0).contains(&math.percentile()));
+    /// assert!(!record.try_score(Subject::Korean).unwrap().percentile_estimated());
+    /// assert!(math.to_string().contains("(추정)"));
+    /// ```
+    pub fn fill_missing_percentiles(&mut self, year: usize) -> Vec<Subject> {
+        let missing: Vec<Subject> = self
+            .iter()
+            .filter(|(subject, score)| {
+                *subject != Subject::English
+                    && score.standard_score() > 0f64
+                    && score.percentile() == 0f64
+            })
+            .map(|(subject, _)| subject)
+            .collect();
+        for &subject in &missing {
+            let score = self.try_score(subject).expect("recorded");
+            let percentile = percentile_from_standard(subject, year, score.standard_score());
+            self.record_score(subject, score.with_estimated_percentile(percentile));
+        }
+        missing
+    }
+}
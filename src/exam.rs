@@ -0,0 +1,155 @@
+use crate::error::SuneungError;
+use crate::score::Locale;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Which exam a record comes from, ordered by when it is taken in the
+/// year (`Other` last)
+#[derive(
+    Debug, Copy, Clone, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ExamKind {
+    /// 3월 학력평가
+    Mock3,
+    /// 6월 모의평가
+    Mock6,
+    /// 9월 모의평가
+    Mock9,
+    /// 대학수학능력시험
+    Csat,
+    #[default]
+    Other,
+}
+
+impl ExamKind {
+    pub fn all() -> &'static [ExamKind] {
+        &[
+            ExamKind::Mock3,
+            ExamKind::Mock6,
+            ExamKind::Mock9,
+            ExamKind::Csat,
+            ExamKind::Other,
+        ]
+    }
+
+    /// Identifier used in stored files
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExamKind::Mock3 => "Mock3",
+            ExamKind::Mock6 => "Mock6",
+            ExamKind::Mock9 => "Mock9",
+            ExamKind::Csat => "Csat",
+            ExamKind::Other => "Other",
+        }
+    }
+
+    /// Label used in reports
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExamKind::Mock3 => "3월 학력평가",
+            ExamKind::Mock6 => "6월 모의평가",
+            ExamKind::Mock9 => "9월 모의평가",
+            ExamKind::Csat => "수능",
+            ExamKind::Other => "기타",
+        }
+    }
+
+    /// `label` in `locale`
+    pub fn localized_label(&self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::Korean => self.label(),
+            Locale::English => match self {
+                ExamKind::Mock3 => "March mock exam",
+                ExamKind::Mock6 => "June mock exam",
+                ExamKind::Mock9 => "September mock exam",
+                ExamKind::Csat => "CSAT",
+                ExamKind::Other => "other exam",
+            },
+        }
+    }
+}
+
+impl FromStr for ExamKind {
+    type Err = SuneungError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ExamKind::all()
+            .iter()
+            .copied()
+            .find(|k| k.name() == s)
+            .ok_or_else(|| SuneungError::Parquet(format!("unknown exam kind {:?}", s)))
+    }
+}
+
+/// When and for which exam a record was entered
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExamMeta {
+    pub kind: ExamKind,
+    /// 학년도 of the exam (e.g. 2025 for the November 2024 수능)
+    pub year: u16,
+    /// Free-form timestamp of data entry, e.g. `2024-06-20`
+    pub noted_at: Option<String>,
+}
+
+impl ExamMeta {
+    pub fn new(kind: ExamKind, year: u16) -> Self {
+        Self {
+            kind,
+            year,
+            noted_at: None,
+        }
+    }
+
+    pub fn noted_at(mut self, noted_at: &str) -> Self {
+        self.noted_at = Some(noted_at.to_string());
+        self
+    }
+
+    pub fn id(&self) -> ExamId {
+        ExamId::new(self.kind, self.year)
+    }
+}
+
+/// One administration of an exam: `ExamMeta` without the entry timestamp.
+/// Ordered chronologically, by 학년도 and then by `ExamKind`.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExamId {
+    /// 학년도 of the exam
+    pub year: u16,
+    pub kind: ExamKind,
+}
+
+impl ExamId {
+    pub fn new(kind: ExamKind, year: u16) -> Self {
+        Self { year, kind }
+    }
+
+    /// The `Display` form in `locale`, `2025 June mock exam` in English
+    pub fn localized_label(&self, locale: Locale) -> String {
+        format!("{} {}", self.year, self.kind.localized_label(locale))
+    }
+}
+
+impl From<&ExamMeta> for ExamId {
+    fn from(exam: &ExamMeta) -> Self {
+        exam.id()
+    }
+}
+
+/// `2025 6월 모의평가`, like `ExamMeta`
+impl fmt::Display for ExamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.year, self.kind.label())
+    }
+}
+
+/// `2025 6월 모의평가`
+impl fmt::Display for ExamMeta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.year, self.kind.label())
+    }
+}
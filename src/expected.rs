@@ -0,0 +1,116 @@
+//! Expected converted score when grades may still change. Between the 9월
+//! 모의평가 and the 수능 the English 등급 of a student is better read as a
+//! distribution than as a point, and since there are at most nine
+//! outcomes the expectation is enumerated exactly rather than sampled.
+//!
+//! `Record` has no 한국사, so only English transitions are modelled.
+
+use crate::error::{Result, SuneungError};
+use crate::score::{Grade, Record, Score, Subject, University};
+use serde::Serialize;
+
+/// How far the probabilities of a `GradeTransitions` may sum away from 1
+pub const PROBABILITY_TOLERANCE: f64 = 1e-6;
+
+/// Probability distribution over the final English 등급 of a student
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GradeTransitions {
+    /// `(grade, probability)`, each grade at most once
+    pub english: Vec<(Grade, f64)>,
+}
+
+impl GradeTransitions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `grade` with `probability`
+    pub fn english(mut self, grade: Grade, probability: f64) -> Self {
+        self.english.push((grade, probability));
+        self
+    }
+
+    /// `InvalidInput` unless every probability is within 0-1, no grade
+    /// appears twice and the probabilities sum to 1 within
+    /// `PROBABILITY_TOLERANCE`
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |reason: String| Err(SuneungError::InvalidInput(reason));
+        for (i, &(grade, p)) in self.english.iter().enumerate() {
+            if !(0f64..=1f64).contains(&p) {
+                return invalid(format!("probability of English {}등급 is {}", grade, p));
+            }
+            if self.english[..i].iter().any(|&(g, _)| g == grade) {
+                return invalid(format!("English {}등급 is given twice", grade));
+            }
+        }
+        let total: f64 = self.english.iter().map(|&(_, p)| p).sum();
+        if (total - 1f64).abs() > PROBABILITY_TOLERANCE {
+            return invalid(format!("English probabilities sum to {}, not 1", total));
+        }
+        Ok(())
+    }
+}
+
+/// Expectation of the converted score over a `GradeTransitions`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExpectedScore {
+    pub mean: f64,
+    /// `(English grade, probability, converted score)` of every outcome,
+    /// in the order of `GradeTransitions::english`
+    pub per_outcome: Vec<(Grade, f64, f64)>,
+}
+
+impl Record {
+    /// Expected converted score at `univ` for `year` when the final English
+    /// 등급 follows `transitions`; the other subjects stay as recorded.
+    /// Fails when `transitions` is invalid (see
+    /// `GradeTransitions::validate`) or an outcome cannot be scored.
+    ///
+    /// ```
+    /// use suneung_calc::expected::GradeTransitions;
+    /// use suneung_calc::score::{Grade, Record, Score, Subject, University};
+    ///
+    /// let grade = |g: u8| Grade::try_from(g).unwrap();
+    /// let record = Record::sample();
+    /// let transitions = GradeTransitions::new()
+    ///     .english(grade(1), 0.3)
+    ///     .english(grade(3), 0.7);
+    /// let expected = record
+    ///     .expected_score(University::KONKUK, 2024, &transitions)
+    ///     .unwrap();
+    ///
+    /// let at = |g: u8| {
+    ///     let mut record = record.clone();
+    ///     record.record_score(Subject::English, Score::grade_only(grade(g)));
+    ///     record.try_calc_with_university(University::KONKUK, 2024).unwrap()
+    /// };
+    /// let by_hand = 0.3 * at(1) + 0.7 * at(3);
+    /// assert!((expected.mean - by_hand).abs() < 1e-9);
+    /// assert_eq!(expected.per_outcome[1], (grade(3), 0.7, at(3)));
+    ///
+    /// let short = GradeTransitions::new().english(grade(1), 0.5);
+    /// assert!(record.expected_score(University::KONKUK, 2024, &short).is_err());
+    /// ```
+    pub fn expected_score(
+        &self,
+        univ: University,
+        year: usize,
+        transitions: &GradeTransitions,
+    ) -> Result<ExpectedScore> {
+        transitions.validate()?;
+        let mut outcome = self.clone();
+        let per_outcome = transitions
+            .english
+            .iter()
+            .map(|&(grade, p)| {
+                outcome.record_score(Subject::English, Score::grade_only(grade));
+                Ok((grade, p, outcome.try_calc_with_university(univ, year)?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(ExpectedScore {
+            mean: per_outcome.iter().map(|&(_, p, score)| p * score).sum(),
+            per_outcome,
+        })
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(feature = "csv")]
+use crate::atomic::write_atomic;
+#[cfg(feature = "csv")]
+use crate::score::Locale;
+use crate::score::{University, UniversityWeight, YEARS};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "csv")]
+use std::error::Error;
+#[cfg(feature = "csv")]
+use std::path::Path;
+
+#[cfg(feature = "csv")]
+const GRADES: usize = 9;
+
+/// Write every university's English grade table for `year` as CSV.
+///
+/// Columns are `university, name, english_weight, english_policy,
+/// english_required, grade1..grade9, comment`. `english_policy` is `ratio`
+/// when English takes part in the reflection ratio and `bonus` when the table
+/// value is added on top (see `Record::calc_with_university`). Universities
+/// without data for `year` keep their row with only the comment filled in. `locale` picks the
+/// language of the `name` column.
+#[cfg(feature = "csv")]
+pub fn export_english_tables_csv<P: AsRef<Path>>(
+    year: usize,
+    path: P,
+    locale: Locale,
+) -> Result<(), Box<dyn Error>> {
+    write_atomic(path.as_ref(), |tmp| {
+        let mut wtr = csv::Writer::from_path(tmp)?;
+
+        let mut header = vec![
+            "university".to_string(),
+            "name".to_string(),
+            "english_weight".to_string(),
+            "english_policy".to_string(),
+            "english_required".to_string(),
+        ];
+        header.extend((1..=GRADES).map(|g| format!("grade{}", g)));
+        header.push("comment".to_string());
+        wtr.write_record(&header)?;
+
+        for univ in University::iter() {
+            let mut row = vec![
+                format!("{:?}", univ),
+                univ.localized_name(locale).to_string(),
+            ];
+            match UniversityWeight::try_load(univ, year) {
+                Some(weight) => {
+                    let policy = if weight.english() > 0f64 {
+                        "ratio"
+                    } else {
+                        "bonus"
+                    };
+                    row.push(weight.english().to_string());
+                    row.push(policy.to_string());
+                    row.push(weight.english_required().to_string());
+                    let table = weight.english_table();
+                    let covered = table.points().len();
+                    row.extend(table.points().iter().map(|x| x.to_string()));
+                    row.extend(std::iter::repeat_n(String::new(), GRADES - covered));
+                    row.push(if covered < GRADES {
+                        format!("no points after grade {}", table.last_grade())
+                    } else {
+                        String::new()
+                    });
+                }
+                None => {
+                    row.extend(std::iter::repeat_n(String::new(), 3 + GRADES));
+                    row.push(format!("no weight data for {}", year));
+                }
+            }
+            wtr.write_record(&row)?;
+        }
+
+        wtr.flush()?;
+        Ok(())
+    })
+}
+
+/// Every embedded weight, as written by `dump_weights_json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeightDatabase {
+    pub crate_version: String,
+    pub weights: Vec<WeightEntry>,
+}
+
+/// One embedded weight of `WeightDatabase`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeightEntry {
+    pub university: University,
+    pub name: String,
+    pub year: usize,
+    pub weight: UniversityWeight,
+}
+
+impl WeightDatabase {
+    /// Every supported (university, year), by year and then in the order of
+    /// `University::iter`
+    pub fn embedded() -> Self {
+        Self {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            weights: embedded_weights(),
+        }
+    }
+}
+
+pub(crate) fn embedded_weights() -> Vec<WeightEntry> {
+    let mut weights = vec![];
+    for year in YEARS {
+        for univ in University::iter() {
+            if let Some(weight) = UniversityWeight::try_load(univ, year) {
+                weights.push(WeightEntry {
+                    university: univ,
+                    name: univ.name().to_string(),
+                    year,
+                    weight,
+                });
+            }
+        }
+    }
+    weights
+}
+
+/// `WeightDatabase::embedded` as pretty-printed JSON. Fields keep the
+/// order of the structs and entries the order of `WeightDatabase::embedded`,
+/// so the document is the same on every run and can be diffed between
+/// releases.
+///
+/// ```
+/// use suneung_calc::export::{dump_weights_json, WeightDatabase};
+///
+/// let json = dump_weights_json();
+/// let database: WeightDatabase = serde_json::from_str(&json).unwrap();
+/// assert_eq!(database.crate_version, env!("CARGO_PKG_VERSION"));
+/// assert!(database.weights.iter().all(|e| e.weight.validate().is_ok()));
+/// // Parsing and writing again gives the same bytes
+/// assert_eq!(serde_json::to_string_pretty(&database).unwrap() + "\n", json);
+/// assert_eq!(dump_weights_json(), json);
+/// ```
+#[cfg(feature = "json")]
+pub fn dump_weights_json() -> String {
+    let json = serde_json::to_string_pretty(&WeightDatabase::embedded())
+        .expect("weights serialize to JSON");
+    json + "\n"
+}
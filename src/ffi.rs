@@ -0,0 +1,249 @@
+//! C ABI for embedding the calculator in other languages.
+//!
+//! Every function returns `SUNEUNG_OK` (0) or an error code; the message of
+//! the last error on the calling thread is available from
+//! `suneung_last_error_message`. Panics are caught at the boundary and
+//! reported as `SUNEUNG_ERR_PANIC`. The header is generated with
+//! `cbindgen --config cbindgen.toml --output include/suneung_calc.h src/ffi.rs`.
+//!
+//! Subject codes follow `Subject::all()` (0 = Korean .. 4 = EarthScience) and
+//! university codes follow `University::all()`.
+
+use crate::error::SuneungError;
+use crate::score::{Grade, Percentile, Record, Score, StandardScore, Subject, University};
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+pub const SUNEUNG_OK: i32 = 0;
+pub const SUNEUNG_ERR_INVALID_ARGUMENT: i32 = -1;
+pub const SUNEUNG_ERR_PANIC: i32 = -2;
+pub const SUNEUNG_ERR_IO: i32 = 1;
+pub const SUNEUNG_ERR_PARQUET: i32 = 2;
+pub const SUNEUNG_ERR_CHECKSUM: i32 = 3;
+pub const SUNEUNG_ERR_WEIGHT_DATA: i32 = 4;
+pub const SUNEUNG_ERR_UNSUPPORTED_YEAR: i32 = 5;
+pub const SUNEUNG_ERR_MISSING_SUBJECT: i32 = 6;
+pub const SUNEUNG_ERR_INVALID_SCORE: i32 = 7;
+pub const SUNEUNG_ERR_REPORT_PARSE: i32 = 8;
+pub const SUNEUNG_ERR_UNKNOWN_NAME: i32 = 9;
+pub const SUNEUNG_ERR_PLOT: i32 = 10;
+pub const SUNEUNG_ERR_CONFIG: i32 = 11;
+pub const SUNEUNG_ERR_PDF: i32 = 12;
+pub const SUNEUNG_ERR_INVALID_INPUT: i32 = 13;
+pub const SUNEUNG_ERR_INSUFFICIENT_DATA: i32 = 14;
+pub const SUNEUNG_ERR_FORMULA_PARSE: i32 = 15;
+pub const SUNEUNG_ERR_STRICT: i32 = 16;
+
+/// Opaque handle to a `Record`
+pub struct SuneungRecord(Record);
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: String) {
+    // Interior NULs cannot be represented in a C string
+    let message = CString::new(message.replace('\0', " ")).unwrap();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message));
+}
+
+fn error_code(e: &SuneungError) -> i32 {
+    match e {
+        SuneungError::Io(_) => SUNEUNG_ERR_IO,
+        SuneungError::Parquet(_) => SUNEUNG_ERR_PARQUET,
+        SuneungError::ChecksumMismatch { .. } => SUNEUNG_ERR_CHECKSUM,
+        SuneungError::WeightData(_) => SUNEUNG_ERR_WEIGHT_DATA,
+        SuneungError::UnsupportedYear { .. } => SUNEUNG_ERR_UNSUPPORTED_YEAR,
+        SuneungError::MissingSubject(_) => SUNEUNG_ERR_MISSING_SUBJECT,
+        SuneungError::InvalidScore { .. } | SuneungError::ScoreOutOfRange(_) => {
+            SUNEUNG_ERR_INVALID_SCORE
+        }
+        SuneungError::ReportParse(_) => SUNEUNG_ERR_REPORT_PARSE,
+        SuneungError::UnknownName { .. } => SUNEUNG_ERR_UNKNOWN_NAME,
+        SuneungError::Plot(_) => SUNEUNG_ERR_PLOT,
+        SuneungError::Config { .. } => SUNEUNG_ERR_CONFIG,
+        SuneungError::Pdf(_) => SUNEUNG_ERR_PDF,
+        SuneungError::InvalidInput(_) => SUNEUNG_ERR_INVALID_INPUT,
+        SuneungError::InsufficientData(_) => SUNEUNG_ERR_INSUFFICIENT_DATA,
+        SuneungError::FormulaParse { .. } => SUNEUNG_ERR_FORMULA_PARSE,
+        SuneungError::Strict(_) => SUNEUNG_ERR_STRICT,
+    }
+}
+
+enum FfiError {
+    InvalidArgument(String),
+    Suneung(SuneungError),
+}
+
+impl From<SuneungError> for FfiError {
+    fn from(e: SuneungError) -> Self {
+        FfiError::Suneung(e)
+    }
+}
+
+// Run `f`, turning errors and panics into a return code
+fn guard<F: FnOnce() -> Result<(), FfiError>>(f: F) -> i32 {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => SUNEUNG_OK,
+        Ok(Err(FfiError::InvalidArgument(message))) => {
+            set_last_error(message);
+            SUNEUNG_ERR_INVALID_ARGUMENT
+        }
+        Ok(Err(FfiError::Suneung(e))) => {
+            set_last_error(e.to_string());
+            error_code(&e)
+        }
+        Err(_) => {
+            set_last_error("panic inside suneung_calc".to_string());
+            SUNEUNG_ERR_PANIC
+        }
+    }
+}
+
+fn invalid(message: &str) -> FfiError {
+    FfiError::InvalidArgument(message.to_string())
+}
+
+/// New empty record, or NULL if `name` is NULL or not UTF-8.
+///
+/// # Safety
+/// `name` must be NULL or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn suneung_record_new(name: *const c_char) -> *mut SuneungRecord {
+    let mut record = ptr::null_mut();
+    guard(|| {
+        if name.is_null() {
+            return Err(invalid("name is NULL"));
+        }
+        let name = CStr::from_ptr(name)
+            .to_str()
+            .map_err(|_| invalid("name is not valid UTF-8"))?;
+        record = Box::into_raw(Box::new(SuneungRecord(Record::new(name))));
+        Ok(())
+    });
+    record
+}
+
+/// Record one subject. English only uses `grade`.
+///
+/// # Safety
+/// `record` must be NULL or a pointer from `suneung_record_new`.
+#[no_mangle]
+pub unsafe extern "C" fn suneung_record_set(
+    record: *mut SuneungRecord,
+    subject_code: u32,
+    standard_score: f64,
+    percentile: f64,
+    grade: u32,
+) -> i32 {
+    guard(|| {
+        let record = record.as_mut().ok_or_else(|| invalid("record is NULL"))?;
+        let subject = *Subject::all()
+            .get(subject_code as usize)
+            .ok_or_else(|| invalid("unknown subject code"))?;
+        let grade = Grade::try_from(grade as usize)?;
+        let score = match subject {
+            Subject::English => Score::grade_only(grade),
+            _ => Score::new_typed(
+                StandardScore::new(standard_score)?,
+                Percentile::new(percentile)?,
+                grade,
+            ),
+        };
+        record.0.record_score(subject, score);
+        Ok(())
+    })
+}
+
+/// Converted score at a university, written to `out_score`.
+///
+/// # Safety
+/// `record` must be NULL or a pointer from `suneung_record_new`, and
+/// `out_score` must be NULL or point to writable memory for an f64.
+#[no_mangle]
+pub unsafe extern "C" fn suneung_calc(
+    record: *const SuneungRecord,
+    university_code: u32,
+    year: u32,
+    out_score: *mut f64,
+) -> i32 {
+    guard(|| {
+        let record = record.as_ref().ok_or_else(|| invalid("record is NULL"))?;
+        if out_score.is_null() {
+            return Err(invalid("out_score is NULL"));
+        }
+        let university = *University::all()
+            .get(university_code as usize)
+            .ok_or_else(|| invalid("unknown university code"))?;
+        let score = record
+            .0
+            .try_calc_with_university(university, year as usize)?;
+        *out_score = score;
+        Ok(())
+    })
+}
+
+/// Free a record. NULL is ignored.
+///
+/// # Safety
+/// `record` must be NULL or a pointer from `suneung_record_new` that has not
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn suneung_record_free(record: *mut SuneungRecord) {
+    if !record.is_null() {
+        drop(Box::from_raw(record));
+    }
+}
+
+/// Message of the last error on this thread, or NULL if there was none. The
+/// string stays valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn suneung_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_error() -> String {
+        let message = suneung_last_error_message();
+        assert!(!message.is_null());
+        unsafe { CStr::from_ptr(message) }
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn panics_are_caught_at_the_boundary() {
+        let code = guard(|| panic!("inside the calculator"));
+        assert_eq!(code, SUNEUNG_ERR_PANIC);
+        assert_eq!(last_error(), "panic inside suneung_calc");
+
+        // The thread is still usable afterwards
+        assert_eq!(guard(|| Ok(())), SUNEUNG_OK);
+    }
+
+    #[test]
+    fn messages_never_hold_a_nul() {
+        let code = guard(|| Err(invalid("bad\0name")));
+        assert_eq!(code, SUNEUNG_ERR_INVALID_ARGUMENT);
+        assert_eq!(last_error(), "bad name");
+    }
+
+    #[test]
+    fn errors_keep_their_code() {
+        let code = guard(|| {
+            Err(SuneungError::UnsupportedYear {
+                university: University::KONKUK,
+                year: 1999,
+            }
+            .into())
+        });
+        assert_eq!(code, SUNEUNG_ERR_UNSUPPORTED_YEAR);
+        assert!(last_error().contains("1999"));
+    }
+}
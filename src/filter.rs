@@ -0,0 +1,70 @@
+use crate::score::{InstitutionType, Record, Region, University};
+
+/// Which universities to consider, e.g. "only Seoul, no 여대":
+///
+/// ```
+/// use suneung_calc::filter::UniversityFilter;
+/// use suneung_calc::score::{Region, University};
+///
+/// let filter = UniversityFilter::new()
+///     .region(Region::Seoul)
+///     .exclude_womens();
+/// assert!(filter.matches(University::KONKUK));
+/// assert!(!filter.matches(University::INHA));
+/// ```
+///
+/// An empty filter accepts every university.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UniversityFilter {
+    regions: Vec<Region>,
+    excluded_types: Vec<InstitutionType>,
+}
+
+impl UniversityFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept universities in `region`. Repeat to accept several regions.
+    pub fn region(mut self, region: Region) -> Self {
+        if !self.regions.contains(&region) {
+            self.regions.push(region);
+        }
+        self
+    }
+
+    /// Reject universities of `institution_type`
+    pub fn exclude(mut self, institution_type: InstitutionType) -> Self {
+        if !self.excluded_types.contains(&institution_type) {
+            self.excluded_types.push(institution_type);
+        }
+        self
+    }
+
+    pub fn exclude_womens(self) -> Self {
+        self.exclude(InstitutionType::WomensUniversity)
+    }
+
+    pub fn matches(&self, university: University) -> bool {
+        (self.regions.is_empty() || self.regions.contains(&university.region()))
+            && !self.excluded_types.contains(&university.institution_type())
+    }
+
+    /// Universities accepted by the filter, in `University::all()` order
+    pub fn universities(&self) -> impl Iterator<Item = University> + '_ {
+        University::iter().filter(|&u| self.matches(u))
+    }
+}
+
+impl Record {
+    /// `calc_all` restricted to the universities accepted by `filter`
+    pub fn calc_all_filtered(
+        &self,
+        year: usize,
+        filter: &UniversityFilter,
+    ) -> crate::error::Result<Vec<(University, f64)>> {
+        let mut scores = self.calc_all(year)?;
+        scores.retain(|&(univ, _)| filter.matches(univ));
+        Ok(scores)
+    }
+}
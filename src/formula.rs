@@ -0,0 +1,339 @@
+use crate::error::{Result, SuneungError};
+use crate::score::{
+    CalcBreakdown, CalcWarning, Grade, Percentile, Record, Score, ScoreMetric, StandardScore,
+    Subject, UniversityWeight,
+};
+use serde::{Deserialize, Serialize};
+
+/// How a university turns a record into a converted score
+pub trait ScoreFormula {
+    fn convert(&self, record: &Record, weight: &UniversityWeight) -> Result<CalcBreakdown>;
+}
+
+/// Which `ScoreFormula` a university's weight data uses
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FormulaKind {
+    #[default]
+    Standard,
+    Percentile,
+    DeductionEnglish,
+}
+
+/// How the two 탐구 scores enter the ratio
+///
+/// ```
+/// use suneung_calc::builder::UniversityWeightBuilder;
+/// use suneung_calc::formula::{ScienceAggregation, ScoreFormula};
+/// use suneung_calc::score::{Record, University, UniversityWeight};
+///
+/// // Embedded weights keep the sum of the two subjects
+/// let record = Record::sample();
+/// let kyunghee = UniversityWeight::try_load(University::KYUNGHEE, 2024).unwrap();
+/// assert_eq!(kyunghee.science_aggregation(), ScienceAggregation::Sum);
+/// let breakdown = record.calc_breakdown(University::KYUNGHEE, 2024).unwrap();
+/// assert_eq!(breakdown.science_aggregation, ScienceAggregation::Sum);
+/// assert!((breakdown.total - 388.2353).abs() < 1e-4);
+/// let sogang = record.calc_with_university(University::SOGANG, 2024);
+/// assert!((sogang - 388.503).abs() < 1e-4);
+///
+/// // 화학Ⅰ 64 and 지구과학Ⅰ 66 average to 65. With equal weights the
+/// // total is (131 + 128 + 65) / 3 × 3, less a quarter of the 2 points
+/// // English 2등급 loses against the default 1등급.
+/// let average = UniversityWeightBuilder::new()
+///     .korean(1.0)
+///     .math(1.0)
+///     .science(1.0)
+///     .science_aggregation(ScienceAggregation::Average)
+///     .english_table([100.0, 98.0, 95.0, 92.0, 86.0, 75.0, 60.0, 40.0, 20.0])
+///     .build()
+///     .unwrap();
+/// let breakdown = average.formula().formula().convert(&record, &average).unwrap();
+/// assert_eq!(breakdown.science_aggregation, ScienceAggregation::Average);
+/// assert!((breakdown.total - 323.5).abs() < 1e-9);
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ScienceAggregation {
+    /// 화학Ⅰ plus 지구과학Ⅰ
+    #[default]
+    Sum,
+    /// Mean of 화학Ⅰ and 지구과학Ⅰ
+    Average,
+    /// Twice the better of the two, for one required subject; ties on the
+    /// reflected metric go by `Score::max_by`
+    BestDoubled,
+}
+
+impl ScienceAggregation {
+    /// What `science_required` meant before the aggregation was recorded:
+    /// the better subject doubled for one, the sum for two
+    pub fn for_required(science_required: usize) -> Self {
+        if science_required == 1 {
+            ScienceAggregation::BestDoubled
+        } else {
+            ScienceAggregation::Sum
+        }
+    }
+
+    /// Number of 탐구 subjects the aggregation needs
+    pub fn subjects(&self) -> usize {
+        match self {
+            ScienceAggregation::BestDoubled => 1,
+            _ => 2,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScienceAggregation::Sum => "2과목 합",
+            ScienceAggregation::Average => "2과목 평균",
+            ScienceAggregation::BestDoubled => "상위 1과목 ×2",
+        }
+    }
+}
+
+impl FormulaKind {
+    pub fn formula(&self) -> &'static dyn ScoreFormula {
+        match self {
+            FormulaKind::Standard => &StandardFormula,
+            FormulaKind::Percentile => &PercentileFormula,
+            FormulaKind::DeductionEnglish => &DeductionEnglishFormula,
+        }
+    }
+}
+
+/// 표준점수 reflection ratio for 국어/수학/탐구, with English either taking
+/// part in the ratio or added as a bonus relative to the default grade.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct StandardFormula;
+
+/// Like `StandardFormula`, but 탐구 is reflected by 백분위
+#[derive(Debug, Copy, Clone, Default)]
+pub struct PercentileFormula;
+
+/// Like `StandardFormula`, but the English table lists points deducted per
+/// grade instead of points awarded.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct DeductionEnglishFormula;
+
+impl ScoreFormula for StandardFormula {
+    fn convert(&self, record: &Record, weight: &UniversityWeight) -> Result<CalcBreakdown> {
+        let (korean, math, science) = weighted_parts(record, weight, ScoreMetric::StandardScore)?;
+        let english = english_ratio_or_bonus(record, weight)?;
+        Ok(breakdown(weight, korean, math, science, english))
+    }
+}
+
+impl ScoreFormula for PercentileFormula {
+    fn convert(&self, record: &Record, weight: &UniversityWeight) -> Result<CalcBreakdown> {
+        let (korean, math, _) = weighted_parts(record, weight, ScoreMetric::StandardScore)?;
+        let (_, _, science) = weighted_parts(record, weight, ScoreMetric::Percentile)?;
+        let english = english_ratio_or_bonus(record, weight)?;
+        Ok(breakdown(weight, korean, math, science, english))
+    }
+}
+
+impl ScoreFormula for DeductionEnglishFormula {
+    fn convert(&self, record: &Record, weight: &UniversityWeight) -> Result<CalcBreakdown> {
+        let (korean, math, science) = weighted_parts(record, weight, ScoreMetric::StandardScore)?;
+        let eng_rank = record.require(Subject::English)?.rank();
+        let english = -weight.english_table().require(eng_rank)?;
+        Ok(breakdown(weight, korean, math, science, english))
+    }
+}
+
+// 국어, 수학 and 탐구 shares of the ratio excluding English, before the x3
+// scaling. `science_metric` picks what 탐구 is reflected by, and which
+// subject `BestDoubled` takes, see `Score::max_by`.
+fn weighted_parts(
+    record: &Record,
+    weight: &UniversityWeight,
+    science_metric: ScoreMetric,
+) -> Result<(f64, f64, f64)> {
+    let weight_sum_except_eng = weight.korean() + weight.math() + weight.science();
+
+    let korean =
+        record.require(Subject::Korean)?.standard_score() * weight.korean() / weight_sum_except_eng;
+    let math =
+        record.require(Subject::Math)?.standard_score() * weight.math() / weight_sum_except_eng;
+    let both = || -> Result<f64> {
+        Ok(science_metric.value(&record.require(Subject::Chemistry)?)
+            + science_metric.value(&record.require(Subject::EarthScience)?))
+    };
+    let science_cand = match weight.science_aggregation() {
+        ScienceAggregation::BestDoubled => {
+            let sciences = [Subject::Chemistry, Subject::EarthScience]
+                .into_iter()
+                .filter_map(|s| record.try_score(s));
+            let best = Score::max_by(sciences, science_metric)
+                .ok_or(SuneungError::MissingSubject(Subject::Chemistry))?;
+            science_metric.value(&best) * 2f64
+        }
+        ScienceAggregation::Sum => both()?,
+        ScienceAggregation::Average => both()? / 2f64,
+    };
+    let science = science_cand * weight.science() / weight_sum_except_eng;
+
+    Ok((korean, math, science))
+}
+
+// English relative to the default grade: part of the reflection ratio when
+// it has a weight, a bonus scaled by 1/4 otherwise
+fn english_ratio_or_bonus(record: &Record, weight: &UniversityWeight) -> Result<f64> {
+    english_relative(weight, record.require(Subject::English)?.rank())
+}
+
+// `english_ratio_or_bonus` of the English grade `grade`
+pub(crate) fn english_relative(weight: &UniversityWeight, grade: Grade) -> Result<f64> {
+    let weight_eng = weight.english();
+    let weight_sum = weight.korean() + weight.math() + weight.science() + weight_eng;
+
+    let default = Grade::try_from(weight.english_required())?;
+    let table = weight.english_table();
+    let delta = table.require(grade)? - table.require(default)?;
+
+    Ok(if weight_eng > 0f64 {
+        delta * weight_eng / weight_sum
+    } else {
+        delta / 4f64
+    })
+}
+
+fn breakdown(
+    weight: &UniversityWeight,
+    korean: f64,
+    math: f64,
+    science: f64,
+    english: f64,
+) -> CalcBreakdown {
+    let total = (korean + math + science) * 3f64;
+    CalcBreakdown {
+        korean: korean * 3f64,
+        math: math * 3f64,
+        science: science * 3f64,
+        english,
+        total: total + english,
+        science_aggregation: weight.science_aggregation(),
+        warnings: vec![],
+    }
+}
+
+/// 200/100/1등급 in every subject, English by grade only
+pub(crate) fn perfect_record() -> Record {
+    let mut record = Record::new("perfect");
+    for &subject in Subject::all() {
+        match subject {
+            Subject::English => record.record_score(subject, Score::grade_only(Grade::MIN)),
+            _ => record.record_typed(subject, StandardScore::MAX, Percentile::MAX, Grade::MIN),
+        }
+    }
+    record
+}
+
+// `InvalidInput` for a contribution that is not finite, then `total` kept
+// within 0 and `max` with a warning when it moved
+pub(crate) fn guard(mut breakdown: CalcBreakdown, max: f64) -> Result<CalcBreakdown> {
+    let parts = [
+        ("국어", breakdown.korean),
+        ("수학", breakdown.math),
+        ("탐구", breakdown.science),
+        ("영어", breakdown.english),
+        ("total", breakdown.total),
+    ];
+    if let Some((name, x)) = parts.iter().find(|(_, x)| !x.is_finite()) {
+        return Err(SuneungError::InvalidInput(format!(
+            "{} contribution to the converted score is {}",
+            name, x
+        )));
+    }
+    let unclamped = breakdown.total;
+    if unclamped < 0f64 {
+        breakdown.total = 0f64;
+        breakdown
+            .warnings
+            .push(CalcWarning::ClampedToZero { unclamped });
+    } else if unclamped > max {
+        breakdown.total = max;
+        breakdown
+            .warnings
+            .push(CalcWarning::ClampedToMax { unclamped, max });
+    }
+    Ok(breakdown)
+}
+
+impl UniversityWeight {
+    /// Converted score of 200/100/1등급 in every subject, the ceiling of
+    /// `convert`. Found once per weight and kept for later calls.
+    pub fn max_score(&self) -> Result<f64> {
+        if let Some(&max) = self.max_score.get() {
+            return Ok(max);
+        }
+        let perfect = self.formula().formula().convert(&perfect_record(), self)?;
+        Ok(*self.max_score.get_or_init(|| perfect.total))
+    }
+
+    /// Converted score of `record` by the weight's formula, with `total`
+    /// kept within 0 and `max_score`; `InvalidInput` when a contribution is
+    /// not finite, e.g. for weights summing to 0.
+    ///
+    /// ```
+    /// use suneung_calc::builder::UniversityWeightBuilder;
+    /// use suneung_calc::formula::FormulaKind;
+    /// use suneung_calc::score::{
+    ///     CalcWarning, Grade, Percentile, Record, Score, StandardScore, Subject,
+    /// };
+    ///
+    /// let grade = |g: u8| Grade::try_from(g).unwrap();
+    ///
+    /// // English 2등급 loses 100 points against the default 1등급, a bonus
+    /// // of -25 on top of nothing
+    /// let weight = UniversityWeightBuilder::new()
+    ///     .korean(1.0)
+    ///     .math(1.0)
+    ///     .science(1.0)
+    ///     .english_table(&[100.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0])
+    ///     .build()
+    ///     .unwrap();
+    /// let mut record = Record::new("zero");
+    /// for subject in [Subject::Korean, Subject::Math, Subject::Chemistry, Subject::EarthScience] {
+    ///     record.record_score(subject, Score::grade_only(grade(9)));
+    /// }
+    /// record.record_score(Subject::English, Score::grade_only(grade(2)));
+    /// let breakdown = weight.convert(&record).unwrap();
+    /// assert_eq!(breakdown.total, 0.0);
+    /// assert_eq!(breakdown.english, -25.0);
+    /// assert_eq!(breakdown.warnings, [CalcWarning::ClampedToZero { unclamped: -25.0 }]);
+    ///
+    /// // A deduction table takes nothing off 1등급, so the maximum is that
+    /// // of 1등급 and a perfect record with 9등급 loses the whole deduction
+    /// let weight = UniversityWeightBuilder::new()
+    ///     .formula(FormulaKind::DeductionEnglish)
+    ///     .korean(1.0)
+    ///     .math(1.0)
+    ///     .science(1.0)
+    ///     .english_table(&[0.0, 0.5, 2.0, 4.0, 6.0, 8.0, 10.0, 12.0, 14.0])
+    ///     .build()
+    ///     .unwrap();
+    /// let max = weight.max_score().unwrap();
+    /// assert_eq!(max, 800.0);
+    /// let mut record = Record::new("perfect");
+    /// for subject in [Subject::Korean, Subject::Math, Subject::Chemistry, Subject::EarthScience] {
+    ///     record.record_typed(subject, StandardScore::MAX, Percentile::MAX, grade(1));
+    /// }
+    /// record.record_score(Subject::English, Score::grade_only(grade(9)));
+    /// let breakdown = weight.convert(&record).unwrap();
+    /// assert_eq!(breakdown.english, -14.0);
+    /// assert_eq!(breakdown.total, max - 14.0);
+    /// assert!(breakdown.warnings.is_empty());
+    ///
+    /// // Points awarded are not points deducted
+    /// let points = [100.0, 98.0, 95.0, 92.0, 86.0, 75.0, 60.0, 40.0, 20.0];
+    /// let builder = UniversityWeightBuilder::new().korean(1.0).math(1.0).science(1.0);
+    /// assert!(builder.clone().english_table(points).build().is_ok());
+    /// assert!(builder.formula(FormulaKind::DeductionEnglish).english_table(points).build().is_err());
+    /// ```
+    pub fn convert(&self, record: &Record) -> Result<CalcBreakdown> {
+        let breakdown = self.formula().formula().convert(record, self)?;
+        guard(breakdown, self.max_score()?)
+    }
+}
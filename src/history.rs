@@ -1,4 +1,4 @@
-use crate::score::{Record, Subject, Subject::*};
+use crate::score::{Record, Score, StandardScore, Subject, Subject::*};
 use peroxide::fuga::*;
 use std::collections::HashMap;
 
@@ -44,17 +44,26 @@ impl History {
 
     pub fn eval_all(&self, student: &Record) -> Record {
         let mut record = Record::new(student.name());
+        record.set_exam(student.exam().cloned());
+        for tag in student.tags() {
+            record.add_tag(tag);
+        }
 
         for subject in [Korean, Math, Chemistry, EarthScience] {
-            record.record(
-                subject,
-                self.eval(subject, student.percentile(subject)).round(),
-                student.percentile(subject),
-                student.rank(subject),
-            );
+            if let Some(score) = student.try_score(subject) {
+                // The curve may overshoot 0-200 far outside the table
+                record.record_typed(
+                    subject,
+                    StandardScore::unchecked(self.eval(subject, score.percentile()).round()),
+                    score.percentile_typed(),
+                    score.rank(),
+                );
+            }
         }
 
-        record.record(English, 0f64, 0f64, student.rank(English));
+        if let Some(score) = student.try_score(English) {
+            record.record_score(English, Score::grade_only(score.rank()));
+        }
 
         record
     }
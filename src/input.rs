@@ -0,0 +1,140 @@
+//! Limits and normalization for text that comes from outside the crate:
+//! pasted 성적통지표 text (`Record::parse_report_text`), config files
+//! (`Config::from_toml_str`), records in TOML (`Record::from_toml_str`), the
+//! anonymization mapping CSV and score arguments on the command line.
+//!
+//! `normalize` runs before anything is parsed and only rewrites characters
+//! that look like ASCII but are not:
+//!
+//! - full-width forms (`１３１`, `／`, `（`, `％`, `Ａ`) become their ASCII
+//!   counterparts
+//! - every other Unicode space (`U+3000`, no-break space, ...) becomes ` `
+//! - leading byte order marks are dropped
+//!
+//! Hangul, `Ⅰ` and everything else is kept, and ASCII text is returned
+//! unchanged, so normalizing twice gives the same text as normalizing once
+//! and a record written by this crate reads back the same.
+//!
+//! ```
+//! use suneung_calc::input::normalize;
+//!
+//! assert_eq!(normalize("국어\u{3000}１３１ ９３ ２"), "국어 131 93 2");
+//! assert_eq!(normalize("영어 2"), "영어 2");
+//! ```
+
+use crate::error::{Result, SuneungError};
+use std::borrow::Cow;
+use std::io::Read;
+use std::path::Path;
+
+/// Largest text accepted by the importers, in bytes (1 MiB). A real
+/// 성적통지표 or config file is a few hundred bytes.
+pub const MAX_INPUT_BYTES: usize = 1 << 20;
+
+/// Longest record name accepted by `check_name`, in characters
+pub const MAX_NAME_CHARS: usize = 100;
+
+const FULL_WIDTH: std::ops::RangeInclusive<char> = '\u{FF01}'..='\u{FF5E}';
+// FULL_WIDTH minus the ASCII characters it mirrors ('!'..='~')
+const FULL_WIDTH_OFFSET: u32 = 0xFEE0;
+
+/// `text` with full-width ASCII and Unicode spaces replaced, see the module
+/// documentation. Borrows when nothing needs replacing.
+pub fn normalize(text: &str) -> Cow<'_, str> {
+    let text = text.trim_start_matches('\u{FEFF}');
+    if !text.chars().any(needs_mapping) {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(text.chars().map(map_char).collect())
+}
+
+fn needs_mapping(c: char) -> bool {
+    FULL_WIDTH.contains(&c) || (c.is_whitespace() && !c.is_ascii())
+}
+
+fn map_char(c: char) -> char {
+    if FULL_WIDTH.contains(&c) {
+        char::from_u32(c as u32 - FULL_WIDTH_OFFSET).unwrap_or(c)
+    } else if c.is_whitespace() && !c.is_ascii() {
+        ' '
+    } else {
+        c
+    }
+}
+
+/// `SuneungError::InvalidInput` if `text` is over `MAX_INPUT_BYTES`;
+/// `what` names the input in the message
+pub fn check_len(what: &str, text: &str) -> Result<()> {
+    if text.len() > MAX_INPUT_BYTES {
+        return Err(SuneungError::InvalidInput(format!(
+            "{} is {} bytes, more than the limit of {}",
+            what,
+            text.len(),
+            MAX_INPUT_BYTES
+        )));
+    }
+    Ok(())
+}
+
+/// Reject record names that are empty, have control characters (newlines,
+/// tabs, NUL, ...) or more than `MAX_NAME_CHARS` characters, or that are not
+/// a single plain path component: `.`, `..`, names with `/` or `\` and
+/// drive prefixes such as `C:`. Names become directory names under the data
+/// directory and table cells, where none of these survives.
+///
+/// ```
+/// use suneung_calc::input::check_name;
+///
+/// assert!(check_name("홍길동").is_ok());
+/// assert!(check_name("../홍길동").is_err());
+/// ```
+pub fn check_name(name: &str) -> Result<()> {
+    let invalid = |reason: String| Err(SuneungError::InvalidInput(reason));
+    if name.is_empty() {
+        return invalid("name is empty".to_string());
+    }
+    if name == "." || name == ".." {
+        return invalid(format!("name {:?} is not a directory name", name));
+    }
+    if let Some(c) = name.chars().find(|c| matches!(c, '/' | '\\')) {
+        return invalid(format!("name {:?} contains path separator {:?}", name, c));
+    }
+    // `C:` and the like are absolute on Windows even without a separator
+    let mut chars = name.chars();
+    if let (Some(drive), Some(':')) = (chars.next(), chars.next()) {
+        if drive.is_ascii_alphabetic() {
+            return invalid(format!("name {:?} starts with a drive prefix", name));
+        }
+    }
+    if let Some(c) = name.chars().find(|c| c.is_control()) {
+        return invalid(format!(
+            "name {:?} contains control character {:?}",
+            name, c
+        ));
+    }
+    let len = name.chars().count();
+    if len > MAX_NAME_CHARS {
+        return invalid(format!(
+            "name is {} characters, more than the limit of {}",
+            len, MAX_NAME_CHARS
+        ));
+    }
+    Ok(())
+}
+
+/// Read `path` as UTF-8 without reading more than `MAX_INPUT_BYTES`; a
+/// larger file is an `InvalidData` error
+pub fn read_to_string(path: &Path) -> std::io::Result<String> {
+    let mut text = String::new();
+    let limit = MAX_INPUT_BYTES as u64 + 1;
+    std::fs::File::open(path)?
+        .take(limit)
+        .read_to_string(&mut text)?;
+    if text.len() > MAX_INPUT_BYTES {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("larger than the limit of {} bytes", MAX_INPUT_BYTES),
+        ));
+    }
+    Ok(text)
+}
@@ -0,0 +1,172 @@
+use crate::error::Result;
+use crate::score::{Grade, Percentile, Record, Score, StandardScore, Subject};
+use std::fmt::Display;
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+impl Record {
+    /// Build a record by prompting for every subject on `writer` and reading
+    /// the answers from `reader`.
+    ///
+    /// Invalid answers are reported and asked again. Science subjects are
+    /// optional: an empty standard score skips the subject.
+    pub fn from_interactive<R: BufRead, W: Write>(
+        name: &str,
+        mut reader: R,
+        mut writer: W,
+    ) -> Result<Record> {
+        let mut record = Record::new(name);
+
+        for &subject in Subject::all() {
+            let optional = matches!(subject, Subject::Chemistry | Subject::EarthScience);
+
+            if subject == Subject::English {
+                let grade = ask(
+                    &mut reader,
+                    &mut writer,
+                    "English grade",
+                    Grade::MIN,
+                    Grade::MAX,
+                    false,
+                )?;
+                record.record_score(subject, Score::grade_only(grade.unwrap()));
+                continue;
+            }
+
+            let prompt = if optional {
+                format!("{} standard score (empty to skip)", subject.name())
+            } else {
+                format!("{} standard score", subject.name())
+            };
+            let Some(standard_score) = ask(
+                &mut reader,
+                &mut writer,
+                &prompt,
+                StandardScore::MIN,
+                StandardScore::MAX,
+                optional,
+            )?
+            else {
+                continue;
+            };
+            let percentile = ask(
+                &mut reader,
+                &mut writer,
+                &format!("{} percentile", subject.name()),
+                Percentile::MIN,
+                Percentile::MAX,
+                false,
+            )?;
+            let grade = ask(
+                &mut reader,
+                &mut writer,
+                &format!("{} grade", subject.name()),
+                Grade::MIN,
+                Grade::MAX,
+                false,
+            )?;
+            record.record_typed(subject, standard_score, percentile.unwrap(), grade.unwrap());
+        }
+
+        Ok(record)
+    }
+}
+
+/// Prompt until a value in `min..=max` is entered. Returns `None` for an
+/// empty answer when `allow_skip` is set.
+fn ask<T, R, W>(
+    reader: &mut R,
+    writer: &mut W,
+    prompt: &str,
+    min: T,
+    max: T,
+    allow_skip: bool,
+) -> Result<Option<T>>
+where
+    T: FromStr + PartialOrd + Display,
+    R: BufRead,
+    W: Write,
+{
+    loop {
+        write!(writer, "{}: ", prompt)?;
+        writer.flush()?;
+
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!("Input ended while waiting for {}", prompt),
+            )
+            .into());
+        }
+
+        let answer = line.trim();
+        if answer.is_empty() && allow_skip {
+            return Ok(None);
+        }
+        match answer.parse::<T>() {
+            Ok(value) if value >= min && value <= max => return Ok(Some(value)),
+            _ => writeln!(
+                writer,
+                "Invalid input {:?}: expected a number between {} and {}",
+                answer, min, max
+            )?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(input: &str) -> (Result<Record>, String) {
+        let mut output = vec![];
+        let record = Record::from_interactive("학생", input.as_bytes(), &mut output);
+        (record, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn every_subject_entered() {
+        let (record, output) = run("131\n93\n2\n128\n89\n2\n2\n64\n92\n2\n66\n94\n1\n");
+        let record = record.unwrap();
+        assert_eq!(record.len(), 5);
+        assert_eq!(record.standard_score(Subject::EarthScience), 66.0);
+        assert_eq!(record.rank(Subject::English), Grade::try_from(2u8).unwrap());
+        assert!(!output.contains("Invalid input"));
+    }
+
+    #[test]
+    fn typo_is_asked_again_and_science_skipped() {
+        // "13l" for 131, a percentile above 100, no chemistry
+        let input = "13l\n131\n930\n93\n2\n128\n89\n2\n2\n\n66\n94\n1\n";
+        let (record, output) = run(input);
+        let record = record.unwrap();
+
+        assert_eq!(record.standard_score(Subject::Korean), 131.0);
+        assert_eq!(record.percentile(Subject::Korean), 93.0);
+        assert!(record.try_score(Subject::Chemistry).is_none());
+        assert_eq!(record.standard_score(Subject::EarthScience), 66.0);
+        assert_eq!(record.len(), 4);
+
+        assert!(output.contains("Invalid input \"13l\""));
+        assert!(output.contains("Invalid input \"930\""));
+        assert_eq!(output.matches("Korean standard score: ").count(), 2);
+        assert_eq!(output.matches("Invalid input").count(), 2);
+    }
+
+    #[test]
+    fn required_subjects_cannot_be_skipped() {
+        let (record, output) = run("\n131\n93\n2\n128\n89\n2\n2\n\n\n");
+        let record = record.unwrap();
+        assert_eq!(record.standard_score(Subject::Korean), 131.0);
+        assert_eq!(record.len(), 3);
+        assert!(output.contains("Invalid input \"\""));
+    }
+
+    #[test]
+    fn ended_input_is_an_error() {
+        let (record, output) = run("131\n93\n");
+        assert!(record.is_err());
+        assert!(output.ends_with("Korean grade: "));
+    }
+}
@@ -1,4 +1,63 @@
+pub mod admission;
+pub mod api;
+#[cfg(feature = "archive")]
+pub mod archive;
+mod atomic;
+#[cfg(feature = "parquet")]
+pub mod batch;
+#[cfg(feature = "binary")]
+mod binary;
+pub mod builder;
+pub mod calculator;
+#[cfg(any(feature = "archive", feature = "parquet"))]
+mod checksum;
+#[cfg(feature = "stats")]
+pub mod cohort;
+#[cfg(feature = "toml")]
+pub mod config;
+pub mod convert;
+pub mod custom_formula;
+pub mod diff;
+mod display;
+pub mod error;
+#[cfg(feature = "stats")]
+pub mod estimate;
+pub mod exam;
+pub mod expected;
+pub mod export;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod filter;
+pub mod formula;
+#[cfg(feature = "stats")]
 pub mod history;
+pub mod input;
+mod interactive;
+#[cfg(feature = "stats")]
+pub mod normalize;
+#[cfg(feature = "stats")]
+pub mod plan;
+#[cfg(feature = "plot")]
+pub mod plot;
+pub mod portfolio;
+mod report_text;
+pub mod record_set;
+#[cfg(feature = "table")]
+pub mod render;
+#[cfg(feature = "stats")]
+pub mod report;
 pub mod score;
+#[cfg(feature = "schema")]
+pub mod schema;
+#[cfg(feature = "server")]
+pub mod server;
 pub mod suneung_data;
+pub mod targets;
 pub mod university_weight;
+pub mod validate;
+#[cfg(feature = "parquet")]
+pub mod watch;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod weight_info;
+pub mod weight_source;
@@ -66,13 +66,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 let grade = Input::with_theme(&theme)
                     .with_prompt(format!("Input {} grade", subject.name()))
                     .interact()?;
-                record.record(subject, standard_score, percentile, grade);
+                record.record_typed(subject, standard_score, percentile, grade);
             }
 
             record.write_parquet()?;
         } else {
             let record_name = options[idx].clone();
-            let record = Record::read_parquet(record_name.as_str());
+            let record = Record::read_parquet(record_name.as_str())?;
             break record;
         }
     };
@@ -0,0 +1,179 @@
+//! Standard scores of different exams on one scale. A 표준점수 of 131 in
+//! a hard 6월 모의평가 and one in an easy 수능 are not the same result, so
+//! trends across exams compare z-scores, `(표준점수 - mean) / σ` with the
+//! national mean and σ of each exam.
+//!
+//! The embedded statistics are those of the 수능 with 등급컷 in
+//! `suneung_data` (2022-2025학년도), fitted as in
+//! `SubjectProfile::from_cuts`. Exams without embedded statistics use the
+//! nominal scale of the 표준점수 (mean 100 and σ 20 for 국어/수학, 50 and
+//! 10 for 탐구) until statistics are inserted into an `ExamStatsTable`.
+
+use crate::cohort::{CohortProfile, SubjectProfile};
+use crate::exam::{ExamId, ExamKind};
+use crate::score::{Record, Subject};
+use std::collections::HashMap;
+
+/// National mean and σ of the 표준점수 of each subject in one exam
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExamStats {
+    subjects: HashMap<Subject, SubjectProfile>,
+}
+
+impl ExamStats {
+    /// No subject has statistics
+    pub fn new() -> Self {
+        Self {
+            subjects: HashMap::new(),
+        }
+    }
+
+    /// The scale 표준점수 are designed on: mean 100 and σ 20 for 국어 and
+    /// 수학, 50 and 10 for 탐구
+    pub fn nominal() -> Self {
+        Self::new()
+            .with(Subject::Korean, SubjectProfile::new(100f64, 20f64))
+            .with(Subject::Math, SubjectProfile::new(100f64, 20f64))
+            .with(Subject::Chemistry, SubjectProfile::new(50f64, 10f64))
+            .with(Subject::EarthScience, SubjectProfile::new(50f64, 10f64))
+    }
+
+    /// Statistics fitted to the 등급컷 of the 수능 for `year`, or `None`
+    /// for a year without them
+    pub fn csat(year: usize) -> Option<Self> {
+        let profile = CohortProfile::for_year(year)?;
+        Some(
+            Self::new()
+                .with(Subject::Korean, profile.korean)
+                .with(Subject::Math, profile.math)
+                .with(Subject::Chemistry, profile.chemistry)
+                .with(Subject::EarthScience, profile.earth_science),
+        )
+    }
+
+    pub fn with(mut self, subject: Subject, profile: SubjectProfile) -> Self {
+        self.set(subject, profile);
+        self
+    }
+
+    pub fn set(&mut self, subject: Subject, profile: SubjectProfile) {
+        self.subjects.insert(subject, profile);
+    }
+
+    pub fn get(&self, subject: Subject) -> Option<&SubjectProfile> {
+        self.subjects.get(&subject)
+    }
+
+    /// z-score of `standard_score` in `subject`, `None` without statistics
+    /// for the subject or with a σ that is not positive
+    pub fn z_score(&self, subject: Subject, standard_score: f64) -> Option<f64> {
+        let p = self.get(subject)?;
+        (p.std > 0f64).then(|| (standard_score - p.mean) / p.std)
+    }
+}
+
+impl Default for ExamStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `ExamStats` per exam, with `ExamStats::nominal` for the others
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ExamStatsTable {
+    exams: HashMap<ExamId, ExamStats>,
+}
+
+impl ExamStatsTable {
+    /// No exam has statistics of its own
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `ExamStats::csat` of every 수능 in `suneung_data`
+    pub fn embedded() -> Self {
+        let mut table = Self::new();
+        for year in 2022..=2025 {
+            if let Some(stats) = ExamStats::csat(year) {
+                table.insert(ExamId::new(ExamKind::Csat, year as u16), stats);
+            }
+        }
+        table
+    }
+
+    /// Use `stats` for `exam`, replacing what it had
+    pub fn insert(&mut self, exam: ExamId, stats: ExamStats) {
+        self.exams.insert(exam, stats);
+    }
+
+    /// Statistics of `exam`, `ExamStats::nominal` when it has none
+    pub fn get(&self, exam: ExamId) -> ExamStats {
+        self.exams
+            .get(&exam)
+            .cloned()
+            .unwrap_or_else(ExamStats::nominal)
+    }
+}
+
+impl Record {
+    /// z-score of the 표준점수 of every recorded subject that `stats`
+    /// covers; English, which has no 표준점수, is left out
+    ///
+    /// ```
+    /// use suneung_calc::cohort::SubjectProfile;
+    /// use suneung_calc::normalize::ExamStats;
+    /// use suneung_calc::score::{Record, Subject};
+    ///
+    /// let stats = ExamStats::new().with(Subject::Korean, SubjectProfile::new(95f64, 18f64));
+    /// let z = Record::sample().normalized_against(&stats);
+    /// // 국어 131: (131 - 95) / 18
+    /// assert_eq!(z[&Subject::Korean], 2.0);
+    /// assert!(!z.contains_key(&Subject::Math));
+    /// ```
+    pub fn normalized_against(&self, stats: &ExamStats) -> HashMap<Subject, f64> {
+        self.iter()
+            .filter(|(subject, _)| *subject != Subject::English)
+            .filter_map(|(subject, score)| {
+                Some((subject, stats.z_score(subject, score.standard_score())?))
+            })
+            .collect()
+    }
+}
+
+/// `z_scores_with` the embedded statistics
+pub fn z_scores(history: &[(ExamId, Record)]) -> Vec<(ExamId, HashMap<Subject, f64>)> {
+    z_scores_with(history, &ExamStatsTable::embedded())
+}
+
+/// Every record of `history` normalized against the statistics of its
+/// exam in `table`, in the order of `history`
+///
+/// ```
+/// use suneung_calc::cohort::SubjectProfile;
+/// use suneung_calc::exam::{ExamId, ExamKind};
+/// use suneung_calc::normalize::{z_scores_with, ExamStats, ExamStatsTable};
+/// use suneung_calc::score::{Record, Subject};
+///
+/// let june = ExamId::new(ExamKind::Mock6, 2025);
+/// let november = ExamId::new(ExamKind::Csat, 2025);
+/// let mut table = ExamStatsTable::new();
+/// table.insert(
+///     june,
+///     ExamStats::new().with(Subject::Math, SubjectProfile::new(90f64, 19f64)),
+/// );
+///
+/// let history = [(june, Record::sample()), (november, Record::sample())];
+/// let z = z_scores_with(&history, &table);
+/// // 수학 128: (128 - 90) / 19 in June, (128 - 100) / 20 on the nominal scale
+/// assert_eq!(z[0].1[&Subject::Math], 2.0);
+/// assert_eq!(z[1].1[&Subject::Math], 1.4);
+/// ```
+pub fn z_scores_with(
+    history: &[(ExamId, Record)],
+    table: &ExamStatsTable,
+) -> Vec<(ExamId, HashMap<Subject, f64>)> {
+    history
+        .iter()
+        .map(|(exam, record)| (*exam, record.normalized_against(&table.get(*exam))))
+        .collect()
+}
@@ -0,0 +1,126 @@
+//! Where a study hour pays off most: for each university, the one subject
+//! whose improvement raises the converted score the most per unit.
+//!
+//! A unit is one 표준점수 point for 국어, 수학 and 탐구, and one 등급 step
+//! for English. Raised scores take the 백분위 and 등급 they have under the
+//! distribution fitted to the year's 등급컷 (`CohortProfile::for_year`,
+//! the latest year for others) when that is better than the recorded one.
+//! `suneung_data` has no 최고점, so a subject counts as maxed at the lowest
+//! 표준점수 with a 백분위 of 100 under that distribution.
+
+use crate::cohort::CohortProfile;
+use crate::score::{Grade, Percentile, Record, Score, StandardScore, Subject, University};
+use serde::Serialize;
+
+/// Best single improvement at one university
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PlanEntry {
+    pub university: University,
+    pub subject: Subject,
+    /// Units of improvement within the budget: 표준점수 points, or English
+    /// 등급 steps
+    pub improvement: f64,
+    /// Converted score after the improvement minus the current one
+    pub gain: f64,
+    /// `gain / improvement`
+    pub leverage: f64,
+}
+
+impl Record {
+    /// For every university with data for `year` that can score the
+    /// record, the subject with the largest converted-score gain per unit
+    /// when improved by up to `budget_points` whole units, highest leverage
+    /// first. Subjects at their maximum and English at 1등급 are not
+    /// considered, and universities where nothing gains are left out. Ties
+    /// go to the subject first in `Subject::all`.
+    ///
+    /// ```
+    /// use suneung_calc::plan::PlanEntry;
+    /// use suneung_calc::score::{Grade, Percentile, Record, StandardScore, Subject, University};
+    ///
+    /// fn at_catholic(plan: &[PlanEntry]) -> Subject {
+    ///     let entry = plan.iter().find(|e| e.university == University::CATHOLIC);
+    ///     entry.unwrap().subject
+    /// }
+    ///
+    /// let mut record = Record::sample();
+    /// let plan = record.improvement_plan(2024, 5f64);
+    /// assert!(plan.windows(2).all(|w| w[0].leverage >= w[1].leverage));
+    /// assert_eq!(at_catholic(&plan), Subject::Korean);
+    ///
+    /// // With 국어 at the top of the scale, 수학 is the best investment
+    /// record.record_typed(Subject::Korean, StandardScore::MAX, Percentile::MAX, Grade::MIN);
+    /// let plan = record.improvement_plan(2024, 5f64);
+    /// assert!(plan.iter().all(|e| e.subject != Subject::Korean));
+    /// assert_eq!(at_catholic(&plan), Subject::Math);
+    /// // English at 2등급 has one step left
+    /// let mut english = plan.iter().filter(|e| e.subject == Subject::English);
+    /// assert!(english.all(|e| e.improvement == 1f64));
+    /// ```
+    pub fn improvement_plan(&self, year: usize, budget_points: f64) -> Vec<PlanEntry> {
+        let profile = CohortProfile::for_year(year).unwrap_or_default();
+        let options: Vec<(Subject, f64, Record)> = Subject::all()
+            .iter()
+            .filter_map(|&subject| improve(self, subject, budget_points, &profile))
+            .collect();
+
+        let mut plan: Vec<PlanEntry> = University::supported(year)
+            .into_iter()
+            .filter_map(|university| {
+                let current = self.try_calc_with_university(university, year).ok()?;
+                options
+                    .iter()
+                    .filter_map(|(subject, improvement, improved)| {
+                        let gain =
+                            improved.try_calc_with_university(university, year).ok()? - current;
+                        (gain > 0f64).then(|| PlanEntry {
+                            university,
+                            subject: *subject,
+                            improvement: *improvement,
+                            gain,
+                            leverage: gain / improvement,
+                        })
+                    })
+                    // `max_by` keeps the last of equal elements
+                    .rev()
+                    .max_by(|a, b| a.leverage.total_cmp(&b.leverage))
+            })
+            .collect();
+        // Stable, so ties keep the order of `University::supported`
+        plan.sort_by(|a, b| b.leverage.total_cmp(&a.leverage));
+        plan
+    }
+}
+
+// `record` with `subject` improved by up to `budget` units, and the units
+// used; `None` when the subject is missing, maxed or the budget is too small
+fn improve(
+    record: &Record,
+    subject: Subject,
+    budget: f64,
+    profile: &CohortProfile,
+) -> Option<(Subject, f64, Record)> {
+    let score = record.try_score(subject)?;
+    let (improvement, improved) = if subject == Subject::English {
+        let steps = (budget.floor() as i64).min(score.rank().get() as i64 - 1);
+        let grade = Grade::try_from((score.rank().get() as i64 - steps.max(0)) as u8).ok()?;
+        (steps as f64, Score::grade_only(grade))
+    } else {
+        let ceiling = profile.ceiling(subject)?;
+        let standard = (score.standard_score() + budget.floor()).min(ceiling);
+        let modelled = profile.score_at(subject, standard)?;
+        let improved = Score::new_typed(
+            StandardScore::new(standard).ok()?,
+            Percentile::new(score.percentile().max(modelled.percentile())).ok()?,
+            score.rank().min(modelled.rank()),
+        );
+        (standard - score.standard_score(), improved)
+    };
+    if improvement <= 0f64 {
+        return None;
+    }
+    let mut record = record.clone();
+    record.record_score(subject, improved);
+    Some((subject, improvement, record))
+}
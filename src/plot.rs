@@ -0,0 +1,678 @@
+//! Charts drawn with plotters, written as PNG or SVG depending on the
+//! extension of the output path.
+//!
+//! Hangul labels need a font that covers them: pass one in
+//! `PlotOptions::font`, otherwise the first installed file of
+//! `FONT_CANDIDATES` is used.
+
+use crate::admission::Chance;
+use crate::api::ComparisonRow;
+use crate::display::label;
+use crate::error::{Result, SuneungError};
+use crate::normalize::{ExamStats, ExamStatsTable};
+use crate::score::{Record, Subject, University};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use plotters::style::{register_font, FontStyle};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Korean fonts looked for when `PlotOptions::font` is not set
+pub const FONT_CANDIDATES: &[&str] = &[
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/nanum/NanumGothic.ttf",
+    "/System/Library/Fonts/AppleSDGothicNeo.ttc",
+    "C:\\Windows\\Fonts\\malgun.ttf",
+];
+
+// Family name the loaded font is registered under
+const FONT_FAMILY: &str = "suneung";
+
+#[derive(Debug, Clone)]
+pub struct PlotOptions {
+    /// TrueType/OpenType font used for every label
+    pub font: Option<PathBuf>,
+    /// Replaces the default caption, which starts with the record's name
+    pub title: Option<String>,
+    pub width: u32,
+    pub height: u32,
+    /// Bars show the percentage of the best score instead of the score
+    pub percent_of_max: bool,
+    /// Converted-score cut lines. Bars of universities with one are
+    /// colored by `Chance`.
+    pub cuts: HashMap<University, f64>,
+    /// Trend lines show percentiles instead of standard scores
+    pub percentile: bool,
+    /// Trend lines show z-scores against these statistics instead of
+    /// standard scores, e.g. `ExamStatsTable::embedded()`; see `normalize`
+    pub normalized: Option<ExamStatsTable>,
+    /// University whose converted score is added to the trend
+    pub overlay: Option<University>,
+}
+
+impl Default for PlotOptions {
+    fn default() -> Self {
+        Self {
+            font: None,
+            title: None,
+            width: 1000,
+            height: 600,
+            percent_of_max: false,
+            cuts: HashMap::new(),
+            percentile: false,
+            normalized: None,
+            overlay: None,
+        }
+    }
+}
+
+/// One bar of `university_bars`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bar {
+    pub university: University,
+    /// Axis label, the row's `university_name`
+    pub name: String,
+    /// Converted score, or its percentage of the best with
+    /// `PlotOptions::percent_of_max`
+    pub value: f64,
+    /// Set when `PlotOptions::cuts` has the university
+    pub chance: Option<Chance>,
+}
+
+/// The lines of `trend`, see `trend_data`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trend {
+    /// Axis label of each exam: its `ExamMeta`, or the record's name
+    pub labels: Vec<String>,
+    /// Standard score, percentile or z-score of each subject at each exam
+    pub lines: Vec<(Subject, Vec<Option<f64>>)>,
+    /// Converted score at `PlotOptions::overlay` for each exam's 학년도
+    pub overlay: Option<Vec<Option<f64>>>,
+}
+
+/// Bars for the scored `rows` (e.g. from `api::compare`), highest first;
+/// rows without a score are left out.
+///
+/// ```
+/// use suneung_calc::admission::Chance;
+/// use suneung_calc::api::{ComparisonRow, Eligibility};
+/// use suneung_calc::plot::{bar_data, PlotOptions};
+/// use suneung_calc::score::University;
+///
+/// let row = |university: University, score: Option<f64>| ComparisonRow {
+///     rank: None,
+///     university,
+///     university_name: university.name().to_string(),
+///     score,
+///     percent_of_max: None,
+///     eligibility: Eligibility::Eligible,
+/// };
+/// let rows = [
+///     row(University::DONGGUK, Some(380.0)),
+///     row(University::KONKUK, Some(390.0)),
+///     row(University::SOONGSIL, None),
+/// ];
+/// let mut opts = PlotOptions::default();
+/// opts.cuts.insert(University::KONKUK, 385.0);
+///
+/// let bars = bar_data(&rows, &opts);
+/// assert_eq!(bars.len(), 2);
+/// assert_eq!(bars[0].university, University::KONKUK);
+/// assert_eq!(bars[0].chance, Some(Chance::Safe));
+/// assert_eq!(bars[1].chance, None);
+/// ```
+pub fn bar_data(rows: &[ComparisonRow], opts: &PlotOptions) -> Vec<Bar> {
+    let best = rows
+        .iter()
+        .filter_map(|r| r.score)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let mut bars: Vec<Bar> = rows
+        .iter()
+        .filter_map(|row| {
+            let score = row.score?;
+            Some(Bar {
+                university: row.university,
+                name: row.university_name.clone(),
+                value: if opts.percent_of_max {
+                    score / best * 100f64
+                } else {
+                    score
+                },
+                chance: opts
+                    .cuts
+                    .get(&row.university)
+                    .map(|&cut| Chance::classify(score, cut)),
+            })
+        })
+        .collect();
+    bars.sort_by(|a, b| b.value.total_cmp(&a.value));
+    bars
+}
+
+/// Horizontal bars of the converted scores in `rows`, best at the top, in
+/// the colors of their `Chance` when cut lines are given. Rows without a
+/// score are left out. Returns `path`.
+pub fn university_bars<P: AsRef<Path>>(
+    record: &Record,
+    rows: &[ComparisonRow],
+    path: P,
+    opts: &PlotOptions,
+) -> Result<PathBuf> {
+    let bars = bar_data(rows, opts);
+    if bars.is_empty() {
+        return Err(SuneungError::Plot(format!(
+            "{} has no converted score to plot",
+            record.name()
+        )));
+    }
+    let title = opts
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("{} 대학별 환산 점수", record.name()));
+    let path = path.as_ref();
+    let size = (opts.width, opts.height);
+    load_font(opts)?;
+    match Format::of(path)? {
+        Format::Png => draw_bars(
+            BitMapBackend::new(path, size).into_drawing_area(),
+            &title,
+            &bars,
+            opts.percent_of_max,
+        )?,
+        Format::Svg => draw_bars(
+            SVGBackend::new(path, size).into_drawing_area(),
+            &title,
+            &bars,
+            opts.percent_of_max,
+        )?,
+    }
+    Ok(path.to_path_buf())
+}
+
+/// Standard score (percentile with `PlotOptions::percentile`, z-score
+/// with `PlotOptions::normalized`) of `subjects` across `records`, which are taken to be exams of one student
+/// in chronological order (3월 → 6월 → 9월 → 수능). English, which has
+/// neither, is skipped; a subject missing from an exam leaves a gap. With
+/// `PlotOptions::overlay` the converted score at that university, for the
+/// 학년도 of each exam, is drawn dashed against a second axis. Returns `path`.
+pub fn trend<P: AsRef<Path>>(
+    records: &[Record],
+    subjects: &[Subject],
+    path: P,
+    opts: &PlotOptions,
+) -> Result<PathBuf> {
+    if records.is_empty() {
+        return Err(SuneungError::Plot("no records to plot".to_string()));
+    }
+    let data = trend_data(records, subjects, opts);
+    if data
+        .lines
+        .iter()
+        .all(|(_, values)| values.iter().all(Option::is_none))
+    {
+        return Err(SuneungError::Plot(
+            "records have no scores of the chosen subjects to plot".to_string(),
+        ));
+    }
+    if let (Some(university), Some(converted)) = (opts.overlay, &data.overlay) {
+        if converted.iter().all(Option::is_none) {
+            return Err(SuneungError::Plot(format!(
+                "no exam has a converted score at {}",
+                university.name()
+            )));
+        }
+    }
+    let metric = metric(opts);
+    let title = opts
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("{} {} 추이", records[0].name(), metric));
+    let path = path.as_ref();
+    let size = (opts.width, opts.height);
+    load_font(opts)?;
+    match Format::of(path)? {
+        Format::Png => draw_trend(
+            BitMapBackend::new(path, size).into_drawing_area(),
+            &title,
+            &data,
+            opts,
+        )?,
+        Format::Svg => draw_trend(
+            SVGBackend::new(path, size).into_drawing_area(),
+            &title,
+            &data,
+            opts,
+        )?,
+    }
+    Ok(path.to_path_buf())
+}
+
+/// What `trend` draws: the values of every line at every exam, `None`
+/// where an exam lacks one.
+///
+/// ```
+/// use suneung_calc::exam::{ExamKind, ExamMeta};
+/// use suneung_calc::plot::{trend_data, PlotOptions};
+/// use suneung_calc::score::{Record, Subject, University};
+///
+/// let mut june = Record::sample();
+/// june.set_exam(Some(ExamMeta::new(ExamKind::Mock6, 2024)));
+/// let mut csat = Record::sample();
+/// csat.set_exam(Some(ExamMeta::new(ExamKind::Csat, 2024)));
+/// csat.remove_subject(Subject::Math);
+///
+/// let opts = PlotOptions { overlay: Some(University::KONKUK), ..PlotOptions::default() };
+/// let data = trend_data(&[june, csat], &[Subject::Math, Subject::English], &opts);
+/// // English has no 표준점수 to draw
+/// assert_eq!(data.lines, vec![(Subject::Math, vec![Some(128.0), None])]);
+/// // and without 수학 the 수능 has no converted score
+/// assert!(data.overlay.unwrap()[1].is_none());
+/// ```
+pub fn trend_data(records: &[Record], subjects: &[Subject], opts: &PlotOptions) -> Trend {
+    let labels = records
+        .iter()
+        .map(|r| {
+            r.exam()
+                .map_or_else(|| r.name().to_string(), |e| e.to_string())
+        })
+        .collect();
+    // Records without exam information are taken on the nominal scale
+    let stats: Vec<ExamStats> = match &opts.normalized {
+        Some(table) => records
+            .iter()
+            .map(|r| {
+                r.exam()
+                    .map_or_else(ExamStats::nominal, |e| table.get(e.id()))
+            })
+            .collect(),
+        None => vec![],
+    };
+    let lines = subjects
+        .iter()
+        .copied()
+        .filter(|&s| s != Subject::English)
+        .map(|s| {
+            let values = records
+                .iter()
+                .enumerate()
+                .map(|(i, r)| {
+                    let x = r.try_score(s)?;
+                    match stats.get(i) {
+                        Some(stats) => stats.z_score(s, x.standard_score()),
+                        None if opts.percentile => Some(x.percentile()),
+                        None => Some(x.standard_score()),
+                    }
+                })
+                .collect();
+            (s, values)
+        })
+        .collect();
+    // Exams without exam information have no 학년도 to convert for
+    let overlay = opts.overlay.map(|university| {
+        records
+            .iter()
+            .map(|r| {
+                let year = r.exam()?.year as usize;
+                r.try_calc_with_university(university, year).ok()
+            })
+            .collect()
+    });
+    Trend {
+        labels,
+        lines,
+        overlay,
+    }
+}
+
+enum Format {
+    Png,
+    Svg,
+}
+
+impl Format {
+    fn of(path: &Path) -> Result<Self> {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+        match ext.as_deref() {
+            Some("png") => Ok(Format::Png),
+            Some("svg") => Ok(Format::Svg),
+            _ => Err(SuneungError::Plot(format!(
+                "{} must end in .png or .svg",
+                path.display()
+            ))),
+        }
+    }
+}
+
+// Font file currently registered as `FONT_FAMILY`
+static LOADED_FONT: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+fn load_font(opts: &PlotOptions) -> Result<()> {
+    let path = match &opts.font {
+        Some(path) => path.clone(),
+        None => FONT_CANDIDATES
+            .iter()
+            .map(PathBuf::from)
+            .find(|p| p.is_file())
+            .ok_or_else(|| {
+                SuneungError::Plot(
+                    "no Korean font found, pass one with --font (e.g. NanumGothic.ttf)".to_string(),
+                )
+            })?,
+    };
+    let mut loaded = LOADED_FONT.lock().unwrap_or_else(|e| e.into_inner());
+    if loaded.as_ref() == Some(&path) {
+        return Ok(());
+    }
+    let bytes = std::fs::read(&path)?;
+    // plotters keeps registered fonts for the rest of the process
+    register_font(
+        FONT_FAMILY,
+        FontStyle::Normal,
+        Box::leak(bytes.into_boxed_slice()),
+    )
+    .map_err(|_| SuneungError::Plot(format!("{} is not a usable font", path.display())))?;
+    *loaded = Some(path);
+    Ok(())
+}
+
+// What the trend lines show
+fn metric(opts: &PlotOptions) -> &'static str {
+    if opts.normalized.is_some() {
+        "z 점수"
+    } else if opts.percentile {
+        "백분위"
+    } else {
+        "표준점수"
+    }
+}
+
+fn plot_err<E: std::fmt::Display>(e: E) -> SuneungError {
+    SuneungError::Plot(e.to_string())
+}
+
+fn font(size: u32) -> TextStyle<'static> {
+    (FONT_FAMILY, size).into_font().into()
+}
+
+// `bars` is sorted best first
+fn draw_bars<DB: DrawingBackend>(
+    root: DrawingArea<DB, Shift>,
+    title: &str,
+    bars: &[Bar],
+    percent: bool,
+) -> Result<()> {
+    root.fill(&WHITE).map_err(plot_err)?;
+    let n = bars.len();
+    let max = bars
+        .iter()
+        .map(|b| b.value)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let min = bars.iter().map(|b| b.value).fold(f64::INFINITY, f64::min);
+    // Scores of one record are close together, so the axis starts at a
+    // round number below the lowest one instead of at zero
+    let base = ((min - (max - min).max(10f64)) / 10f64).floor().max(0f64) * 10f64;
+    // Segment 0 is drawn at the bottom, so the best score goes last
+    let name_of = |i: usize| {
+        n.checked_sub(i + 1)
+            .and_then(|j| bars.get(j))
+            .map(|b| b.name.as_str())
+    };
+    // About 9px per Latin and 16px per Hangul character at size 16
+    let label_width = bars
+        .iter()
+        .map(|b| {
+            b.name
+                .chars()
+                .map(|c| if c.is_ascii() { 9u32 } else { 16 })
+                .sum::<u32>()
+        })
+        .max()
+        .unwrap_or_default()
+        .clamp(140, 400)
+        + 10;
+    // Room right of the longest bar for its label, and for the legend
+    let with_cuts = bars.iter().any(|b| b.chance.is_some());
+    let headroom = if with_cuts { 0.3 } else { 0.1 };
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, font(28))
+        .margin(20)
+        .x_label_area_size(40)
+        .y_label_area_size(label_width)
+        .build_cartesian_2d(
+            base..max + (max - base) * headroom,
+            (0..n - 1).into_segmented(),
+        )
+        .map_err(plot_err)?;
+    chart
+        .configure_mesh()
+        .disable_y_mesh()
+        .y_labels(n)
+        .y_label_formatter(&|v| match v {
+            SegmentValue::CenterOf(i) => name_of(*i).unwrap_or_default().to_string(),
+            _ => String::new(),
+        })
+        .label_style(font(16))
+        .x_desc(if percent {
+            "최고 대비(%)"
+        } else {
+            "환산 점수"
+        })
+        .axis_desc_style(font(16))
+        .draw()
+        .map_err(plot_err)?;
+
+    // One series per color, so that the legend lists each once
+    let groups = Chance::all()
+        .iter()
+        .map(|&c| Some(c))
+        .chain(std::iter::once(None));
+    for chance in groups {
+        if !bars.iter().any(|b| b.chance == chance) {
+            continue;
+        }
+        let color = bar_color(chance, with_cuts);
+        let series = chart
+            .draw_series(
+                bars.iter()
+                    .rev()
+                    .enumerate()
+                    .filter(|(_, b)| b.chance == chance)
+                    .map(|(i, b)| {
+                        Rectangle::new(
+                            [
+                                (base, SegmentValue::Exact(i)),
+                                (b.value, SegmentValue::Exact(i + 1)),
+                            ],
+                            color.filled(),
+                        )
+                    }),
+            )
+            .map_err(plot_err)?;
+        if with_cuts {
+            series
+                .label(chance.map_or("컷 없음", |c| c.label()))
+                .legend(move |(x, y)| {
+                    Rectangle::new([(x, y - 5), (x + 16, y + 5)], color.filled())
+                });
+        }
+    }
+    chart
+        .draw_series(bars.iter().rev().enumerate().map(|(i, b)| {
+            let text = if percent {
+                format!("{:.1}%", b.value)
+            } else {
+                format!("{:.2}", b.value)
+            };
+            Text::new(text, (b.value, SegmentValue::CenterOf(i)), font(14))
+        }))
+        .map_err(plot_err)?;
+    if with_cuts {
+        chart
+            .configure_series_labels()
+            .label_font(font(14))
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .position(SeriesLabelPosition::UpperRight)
+            .draw()
+            .map_err(plot_err)?;
+    }
+    root.present().map_err(plot_err)?;
+    Ok(())
+}
+
+// Without any cut line every bar keeps the plain color
+fn bar_color(chance: Option<Chance>, with_cuts: bool) -> RGBAColor {
+    match chance {
+        Some(Chance::Safe) => RGBColor(46, 160, 67).to_rgba(),
+        Some(Chance::Likely) => RGBColor(37, 99, 235).to_rgba(),
+        Some(Chance::Reach) => RGBColor(234, 140, 0).to_rgba(),
+        Some(Chance::Unlikely) => RGBColor(220, 38, 38).to_rgba(),
+        None if with_cuts => RGBColor(150, 150, 150).to_rgba(),
+        None => BLUE.mix(0.6),
+    }
+}
+
+fn draw_trend<DB: DrawingBackend>(
+    root: DrawingArea<DB, Shift>,
+    title: &str,
+    data: &Trend,
+    opts: &PlotOptions,
+) -> Result<()> {
+    root.fill(&WHITE).map_err(plot_err)?;
+    let n = data.labels.len();
+    let labels = &data.labels;
+    let (lo, hi) = range(
+        data.lines
+            .iter()
+            .flat_map(|(_, v)| v.iter().flatten().copied()),
+    );
+    // Room above and below the lines, in the unit of the axis
+    let pad = if opts.normalized.is_some() { 0.5 } else { 5f64 };
+    let converted = data.overlay.as_deref().unwrap_or_default();
+    let (olo, ohi) = range(converted.iter().flatten().copied());
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, font(28))
+        .margin(20)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .right_y_label_area_size(if opts.overlay.is_some() { 60 } else { 0 })
+        .build_cartesian_2d((0..n - 1).into_segmented(), (lo - pad)..(hi + pad))
+        .map_err(plot_err)?
+        // Unused, and its axis not drawn, without an overlay
+        .set_secondary_coord(
+            (0..n - 1).into_segmented(),
+            if olo > ohi {
+                0f64..1f64
+            } else {
+                (olo - 5f64)..(ohi + 5f64)
+            },
+        );
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_labels(n)
+        .x_label_formatter(&|v| match v {
+            SegmentValue::CenterOf(i) => labels.get(*i).cloned().unwrap_or_default(),
+            _ => String::new(),
+        })
+        .label_style(font(14))
+        .y_desc(metric(opts))
+        .axis_desc_style(font(16))
+        .draw()
+        .map_err(plot_err)?;
+
+    for (subject, values) in &data.lines {
+        // Colors follow `Subject::all`, so a subject keeps its color
+        // whichever others are chosen
+        let k = Subject::all().iter().position(|s| s == subject);
+        let color = Palette99::pick(k.unwrap_or_default()).to_rgba();
+        for (j, run) in runs(values.iter().copied()).iter().enumerate() {
+            let series = chart
+                .draw_series(LineSeries::new(run.clone(), color.stroke_width(2)))
+                .map_err(plot_err)?;
+            if j == 0 {
+                series.label(label(*subject)).legend(move |(x, y)| {
+                    Rectangle::new([(x, y - 4), (x + 16, y + 4)], color.filled())
+                });
+            }
+            chart
+                .draw_series(
+                    run.iter()
+                        .map(|p| Circle::new(p.clone(), 4, color.filled())),
+                )
+                .map_err(plot_err)?;
+        }
+    }
+
+    if let Some(university) = opts.overlay {
+        chart
+            .configure_secondary_axes()
+            .label_style(font(14))
+            .y_desc(format!("{} 환산 점수", university.name()))
+            .axis_desc_style(font(16))
+            .draw()
+            .map_err(plot_err)?;
+        let color = BLACK.to_rgba();
+        for (j, run) in runs(converted.iter().copied()).iter().enumerate() {
+            let series = chart
+                .draw_secondary_series(DashedLineSeries::new(
+                    run.clone(),
+                    6,
+                    4,
+                    color.stroke_width(2),
+                ))
+                .map_err(plot_err)?;
+            if j == 0 {
+                series
+                    .label(format!("{} 환산 점수", university.name()))
+                    .legend(move |(x, y)| {
+                        PathElement::new(vec![(x, y), (x + 16, y)], color.stroke_width(2))
+                    });
+            }
+            chart
+                .draw_secondary_series(
+                    run.iter()
+                        .map(|p| TriangleMarker::new(p.clone(), 5, color.filled())),
+                )
+                .map_err(plot_err)?;
+        }
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .label_font(font(14))
+        .draw()
+        .map_err(plot_err)?;
+    root.present().map_err(plot_err)?;
+    Ok(())
+}
+
+// (min, max) of `values`; min > max when there are none
+fn range(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), x| {
+        (lo.min(x), hi.max(x))
+    })
+}
+
+// Points of consecutive exams that have a value. A missing value splits the
+// line instead of bridging the gap.
+fn runs(values: impl Iterator<Item = Option<f64>>) -> Vec<Vec<(SegmentValue<usize>, f64)>> {
+    let points: Vec<Option<(SegmentValue<usize>, f64)>> = values
+        .enumerate()
+        .map(|(i, x)| x.map(|x| (SegmentValue::CenterOf(i), x)))
+        .collect();
+    points
+        .split(|p| p.is_none())
+        .filter(|run| !run.is_empty())
+        .map(|run| run.iter().flatten().cloned().collect())
+        .collect()
+}
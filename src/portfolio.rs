@@ -0,0 +1,243 @@
+//! Choosing one application per 모집군. A student applies to at most one
+//! university in each of 가/나/다군 and attends the most preferred one that
+//! admits them, so the three are chosen together.
+//!
+//! Admission probabilities come from the gap to the cut line: a logistic
+//! curve in the gap in percent of the cut, `1 / (1 + e^(-gap / MARGIN))`
+//! with `Chance::MARGIN`, i.e. 50% at the cut and about 73% one margin
+//! above it. That is a model of the 안정/적정/소신 classification, not a
+//! published figure.
+
+use crate::admission::{AdmissionGroup, AdmissionGroupData, Chance};
+use crate::score::University;
+use serde::Serialize;
+
+/// Converted score of a student at one university and its cut line
+#[derive(Debug, Copy, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct UniversityResult {
+    pub university: University,
+    pub score: f64,
+    pub cut: f64,
+}
+
+impl UniversityResult {
+    pub fn new(university: University, score: f64, cut: f64) -> Self {
+        Self {
+            university,
+            score,
+            cut,
+        }
+    }
+
+    /// `(score - cut) / cut` in percent
+    pub fn gap_percent(&self) -> f64 {
+        (self.score - self.cut) / self.cut * 100f64
+    }
+
+    pub fn chance(&self) -> Chance {
+        Chance::classify(self.score, self.cut)
+    }
+
+    /// Modelled probability of admission, see the module documentation
+    pub fn probability(&self) -> f64 {
+        1f64 / (1f64 + (-self.gap_percent() / Chance::MARGIN).exp())
+    }
+}
+
+/// What `optimize` looks for
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Strategy {
+    /// One 안정, one 적정 and one 소신 application, as many of them as the
+    /// results allow, then the most preferred universities
+    #[default]
+    Balanced,
+    /// The highest expected preference of the university attended
+    MaximizeExpected,
+}
+
+/// The application of one 모집군
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PortfolioChoice {
+    pub group: AdmissionGroup,
+    pub university: University,
+    pub chance: Chance,
+    pub probability: f64,
+    /// Why the university fits, e.g. `안정 지원, 컷 대비 +1.30%, 합격 확률 79%`
+    pub rationale: String,
+}
+
+/// Applications chosen by `optimize`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Portfolio {
+    pub strategy: Strategy,
+    /// In 가/나/다 order; a group without candidates has no choice
+    pub choices: Vec<PortfolioChoice>,
+    /// Expected preference of the university attended, from 0 (admitted
+    /// nowhere) to 1 (admitted to the most preferred of the results)
+    pub expected: f64,
+    /// Universities of the results left out because `groups` has no 모집군
+    /// for them, in the order of the results
+    pub excluded: Vec<University>,
+    /// Names the `excluded` universities, e.g. `모집군 자료가 없어 제외: 세종대`;
+    /// `None` when nothing was left out
+    pub note: Option<String>,
+}
+
+/// The best application per 모집군 for `strategy`. `results` are in order
+/// of preference, most wanted first; the preference of the `i`th of `n`
+/// results is `(n - i) / n`. Ties go to the more preferred universities.
+///
+/// `AdmissionGroupData::embedded` only knows the 모집군 of 경희대
+/// (2022-2025) and 서강대 (2024-2025), so with it every other university
+/// ends up in `Portfolio::excluded` and the note.
+///
+/// ```
+/// use suneung_calc::admission::{AdmissionGroup::*, AdmissionGroupData, Chance};
+/// use suneung_calc::portfolio::{optimize, Strategy, UniversityResult};
+/// use suneung_calc::score::University::*;
+///
+/// let groups = AdmissionGroupData::new()
+///     .with(CHUNGANG, Ga)
+///     .with(SOGANG, Ga)
+///     .with(KYUNGHEE, Na)
+///     .with(KONKUK, Na)
+///     .with(DONGGUK, Da);
+/// let results = [
+///     UniversityResult::new(CHUNGANG, 390.0, 400.0), // 상향
+///     UniversityResult::new(SOGANG, 398.0, 400.0),   // 소신
+///     UniversityResult::new(KYUNGHEE, 401.0, 400.0), // 적정
+///     UniversityResult::new(KONKUK, 380.0, 400.0),   // 상향
+///     UniversityResult::new(DONGGUK, 410.0, 400.0),  // 안정
+///     UniversityResult::new(SEJONG, 420.0, 400.0),   // no 모집군
+/// ];
+///
+/// // The only trio with 안정, 적정 and 소신
+/// let balanced = optimize(&results, &groups, Strategy::Balanced);
+/// let chosen: Vec<_> = balanced.choices.iter().map(|c| (c.group, c.university)).collect();
+/// assert_eq!(chosen, [(Ga, SOGANG), (Na, KYUNGHEE), (Da, DONGGUK)]);
+/// let chances: Vec<_> = balanced.choices.iter().map(|c| c.chance).collect();
+/// assert_eq!(chances, [Chance::Reach, Chance::Likely, Chance::Safe]);
+/// assert_eq!(balanced.excluded, [SEJONG]);
+/// assert_eq!(balanced.note.as_deref(), Some("모집군 자료가 없어 제외: 세종대"));
+///
+/// // Within 가군, a fair chance at the second choice is worth more than a
+/// // long shot at the first
+/// let expected = optimize(&results[..2], &groups, Strategy::MaximizeExpected);
+/// assert_eq!(expected.choices.len(), 1);
+/// assert_eq!(expected.choices[0].university, SOGANG);
+/// assert_eq!(expected.note, None);
+///
+/// // The embedded groups leave all but 경희대 and 서강대 out
+/// let embedded = optimize(&results, &AdmissionGroupData::embedded(2024), Strategy::Balanced);
+/// assert_eq!(embedded.excluded, [CHUNGANG, KONKUK, DONGGUK, SEJONG]);
+/// let chosen: Vec<_> = embedded.choices.iter().map(|c| (c.group, c.university)).collect();
+/// assert_eq!(chosen, [(Ga, SOGANG), (Na, KYUNGHEE)]);
+/// ```
+pub fn optimize(
+    results: &[UniversityResult],
+    groups: &AdmissionGroupData,
+    strategy: Strategy,
+) -> Portfolio {
+    let n = results.len() as f64;
+    let preference = |i: usize| (n - i as f64) / n;
+    let excluded: Vec<University> = results
+        .iter()
+        .filter(|r| groups.get(r.university).is_none())
+        .map(|r| r.university)
+        .collect();
+    let note = (!excluded.is_empty()).then(|| {
+        let names: Vec<&str> = excluded.iter().map(|u| u.name()).collect();
+        format!("모집군 자료가 없어 제외: {}", names.join(", "))
+    });
+    // Indices into `results`, per group in 가/나/다 order
+    let candidates: Vec<Vec<usize>> = AdmissionGroup::all()
+        .iter()
+        .map(|&g| {
+            (0..results.len())
+                .filter(|&i| groups.get(results[i].university) == Some(g))
+                .collect()
+        })
+        .collect();
+    let options = |g: usize| candidates[g].iter().copied().map(Some).chain([None]);
+
+    // Objective of `strategy`, compared lexicographically
+    let mut best: Option<(usize, f64)> = None;
+    let mut best_picks = [None; 3];
+    for ga in options(0) {
+        for na in options(1) {
+            for da in options(2) {
+                let picks = [ga, na, da];
+                let value = match strategy {
+                    Strategy::Balanced => {
+                        let roles = [Chance::Safe, Chance::Likely, Chance::Reach]
+                            .iter()
+                            .filter(|&&role| {
+                                picks.iter().flatten().any(|&i| results[i].chance() == role)
+                            })
+                            .count();
+                        (roles, picks.iter().flatten().map(|&i| preference(i)).sum())
+                    }
+                    Strategy::MaximizeExpected => (0, expected(results, &picks, preference)),
+                };
+                let better =
+                    best.is_none_or(|v| value.0 > v.0 || (value.0 == v.0 && value.1 > v.1));
+                if better {
+                    best = Some(value);
+                    best_picks = picks;
+                }
+            }
+        }
+    }
+    let picks = best_picks;
+
+    let choices = AdmissionGroup::all()
+        .iter()
+        .zip(picks)
+        .filter_map(|(&group, pick)| {
+            let result = &results[pick?];
+            Some(PortfolioChoice {
+                group,
+                university: result.university,
+                chance: result.chance(),
+                probability: result.probability(),
+                rationale: format!(
+                    "{} 지원, 컷 대비 {:+.2}%, 합격 확률 {:.0}%",
+                    result.chance(),
+                    result.gap_percent(),
+                    result.probability() * 100f64
+                ),
+            })
+        })
+        .collect();
+    Portfolio {
+        strategy,
+        choices,
+        expected: expected(results, &picks, preference),
+        excluded,
+        note,
+    }
+}
+
+// Expected preference of the university attended: the most preferred of
+// those that admit, each admission independent
+fn expected(
+    results: &[UniversityResult],
+    picks: &[Option<usize>],
+    preference: impl Fn(usize) -> f64,
+) -> f64 {
+    let mut picks: Vec<usize> = picks.iter().flatten().copied().collect();
+    picks.sort_unstable();
+    let mut rejected_so_far = 1f64;
+    let mut total = 0f64;
+    for i in picks {
+        let p = results[i].probability();
+        total += rejected_so_far * p * preference(i);
+        rejected_so_far *= 1f64 - p;
+    }
+    total
+}
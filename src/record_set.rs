@@ -0,0 +1,162 @@
+#[cfg(feature = "csv")]
+use crate::atomic::write_atomic;
+#[cfg(feature = "csv")]
+use crate::score::pseudonym;
+use crate::score::{Record, University};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "csv")]
+use std::collections::HashMap;
+#[cfg(any(feature = "binary", feature = "csv"))]
+use std::error::Error;
+#[cfg(feature = "csv")]
+use std::path::Path;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecordSet {
+    records: Vec<Record>,
+}
+
+impl RecordSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, record: Record) {
+        self.records.push(record);
+    }
+
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Record> {
+        self.records.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    #[cfg(feature = "binary")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        crate::binary::encode(self)
+    }
+
+    #[cfg(feature = "binary")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        crate::binary::decode(bytes)
+    }
+
+    /// Sort best-first by converted score at `university` (see
+    /// `Record::cmp_by_university`).
+    pub fn sort_by_university(&mut self, university: University, year: usize) {
+        self.records
+            .sort_by(|a, b| a.cmp_by_university(b, university, year));
+    }
+
+    /// Anonymize every record with the same salt (see `Record::anonymized`).
+    pub fn anonymize_all(&self, salt: &str) -> RecordSet {
+        RecordSet {
+            records: self.records.iter().map(|r| r.anonymized(salt)).collect(),
+        }
+    }
+
+    /// Write the `pseudonym,name` reverse lookup table for `salt`.
+    ///
+    /// This is never called implicitly by `anonymize_all`; the mapping file
+    /// undoes the anonymization, so keep it away from the shared data.
+    #[cfg(feature = "csv")]
+    pub fn write_anonymization_mapping<P: AsRef<Path>>(
+        &self,
+        salt: &str,
+        path: P,
+    ) -> Result<(), Box<dyn Error>> {
+        write_atomic(path.as_ref(), |tmp| {
+            let mut wtr = csv::Writer::from_path(tmp)?;
+            wtr.write_record(["pseudonym", "name"])?;
+            for record in self.records.iter() {
+                wtr.write_record([pseudonym(salt, record.name()).as_str(), record.name()])?;
+            }
+            wtr.flush()?;
+            Ok(())
+        })
+    }
+
+    /// Read a mapping written by `write_anonymization_mapping` (pseudonym -> name).
+    #[cfg(feature = "csv")]
+    pub fn read_anonymization_mapping<P: AsRef<Path>>(
+        path: P,
+    ) -> Result<HashMap<String, String>, Box<dyn Error>> {
+        Self::anonymization_mapping_from_str(&crate::input::read_to_string(path.as_ref())?)
+    }
+
+    /// `read_anonymization_mapping` for the file's contents. Fields are
+    /// trimmed; a row without both a pseudonym and a name is an error, as is
+    /// text over `input::MAX_INPUT_BYTES`.
+    #[cfg(feature = "csv")]
+    pub fn anonymization_mapping_from_str(
+        text: &str,
+    ) -> Result<HashMap<String, String>, Box<dyn Error>> {
+        crate::input::check_len("anonymization mapping", text)?;
+        let mut rdr = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .flexible(true)
+            .from_reader(text.as_bytes());
+        let mut mapping = HashMap::new();
+        for (i, row) in rdr.records().enumerate() {
+            let row = row?;
+            let (Some(pseudonym), Some(name)) = (row.get(0), row.get(1)) else {
+                // +2: 1-based, after the header
+                return Err(format!("line {}: expected pseudonym,name", i + 2).into());
+            };
+            mapping.insert(pseudonym.to_string(), name.to_string());
+        }
+        Ok(mapping)
+    }
+}
+
+impl From<Vec<Record>> for RecordSet {
+    fn from(records: Vec<Record>) -> Self {
+        Self { records }
+    }
+}
+
+impl<'a> IntoIterator for &'a RecordSet {
+    type Item = &'a Record;
+    type IntoIter = std::slice::Iter<'a, Record>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.records.iter()
+    }
+}
+
+/// Sort `records` best-first at `university` and return `(rank, name, score)`
+/// for every record that could be calculated.
+///
+/// Ties share a rank and leave a gap after them (1, 2, 2, 4). Records whose
+/// calculation fails are moved to the end of the slice and left out of the
+/// result.
+pub fn rank_records(
+    records: &mut [Record],
+    university: University,
+    year: usize,
+) -> Vec<(usize, String, f64)> {
+    records.sort_by(|a, b| a.cmp_by_university(b, university, year));
+
+    let mut ranked: Vec<(usize, String, f64)> = vec![];
+    for (i, record) in records.iter().enumerate() {
+        let Ok(score) = record.try_calc_with_university(university, year) else {
+            break;
+        };
+        let rank = match ranked.last() {
+            Some(&(prev_rank, _, prev_score)) if prev_score == score => prev_rank,
+            _ => i + 1,
+        };
+        ranked.push((rank, record.name().to_string(), score));
+    }
+    ranked
+}
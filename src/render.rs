@@ -0,0 +1,243 @@
+//! Terminal tables built with comfy-table, which measures Hangul as two
+//! columns so long university names do not break the layout.
+
+#[cfg(feature = "color")]
+use crate::admission::{ColorClass, ColorThresholds};
+use crate::api::{ComparisonRow, Eligibility};
+use crate::display::fmt_number;
+use crate::score::{CalcBreakdown, Locale, Record, Subject, University};
+use comfy_table::{Cell, CellAlignment, Color, Table};
+#[cfg(feature = "color")]
+use std::collections::HashMap;
+
+/// Shown where a student cannot be scored at a university
+const INELIGIBLE: &str = "불가";
+
+/// Universities as rows and students as columns. `results[i]` holds the
+/// scores of `records[i]`, as returned by `Record::calc_all`; a university
+/// missing from it is marked "불가". Rows are sorted by the first student's
+/// score, best first, with the universities they cannot be scored at last.
+pub fn render_comparison(records: &[Record], results: &[Vec<(University, f64)>]) -> String {
+    comparison_table(records, results, None)
+}
+
+/// `render_comparison` with a leading 모집군 column for `year`, grouped in
+/// 가/나/다 order and then by score. Universities of unknown group come last.
+pub fn render_comparison_grouped(
+    records: &[Record],
+    results: &[Vec<(University, f64)>],
+    year: usize,
+) -> String {
+    comparison_table(records, results, Some(year))
+}
+
+fn comparison_table(
+    records: &[Record],
+    results: &[Vec<(University, f64)>],
+    group_year: Option<usize>,
+) -> String {
+    let score_of = |i: usize, univ: University| {
+        results
+            .get(i)
+            .and_then(|r| r.iter().find(|(u, _)| *u == univ))
+            .map(|&(_, score)| score)
+    };
+    // `None` sorts before `Some`, so unknown groups are pushed to the end
+    let group_key = |univ: University| {
+        group_year.map(|year| {
+            (
+                univ.admission_group(year).is_none(),
+                univ.admission_group(year),
+            )
+        })
+    };
+
+    let mut universities: Vec<University> = University::iter()
+        .filter(|&univ| (0..results.len()).any(|i| score_of(i, univ).is_some()))
+        .collect();
+    // Stable, so ties keep the order of University::all()
+    universities.sort_by(|&a, &b| {
+        group_key(a)
+            .cmp(&group_key(b))
+            .then_with(|| match (score_of(0, a), score_of(0, b)) {
+                (Some(a), Some(b)) => b.total_cmp(&a),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            })
+    });
+
+    let mut table = Table::new();
+    let mut header = vec![];
+    if group_year.is_some() {
+        header.push("군".to_string());
+    }
+    header.push("대학".to_string());
+    header.extend(records.iter().map(|r| r.name().to_string()));
+    table.set_header(header);
+    for univ in universities {
+        let mut row = vec![];
+        if let Some(year) = group_year {
+            row.push(
+                univ.admission_group(year)
+                    .map_or("-".to_string(), |g| g.to_string()),
+            );
+        }
+        row.push(univ.to_string());
+        row.extend((0..records.len()).map(|i| match score_of(i, univ) {
+            Some(score) => fmt_number(score, Some(2)),
+            None => INELIGIBLE.to_string(),
+        }));
+        table.add_row(row);
+    }
+    let first = if group_year.is_some() { 2 } else { 1 };
+    align_right(&mut table, first..=first + records.len() - 1);
+    table.to_string()
+}
+
+/// Subjects of one student with 표준점수, 백분위 and 등급
+pub fn render_record(record: &Record) -> String {
+    let mut table = Table::new();
+    table.set_header(vec!["과목", "표준점수", "백분위", "등급"]);
+    for (subject, score) in record.iter() {
+        let (standard, percentile) = if subject == Subject::English {
+            ("-".to_string(), "-".to_string())
+        } else {
+            (
+                fmt_number(score.standard_score(), None),
+                score.fmt_percentile_short(),
+            )
+        };
+        table.add_row(vec![
+            record.display_subject(subject),
+            standard,
+            percentile,
+            score.grade_label(),
+        ]);
+    }
+    align_right(&mut table, 1..=3);
+    table.to_string()
+}
+
+/// Ranked universities for one student (see `api::compare`). Universities
+/// without data show "-" and ineligible ones "불가"; both are explained in
+/// footnotes below the table.
+pub fn render_ranking(rows: &[ComparisonRow], year: usize) -> String {
+    ranking_table(rows, year, |_| None)
+}
+
+/// `render_ranking` with the university and score of each row colored by
+/// its `ColorClass` against `cuts`, e.g. last year's 70% cut lines. Colors
+/// are always emitted, even with NO_COLOR set, so callers decide first
+/// whether the output should be colored.
+#[cfg(feature = "color")]
+pub fn render_ranking_colored(
+    rows: &[ComparisonRow],
+    year: usize,
+    cuts: &HashMap<University, f64>,
+    thresholds: &ColorThresholds,
+) -> String {
+    crossterm::style::force_color_output(true);
+    ranking_table(rows, year, |row| {
+        match ColorClass::classify(row.score, cuts.get(&row.university).copied(), thresholds) {
+            ColorClass::Clear => Some(Color::Green),
+            ColorClass::Borderline => Some(Color::Yellow),
+            ColorClass::Reach => Some(Color::Red),
+            ColorClass::Plain => None,
+        }
+    })
+}
+
+fn ranking_table<F>(rows: &[ComparisonRow], year: usize, color: F) -> String
+where
+    F: Fn(&ComparisonRow) -> Option<Color>,
+{
+    let mut table = Table::new();
+    table.set_header(vec!["순위", "대학", "환산 점수", "최고 대비(%)", "지원"]);
+    let mut notes = vec![];
+    let mut colored = false;
+    for row in rows {
+        let (flag, note) = match &row.eligibility {
+            Eligibility::Eligible => ("가능".to_string(), None),
+            Eligibility::Ineligible { .. } => (
+                format!("{}*", INELIGIBLE),
+                Some(format!(
+                    "* {}: {}",
+                    row.university_name,
+                    row.eligibility.reason(Locale::Korean).unwrap_or_default()
+                )),
+            ),
+            Eligibility::NoData => (
+                "-*".to_string(),
+                Some(format!(
+                    "* {}: {}학년도 반영 비율 없음",
+                    row.university_name, year
+                )),
+            ),
+        };
+        notes.extend(note);
+        let paint = |cell: Cell| match color(row) {
+            Some(c) => cell.fg(c),
+            None => cell,
+        };
+        colored |= color(row).is_some();
+        table.add_row(vec![
+            Cell::new(row.rank.map_or("-".to_string(), |r| r.to_string())),
+            paint(Cell::new(&row.university_name)),
+            paint(Cell::new(
+                row.score
+                    .map_or("-".to_string(), |s| fmt_number(s, Some(2))),
+            )),
+            Cell::new(
+                row.percent_of_max
+                    .map_or("-".to_string(), |p| fmt_number(p, Some(1))),
+            ),
+            Cell::new(flag),
+        ]);
+    }
+    if colored {
+        table.enforce_styling().style_text_only();
+    }
+    align_right(&mut table, 0..=0);
+    align_right(&mut table, 2..=3);
+
+    let mut out = table.to_string();
+    for note in notes {
+        out.push('\n');
+        out.push_str(&note);
+    }
+    out
+}
+
+/// Contribution of each subject to a converted score, ending with the total
+/// and a line for each of its warnings
+pub fn render_breakdown(breakdown: &CalcBreakdown) -> String {
+    let mut table = Table::new();
+    table.set_header(vec!["항목", "점수"]);
+    for (item, value) in [
+        ("국어", breakdown.korean),
+        ("수학", breakdown.math),
+        (
+            &format!("탐구 ({})", breakdown.science_aggregation.label()),
+            breakdown.science,
+        ),
+        ("영어", breakdown.english),
+        ("합계", breakdown.total),
+    ] {
+        table.add_row(vec![item.to_string(), fmt_number(value, Some(2))]);
+    }
+    align_right(&mut table, 1..=1);
+    let mut out = table.to_string();
+    for warning in &breakdown.warnings {
+        out.push_str(&format!("\n주의: {}", warning));
+    }
+    out
+}
+
+fn align_right(table: &mut Table, columns: std::ops::RangeInclusive<usize>) {
+    for i in columns {
+        if let Some(column) = table.column_mut(i) {
+            column.set_cell_alignment(CellAlignment::Right);
+        }
+    }
+}
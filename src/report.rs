@@ -0,0 +1,113 @@
+//! Documents generated from records, independent of any frontend
+
+mod chart;
+pub mod html;
+pub mod latex;
+pub mod markdown;
+mod matrix;
+#[cfg(feature = "pdf")]
+pub mod pdf;
+pub mod radar;
+mod summary;
+mod tables;
+
+#[cfg(feature = "parallel")]
+pub(crate) use matrix::matrix_par;
+pub use matrix::{matrix, Matrix, MatrixCell, MatrixFooter, MatrixRow};
+pub use radar::{radar_data, radar_series, radar_series_localized, RadarNorm};
+pub use summary::{
+    cohort_summary, CohortSummary, CutCount, SubjectSummary, SummaryOptions, TopEntry, TopList,
+};
+
+use crate::plan::PlanEntry;
+use crate::score::{Locale, University};
+use crate::targets::ProgressRow;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// What goes into a report besides the record itself
+#[derive(Debug, Clone)]
+pub struct ReportOptions {
+    /// Converted-score cut lines (e.g. last year's 70% cut). Adds a gap
+    /// column to the comparison when not empty.
+    pub cuts: HashMap<University, f64>,
+    /// Number of top universities in the English sweep
+    pub sweep_count: usize,
+    /// Length of the top list of each university in cohort reports
+    pub top_count: usize,
+    /// Language of headings, table cells and chart labels
+    pub locale: Locale,
+    pub sections: Sections,
+    /// Font directories searched before the system fonts, for PDF reports
+    pub font_dirs: Vec<PathBuf>,
+    /// Rows of `targets::progress` for the student; the progress section
+    /// is left out when empty
+    pub progress: Vec<ProgressRow>,
+    /// `Record::improvement_plan` of the student; the plan section is left
+    /// out when empty
+    pub plan: Vec<PlanEntry>,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            cuts: HashMap::new(),
+            sweep_count: 3,
+            top_count: 10,
+            locale: Locale::default(),
+            sections: Sections::default(),
+            font_dirs: vec![],
+            progress: vec![],
+            plan: vec![],
+        }
+    }
+}
+
+/// Sections a report includes; all of them by default. The first five
+/// belong to student reports, the rest to cohort reports.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Sections {
+    /// Standard score, percentile and grade of each subject
+    pub scores: bool,
+    /// Universities ranked by converted score, with percent of the best
+    pub comparison: bool,
+    /// Converted scores at the best universities for each English grade
+    pub english_sweep: bool,
+    /// Converted score at each target across the student's exams, when
+    /// `ReportOptions::progress` has rows
+    pub progress: bool,
+    /// Best subject to improve at each university, when
+    /// `ReportOptions::plan` has entries
+    pub plan: bool,
+    /// Converted score of every student at every university
+    pub matrix: bool,
+    /// Per-subject statistics of the cohort
+    pub statistics: bool,
+    /// Correlation of the standard scores of each pair of subjects
+    pub correlation: bool,
+    /// 등급컷 of each subject within the cohort, see
+    /// `Cohort::estimate_grade_cuts`
+    pub grade_cuts: bool,
+    /// Place of every student at each university, best average first
+    pub ranking: bool,
+    /// Best students at each university, see `Cohort::rank_table`
+    pub top: bool,
+}
+
+impl Default for Sections {
+    fn default() -> Self {
+        Self {
+            scores: true,
+            comparison: true,
+            english_sweep: true,
+            progress: true,
+            plan: true,
+            matrix: true,
+            statistics: true,
+            correlation: true,
+            grade_cuts: true,
+            ranking: true,
+            top: true,
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Inline SVG charts shared by the HTML and PDF reports
+
+use super::html::escape;
+use crate::api::ComparisonRow;
+use crate::display::fmt_number;
+use crate::score::Locale;
+use std::fmt::Write;
+
+// One horizontal bar per scored university, in the order of `rows`. Bars
+// start from a round base below the lowest score, as in `plot`, so that
+// differences of a few points stay visible. Returns the SVG element and
+// the base. Colors are attributes so the SVG needs no stylesheet.
+pub(super) fn bars(rows: &[ComparisonRow], locale: Locale) -> Option<(String, f64)> {
+    const BARS: f64 = 400f64;
+    const VALUE: f64 = 70f64;
+    const ROW: f64 = 24f64;
+    const BAR: f64 = 16f64;
+
+    let scored: Vec<(&ComparisonRow, f64)> = rows
+        .iter()
+        .filter_map(|row| row.score.map(|s| (row, s)))
+        .collect();
+    if scored.is_empty() {
+        return None;
+    }
+    let max = scored.iter().map(|(_, s)| *s).fold(f64::MIN, f64::max);
+    let min = scored.iter().map(|(_, s)| *s).fold(f64::MAX, f64::min);
+    let base = ((min - (max - min).max(10f64)) / 10f64).floor().max(0f64) * 10f64;
+    let label = scored
+        .iter()
+        .map(|(row, _)| text_width(row.university.localized_name(locale)))
+        .fold(0f64, f64::max)
+        + 8f64;
+
+    let width = label + BARS + VALUE;
+    let height = ROW * scored.len() as f64;
+    let mut out = String::new();
+    writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\" width=\"100%\" \
+         font-size=\"12\" role=\"img\" aria-label=\"{}\">",
+        width,
+        height,
+        locale.pick("대학별 환산 점수", "Converted scores by university")
+    )
+    .unwrap();
+    for (i, (row, score)) in scored.iter().enumerate() {
+        let name = escape(row.university.localized_name(locale));
+        let y = ROW * i as f64;
+        let length = if max > base {
+            (score - base) / (max - base) * BARS
+        } else {
+            BARS
+        };
+        let text_y = y + ROW / 2f64 + 4f64;
+        writeln!(
+            out,
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>",
+            label - 8f64,
+            text_y,
+            name
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "<rect class=\"bar\" fill=\"#2563eb\" x=\"{}\" y=\"{}\" width=\"{:.1}\" height=\"{}\">\
+             <title>{}: {}</title></rect>",
+            label,
+            y + (ROW - BAR) / 2f64,
+            length,
+            BAR,
+            name,
+            fmt_number(*score, Some(2))
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "<text x=\"{:.1}\" y=\"{}\">{}</text>",
+            label + length + 6f64,
+            text_y,
+            fmt_number(*score, Some(2))
+        )
+        .unwrap();
+    }
+    writeln!(out, "</svg>").unwrap();
+    Some((out, base))
+}
+
+/// Caption of a `bars` chart starting at `base`
+pub(super) fn caption(base: f64, locale: Locale) -> String {
+    let base = fmt_number(base, None);
+    match locale {
+        Locale::Korean => format!("막대는 {}점부터 그렸습니다.", base),
+        Locale::English => format!("Bars start at {} points.", base),
+    }
+}
+
+// Rough width of `s` in 12px text: Hangul is about twice as wide as Latin
+fn text_width(s: &str) -> f64 {
+    s.chars()
+        .map(|c| if c.is_ascii() { 7f64 } else { 12f64 })
+        .sum()
+}
@@ -0,0 +1,494 @@
+//! Self-contained HTML reports for printing or mailing: inline CSS and an
+//! inline SVG chart, no external assets
+
+pub use super::{ReportOptions, Sections};
+
+use super::chart;
+use super::summary::{CohortSummary, SubjectSummary};
+use super::tables::{self, Align, Table};
+use crate::api::{compare, CalculatorForm, ComparisonRow};
+use crate::cohort::{Cohort, Metric, RankTable};
+use crate::display::{grade_label, source_notes};
+use crate::error::Result;
+use crate::score::{Locale, Record, Subject, University, YEARS};
+use std::fmt::Write;
+
+const STYLE: &str = "\
+body { font-family: 'Apple SD Gothic Neo', 'Malgun Gothic', 'Noto Sans KR', sans-serif; \
+max-width: 960px; margin: 2rem auto; padding: 0 1rem; color: #1f2937; line-height: 1.5; }
+h1 { font-size: 1.6rem; border-bottom: 2px solid #1f2937; padding-bottom: .3rem; }
+h2 { font-size: 1.2rem; margin-top: 2rem; }
+.table-wrap { overflow-x: auto; }
+table { border-collapse: collapse; width: 100%; font-size: .9rem; }
+th, td { border: 1px solid #d1d5db; padding: .3rem .5rem; white-space: nowrap; }
+th { background: #f3f4f6; text-align: left; }
+td.num, th.num { text-align: right; font-variant-numeric: tabular-nums; }
+figure { margin: 0 0 1rem; }
+figcaption { font-size: .8rem; color: #6b7280; }
+.sources { font-size: .8rem; color: #6b7280; margin: .5rem 0 0; padding-left: 1.2rem; }
+.note { font-size: .8rem; color: #6b7280; margin: .5rem 0 0; }
+.error { color: #b91c1c; font-weight: bold; }
+td input { font: inherit; text-align: right; }
+.histograms { display: flex; flex-wrap: wrap; gap: 1.5rem; }
+svg .bar { fill: #2563eb; }
+svg text { font-size: 12px; fill: #1f2937; }
+@media print {
+  body { margin: 0; max-width: none; }
+  .table-wrap { overflow: visible; }
+  table { font-size: 9pt; }
+  th, td { white-space: normal; }
+  section { break-inside: avoid; }
+}
+";
+
+/// Same sections as [`markdown::student_report`](super::markdown::student_report),
+/// with a bar chart of the converted scores above the comparison table.
+pub fn student_report(record: &Record, year: usize, opts: &ReportOptions) -> String {
+    let locale = opts.locale;
+    let title = match record.exam() {
+        Some(exam) => format!("{} ({})", record.name(), exam.id().localized_label(locale)),
+        None => record.name().to_string(),
+    };
+    document(&title, &student_sections(record, year, opts), locale)
+}
+
+// Sections of `student_report`, each with its heading
+fn student_sections(record: &Record, year: usize, opts: &ReportOptions) -> String {
+    let locale = opts.locale;
+    let heading = |heading| tables::heading(heading, locale);
+    let mut body = String::new();
+
+    if opts.sections.scores {
+        let mut content = table(&tables::scores(record, locale));
+        content.push_str(&sources(&source_notes(record, locale)));
+        section(&mut body, "scores", heading(tables::SCORES), &content);
+    }
+
+    let rows = compare(record, &University::supported(year), year, true);
+    if opts.sections.comparison {
+        let mut content = bar_chart(&rows, locale);
+        content.push_str(&table(&tables::comparison(&rows, opts)));
+        let universities: Vec<University> = rows.iter().map(|r| r.university).collect();
+        content.push_str(&sources(&tables::sources(&universities, year, locale)));
+        section(
+            &mut body,
+            "comparison",
+            &tables::comparison_title(year, locale),
+            &content,
+        );
+    }
+
+    let top = tables::top_universities(&rows, opts.sweep_count);
+    if opts.sections.english_sweep && !top.is_empty() {
+        let sweep = tables::english_sweep(record, &top, year, locale);
+        section(
+            &mut body,
+            "english-sweep",
+            heading(tables::ENGLISH_SWEEP),
+            &table(&sweep),
+        );
+    }
+
+    if opts.sections.progress && !opts.progress.is_empty() {
+        let progress = tables::progress(&opts.progress, locale);
+        section(
+            &mut body,
+            "progress",
+            heading(tables::PROGRESS),
+            &table(&progress),
+        );
+    }
+
+    if opts.sections.plan && !opts.plan.is_empty() {
+        let plan = tables::plan(&opts.plan, locale);
+        section(&mut body, "plan", heading(tables::PLAN), &table(&plan));
+    }
+    body
+}
+
+/// The calculator page of the `server` module: a form for the five
+/// subjects and the year, filled in with `form`, then either why it does
+/// not make a record or the `student_report` sections of the record it
+/// makes, whose comparison ranks every university and marks those the
+/// record cannot apply to. `outcome` is `None` for the blank form.
+pub fn calculator(
+    form: &CalculatorForm,
+    outcome: Option<&Result<(Record, usize)>>,
+    opts: &ReportOptions,
+) -> String {
+    let locale = opts.locale;
+    let mut body = calculator_form(form, locale);
+    match outcome {
+        Some(Ok((record, year))) => body.push_str(&student_sections(record, *year, opts)),
+        Some(Err(e)) => {
+            writeln!(
+                body,
+                "<p class=\"error\" role=\"alert\">{}</p>",
+                escape(&e.to_string())
+            )
+            .unwrap();
+        }
+        None => {}
+    }
+    document(tables::heading(tables::CALCULATOR, locale), &body, locale)
+}
+
+// The form of `calculator`, posting back to the page
+fn calculator_form(form: &CalculatorForm, locale: Locale) -> String {
+    let mut out = String::new();
+    writeln!(out, "<form method=\"post\" action=\"/\">").unwrap();
+    write!(
+        out,
+        "<p><label>{} <input name=\"name\" value=\"{}\"></label> ",
+        locale.pick("이름", "Name"),
+        escape(&form.name)
+    )
+    .unwrap();
+    let year = form.year.trim().parse().unwrap_or(YEARS[YEARS.len() - 1]);
+    write!(
+        out,
+        "<label>{} <select name=\"year\">",
+        locale.pick("학년도", "Admission year")
+    )
+    .unwrap();
+    for y in YEARS {
+        let selected = if y == year { " selected" } else { "" };
+        write!(out, "<option{}>{}</option>", selected, y).unwrap();
+    }
+    writeln!(out, "</select></label></p>").unwrap();
+
+    writeln!(out, "<div class=\"table-wrap\">\n<table>").unwrap();
+    out.push_str("<thead><tr>");
+    for (i, (korean, english)) in tables::SCORE_COLUMNS.into_iter().enumerate() {
+        let class = if i == 0 { "" } else { " class=\"num\"" };
+        write!(out, "<th{}>{}</th>", class, locale.pick(korean, english)).unwrap();
+    }
+    writeln!(out, "</tr></thead>").unwrap();
+    writeln!(out, "<tbody>").unwrap();
+    for &subject in Subject::all() {
+        write!(out, "<tr><td>{}</td>", subject.localized_name(locale)).unwrap();
+        let names = CalculatorForm::field_names(subject);
+        for (name, value) in names.into_iter().zip(form.fields(subject)) {
+            if name.is_empty() {
+                out.push_str("<td class=\"num\">-</td>");
+            } else {
+                write!(
+                    out,
+                    "<td class=\"num\"><input name=\"{}\" value=\"{}\" \
+                     inputmode=\"decimal\" size=\"5\"></td>",
+                    name,
+                    escape(value)
+                )
+                .unwrap();
+            }
+        }
+        writeln!(out, "</tr>").unwrap();
+    }
+    writeln!(out, "</tbody>\n</table>\n</div>").unwrap();
+    writeln!(
+        out,
+        "<p><button type=\"submit\">{}</button></p>",
+        locale.pick("계산", "Calculate")
+    )
+    .unwrap();
+    writeln!(out, "</form>").unwrap();
+    out
+}
+
+/// Same sections as [`markdown::cohort_report`](super::markdown::cohort_report)
+pub fn cohort_report(records: &[Record], year: usize, opts: &ReportOptions) -> String {
+    let matrix = super::matrix(records, &University::supported(year), year);
+    let cohort = Cohort::new(records.to_vec());
+    let locale = opts.locale;
+    let heading = |heading| tables::heading(heading, locale);
+    let mut body = String::new();
+
+    if opts.sections.matrix {
+        let mut content = table(&tables::matrix(&matrix, locale));
+        content.push_str(&sources(&tables::sources(
+            &matrix.universities,
+            year,
+            locale,
+        )));
+        section(&mut body, "matrix", heading(tables::MATRIX), &content);
+    }
+    if opts.sections.statistics {
+        let statistics = tables::statistics(&cohort, locale);
+        section(
+            &mut body,
+            "statistics",
+            heading(tables::STATISTICS),
+            &table(&statistics),
+        );
+    }
+    if opts.sections.correlation {
+        let correlation = cohort.correlation_matrix(Metric::StandardScore);
+        section(
+            &mut body,
+            "correlation",
+            heading(tables::CORRELATION),
+            &table(&tables::correlation(&correlation, locale)),
+        );
+    }
+    if opts.sections.grade_cuts {
+        let estimates = cohort.estimate_all_grade_cuts(Metric::StandardScore);
+        let mut content = table(&tables::grade_cuts(&estimates, locale));
+        if let Some(note) = tables::grade_cuts_note(&estimates, locale) {
+            writeln!(content, "<p class=\"note\">{}</p>", escape(&note)).unwrap();
+        }
+        section(
+            &mut body,
+            "grade-cuts",
+            heading(tables::GRADE_CUTS),
+            &content,
+        );
+    }
+    if opts.sections.ranking {
+        let ranking = tables::ranking(&matrix, locale);
+        section(
+            &mut body,
+            "ranking",
+            heading(tables::RANKING),
+            &table(&ranking),
+        );
+    }
+    if opts.sections.top {
+        for ranked in RankTable::from_matrix(&matrix) {
+            if ranked.top(opts.top_count).is_empty() {
+                continue;
+            }
+            let mut content = String::new();
+            if let Some(formula) = tables::rank_formula(&ranked, year) {
+                writeln!(content, "<p>{}</p>", escape(&formula)).unwrap();
+            }
+            content.push_str(&table(&tables::rank_top(&ranked, opts.top_count, locale)));
+            if let Some(note) = tables::rank_ineligible(&ranked, locale) {
+                writeln!(content, "<p class=\"note\">{}</p>", escape(&note)).unwrap();
+            }
+            section(
+                &mut body,
+                &format!("top-{:?}", ranked.university).to_lowercase(),
+                &tables::rank_top_title(&ranked, opts.top_count, locale),
+                &content,
+            );
+        }
+    }
+    document(
+        &tables::cohort_title(year, records.len(), locale),
+        &body,
+        locale,
+    )
+}
+
+/// Same sections as [`markdown::cohort_summary`](super::markdown::cohort_summary),
+/// with a histogram of the grades of each subject above the grade table.
+pub fn cohort_summary(summary: &CohortSummary, locale: Locale) -> String {
+    let heading = |heading| tables::heading(heading, locale);
+    let mut body = String::new();
+    section(
+        &mut body,
+        "statistics",
+        heading(tables::STATISTICS),
+        &table(&tables::summary_statistics(summary, locale)),
+    );
+
+    let mut grades = String::from("<div class=\"histograms\">\n");
+    for subject in &summary.subjects {
+        grades.push_str(&histogram(subject, locale));
+    }
+    grades.push_str("</div>\n");
+    grades.push_str(&table(&tables::grade_distribution(summary, locale)));
+    section(&mut body, "grades", heading(tables::GRADES), &grades);
+
+    for list in &summary.top {
+        section(
+            &mut body,
+            &format!("top-{}", list.university.name()),
+            &tables::top_list_title(list, locale),
+            &table(&tables::top_list(list, locale)),
+        );
+    }
+    if !summary.cuts.is_empty() {
+        section(
+            &mut body,
+            "cuts",
+            heading(tables::CUTS),
+            &table(&tables::cut_counts(summary, locale)),
+        );
+    }
+    document(&tables::summary_title(summary, locale), &body, locale)
+}
+
+fn document(title: &str, body: &str, locale: Locale) -> String {
+    let mut out = String::new();
+    writeln!(out, "<!DOCTYPE html>").unwrap();
+    writeln!(out, "<html lang=\"{}\">", locale.pick("ko", "en")).unwrap();
+    writeln!(out, "<head>").unwrap();
+    writeln!(out, "<meta charset=\"utf-8\">").unwrap();
+    writeln!(
+        out,
+        "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">"
+    )
+    .unwrap();
+    writeln!(out, "<title>{}</title>", escape(title)).unwrap();
+    writeln!(out, "<style>\n{}</style>", STYLE).unwrap();
+    writeln!(out, "</head>").unwrap();
+    writeln!(out, "<body>").unwrap();
+    writeln!(out, "<h1>{}</h1>", escape(title)).unwrap();
+    out.push_str(body);
+    writeln!(out, "</body>").unwrap();
+    writeln!(out, "</html>").unwrap();
+    out
+}
+
+fn section(out: &mut String, id: &str, heading: &str, content: &str) {
+    writeln!(out, "<section id=\"{}\">", id).unwrap();
+    writeln!(out, "<h2>{}</h2>", escape(heading)).unwrap();
+    out.push_str(content);
+    writeln!(out, "</section>").unwrap();
+}
+
+// Footnotes naming where each weight comes from
+fn sources(lines: &[String]) -> String {
+    let mut out = String::new();
+    if !lines.is_empty() {
+        writeln!(out, "<ul class=\"sources\">").unwrap();
+        for line in lines {
+            writeln!(out, "<li>{}</li>", escape(line)).unwrap();
+        }
+        writeln!(out, "</ul>").unwrap();
+    }
+    out
+}
+
+fn table(table: &Table) -> String {
+    let class = |align: Option<&Align>| match align {
+        Some(Align::Right) => " class=\"num\"",
+        _ => "",
+    };
+    let mut out = String::new();
+    writeln!(out, "<div class=\"table-wrap\">\n<table>").unwrap();
+    out.push_str("<thead><tr>");
+    for (i, cell) in table.header.iter().enumerate() {
+        write!(
+            out,
+            "<th{}>{}</th>",
+            class(table.align.get(i)),
+            escape(cell)
+        )
+        .unwrap();
+    }
+    writeln!(out, "</tr></thead>").unwrap();
+    writeln!(out, "<tbody>").unwrap();
+    for row in &table.rows {
+        out.push_str("<tr>");
+        for (i, cell) in row.iter().enumerate() {
+            write!(
+                out,
+                "<td{}>{}</td>",
+                class(table.align.get(i)),
+                escape(cell)
+            )
+            .unwrap();
+        }
+        writeln!(out, "</tr>").unwrap();
+    }
+    writeln!(out, "</tbody>\n</table>\n</div>").unwrap();
+    out
+}
+
+// `chart::bars` in a figure captioned with its base
+fn bar_chart(rows: &[ComparisonRow], locale: Locale) -> String {
+    let Some((svg, base)) = chart::bars(rows, locale) else {
+        return String::new();
+    };
+    let mut out = String::new();
+    writeln!(out, "<figure>").unwrap();
+    out.push_str(&svg);
+    writeln!(
+        out,
+        "<figcaption>{}</figcaption>",
+        escape(&chart::caption(base, locale))
+    )
+    .unwrap();
+    writeln!(out, "</figure>").unwrap();
+    out
+}
+
+// Column per grade, 1등급 on the left, scaled to the largest count
+fn histogram(subject: &SubjectSummary, locale: Locale) -> String {
+    const COLUMN: f64 = 20f64;
+    const HEIGHT: f64 = 80f64;
+    const TEXT: f64 = 16f64;
+
+    let name = escape(subject.subject.localized_name(locale));
+    let most = subject.grades.iter().copied().max().unwrap_or(0).max(1) as f64;
+    let width = COLUMN * subject.grades.len() as f64;
+    let mut out = String::new();
+    writeln!(out, "<figure>").unwrap();
+    writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\" width=\"{}\" \
+         role=\"img\" aria-label=\"{}: {}\">",
+        width,
+        HEIGHT + 2f64 * TEXT,
+        width,
+        name,
+        tables::heading(tables::GRADES, locale)
+    )
+    .unwrap();
+    for (i, &count) in subject.grades.iter().enumerate() {
+        let x = COLUMN * i as f64;
+        let length = count as f64 / most * HEIGHT;
+        let center = x + COLUMN / 2f64;
+        writeln!(
+            out,
+            "<rect class=\"bar\" x=\"{}\" y=\"{:.1}\" width=\"{}\" height=\"{:.1}\">\
+             <title>{}: {}</title></rect>",
+            x + 2f64,
+            TEXT + HEIGHT - length,
+            COLUMN - 4f64,
+            length,
+            grade_label(i + 1, locale),
+            tables::students(count, locale)
+        )
+        .unwrap();
+        if count > 0 {
+            writeln!(
+                out,
+                "<text x=\"{}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>",
+                center,
+                TEXT + HEIGHT - length - 4f64,
+                count
+            )
+            .unwrap();
+        }
+        writeln!(
+            out,
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>",
+            center,
+            2f64 * TEXT + HEIGHT - 4f64,
+            i + 1
+        )
+        .unwrap();
+    }
+    writeln!(out, "</svg>").unwrap();
+    writeln!(out, "<figcaption>{}</figcaption>", name).unwrap();
+    writeln!(out, "</figure>").unwrap();
+    out
+}
+
+pub(super) fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
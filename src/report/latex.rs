@@ -0,0 +1,113 @@
+//! booktabs tables for LaTeX documents. Korean text is passed through as
+//! it is, so the document needs xelatex or lualatex with a Korean font
+//! (e.g. `kotex`), plus the `booktabs` and, for long tables, `longtable`
+//! packages.
+
+pub use super::ReportOptions;
+
+use super::tables::{self, Align, Table};
+use crate::api::ComparisonRow;
+use crate::display::source_notes;
+use crate::score::Record;
+use std::fmt::Write;
+
+/// Rows above which `Layout::Auto` switches to `longtable`, about what fits
+/// on an A4 page under a heading
+pub const PAGE_ROWS: usize = 30;
+
+/// Environment a table is set in
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Layout {
+    /// `longtable` for more than `PAGE_ROWS` rows, otherwise `tabular`
+    #[default]
+    Auto,
+    Tabular,
+    /// Breaks across pages, repeating the header
+    Longtable,
+}
+
+/// Standard score, percentile and grade of each subject of `record`, with
+/// a line under the table for each exam the scores are marked as taken
+/// from. `opts` supplies the locale.
+pub fn score_table(record: &Record, opts: &ReportOptions) -> String {
+    let mut out = render(&tables::scores(record, opts.locale), Layout::Tabular);
+    for note in source_notes(record, opts.locale) {
+        writeln!(out, "\\par{{\\footnotesize {}}}", escape(&note)).unwrap();
+    }
+    out
+}
+
+/// Ranked comparison like the Markdown report's, from `rows` of
+/// `api::compare`. `opts` supplies the cut lines and the locale of
+/// university names.
+pub fn comparison_table(rows: &[ComparisonRow], opts: &ReportOptions, layout: Layout) -> String {
+    render(&tables::comparison(rows, opts), layout)
+}
+
+fn render(table: &Table, layout: Layout) -> String {
+    let long = match layout {
+        Layout::Auto => table.rows.len() > PAGE_ROWS,
+        Layout::Tabular => false,
+        Layout::Longtable => true,
+    };
+    let spec: String = table
+        .align
+        .iter()
+        .map(|a| match a {
+            Align::Left => 'l',
+            Align::Right => 'r',
+        })
+        .collect();
+    let env = if long { "longtable" } else { "tabular" };
+
+    let mut out = String::new();
+    writeln!(out, "\\begin{{{}}}{{{}}}", env, spec).unwrap();
+    writeln!(out, "\\toprule").unwrap();
+    writeln!(out, "{} \\\\", row(&table.header)).unwrap();
+    writeln!(out, "\\midrule").unwrap();
+    if long {
+        writeln!(out, "\\endhead").unwrap();
+        writeln!(out, "\\bottomrule").unwrap();
+        writeln!(out, "\\endlastfoot").unwrap();
+    }
+    for cells in &table.rows {
+        writeln!(out, "{} \\\\", row(cells)).unwrap();
+    }
+    if !long {
+        writeln!(out, "\\bottomrule").unwrap();
+    }
+    writeln!(out, "\\end{{{}}}", env).unwrap();
+    out
+}
+
+fn row(cells: &[String]) -> String {
+    cells
+        .iter()
+        .map(|c| escape(c))
+        .collect::<Vec<_>>()
+        .join(" & ")
+}
+
+/// `s` with the characters that LaTeX treats specially escaped
+///
+/// ```
+/// use suneung_calc::report::latex::escape;
+///
+/// assert_eq!(escape("상위 5% & A_1"), "상위 5\\% \\& A\\_1");
+/// ```
+pub fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\textbackslash{}"),
+            '~' => out.push_str("\\textasciitilde{}"),
+            '^' => out.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                out.push('\\');
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,254 @@
+//! GitHub-flavored Markdown reports
+
+pub use super::{ReportOptions, Sections};
+
+use super::summary::CohortSummary;
+use super::tables::{self, Align, Table};
+use crate::api::compare;
+use crate::cohort::{Cohort, Metric, RankTable};
+use crate::display::source_notes;
+use crate::score::{Locale, Record, University};
+use std::fmt::Write;
+
+/// Score table, ranked comparison over every university with data for
+/// `year`, the English sweep for the best universities, progress towards
+/// the student's targets and the improvement plan, as selected by
+/// `opts.sections`.
+///
+/// `opts.locale` picks the language of every heading and table:
+///
+/// ```
+/// use suneung_calc::report::markdown::{student_report, ReportOptions, Sections};
+/// use suneung_calc::score::{Grade, Locale, Percentile, Record, Score, StandardScore, Subject};
+///
+/// let mut record = Record::new("홍길동");
+/// record.record_typed(
+///     Subject::Chemistry,
+///     StandardScore::new(64.0).unwrap(),
+///     Percentile::new(92.0).unwrap(),
+///     Grade::try_from(2u8).unwrap(),
+/// );
+/// record.record_score(Subject::English, Score::grade_only(Grade::try_from(3u8).unwrap()));
+/// let report = |locale| {
+///     let opts = ReportOptions {
+///         locale,
+///         sections: Sections {
+///             comparison: false,
+///             english_sweep: false,
+///             ..Default::default()
+///         },
+///         ..Default::default()
+///     };
+///     student_report(&record, 2024, &opts)
+/// };
+/// assert_eq!(
+///     report(Locale::Korean),
+///     "# 홍길동\n\
+///      \n## 성적\n\
+///      | 과목 | 표준점수 | 백분위 | 등급 |\n\
+///      | --- | ---: | ---: | ---: |\n\
+///      | 영어 | - | - | 3등급 |\n\
+///      | 탐구(화학Ⅰ) | 64 | 92 | 2등급 |\n"
+/// );
+/// assert_eq!(
+///     report(Locale::English),
+///     "# 홍길동\n\
+///      \n## Scores\n\
+///      | Subject | Standard score | Percentile | Grade |\n\
+///      | --- | ---: | ---: | ---: |\n\
+///      | English | - | - | Grade 3 |\n\
+///      | Science (Chemistry I) | 64 | 92 | Grade 2 |\n"
+/// );
+/// ```
+pub fn student_report(record: &Record, year: usize, opts: &ReportOptions) -> String {
+    let locale = opts.locale;
+    let mut out = String::new();
+    match record.exam() {
+        Some(exam) => writeln!(
+            out,
+            "# {} ({})",
+            escape(record.name()),
+            exam.id().localized_label(locale)
+        )
+        .unwrap(),
+        None => writeln!(out, "# {}", escape(record.name())).unwrap(),
+    }
+
+    if opts.sections.scores {
+        writeln!(out, "\n## {}", tables::heading(tables::SCORES, locale)).unwrap();
+        out.push_str(&table(&tables::scores(record, locale)));
+        let heading = tables::heading(tables::SCORE_SOURCES, locale);
+        out.push_str(&list(heading, &source_notes(record, locale)));
+    }
+
+    let rows = compare(record, &University::supported(year), year, true);
+    if opts.sections.comparison {
+        writeln!(out, "\n## {}", tables::comparison_title(year, locale)).unwrap();
+        out.push_str(&table(&tables::comparison(&rows, opts)));
+        let universities: Vec<University> = rows.iter().map(|r| r.university).collect();
+        out.push_str(&sources(
+            &tables::sources(&universities, year, locale),
+            locale,
+        ));
+    }
+
+    let top = tables::top_universities(&rows, opts.sweep_count);
+    if opts.sections.english_sweep && !top.is_empty() {
+        let heading = tables::heading(tables::ENGLISH_SWEEP, locale);
+        writeln!(out, "\n## {}", heading).unwrap();
+        out.push_str(&table(&tables::english_sweep(record, &top, year, locale)));
+    }
+
+    if opts.sections.progress && !opts.progress.is_empty() {
+        let heading = tables::heading(tables::PROGRESS, locale);
+        writeln!(out, "\n## {}", heading).unwrap();
+        out.push_str(&table(&tables::progress(&opts.progress, locale)));
+    }
+
+    if opts.sections.plan && !opts.plan.is_empty() {
+        let heading = tables::heading(tables::PLAN, locale);
+        writeln!(out, "\n## {}", heading).unwrap();
+        out.push_str(&table(&tables::plan(&opts.plan, locale)));
+    }
+    out
+}
+
+/// Converted scores of every student at every university with data for
+/// `year`, per-subject statistics of the cohort, the correlation between
+/// subjects, the cohort's own 등급컷, the place of every
+/// student at each university and the `opts.top_count` best students at
+/// each university, as selected by `opts.sections`.
+pub fn cohort_report(records: &[Record], year: usize, opts: &ReportOptions) -> String {
+    let matrix = super::matrix(records, &University::supported(year), year);
+    let cohort = Cohort::new(records.to_vec());
+    let locale = opts.locale;
+    let mut out = String::new();
+    writeln!(
+        out,
+        "# {}",
+        tables::cohort_title(year, records.len(), locale)
+    )
+    .unwrap();
+
+    if opts.sections.matrix {
+        let heading = tables::heading(tables::MATRIX, locale);
+        writeln!(out, "\n## {}", heading).unwrap();
+        out.push_str(&table(&tables::matrix(&matrix, locale)));
+        out.push_str(&sources(
+            &tables::sources(&matrix.universities, year, locale),
+            locale,
+        ));
+    }
+    if opts.sections.statistics {
+        writeln!(out, "\n## {}", tables::heading(tables::STATISTICS, locale)).unwrap();
+        out.push_str(&table(&tables::statistics(&cohort, locale)));
+    }
+    if opts.sections.correlation {
+        let heading = tables::heading(tables::CORRELATION, locale);
+        writeln!(out, "\n## {}", heading).unwrap();
+        let correlation = cohort.correlation_matrix(Metric::StandardScore);
+        out.push_str(&table(&tables::correlation(&correlation, locale)));
+    }
+    if opts.sections.grade_cuts {
+        let heading = tables::heading(tables::GRADE_CUTS, locale);
+        writeln!(out, "\n## {}", heading).unwrap();
+        let estimates = cohort.estimate_all_grade_cuts(Metric::StandardScore);
+        out.push_str(&table(&tables::grade_cuts(&estimates, locale)));
+        if let Some(note) = tables::grade_cuts_note(&estimates, locale) {
+            writeln!(out, "\n{}", note).unwrap();
+        }
+    }
+    if opts.sections.ranking {
+        writeln!(out, "\n## {}", tables::heading(tables::RANKING, locale)).unwrap();
+        out.push_str(&table(&tables::ranking(&matrix, locale)));
+    }
+    if opts.sections.top {
+        for ranked in RankTable::from_matrix(&matrix) {
+            if ranked.top(opts.top_count).is_empty() {
+                continue;
+            }
+            let title = tables::rank_top_title(&ranked, opts.top_count, locale);
+            writeln!(out, "\n## {}", escape(&title)).unwrap();
+            if let Some(formula) = tables::rank_formula(&ranked, year) {
+                writeln!(out, "\n{}\n", escape(&formula)).unwrap();
+            }
+            out.push_str(&table(&tables::rank_top(&ranked, opts.top_count, locale)));
+            if let Some(note) = tables::rank_ineligible(&ranked, locale) {
+                writeln!(out, "\n{}", note).unwrap();
+            }
+        }
+    }
+    out
+}
+
+/// One page for `summary` (see `report::cohort_summary`): statistics and
+/// grade counts per subject, the top list at each chosen university and,
+/// when cut lines were given, how many students clear them.
+pub fn cohort_summary(summary: &CohortSummary, locale: Locale) -> String {
+    let mut out = String::new();
+    writeln!(out, "# {}", tables::summary_title(summary, locale)).unwrap();
+
+    writeln!(out, "\n## {}", tables::heading(tables::STATISTICS, locale)).unwrap();
+    out.push_str(&table(&tables::summary_statistics(summary, locale)));
+    writeln!(out, "\n## {}", tables::heading(tables::GRADES, locale)).unwrap();
+    out.push_str(&table(&tables::grade_distribution(summary, locale)));
+    for list in &summary.top {
+        let title = tables::top_list_title(list, locale);
+        writeln!(out, "\n## {}", escape(&title)).unwrap();
+        out.push_str(&table(&tables::top_list(list, locale)));
+    }
+    if !summary.cuts.is_empty() {
+        let heading = tables::heading(tables::CUTS, locale);
+        writeln!(out, "\n## {}", heading).unwrap();
+        out.push_str(&table(&tables::cut_counts(summary, locale)));
+    }
+    out
+}
+
+fn table(table: &Table) -> String {
+    let mut out = String::new();
+    writeln!(out, "| {} |", row(&table.header)).unwrap();
+    let separators: Vec<&str> = table
+        .align
+        .iter()
+        .map(|a| match a {
+            Align::Left => "---",
+            Align::Right => "---:",
+        })
+        .collect();
+    writeln!(out, "| {} |", separators.join(" | ")).unwrap();
+    for cells in &table.rows {
+        writeln!(out, "| {} |", row(cells)).unwrap();
+    }
+    out
+}
+
+fn row(cells: &[String]) -> String {
+    cells
+        .iter()
+        .map(|c| escape(c))
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+// Footnotes under a table
+fn sources(lines: &[String], locale: Locale) -> String {
+    list(tables::heading(tables::WEIGHT_SOURCES, locale), lines)
+}
+
+// Bulleted `lines` under `heading`, nothing without lines
+fn list(heading: &str, lines: &[String]) -> String {
+    let mut out = String::new();
+    if !lines.is_empty() {
+        writeln!(out, "\n{}:\n", heading).unwrap();
+        for line in lines {
+            writeln!(out, "- {}", line).unwrap();
+        }
+    }
+    out
+}
+
+// `|` would end the table cell
+fn escape(s: &str) -> String {
+    s.replace('|', "\\|")
+}
@@ -0,0 +1,216 @@
+//! Students × universities table of converted scores, shared by the
+//! exporters of cohort results
+
+use crate::calculator::score_columns;
+use crate::score::{Record, Subject, University};
+use serde::Serialize;
+#[cfg(feature = "csv")]
+use std::error::Error;
+
+/// Converted score of one student at one university, or why there is none
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MatrixCell {
+    Score {
+        value: f64,
+    },
+    /// `code` is `SuneungError::code`, e.g. `unsupported_year` when the
+    /// university has no data for the year or `missing_subject` when the
+    /// record lacks a required subject
+    Blank {
+        code: &'static str,
+        message: String,
+    },
+}
+
+impl MatrixCell {
+    pub fn score(&self) -> Option<f64> {
+        match self {
+            MatrixCell::Score { value } => Some(*value),
+            MatrixCell::Blank { .. } => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MatrixRow {
+    pub name: String,
+    /// Aligned with `Matrix::universities`
+    pub cells: Vec<MatrixCell>,
+    /// 표점합: standard scores of the recorded subjects except English
+    pub standard_sum: Option<f64>,
+    /// 백분위합 over the same subjects
+    pub percentile_sum: Option<f64>,
+}
+
+/// One aggregate (mean or max) of every column over the students that
+/// have a value there
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MatrixFooter {
+    pub scores: Vec<Option<f64>>,
+    pub standard_sum: Option<f64>,
+    pub percentile_sum: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Matrix {
+    pub year: usize,
+    pub universities: Vec<University>,
+    /// In the order of the records
+    pub rows: Vec<MatrixRow>,
+    pub mean: MatrixFooter,
+    pub max: MatrixFooter,
+}
+
+/// Score every record at every one of `universities` for `year`
+///
+/// ```
+/// use suneung_calc::report::matrix;
+/// use suneung_calc::score::{Grade, Percentile, Record, StandardScore, Subject, University};
+///
+/// let mut record = Record::new("홍길동");
+/// record.record_typed(
+///     Subject::Korean,
+///     StandardScore::new(131f64).unwrap(),
+///     Percentile::new(93f64).unwrap(),
+///     Grade::try_from(2u8).unwrap(),
+/// );
+/// record.record_typed(
+///     Subject::Math,
+///     StandardScore::new(128f64).unwrap(),
+///     Percentile::new(89f64).unwrap(),
+///     Grade::try_from(2u8).unwrap(),
+/// );
+///
+/// let m = matrix(&[record], &[University::KONKUK], 2024);
+/// assert_eq!(m.rows[0].standard_sum, Some(259.0));
+/// assert_eq!(m.rows[0].cells[0].score(), None);
+/// assert_eq!(m.mean.scores, vec![None]);
+/// ```
+pub fn matrix(records: &[Record], universities: &[University], year: usize) -> Matrix {
+    let n = records.len();
+    let scores = score_columns(records, universities.iter().map(|&u| (u, year)));
+    let rows = records
+        .iter()
+        .enumerate()
+        .map(|(i, record)| row(record, (0..universities.len()).map(|j| &scores[j * n + i])))
+        .collect();
+    with_footers(rows, universities, year)
+}
+
+/// `matrix` with the records scored on the rayon thread pool; the rows stay
+/// in the order of the records
+#[cfg(feature = "parallel")]
+pub(crate) fn matrix_par(records: &[Record], universities: &[University], year: usize) -> Matrix {
+    use rayon::prelude::*;
+
+    let rows = records
+        .par_iter()
+        .map(|record| {
+            let scores: Vec<_> = universities
+                .iter()
+                .map(|&u| record.try_calc_with_university(u, year))
+                .collect();
+            row(record, scores.iter())
+        })
+        .collect();
+    with_footers(rows, universities, year)
+}
+
+fn row<'a>(
+    record: &Record,
+    scores: impl Iterator<Item = &'a crate::error::Result<f64>>,
+) -> MatrixRow {
+    let cells = scores
+        .map(|score| match score {
+            Ok(value) => MatrixCell::Score { value: *value },
+            Err(e) => MatrixCell::Blank {
+                code: e.code(),
+                message: e.to_string(),
+            },
+        })
+        .collect();
+    let (standard_sum, percentile_sum) = sums(record);
+    MatrixRow {
+        name: record.name().to_string(),
+        cells,
+        standard_sum,
+        percentile_sum,
+    }
+}
+
+fn with_footers(rows: Vec<MatrixRow>, universities: &[University], year: usize) -> Matrix {
+    let footer = |aggregate: fn(&[f64]) -> f64| {
+        let column = |value: &dyn Fn(&MatrixRow) -> Option<f64>| {
+            let values: Vec<f64> = rows.iter().filter_map(value).collect();
+            (!values.is_empty()).then(|| aggregate(&values))
+        };
+        MatrixFooter {
+            scores: (0..universities.len())
+                .map(|j| column(&|r| r.cells[j].score()))
+                .collect(),
+            standard_sum: column(&|r| r.standard_sum),
+            percentile_sum: column(&|r| r.percentile_sum),
+        }
+    };
+    let mean = footer(|xs| xs.iter().sum::<f64>() / xs.len() as f64);
+    let max = footer(|xs| xs.iter().copied().fold(f64::NEG_INFINITY, f64::max));
+    Matrix {
+        year,
+        universities: universities.to_vec(),
+        rows,
+        mean,
+        max,
+    }
+}
+
+// 표점합 and 백분위합, `None` for a record with only English
+fn sums(record: &Record) -> (Option<f64>, Option<f64>) {
+    let scores: Vec<_> = record
+        .iter()
+        .filter(|(subject, _)| *subject != Subject::English)
+        .map(|(_, score)| score)
+        .collect();
+    if scores.is_empty() {
+        return (None, None);
+    }
+    (
+        Some(scores.iter().map(|s| s.standard_score()).sum()),
+        Some(scores.iter().map(|s| s.percentile()).sum()),
+    )
+}
+
+impl Matrix {
+    /// Header, one line per student, then the 평균 and 최고 lines. Blank
+    /// cells hold their reason code in brackets, e.g. `[missing_subject]`.
+    #[cfg(feature = "csv")]
+    pub fn write_csv<W: std::io::Write>(&self, writer: W) -> Result<(), Box<dyn Error>> {
+        let number = |x: Option<f64>| x.map_or(String::new(), |x| format!("{:.2}", x));
+        let mut wtr = csv::Writer::from_writer(writer);
+        let mut header = vec!["이름".to_string()];
+        header.extend(self.universities.iter().map(|u| u.name().to_string()));
+        header.extend(["표점합".to_string(), "백분위합".to_string()]);
+        wtr.write_record(&header)?;
+        for row in &self.rows {
+            let mut line = vec![row.name.clone()];
+            line.extend(row.cells.iter().map(|cell| match cell {
+                MatrixCell::Score { value } => format!("{:.2}", value),
+                MatrixCell::Blank { code, .. } => format!("[{}]", code),
+            }));
+            line.extend([number(row.standard_sum), number(row.percentile_sum)]);
+            wtr.write_record(&line)?;
+        }
+        for (label, footer) in [("평균", &self.mean), ("최고", &self.max)] {
+            let mut line = vec![label.to_string()];
+            line.extend(footer.scores.iter().map(|&x| number(x)));
+            line.extend([number(footer.standard_sum), number(footer.percentile_sum)]);
+            wtr.write_record(&line)?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+//! PDF reports for handing out, typeset in-process from an embedded Typst
+//! template, so no TeX installation is needed.
+//!
+//! Hangul needs a Korean font. Fonts are looked up in
+//! `ReportOptions::font_dirs` first and then among the system fonts; if
+//! none of them covers Hangul the report fails with `SuneungError::Pdf`
+//! instead of printing empty boxes. Noto Sans KR is used when available.
+
+pub use super::ReportOptions;
+
+use super::chart;
+use super::tables::{self, Align, Table};
+use crate::api::compare;
+use crate::display::source_notes;
+use crate::error::{Result, SuneungError};
+use crate::score::{Locale, Record, University};
+use serde_json::{json, Value};
+use std::path::Path;
+use typst::diag::{FileError, FileResult, SourceDiagnostic};
+use typst::foundations::{Bytes, Datetime};
+use typst::layout::PagedDocument;
+use typst::syntax::{FileId, Source, VirtualPath};
+use typst::text::{Font, FontBook, FontVariant};
+use typst::utils::LazyHash;
+use typst::{Library, World};
+use typst_kit::fonts::{FontSearcher, FontSlot};
+
+const TEMPLATE: &str = include_str!("pdf.typ");
+
+/// Same sections as [`markdown::student_report`](super::markdown::student_report)
+/// under a cover block with the name and exam, with the bar chart of the
+/// HTML report above the comparison table, written to `out`.
+pub fn student_report(
+    record: &Record,
+    year: usize,
+    opts: &ReportOptions,
+    out: &Path,
+) -> Result<()> {
+    std::fs::write(out, student_report_bytes(record, year, opts)?)?;
+    Ok(())
+}
+
+/// `student_report` as bytes instead of a file
+pub fn student_report_bytes(record: &Record, year: usize, opts: &ReportOptions) -> Result<Vec<u8>> {
+    let locale = opts.locale;
+    let mut sections = vec![];
+    if opts.sections.scores {
+        let heading = tables::heading(tables::SCORES, locale);
+        let notes = source_notes(record, locale);
+        let table = tables::scores(record, locale);
+        let notes = (tables::heading(tables::SCORE_SOURCES, locale), &notes[..]);
+        sections.push(section(heading, &table, false, notes));
+    }
+
+    let rows = compare(record, &University::supported(year), year, true);
+    let bars = chart::bars(&rows, locale);
+    if opts.sections.comparison {
+        let heading = tables::comparison_title(year, locale);
+        let table = tables::comparison(&rows, opts);
+        let universities: Vec<University> = rows.iter().map(|r| r.university).collect();
+        let notes = tables::sources(&universities, year, locale);
+        let notes = (tables::heading(tables::WEIGHT_SOURCES, locale), &notes[..]);
+        sections.push(section(&heading, &table, bars.is_some(), notes));
+    }
+
+    let top = tables::top_universities(&rows, opts.sweep_count);
+    if opts.sections.english_sweep && !top.is_empty() {
+        let heading = tables::heading(tables::ENGLISH_SWEEP, locale);
+        let sweep = tables::english_sweep(record, &top, year, locale);
+        sections.push(section(heading, &sweep, false, ("", &[])));
+    }
+
+    let data = json!({
+        "title": record.name(),
+        "lang": locale.pick("ko", "en"),
+        "exam": record.exam().map(|e| e.id().localized_label(locale)),
+        "basis": basis(year, locale),
+        "caption": bars.as_ref().map(|&(_, base)| chart::caption(base, locale)),
+        "sections": sections,
+    });
+    let mut files = vec![("data.json", Bytes::new(data.to_string().into_bytes()))];
+    if let Some((svg, _)) = bars {
+        files.push(("chart.svg", Bytes::new(svg.into_bytes())));
+    }
+    render(&ReportWorld::new(files, &opts.font_dirs)?)
+}
+
+// `2024학년도 대학별 반영 비율 기준` under the title
+fn basis(year: usize, locale: Locale) -> String {
+    match locale {
+        Locale::Korean => format!("{}학년도 대학별 반영 비율 기준", year),
+        Locale::English => format!("Weights of the {} admission", year),
+    }
+}
+
+// `notes` are printed small under the table, after their heading
+fn section(heading: &str, table: &Table, chart: bool, notes: (&str, &[String])) -> Value {
+    let (notes_heading, notes) = notes;
+    let align: Vec<&str> = table
+        .align
+        .iter()
+        .map(|a| match a {
+            Align::Left => "left",
+            Align::Right => "right",
+        })
+        .collect();
+    json!({
+        "heading": heading,
+        "chart": chart,
+        "table": { "header": table.header, "align": align, "rows": table.rows },
+        "notes_heading": notes_heading,
+        "notes": notes,
+    })
+}
+
+fn render(world: &ReportWorld) -> Result<Vec<u8>> {
+    let document: PagedDocument = typst::compile(world).output.map_err(diagnostics)?;
+    typst_pdf::pdf(&document, &typst_pdf::PdfOptions::default()).map_err(diagnostics)
+}
+
+fn diagnostics(errors: typst::ecow::EcoVec<SourceDiagnostic>) -> SuneungError {
+    let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+    SuneungError::Pdf(messages.join("; "))
+}
+
+// The template and its data files, all in memory, plus the fonts found on
+// disk
+struct ReportWorld {
+    library: LazyHash<Library>,
+    book: LazyHash<FontBook>,
+    fonts: Vec<FontSlot>,
+    main: Source,
+    files: Vec<(FileId, Bytes)>,
+}
+
+impl ReportWorld {
+    fn new(files: Vec<(&str, Bytes)>, font_dirs: &[std::path::PathBuf]) -> Result<Self> {
+        let fonts = FontSearcher::new().search_with(font_dirs);
+        if fonts
+            .book
+            .select_fallback(None, FontVariant::default(), "가")
+            .is_none()
+        {
+            return Err(SuneungError::Pdf(
+                "no font with Hangul found; install Noto Sans KR or give a font directory"
+                    .to_string(),
+            ));
+        }
+        let id = |path: &str| FileId::new(None, VirtualPath::new(path));
+        Ok(Self {
+            library: LazyHash::new(Library::default()),
+            book: LazyHash::new(fonts.book),
+            fonts: fonts.fonts,
+            main: Source::new(id("main.typ"), TEMPLATE.to_string()),
+            files: files
+                .into_iter()
+                .map(|(path, bytes)| (id(path), bytes))
+                .collect(),
+        })
+    }
+}
+
+impl World for ReportWorld {
+    fn library(&self) -> &LazyHash<Library> {
+        &self.library
+    }
+
+    fn book(&self) -> &LazyHash<FontBook> {
+        &self.book
+    }
+
+    fn main(&self) -> FileId {
+        self.main.id()
+    }
+
+    fn source(&self, id: FileId) -> FileResult<Source> {
+        if id == self.main.id() {
+            Ok(self.main.clone())
+        } else {
+            Err(not_found(id))
+        }
+    }
+
+    fn file(&self, id: FileId) -> FileResult<Bytes> {
+        self.files
+            .iter()
+            .find(|(file, _)| *file == id)
+            .map(|(_, bytes)| bytes.clone())
+            .ok_or_else(|| not_found(id))
+    }
+
+    fn font(&self, index: usize) -> Option<Font> {
+        self.fonts.get(index)?.get()
+    }
+
+    // The template prints no dates
+    fn today(&self, _: Option<i64>) -> Option<Datetime> {
+        None
+    }
+}
+
+fn not_found(id: FileId) -> FileError {
+    FileError::NotFound(id.vpath().as_rootless_path().to_path_buf())
+}
@@ -0,0 +1,48 @@
+// Student report for `report::pdf`. Everything comes from `data.json`;
+// `chart.svg` exists when `data.chart` is true.
+
+#let data = json("data.json")
+
+#set document(title: data.title)
+#set page(
+  paper: "a4",
+  margin: (x: 2cm, y: 2.2cm),
+  footer: context align(center, text(8pt, fill: luma(110), counter(page).display("1 / 1", both: true))),
+)
+#set text(
+  font: ("Noto Sans KR", "Noto Sans CJK KR", "Apple SD Gothic Neo", "Malgun Gothic", "NanumGothic"),
+  size: 10pt,
+  lang: data.lang,
+)
+#show heading.where(level: 2): it => block(above: 1.6em, below: 0.8em, text(12pt, weight: "bold", it.body))
+
+#let report-table(t) = table(
+  columns: t.align.len(),
+  align: t.align.map(a => if a == "right" { right } else { left }),
+  stroke: none,
+  inset: (x: 6pt, y: 4pt),
+  table.hline(stroke: 1pt),
+  table.header(..t.header.map(h => strong(h))),
+  table.hline(stroke: 0.5pt),
+  ..t.rows.flatten(),
+  table.hline(stroke: 1pt),
+)
+
+#block(width: 100%, inset: (bottom: 10pt), stroke: (bottom: 1.5pt))[
+  #text(20pt, weight: "bold", data.title)
+  #if data.exam != none [
+    \ #text(12pt, data.exam)
+  ]
+  \ #text(9pt, fill: luma(90), data.basis)
+]
+
+#for s in data.sections [
+  == #s.heading
+  #if s.chart [
+    #figure(image("chart.svg", width: 100%), caption: data.caption, numbering: none)
+  ]
+  #report-table(s.table)
+  #if s.notes.len() > 0 [
+    #text(8pt, fill: luma(90))[#s.notes_heading: #s.notes.join("; ")]
+  ]
+]
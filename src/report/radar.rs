@@ -0,0 +1,144 @@
+//! Per-subject values on a common 0-100 scale, for radar charts drawn by
+//! front-end chart libraries
+
+use crate::display::label;
+use crate::score::{Grade, Locale, Record, Subject};
+use serde::Serialize;
+use std::collections::HashMap;
+#[cfg(feature = "csv")]
+use std::error::Error;
+
+/// How standard scores are put on the 0-100 scale. English, which only has
+/// a grade, always maps 1등급 to 100 and 9등급 to 0 in equal steps.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RadarNorm {
+    /// The percentile as it is
+    Percentile,
+    /// Percentage of the year's 표준점수 최고점 of each subject, as
+    /// published by 평가원. Subjects missing from the map are left out.
+    FractionOfMax(HashMap<Subject, f64>),
+}
+
+/// `English` from its grade: 100 for 1등급 down to 0 for 9등급
+pub fn english_scale(grade: Grade) -> f64 {
+    let steps = (Grade::MAX.get() - Grade::MIN.get()) as f64;
+    (Grade::MAX.get() - grade.get()) as f64 / steps * 100f64
+}
+
+fn value(record: &Record, subject: Subject, norm: &RadarNorm) -> Option<f64> {
+    let score = record.try_score(subject)?;
+    if subject == Subject::English {
+        return Some(english_scale(score.rank()));
+    }
+    match norm {
+        RadarNorm::Percentile => Some(score.percentile()),
+        RadarNorm::FractionOfMax(max) => Some(score.standard_score() / max.get(&subject)? * 100f64),
+    }
+}
+
+/// `(axis label, value)` of every subject `record` has, in the order of
+/// `Subject::all`.
+///
+/// ```
+/// use suneung_calc::report::{radar_data, RadarNorm};
+/// use suneung_calc::score::{Grade, Percentile, Record, Score, StandardScore, Subject};
+///
+/// let mut record = Record::new("홍길동");
+/// record.record_typed(
+///     Subject::Korean,
+///     StandardScore::new(131f64).unwrap(),
+///     Percentile::new(93f64).unwrap(),
+///     Grade::try_from(2u8).unwrap(),
+/// );
+/// record.record_score(Subject::English, Score::grade_only(Grade::try_from(3u8).unwrap()));
+///
+/// let data = radar_data(&record, &RadarNorm::Percentile);
+/// assert_eq!(data, vec![("국어".to_string(), 93.0), ("영어".to_string(), 75.0)]);
+/// ```
+pub fn radar_data(record: &Record, norm: &RadarNorm) -> Vec<(String, f64)> {
+    Subject::all()
+        .iter()
+        .filter_map(|&s| Some((label(s).to_string(), value(record, s, norm)?)))
+        .collect()
+}
+
+/// Radar values of several records on shared axes, e.g. two exams of one
+/// student overlaid
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RadarSeries {
+    /// Subjects any of the records has a value for, in the order of
+    /// `Subject::all`
+    pub axes: Vec<String>,
+    pub series: Vec<RadarLine>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RadarLine {
+    /// Record name, with the exam when it has one
+    pub name: String,
+    /// One per axis; `None` where the record lacks the subject
+    pub values: Vec<Option<f64>>,
+}
+
+/// `radar_data` of every record on the union of their axes
+pub fn radar_series(records: &[Record], norm: &RadarNorm) -> RadarSeries {
+    radar_series_localized(records, norm, Locale::Korean)
+}
+
+/// `radar_series` with the axes and exams named in `locale`
+///
+/// ```
+/// use suneung_calc::report::{radar_series_localized, RadarNorm};
+/// use suneung_calc::score::{Locale, Record};
+///
+/// let series = radar_series_localized(&[Record::sample()], &RadarNorm::Percentile, Locale::English);
+/// assert_eq!(series.axes[..2], ["Korean", "Math"]);
+/// ```
+pub fn radar_series_localized(records: &[Record], norm: &RadarNorm, locale: Locale) -> RadarSeries {
+    let subjects: Vec<Subject> = Subject::all()
+        .iter()
+        .copied()
+        .filter(|&s| records.iter().any(|r| value(r, s, norm).is_some()))
+        .collect();
+    RadarSeries {
+        axes: subjects
+            .iter()
+            .map(|&s| s.localized_name(locale).to_string())
+            .collect(),
+        series: records
+            .iter()
+            .map(|r| RadarLine {
+                name: match r.exam() {
+                    Some(exam) => format!("{} ({})", r.name(), exam.id().localized_label(locale)),
+                    None => r.name().to_string(),
+                },
+                values: subjects.iter().map(|&s| value(r, s, norm)).collect(),
+            })
+            .collect(),
+    }
+}
+
+impl RadarSeries {
+    /// One row per axis: `axis, <series name>...`, blank where a series has
+    /// no value
+    #[cfg(feature = "csv")]
+    pub fn write_csv<W: std::io::Write>(&self, writer: W) -> Result<(), Box<dyn Error>> {
+        let mut wtr = csv::Writer::from_writer(writer);
+        let mut header = vec!["axis".to_string()];
+        header.extend(self.series.iter().map(|s| s.name.clone()));
+        wtr.write_record(&header)?;
+        for (i, axis) in self.axes.iter().enumerate() {
+            let mut row = vec![axis.clone()];
+            row.extend(
+                self.series
+                    .iter()
+                    .map(|s| s.values[i].map_or(String::new(), |v| format!("{:.2}", v))),
+            );
+            wtr.write_record(&row)?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,206 @@
+//! One-page overview of a cohort, e.g. of everyone who sat one mock exam
+
+use crate::cohort::Cohort;
+use crate::exam::ExamMeta;
+use crate::score::{Grade, Subject, University};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// What goes into a cohort summary
+#[derive(Debug, Clone)]
+pub struct SummaryOptions {
+    /// Universities with a top list; the first three with data for the
+    /// year when empty
+    pub universities: Vec<University>,
+    /// Length of each top list
+    pub top: usize,
+    /// Previous cut lines; a university gets a cleared count when it has
+    /// one here
+    pub cuts: HashMap<University, f64>,
+}
+
+impl Default for SummaryOptions {
+    fn default() -> Self {
+        Self {
+            universities: vec![],
+            top: 10,
+            cuts: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CohortSummary {
+    pub year: usize,
+    /// Exam every record comes from, if they all share one
+    pub exam: Option<ExamMeta>,
+    pub students: usize,
+    /// Subjects at least one student has, in the order of `Subject::all`
+    pub subjects: Vec<SubjectSummary>,
+    pub top: Vec<TopList>,
+    /// In the order of `University::all`
+    pub cuts: Vec<CutCount>,
+}
+
+/// Statistics of the standard score of one subject (of the grade for
+/// English) and how many students got each grade
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SubjectSummary {
+    pub subject: Subject,
+    pub mean: f64,
+    /// Sample standard deviation, 0 for a single student
+    pub std: f64,
+    pub max: f64,
+    pub n: usize,
+    /// `grades[i]`: students with grade `i + 1`
+    pub grades: [usize; 9],
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TopList {
+    pub university: University,
+    /// Best first, with competition places (1, 2, 2, 4)
+    pub entries: Vec<TopEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TopEntry {
+    pub place: usize,
+    pub name: String,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CutCount {
+    pub university: University,
+    pub cut: f64,
+    /// Students whose converted score is at or above the cut
+    pub cleared: usize,
+    /// Students that could be scored at the university
+    pub scored: usize,
+}
+
+/// Statistics, grade counts, top lists and cleared cuts of `cohort` for
+/// `year`
+///
+/// ```
+/// use suneung_calc::cohort::Cohort;
+/// use suneung_calc::report::{cohort_summary, SummaryOptions};
+/// use suneung_calc::score::{Grade, Percentile, Record, StandardScore, Subject};
+///
+/// let records: Vec<Record> = [(131f64, 2u8), (125f64, 3u8), (119f64, 3u8)]
+///     .iter()
+///     .enumerate()
+///     .map(|(i, &(standard, grade))| {
+///         let mut record = Record::new(&format!("학생{}", i + 1));
+///         record.record_typed(
+///             Subject::Korean,
+///             StandardScore::new(standard).unwrap(),
+///             Percentile::new(90f64).unwrap(),
+///             Grade::try_from(grade).unwrap(),
+///         );
+///         record
+///     })
+///     .collect();
+///
+/// let summary = cohort_summary(&Cohort::new(records), 2024, &SummaryOptions::default());
+/// let korean = &summary.subjects[0];
+/// assert_eq!((korean.mean, korean.max, korean.n), (125.0, 131.0, 3));
+/// assert_eq!(korean.grades[..3], [0, 1, 2]);
+/// ```
+pub fn cohort_summary(cohort: &Cohort, year: usize, opts: &SummaryOptions) -> CohortSummary {
+    let records = cohort.records();
+    let exam = records
+        .first()
+        .and_then(|r| r.exam())
+        .filter(|&exam| records.iter().all(|r| r.exam() == Some(exam)))
+        .cloned();
+
+    let subjects = Subject::all()
+        .iter()
+        .filter_map(|&subject| {
+            let stats = cohort.stats(subject)?;
+            let mut grades = [0; 9];
+            for record in records {
+                if let Some(score) = record.try_score(subject) {
+                    grades[(score.rank().get() - Grade::MIN.get()) as usize] += 1;
+                }
+            }
+            Some(SubjectSummary {
+                subject,
+                mean: stats.mean,
+                std: stats.std,
+                max: stats.max,
+                n: stats.n,
+                grades,
+            })
+        })
+        .collect();
+
+    let scores = |university: University| -> Vec<(&str, f64)> {
+        records
+            .iter()
+            .filter_map(|r| Some((r.name(), r.try_calc_with_university(university, year).ok()?)))
+            .collect()
+    };
+
+    let universities = if opts.universities.is_empty() {
+        University::supported(year).into_iter().take(3).collect()
+    } else {
+        opts.universities.clone()
+    };
+    let top = universities
+        .iter()
+        .map(|&university| {
+            let mut scored = scores(university);
+            // Stable, so ties keep the order of the cohort
+            scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+            let mut entries: Vec<TopEntry> = vec![];
+            for (i, &(name, score)) in scored.iter().enumerate() {
+                let place = match entries.last() {
+                    Some(last) if last.score == score => last.place,
+                    _ => i + 1,
+                };
+                if place > opts.top {
+                    break;
+                }
+                entries.push(TopEntry {
+                    place,
+                    name: name.to_string(),
+                    score,
+                });
+            }
+            TopList {
+                university,
+                entries,
+            }
+        })
+        .collect();
+
+    let cuts = University::iter()
+        .filter_map(|university| {
+            let cut = *opts.cuts.get(&university)?;
+            let scored = scores(university);
+            Some(CutCount {
+                university,
+                cut,
+                cleared: scored.iter().filter(|&&(_, s)| s >= cut).count(),
+                scored: scored.len(),
+            })
+        })
+        .collect();
+
+    CohortSummary {
+        year,
+        exam,
+        students: records.len(),
+        subjects,
+        top,
+        cuts,
+    }
+}
@@ -0,0 +1,794 @@
+//! Section tables shared by the Markdown and HTML reports. Cells are plain
+//! text; each renderer escapes them for its own format.
+
+use super::summary::{CohortSummary, TopList};
+use super::{Matrix, ReportOptions};
+use crate::api::{ComparisonRow, Eligibility};
+use crate::cohort::{
+    Cohort, CorrelationMatrix, GradeCutEstimate, RankTable, GRADE_PERCENTILES, MIN_TOP_GRADE,
+};
+use crate::display::{fmt_number, grade_label, source_label};
+use crate::plan::PlanEntry;
+use crate::score::{Grade, Locale, Record, Score, Subject, University, UniversityWeight};
+use crate::targets::ProgressRow;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(super) enum Align {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Table {
+    pub header: Vec<String>,
+    pub align: Vec<Align>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// A heading in Korean and English, see `heading`
+pub(super) type Heading = (&'static str, &'static str);
+
+pub(super) const SCORES: Heading = ("성적", "Scores");
+pub(super) const SCORE_SOURCES: Heading = ("성적 출처", "Score sources");
+pub(super) const WEIGHT_SOURCES: Heading = ("반영 비율 출처", "Weight sources");
+pub(super) const ENGLISH_SWEEP: Heading =
+    ("영어 등급별 환산 점수", "Converted scores by English grade");
+pub(super) const PROGRESS: Heading = ("목표 대비 추이", "Progress towards targets");
+pub(super) const PLAN: Heading = ("과목별 향상 효율", "Gain per subject");
+pub(super) const MATRIX: Heading = ("학생별 환산 점수", "Converted scores by student");
+pub(super) const STATISTICS: Heading = ("과목별 통계", "Statistics by subject");
+pub(super) const CORRELATION: Heading = (
+    "과목 간 상관계수 (표준점수)",
+    "Correlation between subjects (standard scores)",
+);
+pub(super) const GRADE_CUTS: Heading = (
+    "추정 등급컷 (표준점수)",
+    "Estimated grade cuts (standard scores)",
+);
+pub(super) const RANKING: Heading = ("대학별 석차", "Places by university");
+pub(super) const GRADES: Heading = ("등급 분포", "Grade distribution");
+pub(super) const CUTS: Heading = ("전년도 컷 통과 인원", "Students clearing last year's cut");
+pub(super) const CALCULATOR: Heading = ("수능 환산 점수 계산기", "CSAT converted score calculator");
+
+/// Columns of a `scores` table, also the fields of each subject of the
+/// calculator form
+pub(super) const SCORE_COLUMNS: [Heading; 4] = [
+    ("과목", "Subject"),
+    ("표준점수", "Standard score"),
+    ("백분위", "Percentile"),
+    ("등급", "Grade"),
+];
+
+pub(super) fn heading(heading: Heading, locale: Locale) -> &'static str {
+    locale.pick(heading.0, heading.1)
+}
+
+/// `3명`, or `3 students` in English
+pub(super) fn students(count: usize, locale: Locale) -> String {
+    match (locale, count) {
+        (Locale::Korean, n) => format!("{}명", n),
+        (Locale::English, 1) => "1 student".to_string(),
+        (Locale::English, n) => format!("{} students", n),
+    }
+}
+
+/// `2024학년도 대학별 환산 점수`, the heading of a `comparison` table
+pub(super) fn comparison_title(year: usize, locale: Locale) -> String {
+    match locale {
+        Locale::Korean => format!("{}학년도 대학별 환산 점수", year),
+        Locale::English => format!("Converted scores for {} admission", year),
+    }
+}
+
+/// `2024학년도 환산 점수 (30명)`, the title of a cohort report
+pub(super) fn cohort_title(year: usize, students: usize, locale: Locale) -> String {
+    let students = self::students(students, locale);
+    match locale {
+        Locale::Korean => format!("{}학년도 환산 점수 ({})", year, students),
+        Locale::English => format!("Converted scores for {} admission ({})", year, students),
+    }
+}
+
+/// `대학 상위권`, the heading of a `top_list` table
+pub(super) fn top_list_title(list: &TopList, locale: Locale) -> String {
+    let university = list.university.localized_name(locale);
+    match locale {
+        Locale::Korean => format!("{} 상위권", university),
+        Locale::English => format!("{}: top students", university),
+    }
+}
+
+/// The `n` best ranked universities of a comparison
+pub(super) fn top_universities(rows: &[ComparisonRow], n: usize) -> Vec<University> {
+    rows.iter()
+        .filter(|r| r.rank.is_some())
+        .take(n)
+        .map(|r| r.university)
+        .collect()
+}
+
+/// Label for a weight without a recorded `Provenance`
+pub(super) const NO_PROVENANCE: &str = "출처 미기재";
+
+// Header cells in `locale`, each given in Korean and English
+fn localized(locale: Locale, cells: &[(&str, &str)]) -> Vec<String> {
+    cells
+        .iter()
+        .map(|&(korean, english)| locale.pick(korean, english).to_string())
+        .collect()
+}
+
+/// One footnote per university with data for `year`, naming the source of
+/// its weight, e.g. `서강대: 2024학년도 정시 모집요강`
+pub(super) fn sources(universities: &[University], year: usize, locale: Locale) -> Vec<String> {
+    universities
+        .iter()
+        .filter_map(|&univ| {
+            let weight = UniversityWeight::load_cached(univ, year)?;
+            let source = weight.provenance().map_or(
+                locale.pick(NO_PROVENANCE, "source not given").to_string(),
+                |p| p.to_string(),
+            );
+            Some(format!("{}: {}", univ.localized_name(locale), source))
+        })
+        .collect()
+}
+
+pub(super) fn matrix(matrix: &Matrix, locale: Locale) -> Table {
+    let number =
+        |x: Option<f64>, precision| x.map_or("-".to_string(), |x| fmt_number(x, precision));
+    let mut header = localized(locale, &[("이름", "Name")]);
+    header.extend(
+        matrix
+            .universities
+            .iter()
+            .map(|u| u.localized_name(locale).to_string()),
+    );
+    header.extend(localized(
+        locale,
+        &[("표점합", "Standard sum"), ("백분위합", "Percentile sum")],
+    ));
+    let mut align = vec![Align::Left];
+    align.extend(matrix.universities.iter().map(|_| Align::Right));
+    align.extend([Align::Right; 2]);
+    let mut rows = matrix
+        .rows
+        .iter()
+        .map(|row| {
+            let mut cells = vec![row.name.clone()];
+            cells.extend(row.cells.iter().map(|c| number(c.score(), Some(2))));
+            cells.extend([
+                number(row.standard_sum, None),
+                number(row.percentile_sum, None),
+            ]);
+            cells
+        })
+        .collect::<Vec<_>>();
+    let footers = [
+        (locale.pick("평균", "Mean"), &matrix.mean),
+        (locale.pick("최고", "Max"), &matrix.max),
+    ];
+    for (label, footer) in footers {
+        let mut cells = vec![label.to_string()];
+        cells.extend(footer.scores.iter().map(|&x| number(x, Some(2))));
+        cells.extend([
+            number(footer.standard_sum, Some(1)),
+            number(footer.percentile_sum, Some(1)),
+        ]);
+        rows.push(cells);
+    }
+    Table {
+        header,
+        align,
+        rows,
+    }
+}
+
+pub(super) fn statistics(cohort: &Cohort, locale: Locale) -> Table {
+    let header = localized(
+        locale,
+        &[
+            ("과목", "Subject"),
+            ("평균", "Mean"),
+            ("표준편차", "Std. dev."),
+            ("중앙값", "Median"),
+            ("최저", "Min"),
+            ("최고", "Max"),
+            ("인원", "Students"),
+        ],
+    );
+    let mut align = vec![Align::Left];
+    align.extend([Align::Right; 6]);
+    let rows = Subject::all()
+        .iter()
+        .filter_map(|&subject| {
+            let stats = cohort.stats(subject)?;
+            Some(vec![
+                subject_name(subject, locale),
+                fmt_number(stats.mean, Some(1)),
+                fmt_number(stats.std, Some(1)),
+                fmt_number(stats.median, Some(1)),
+                fmt_number(stats.min, None),
+                fmt_number(stats.max, None),
+                stats.n.to_string(),
+            ])
+        })
+        .collect::<Vec<_>>();
+    Table {
+        header,
+        align,
+        rows,
+    }
+}
+
+// Pearson r of each pair of subjects with the number of students behind it,
+// `-` where it cannot be computed
+pub(super) fn correlation(matrix: &CorrelationMatrix, locale: Locale) -> Table {
+    let mut header = localized(locale, &[("과목", "Subject")]);
+    header.extend(matrix.subjects.iter().map(|&s| subject_name(s, locale)));
+    let mut align = vec![Align::Left];
+    align.extend(matrix.subjects.iter().map(|_| Align::Right));
+    let rows = matrix
+        .subjects
+        .iter()
+        .zip(&matrix.cells)
+        .map(|(&subject, cells)| {
+            let mut row = vec![subject_name(subject, locale)];
+            row.extend(cells.iter().map(|c| {
+                c.map_or("-".to_string(), |c| {
+                    format!("{} (n={})", fmt_number(c.r, Some(2)), c.n)
+                })
+            }));
+            row
+        })
+        .collect();
+    Table {
+        header,
+        align,
+        rows,
+    }
+}
+
+/// Estimated 등급컷 of 1등급 to 8등급, one column per subject in
+/// `estimates`
+pub(super) fn grade_cuts(estimates: &[(Subject, GradeCutEstimate)], locale: Locale) -> Table {
+    let mut header = localized(locale, &[("등급", "Grade")]);
+    header.extend(
+        estimates
+            .iter()
+            .map(|(s, e)| format!("{} (n={})", subject_name(*s, locale), e.n)),
+    );
+    let mut align = vec![Align::Left];
+    align.extend(estimates.iter().map(|_| Align::Right));
+    let rows = (0..GRADE_PERCENTILES.len())
+        .map(|k| {
+            let mut row = vec![grade_label(k + 1, locale)];
+            row.extend(estimates.iter().map(|(_, e)| {
+                e.cuts
+                    .get(k)
+                    .map_or("-".to_string(), |&(_, cut)| fmt_number(cut, None))
+            }));
+            row
+        })
+        .collect();
+    Table {
+        header,
+        align,
+        rows,
+    }
+}
+
+/// `표본이 작아 상위 등급컷이 불안정함: 화학Ⅰ` under a `grade_cuts` table,
+/// `None` when every subject has enough students in 1등급
+pub(super) fn grade_cuts_note(
+    estimates: &[(Subject, GradeCutEstimate)],
+    locale: Locale,
+) -> Option<String> {
+    let small: Vec<String> = estimates
+        .iter()
+        .filter(|(_, e)| e.small_sample)
+        .map(|(s, _)| subject_name(*s, locale))
+        .collect();
+    (!small.is_empty()).then(|| match locale {
+        Locale::Korean => format!(
+            "표본이 작아 상위 등급컷이 불안정함 (1등급 {}명 미만): {}",
+            MIN_TOP_GRADE,
+            small.join(", ")
+        ),
+        Locale::English => format!(
+            "Top cuts are unstable on small samples (under {} students in grade 1): {}",
+            MIN_TOP_GRADE,
+            small.join(", ")
+        ),
+    })
+}
+
+// Competition ranking (1, 2, 2, 4) of the students at each university,
+// ordered by the mean place over the universities they can be scored at
+pub(super) fn ranking(matrix: &Matrix, locale: Locale) -> Table {
+    let scores: Vec<Vec<Option<f64>>> = matrix
+        .rows
+        .iter()
+        .map(|row| row.cells.iter().map(|c| c.score()).collect())
+        .collect();
+    let places: Vec<Vec<Option<usize>>> = scores
+        .iter()
+        .map(|row| {
+            row.iter()
+                .enumerate()
+                .map(|(j, score)| {
+                    let score = (*score)?;
+                    let better = scores
+                        .iter()
+                        .filter(|other| other[j].is_some_and(|s| s > score))
+                        .count();
+                    Some(better + 1)
+                })
+                .collect()
+        })
+        .collect();
+    let mean_place = |row: &[Option<usize>]| {
+        let placed: Vec<usize> = row.iter().flatten().copied().collect();
+        (!placed.is_empty()).then(|| placed.iter().sum::<usize>() as f64 / placed.len() as f64)
+    };
+
+    let mut order: Vec<usize> = (0..matrix.rows.len()).collect();
+    // Students without any score go last
+    order.sort_by(|&a, &b| {
+        let (a, b) = (mean_place(&places[a]), mean_place(&places[b]));
+        a.unwrap_or(f64::INFINITY)
+            .total_cmp(&b.unwrap_or(f64::INFINITY))
+    });
+
+    let mut header = localized(locale, &[("이름", "Name"), ("평균 석차", "Mean place")]);
+    header.extend(
+        matrix
+            .universities
+            .iter()
+            .map(|u| u.localized_name(locale).to_string()),
+    );
+    let mut align = vec![Align::Left, Align::Right];
+    align.extend(matrix.universities.iter().map(|_| Align::Right));
+    let rows = order
+        .into_iter()
+        .map(|i| {
+            let mut row = vec![
+                matrix.rows[i].name.clone(),
+                mean_place(&places[i]).map_or("-".to_string(), |p| fmt_number(p, Some(1))),
+            ];
+            row.extend(
+                places[i]
+                    .iter()
+                    .map(|p| p.map_or("-".to_string(), |p| p.to_string())),
+            );
+            row
+        })
+        .collect::<Vec<_>>();
+    Table {
+        header,
+        align,
+        rows,
+    }
+}
+
+/// Scores of `record`, each subject marked with the exam it came from as
+/// listed by `display::source_notes`
+pub(super) fn scores(record: &Record, locale: Locale) -> Table {
+    let header = localized(locale, &SCORE_COLUMNS);
+    let rows = record
+        .iter()
+        .map(|(subject, score)| {
+            let (standard, percentile) = match subject {
+                Subject::English => ("-".to_string(), "-".to_string()),
+                _ => (
+                    fmt_number(score.standard_score(), None),
+                    score.fmt_percentile(locale),
+                ),
+            };
+            vec![
+                source_label(record, subject, score, locale),
+                standard,
+                percentile,
+                score.localized_grade_label(locale),
+            ]
+        })
+        .collect::<Vec<_>>();
+    Table {
+        header,
+        align: vec![Align::Left, Align::Right, Align::Right, Align::Right],
+        rows,
+    }
+}
+
+pub(super) fn comparison(rows: &[ComparisonRow], opts: &ReportOptions) -> Table {
+    let with_cuts = !opts.cuts.is_empty();
+    let locale = opts.locale;
+    let mut header = localized(
+        locale,
+        &[
+            ("순위", "Rank"),
+            ("대학", "University"),
+            ("환산 점수", "Converted score"),
+            ("최고 대비(%)", "Of best (%)"),
+            ("지원", "Eligible"),
+        ],
+    );
+    let mut align = vec![
+        Align::Right,
+        Align::Left,
+        Align::Right,
+        Align::Right,
+        Align::Left,
+    ];
+    if with_cuts {
+        header.extend(localized(locale, &[("컷", "Cut"), ("컷 대비", "Over cut")]));
+        align.extend([Align::Right, Align::Right]);
+    }
+
+    let body = rows
+        .iter()
+        .map(|row| {
+            let mut cells = vec![
+                row.rank.map_or("-".to_string(), |r| r.to_string()),
+                row.university.localized_name(locale).to_string(),
+                row.score
+                    .map_or("-".to_string(), |s| fmt_number(s, Some(2))),
+                row.percent_of_max
+                    .map_or("-".to_string(), |p| fmt_number(p, Some(1))),
+                match &row.eligibility {
+                    Eligibility::Eligible => locale.pick("가능", "yes").to_string(),
+                    Eligibility::Ineligible { .. } => format!(
+                        "{} ({})",
+                        locale.pick("불가", "no"),
+                        row.eligibility.reason(locale).unwrap_or_default()
+                    ),
+                    Eligibility::NoData => locale.pick("데이터 없음", "no data").to_string(),
+                },
+            ];
+            if with_cuts {
+                let cut = opts.cuts.get(&row.university).copied();
+                cells.push(cut.map_or("-".to_string(), |c| fmt_number(c, Some(2))));
+                cells.push(match (row.score, cut) {
+                    (Some(score), Some(cut)) => format!("{:+.2}", score - cut),
+                    _ => "-".to_string(),
+                });
+            }
+            cells
+        })
+        .collect::<Vec<_>>();
+    Table {
+        header,
+        align,
+        rows: body,
+    }
+}
+
+// Converted score at each of `universities` if English were each grade
+pub(super) fn english_sweep(
+    record: &Record,
+    universities: &[University],
+    year: usize,
+    locale: Locale,
+) -> Table {
+    let mut header = localized(locale, &[("영어 등급", "English grade")]);
+    header.extend(
+        universities
+            .iter()
+            .map(|u| u.localized_name(locale).to_string()),
+    );
+    let mut align = vec![Align::Left];
+    align.extend(universities.iter().map(|_| Align::Right));
+    let current = record.try_score(Subject::English).map(|s| s.rank());
+
+    let rows = (Grade::MIN.get()..=Grade::MAX.get())
+        .map(|g| {
+            let grade = Grade::try_from(g).unwrap();
+            let mut hypothetical = record.clone();
+            hypothetical.record_score(Subject::English, Score::grade_only(grade));
+            let mark = if current == Some(grade) {
+                locale.pick(" (현재)", " (current)")
+            } else {
+                ""
+            };
+            let mut row = vec![format!("{}{}", grade_label(grade, locale), mark)];
+            row.extend(universities.iter().map(|&u| {
+                hypothetical
+                    .try_calc_with_university(u, year)
+                    .map_or("-".to_string(), |s| fmt_number(s, Some(2)))
+            }));
+            row
+        })
+        .collect::<Vec<_>>();
+    Table {
+        header,
+        align,
+        rows,
+    }
+}
+
+/// `2025 6월 모의평가 요약 (30명)`, or the 학년도 when the records come from
+/// different exams
+pub(super) fn summary_title(summary: &CohortSummary, locale: Locale) -> String {
+    let students = students(summary.students, locale);
+    let exam = summary
+        .exam
+        .as_ref()
+        .map(|e| e.id().localized_label(locale));
+    match (exam, locale) {
+        (Some(exam), Locale::Korean) => format!("{} 요약 ({})", exam, students),
+        (None, Locale::Korean) => format!("{}학년도 성적 요약 ({})", summary.year, students),
+        (Some(exam), Locale::English) => format!("{} summary ({})", exam, students),
+        (None, Locale::English) => format!("{} score summary ({})", summary.year, students),
+    }
+}
+
+pub(super) fn summary_statistics(summary: &CohortSummary, locale: Locale) -> Table {
+    let header = localized(
+        locale,
+        &[
+            ("과목", "Subject"),
+            ("평균", "Mean"),
+            ("표준편차", "Std. dev."),
+            ("최고", "Max"),
+            ("인원", "Students"),
+        ],
+    );
+    let mut align = vec![Align::Left];
+    align.extend([Align::Right; 4]);
+    let rows = summary
+        .subjects
+        .iter()
+        .map(|s| {
+            vec![
+                subject_name(s.subject, locale),
+                fmt_number(s.mean, Some(1)),
+                fmt_number(s.std, Some(1)),
+                fmt_number(s.max, None),
+                s.n.to_string(),
+            ]
+        })
+        .collect();
+    Table {
+        header,
+        align,
+        rows,
+    }
+}
+
+// Students per grade, 1등급 first
+pub(super) fn grade_distribution(summary: &CohortSummary, locale: Locale) -> Table {
+    let mut header = localized(locale, &[("과목", "Subject")]);
+    header.extend((Grade::MIN.get()..=Grade::MAX.get()).map(|g| grade_label(g, locale)));
+    let mut align = vec![Align::Left];
+    align.extend(header[1..].iter().map(|_| Align::Right));
+    let rows = summary
+        .subjects
+        .iter()
+        .map(|s| {
+            let mut cells = vec![s.subject.localized_name(locale).to_string()];
+            cells.extend(s.grades.iter().map(|n| n.to_string()));
+            cells
+        })
+        .collect();
+    Table {
+        header,
+        align,
+        rows,
+    }
+}
+
+pub(super) fn top_list(list: &TopList, locale: Locale) -> Table {
+    Table {
+        header: top_header(locale),
+        align: vec![Align::Right, Align::Left, Align::Right],
+        rows: list
+            .entries
+            .iter()
+            .map(|e| {
+                vec![
+                    e.place.to_string(),
+                    e.name.clone(),
+                    fmt_number(e.score, Some(2)),
+                ]
+            })
+            .collect(),
+    }
+}
+
+/// `targets::progress` rows: exam, university, target, converted score,
+/// gap to the target and change since the previous exam
+pub(super) fn progress(rows: &[ProgressRow], locale: Locale) -> Table {
+    let header = localized(
+        locale,
+        &[
+            ("시험", "Exam"),
+            ("대학", "University"),
+            ("목표", "Target"),
+            ("환산 점수", "Converted score"),
+            ("차이", "Gap"),
+            ("변화", "Change"),
+        ],
+    );
+    let mut align = vec![Align::Left, Align::Left];
+    align.extend([Align::Right; 4]);
+    let number = |x: Option<f64>| x.map_or("-".to_string(), |x| fmt_number(x, Some(2)));
+    let signed = |x: Option<f64>| x.map_or("-".to_string(), |x| format!("{:+.2}", x));
+    let rows = rows
+        .iter()
+        .map(|r| {
+            let change = match (r.trend, r.change) {
+                (Some(trend), Some(change)) => {
+                    format!("{} {}", trend.symbol(), signed(Some(change)))
+                }
+                _ => "-".to_string(),
+            };
+            vec![
+                r.exam
+                    .map_or("-".to_string(), |e| e.localized_label(locale)),
+                format!("{} ({})", r.university.localized_name(locale), r.year),
+                fmt_number(r.target, Some(2)),
+                number(r.score),
+                signed(r.gap),
+                change,
+            ]
+        })
+        .collect();
+    Table {
+        header,
+        align,
+        rows,
+    }
+}
+
+/// `Record::improvement_plan` entries: university, subject to improve,
+/// improvement, gain and gain per unit
+pub(super) fn plan(entries: &[PlanEntry], locale: Locale) -> Table {
+    let header = localized(
+        locale,
+        &[
+            ("대학", "University"),
+            ("과목", "Subject"),
+            ("향상", "Improvement"),
+            ("환산 점수 증가", "Gain"),
+            ("단위당 증가", "Gain per unit"),
+        ],
+    );
+    let mut align = vec![Align::Left, Align::Left];
+    align.extend([Align::Right; 3]);
+    let rows = entries
+        .iter()
+        .map(|e| {
+            let unit = match (e.subject, locale) {
+                (Subject::English, Locale::Korean) => "등급",
+                (Subject::English, Locale::English) => " grade",
+                (_, Locale::Korean) => "점",
+                (_, Locale::English) => " pt",
+            };
+            vec![
+                e.university.localized_name(locale).to_string(),
+                e.subject.localized_name(locale).to_string(),
+                format!("{}{}", fmt_number(e.improvement, None), unit),
+                format!("+{}", fmt_number(e.gain, Some(2))),
+                fmt_number(e.leverage, Some(2)),
+            ]
+        })
+        .collect();
+    Table {
+        header,
+        align,
+        rows,
+    }
+}
+
+/// The first `n` places of `ranked`
+pub(super) fn rank_top(ranked: &RankTable, n: usize, locale: Locale) -> Table {
+    Table {
+        header: top_header(locale),
+        align: vec![Align::Right, Align::Left, Align::Right],
+        rows: ranked
+            .top(n)
+            .iter()
+            .map(|e| {
+                vec![
+                    e.rank.map_or("-".to_string(), |r| r.to_string()),
+                    e.name.clone(),
+                    e.score.map_or("-".to_string(), |s| fmt_number(s, Some(2))),
+                ]
+            })
+            .collect(),
+    }
+}
+
+/// `대학 상위 10명`, the heading of a `rank_top` table
+pub(super) fn rank_top_title(ranked: &RankTable, n: usize, locale: Locale) -> String {
+    let university = ranked.university.localized_name(locale);
+    match locale {
+        Locale::Korean => format!("{} 상위 {}명", university, n),
+        Locale::English => format!("{}: top {}", university, n),
+    }
+}
+
+/// `UniversityWeight::describe` of the university of a `rank_top` table,
+/// `None` without weight data for `year`
+pub(super) fn rank_formula(ranked: &RankTable, year: usize) -> Option<String> {
+    let weight = UniversityWeight::load_cached(ranked.university, year)?;
+    Some(weight.describe().to_string())
+}
+
+/// `환산 불가 2명 (missing_subject)` under a `rank_top` table, `None` when
+/// every student could be scored
+pub(super) fn rank_ineligible(ranked: &RankTable, locale: Locale) -> Option<String> {
+    let ineligible = ranked.ineligible();
+    if ineligible.is_empty() {
+        return None;
+    }
+    let mut reasons: Vec<&str> = ineligible.iter().filter_map(|e| e.reason).collect();
+    reasons.sort_unstable();
+    reasons.dedup();
+    let reasons = reasons.join(", ");
+    Some(match locale {
+        Locale::Korean => format!("환산 불가 {}명 ({})", ineligible.len(), reasons),
+        Locale::English => format!(
+            "{} not scored ({})",
+            students(ineligible.len(), locale),
+            reasons
+        ),
+    })
+}
+
+pub(super) fn cut_counts(summary: &CohortSummary, locale: Locale) -> Table {
+    let header = localized(
+        locale,
+        &[
+            ("대학", "University"),
+            ("컷", "Cut"),
+            ("통과", "Cleared"),
+            ("환산 인원", "Scored"),
+            ("통과 비율(%)", "Cleared (%)"),
+        ],
+    );
+    let mut align = vec![Align::Left];
+    align.extend([Align::Right; 4]);
+    let rows = summary
+        .cuts
+        .iter()
+        .map(|c| {
+            vec![
+                c.university.localized_name(locale).to_string(),
+                fmt_number(c.cut, Some(2)),
+                c.cleared.to_string(),
+                c.scored.to_string(),
+                match c.scored {
+                    0 => "-".to_string(),
+                    n => fmt_number(c.cleared as f64 / n as f64 * 100f64, Some(1)),
+                },
+            ]
+        })
+        .collect();
+    Table {
+        header,
+        align,
+        rows,
+    }
+}
+
+// Header of `top_list` and `rank_top`
+fn top_header(locale: Locale) -> Vec<String> {
+    localized(
+        locale,
+        &[
+            ("순위", "Rank"),
+            ("이름", "Name"),
+            ("환산 점수", "Converted score"),
+        ],
+    )
+}
+
+// English statistics are over grades, not standard scores
+fn subject_name(subject: Subject, locale: Locale) -> String {
+    let name = subject.localized_name(locale);
+    match subject {
+        Subject::English => format!("{} ({})", name, locale.pick("등급", "grade")),
+        _ => name.to_string(),
+    }
+}
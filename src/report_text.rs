@@ -0,0 +1,222 @@
+use crate::convert::{KoreanTrack, MathTrack, Track};
+use crate::error::{LineError, Result, SuneungError};
+use crate::input;
+use crate::score::{Grade, Percentile, Record, Score, StandardScore, Subject};
+
+// Subject as it appears on the 성적통지표
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Entry {
+    Subject(Subject),
+    // 한국사 is recognized so pasted reports parse, but it is not stored
+    KoreanHistory,
+}
+
+const ELECTIVES: [(&str, Track); 9] = [
+    ("화법과작문", Track::Korean(KoreanTrack::SpeechWriting)),
+    ("화작", Track::Korean(KoreanTrack::SpeechWriting)),
+    ("언어와매체", Track::Korean(KoreanTrack::LanguageMedia)),
+    ("언매", Track::Korean(KoreanTrack::LanguageMedia)),
+    ("확률과통계", Track::Math(MathTrack::Statistics)),
+    ("확통", Track::Math(MathTrack::Statistics)),
+    ("미적분", Track::Math(MathTrack::Calculus)),
+    ("미적", Track::Math(MathTrack::Calculus)),
+    ("기하", Track::Math(MathTrack::Geometry)),
+];
+// Area labels that may precede the 탐구 subject name
+const INQUIRY_LABELS: [&str; 4] = ["과학탐구", "과탐", "탐구", "과학"];
+// Characters of a segment quoted back in a `LineError`
+const QUOTE_CHARS: usize = 80;
+
+impl Record {
+    /// Parse plain text pasted from a 성적통지표, e.g.
+    /// `국어 언어와매체 131 93 2 / 수학 미적분 128 89 2 / 영어 2 / 화학Ⅰ 64 92 2`.
+    ///
+    /// Subjects are separated by newlines or `/`. Each one lists an optional
+    /// elective followed by 표준점수, 백분위 and 등급; 영어 and 한국사 only
+    /// carry the 등급. Electives are kept on the scores, see
+    /// `Record::display_subject`. Segments that neither start with a subject nor contain
+    /// a standalone number (headers, labels) are ignored, and everything else that cannot be understood is reported
+    /// per line in `SuneungError::ReportParse`.
+    ///
+    /// The text is normalized first (see [`input`](crate::input)), so
+    /// full-width digits such as `１３１` and ideographic spaces read like
+    /// their ASCII forms. Text over `input::MAX_INPUT_BYTES` and names that
+    /// fail `input::check_name` are `SuneungError::InvalidInput`.
+    pub fn parse_report_text(name: &str, text: &str) -> Result<Record> {
+        input::check_name(name)?;
+        input::check_len("score report", text)?;
+        let text = input::normalize(text);
+        let mut record = Record::new(name);
+        let mut errors = vec![];
+
+        for (i, line) in text.lines().enumerate() {
+            for segment in line.split('/') {
+                let segment = segment.trim();
+                if segment.is_empty() {
+                    continue;
+                }
+                let fail = |reason: String| LineError {
+                    line: i + 1,
+                    text: quote(segment),
+                    reason,
+                };
+
+                match parse_segment(segment) {
+                    Ok(None) => (),
+                    Ok(Some((Entry::KoreanHistory, ..))) => (),
+                    Ok(Some((Entry::Subject(subject), values, elective))) => {
+                        if record.try_score(subject).is_some() {
+                            errors.push(fail(format!("{} appears twice", subject.name())));
+                            continue;
+                        }
+                        let score = match values {
+                            Values::Grade(grade) => Score::grade_only(grade),
+                            Values::Full(standard_score, percentile, grade) => {
+                                Score::new_typed(standard_score, percentile, grade)
+                            }
+                        };
+                        record.record_score(
+                            subject,
+                            match elective {
+                                Some(elective) => score.with_elective(elective),
+                                None => score,
+                            },
+                        );
+                    }
+                    Err(reason) => errors.push(fail(reason)),
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(record)
+        } else {
+            Err(SuneungError::ReportParse(errors))
+        }
+    }
+}
+
+enum Values {
+    Grade(Grade),
+    Full(StandardScore, Percentile, Grade),
+}
+
+// Entry, numbers and elective of a segment naming a subject
+type Segment = (Entry, Values, Option<Track>);
+
+fn parse_segment(segment: &str) -> std::result::Result<Option<Segment>, String> {
+    let mut tokens = segment.split_whitespace().peekable();
+
+    while tokens.peek().is_some_and(|t| INQUIRY_LABELS.contains(t)) {
+        tokens.next();
+    }
+
+    let Some(first) = tokens.next() else {
+        return Ok(None);
+    };
+    // "국어(언어와매체)" spells the elective right after the subject, and
+    // "탐구(화학Ⅰ)" the subject after its area
+    let (first, attached) = match first.split_once('(') {
+        Some((area, subject)) if INQUIRY_LABELS.contains(&area) => {
+            (subject.trim_end_matches(')'), None)
+        }
+        Some((subject, elective)) => (subject, Some(elective)),
+        None => (first, None),
+    };
+    let Some(entry) = entry(first) else {
+        if segment
+            .split_whitespace()
+            .any(|t| strip_unit(t).parse::<f64>().is_ok())
+        {
+            return Err(format!("unknown subject {:?}", quote(first)));
+        }
+        return Ok(None);
+    };
+    let mut track = match attached {
+        Some(token) => Some(
+            elective(entry, token).ok_or_else(|| format!("unknown elective {:?}", quote(token)))?,
+        ),
+        None => None,
+    };
+
+    let mut numbers = vec![];
+    for token in tokens {
+        let number = strip_unit(token);
+        if let Ok(x) = number.parse::<f64>() {
+            numbers.push(x);
+        } else if let Some(t) = elective(entry, token).filter(|_| numbers.is_empty()) {
+            track = Some(t);
+        } else {
+            return Err(format!("unexpected {:?}", quote(token)));
+        }
+    }
+
+    let grade = |x: f64| Grade::try_from(x).map_err(|_| format!("grade must be 1-9, got {}", x));
+
+    let grade_only = matches!(
+        entry,
+        Entry::KoreanHistory | Entry::Subject(Subject::English)
+    );
+    let values = match (grade_only, numbers.as_slice()) {
+        (true, [g]) => Values::Grade(grade(*g)?),
+        (true, _) => {
+            return Err(format!(
+                "expected only a grade, got {} numbers",
+                numbers.len()
+            ))
+        }
+        (false, [s, p, g]) => {
+            let standard = StandardScore::new(*s)
+                .map_err(|_| format!("standard score out of range: {}", s))?;
+            let percentile =
+                Percentile::new(*p).map_err(|_| format!("percentile out of range: {}", p))?;
+            Values::Full(standard, percentile, grade(*g)?)
+        }
+        (false, _) => {
+            return Err(format!(
+                "expected standard score, percentile and grade, got {} numbers",
+                numbers.len()
+            ))
+        }
+    };
+
+    Ok(Some((entry, values, track)))
+}
+
+fn entry(token: &str) -> Option<Entry> {
+    let token = token.trim_end_matches(['Ⅰ', 'I', '1']);
+    let entry = match token {
+        "국어" | "국" => Entry::Subject(Subject::Korean),
+        "수학" | "수" => Entry::Subject(Subject::Math),
+        "영어" | "영" => Entry::Subject(Subject::English),
+        "한국사" | "한" => Entry::KoreanHistory,
+        "화학" | "화" => Entry::Subject(Subject::Chemistry),
+        "지구과학" | "지구" | "지" => Entry::Subject(Subject::EarthScience),
+        _ => return None,
+    };
+    Some(entry)
+}
+
+// 선택과목 of `entry` spelled by `token`
+fn elective(entry: Entry, token: &str) -> Option<Track> {
+    let token = token.trim_matches(|c| c == '(' || c == ')');
+    let Entry::Subject(subject) = entry else {
+        return None;
+    };
+    ELECTIVES
+        .iter()
+        .find(|&&(name, track)| name == token && track.subject() == subject)
+        .map(|&(_, track)| track)
+}
+
+fn strip_unit(token: &str) -> &str {
+    token.trim_end_matches(['등', '급', '점', '%'])
+}
+
+// `segment`, cut to `QUOTE_CHARS` so a garbled line does not flood the error
+fn quote(segment: &str) -> String {
+    match segment.char_indices().nth(QUOTE_CHARS) {
+        Some((end, _)) => format!("{}…", &segment[..end]),
+        None => segment.to_string(),
+    }
+}
@@ -0,0 +1,51 @@
+//! JSON Schema documents for the serde models, for clients of the JSON API
+//! that want to validate what they send and receive.
+
+use crate::api::{CalcAllRequest, CalcRequest, CalcResponse, RankedScore, UniversityInfo};
+use crate::score::{CalcBreakdown, Record};
+use schemars::{schema_for, JsonSchema};
+use serde_json::Value;
+use std::error::Error;
+use std::path::Path;
+
+fn schema<T: JsonSchema>() -> Value {
+    serde_json::to_value(schema_for!(T)).unwrap()
+}
+
+pub fn record_schema() -> Value {
+    schema::<Record>()
+}
+
+pub fn calc_request_schema() -> Value {
+    schema::<CalcRequest>()
+}
+
+pub fn calc_breakdown_schema() -> Value {
+    schema::<CalcBreakdown>()
+}
+
+/// Every schema by the file name `dump_schemas` writes it to
+pub fn all_schemas() -> Vec<(&'static str, Value)> {
+    vec![
+        ("record.schema.json", record_schema()),
+        ("calc_request.schema.json", calc_request_schema()),
+        ("calc_response.schema.json", schema::<CalcResponse>()),
+        ("calc_all_request.schema.json", schema::<CalcAllRequest>()),
+        ("calc_breakdown.schema.json", calc_breakdown_schema()),
+        (
+            "calc_all_response.schema.json",
+            schema::<Vec<RankedScore>>(),
+        ),
+        ("universities.schema.json", schema::<Vec<UniversityInfo>>()),
+    ]
+}
+
+/// Write `all_schemas` into `dir`, creating it if needed.
+pub fn dump_schemas<P: AsRef<Path>>(dir: P) -> Result<(), Box<dyn Error>> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)?;
+    for (file, schema) in all_schemas() {
+        std::fs::write(dir.join(file), serde_json::to_string_pretty(&schema)?)?;
+    }
+    Ok(())
+}
@@ -1,31 +1,256 @@
+use crate::convert::Track;
+use crate::error::{SuneungError, WeightValidationError};
+use crate::exam::{ExamId, ExamKind, ExamMeta};
+use crate::formula::{FormulaKind, ScienceAggregation};
 use crate::university_weight::*;
+use crate::weight_source::WeightSource;
 use paste::paste;
-use peroxide::fuga::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::sync::{Arc, LazyLock, OnceLock};
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Score {
-    standard_score: f64,
-    percentile: f64,
-    rank: usize,
+    // English only carries a grade, so these may be left out in JSON
+    #[serde(default)]
+    standard_score: StandardScore,
+    #[serde(default)]
+    percentile: Percentile,
+    rank: Grade,
+    // Set by `Record::fill_missing_percentiles`. Not serialized, so stored
+    // records and JSON output do not keep it.
+    #[serde(skip)]
+    percentile_estimated: bool,
+    // Exam the score was taken from, when it may differ from the record's,
+    // see `Record::best_of`
+    #[serde(default)]
+    source: Option<ExamId>,
+    // 선택과목 of 국어 or 수학 when known, see `Record::set_elective`
+    #[serde(default)]
+    elective: Option<Track>,
+}
+
+/// 등급, 1 (best) to 9. Values outside that range cannot be constructed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "usize", into = "usize")]
+pub struct Grade(u8);
+
+impl Grade {
+    pub const MIN: Grade = Grade(1);
+    pub const MAX: Grade = Grade(9);
+
+    pub fn get(&self) -> u8 {
+        self.0
+    }
+}
+
+// The serde form is a plain integer, see `#[serde(try_from)]` above
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Grade {
+    fn schema_name() -> String {
+        "Grade".to_string()
+    }
+
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::Integer.into()),
+            number: Some(Box::new(schemars::schema::NumberValidation {
+                minimum: Some(1f64),
+                maximum: Some(9f64),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+impl TryFrom<u8> for Grade {
+    type Error = SuneungError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        if (Self::MIN.0..=Self::MAX.0).contains(&value) {
+            Ok(Self(value))
+        } else {
+            Err(SuneungError::ScoreOutOfRange(format!(
+                "grade must be 1-9, got {}",
+                value
+            )))
+        }
+    }
+}
+
+impl TryFrom<usize> for Grade {
+    type Error = SuneungError;
+
+    fn try_from(value: usize) -> Result<Self, Self::Error> {
+        u8::try_from(value)
+            .map_err(|_| SuneungError::ScoreOutOfRange(format!("grade must be 1-9, got {}", value)))
+            .and_then(Grade::try_from)
+    }
+}
+
+impl TryFrom<f64> for Grade {
+    type Error = SuneungError;
+
+    /// Grades stored as floats (parquet) must be whole numbers
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        if value.fract() == 0.0 && (1.0..=9.0).contains(&value) {
+            Ok(Self(value as u8))
+        } else {
+            Err(SuneungError::ScoreOutOfRange(format!(
+                "grade must be 1-9, got {}",
+                value
+            )))
+        }
+    }
+}
+
+impl From<Grade> for usize {
+    fn from(grade: Grade) -> Self {
+        grade.0 as usize
+    }
+}
+
+impl From<Grade> for f64 {
+    fn from(grade: Grade) -> Self {
+        grade.0 as f64
+    }
+}
+
+impl std::fmt::Display for Grade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::str::FromStr for Grade {
+    type Err = SuneungError;
+
+    /// Full-width digits are accepted, see `input::normalize`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value: u8 = crate::input::normalize(s).trim().parse().map_err(|_| {
+            SuneungError::ScoreOutOfRange(format!("grade must be 1-9, got {:?}", s))
+        })?;
+        Grade::try_from(value)
+    }
 }
 
 impl Score {
+    /// Score of a 표준점수 and 백분위 validated by their own types
+    pub fn new_typed(standard_score: StandardScore, percentile: Percentile, grade: Grade) -> Self {
+        Self {
+            standard_score,
+            percentile,
+            rank: grade,
+            percentile_estimated: false,
+            source: None,
+            elective: None,
+        }
+    }
+
+    /// Score with 표준점수 0-200 and 백분위 0-100
+    #[deprecated(note = "the two numbers are easily swapped; use `Score::new_typed`")]
+    pub fn new(standard_score: f64, percentile: f64, grade: Grade) -> crate::error::Result<Self> {
+        let score = Self {
+            standard_score: StandardScore::unchecked(standard_score),
+            percentile: Percentile::unchecked(percentile),
+            rank: grade,
+            percentile_estimated: false,
+            source: None,
+            elective: None,
+        };
+        match range_problem(&score, false) {
+            Some(reason) => Err(SuneungError::ScoreOutOfRange(reason)),
+            None => Ok(score),
+        }
+    }
+
+    /// Score carrying only a grade, for absolute-graded subjects like English
+    pub fn grade_only(grade: Grade) -> Self {
+        Self {
+            standard_score: StandardScore::MIN,
+            percentile: Percentile::MIN,
+            rank: grade,
+            percentile_estimated: false,
+            source: None,
+            elective: None,
+        }
+    }
+
     pub fn standard_score(&self) -> f64 {
-        self.standard_score
+        self.standard_score.get()
     }
 
     pub fn percentile(&self) -> f64 {
+        self.percentile.get()
+    }
+
+    pub fn standard_score_typed(&self) -> StandardScore {
+        self.standard_score
+    }
+
+    pub fn percentile_typed(&self) -> Percentile {
         self.percentile
     }
 
-    pub fn rank(&self) -> usize {
+    pub fn rank(&self) -> Grade {
         self.rank
     }
+
+    /// Whether the 백분위 is an estimate (see
+    /// `Record::fill_missing_percentiles`) rather than a published one
+    pub fn percentile_estimated(&self) -> bool {
+        self.percentile_estimated
+    }
+
+    /// This score with `percentile` as an estimated 백분위
+    pub fn with_estimated_percentile(self, percentile: f64) -> Self {
+        Self {
+            percentile: Percentile::unchecked(percentile),
+            percentile_estimated: true,
+            ..self
+        }
+    }
+
+    /// Exam the score was taken from, if recorded with one; see
+    /// `Record::record_with_source`
+    pub fn source(&self) -> Option<ExamId> {
+        self.source
+    }
+
+    /// This score as taken from `source`
+    pub fn with_source(self, source: ExamId) -> Self {
+        Self {
+            source: Some(source),
+            ..self
+        }
+    }
+
+    /// 선택과목 the score was taken in, if known; see `Record::set_elective`
+    pub fn elective(&self) -> Option<Track> {
+        self.elective
+    }
+
+    /// This score as taken in `elective`
+    pub fn with_elective(self, elective: Track) -> Self {
+        Self {
+            elective: Some(elective),
+            ..self
+        }
+    }
+
+    #[deprecated(note = "`rank()` returns a `Grade`; use `usize::from(score.rank())`")]
+    pub fn rank_usize(&self) -> usize {
+        self.rank.into()
+    }
 }
 
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Subject {
     Korean,
     Math,
@@ -35,6 +260,17 @@ pub enum Subject {
 }
 
 impl Subject {
+    /// Every subject in the canonical order used for storage and display
+    pub fn all() -> &'static [Subject] {
+        &[
+            Subject::Korean,
+            Subject::Math,
+            Subject::English,
+            Subject::Chemistry,
+            Subject::EarthScience,
+        ]
+    }
+
     pub fn name(&self) -> &'static str {
         match self {
             Subject::Korean => "Korean",
@@ -44,37 +280,284 @@ impl Subject {
             Subject::EarthScience => "EarthScience",
         }
     }
+
+    /// 과목명 as the 성적통지표 prints it, `localized_name` in Korean
+    pub fn korean_name(&self) -> &'static str {
+        self.localized_name(Locale::Korean)
+    }
+
+    /// Name of the subject as reports print it: 과목명 in Korean or its
+    /// English translation, unlike the identifier `name`
+    ///
+    /// ```
+    /// use suneung_calc::score::{Locale, Subject};
+    ///
+    /// assert_eq!(Subject::Chemistry.localized_name(Locale::Korean), "화학Ⅰ");
+    /// assert_eq!(Subject::Chemistry.localized_name(Locale::English), "Chemistry I");
+    /// assert_eq!(Subject::Chemistry.name(), "Chemistry");
+    /// ```
+    pub fn localized_name(&self, locale: Locale) -> &'static str {
+        match (self, locale) {
+            (Subject::Korean, Locale::Korean) => "국어",
+            (Subject::Math, Locale::Korean) => "수학",
+            (Subject::English, Locale::Korean) => "영어",
+            (Subject::Chemistry, Locale::Korean) => "화학Ⅰ",
+            (Subject::EarthScience, Locale::Korean) => "지구과학Ⅰ",
+            (Subject::Korean, Locale::English) => "Korean",
+            (Subject::Math, Locale::English) => "Math",
+            (Subject::English, Locale::English) => "English",
+            (Subject::Chemistry, Locale::English) => "Chemistry I",
+            (Subject::EarthScience, Locale::English) => "Earth Science I",
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
+mod calc_options;
+mod electives;
+mod english_table;
+mod merge;
+mod ordering;
+#[cfg(feature = "parquet")]
+mod parquet;
+mod provenance;
+mod revision;
+mod sample;
+mod subject_scores;
+mod tags;
+mod units;
+
+pub use calc_options::{AbsentEnglish, CalcOptions, ScienceSelection, Strictness};
+pub use english_table::EnglishTable;
+pub use merge::MergePolicy;
+pub use ordering::ScoreMetric;
+pub use units::{Percentile, StandardScore};
+
+#[cfg(feature = "parquet")]
+pub use parquet::{list_records, ReadOptions, StoredRecord, DATA_DIR};
+use revision::History;
+pub use revision::{Revision, DEFAULT_HISTORY_LIMIT};
+use subject_scores::SubjectScores;
+
+/// Scores of one student, kept in `Subject::all()` order whatever order they
+/// were recorded in; `Debug`, serde output and the columns of
+/// `to_dataframe` follow the same order.
+///
+/// ```
+/// use suneung_calc::score::{Grade, Percentile, Record, StandardScore, Subject};
+///
+/// let grade = Grade::try_from(2u8).unwrap();
+/// let standard = |x| StandardScore::new(x).unwrap();
+/// let percentile = |x| Percentile::new(x).unwrap();
+/// let mut a = Record::new("홍길동");
+/// a.record_typed(Subject::Korean, standard(131f64), percentile(93f64), grade);
+/// a.record_typed(Subject::Math, standard(128f64), percentile(89f64), grade);
+/// let mut b = Record::new("홍길동");
+/// b.record_typed(Subject::Math, standard(128f64), percentile(89f64), grade);
+/// b.record_typed(Subject::Korean, standard(131f64), percentile(93f64), grade);
+///
+/// assert_eq!(format!("{:?}", a), format!("{:?}", b));
+/// assert_eq!(serde_json::to_string(&a)?, serde_json::to_string(&b)?);
+/// assert_eq!(b.subjects().collect::<Vec<_>>(), [Subject::Korean, Subject::Math]);
+/// #[cfg(feature = "parquet")]
+/// assert_eq!(a.to_dataframe().header(), b.to_dataframe().header());
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Record {
     name: String,
-    scores: HashMap<Subject, Score>,
+    scores: SubjectScores,
+    #[serde(default)]
+    exam: Option<ExamMeta>,
+    #[serde(default, deserialize_with = "tags::deserialize")]
+    tags: Vec<String>,
+    // Off by default; never serialized, see `write_history_in`
+    #[serde(skip)]
+    #[cfg_attr(feature = "schema", schemars(skip))]
+    history: Option<Box<History>>,
 }
 
 impl Record {
     pub fn new(name: &str) -> Self {
         Self {
             name: name.to_string(),
-            scores: HashMap::new(),
+            scores: SubjectScores::new(),
+            exam: None,
+            tags: vec![],
+            history: None,
         }
     }
 
-    pub fn record(&mut self, subject: Subject, standard_score: f64, percentile: f64, rank: usize) {
-        self.scores.insert(
+    /// Record a score as it is, in range or not; see `validate`
+    #[deprecated(note = "the two numbers are easily swapped; use `record_typed`")]
+    pub fn record(&mut self, subject: Subject, standard_score: f64, percentile: f64, rank: Grade) {
+        self.record_typed(
             subject,
-            Score {
-                standard_score,
-                percentile,
-                rank,
-            },
+            StandardScore::unchecked(standard_score),
+            Percentile::unchecked(percentile),
+            rank,
         );
     }
 
+    /// Record a score of `subject`, replacing any before. English takes
+    /// `Score::grade_only`, or `StandardScore::MIN` and `Percentile::MIN`.
+    pub fn record_typed(
+        &mut self,
+        subject: Subject,
+        standard_score: StandardScore,
+        percentile: Percentile,
+        rank: Grade,
+    ) {
+        self.record_score(subject, Score::new_typed(standard_score, percentile, rank));
+    }
+
+    pub fn record_score(&mut self, subject: Subject, score: Score) {
+        let before = self.scores.insert(subject, score);
+        self.log_revision(subject, before, Some(score));
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    /// Copy of this record with the name replaced by a pseudonym.
+    ///
+    /// The pseudonym depends only on `salt` and the name, so the same student
+    /// maps to the same pseudonym across exams as long as the salt is kept.
+    pub fn anonymized(&self, salt: &str) -> Record {
+        Record {
+            name: pseudonym(salt, &self.name),
+            scores: self.scores,
+            exam: self.exam.clone(),
+            tags: self.tags.clone(),
+            history: None,
+        }
+    }
+
+    pub fn exam(&self) -> Option<&ExamMeta> {
+        self.exam.as_ref()
+    }
+
+    /// `ExamKind::Other` for records without exam metadata
+    pub fn exam_kind(&self) -> ExamKind {
+        self.exam.as_ref().map_or(ExamKind::Other, |e| e.kind)
+    }
+
+    pub fn set_exam(&mut self, exam: Option<ExamMeta>) {
+        self.exam = exam;
+    }
+
+    /// Warning to show when comparing records from different kinds of exam
+    pub fn exam_mismatch(&self, other: &Record) -> Option<String> {
+        let (a, b) = (self.exam_kind(), other.exam_kind());
+        (a != b).then(|| {
+            format!(
+                "{} ({}) and {} ({}) come from different exams",
+                self.name,
+                a.label(),
+                other.name,
+                b.label()
+            )
+        })
+    }
+
+    pub fn try_score(&self, subject: Subject) -> Option<Score> {
+        self.scores.get(&subject).copied()
+    }
+
+    /// Recorded subjects in `Subject::all()` order, independent of the order
+    /// they were recorded in.
+    pub fn subjects(&self) -> impl Iterator<Item = Subject> + '_ {
+        self.scores.iter().map(|(subject, _)| subject)
+    }
+
+    /// Recorded scores in `Subject::all()` order
+    pub fn iter(&self) -> impl Iterator<Item = (Subject, &Score)> + '_ {
+        self.scores.iter()
+    }
+
+    /// Number of recorded subjects
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+
+    pub fn contains(&self, subject: Subject) -> bool {
+        self.scores.contains_key(&subject)
+    }
+
+    pub fn rename(&mut self, new_name: &str) {
+        self.name = new_name.to_string();
+    }
+
+    pub fn remove_subject(&mut self, subject: Subject) -> Option<Score> {
+        let before = self.scores.remove(&subject);
+        if before.is_some() {
+            self.log_revision(subject, before, None);
+        }
+        before
+    }
+
+    #[deprecated(note = "use `set_standard_score_typed`")]
+    pub fn set_standard_score(
+        &mut self,
+        subject: Subject,
+        standard_score: f64,
+    ) -> crate::error::Result<()> {
+        self.update_score(subject, |s| {
+            s.standard_score = StandardScore::unchecked(standard_score)
+        })
+    }
+
+    #[deprecated(note = "use `set_percentile_typed`")]
+    pub fn set_percentile(
+        &mut self,
+        subject: Subject,
+        percentile: f64,
+    ) -> crate::error::Result<()> {
+        self.update_score(subject, |s| {
+            s.percentile = Percentile::unchecked(percentile)
+        })
+    }
+
+    pub fn set_standard_score_typed(
+        &mut self,
+        subject: Subject,
+        standard_score: StandardScore,
+    ) -> crate::error::Result<()> {
+        self.update_score(subject, |s| s.standard_score = standard_score)
+    }
+
+    pub fn set_percentile_typed(
+        &mut self,
+        subject: Subject,
+        percentile: Percentile,
+    ) -> crate::error::Result<()> {
+        self.update_score(subject, |s| s.percentile = percentile)
+    }
+
+    pub fn set_grade(&mut self, subject: Subject, grade: Grade) -> crate::error::Result<()> {
+        self.update_score(subject, |s| s.rank = grade)
+    }
+
+    // Apply `f` to a recorded score, keeping the old value if the result is
+    // out of range
+    fn update_score<F: FnOnce(&mut Score)>(
+        &mut self,
+        subject: Subject,
+        f: F,
+    ) -> crate::error::Result<()> {
+        let mut score = self.require(subject)?;
+        f(&mut score);
+        check_score(subject, &score)?;
+        let before = self.scores.insert(subject, score);
+        self.log_revision(subject, before, Some(score));
+        Ok(())
+    }
+
     pub fn korean(&self) -> Score {
         *self.scores.get(&Subject::Korean).unwrap()
     }
@@ -96,270 +579,876 @@ impl Record {
     }
 
     pub fn standard_score(&self, subject: Subject) -> f64 {
-        self.scores.get(&subject).unwrap().standard_score
+        self.scores.get(&subject).unwrap().standard_score()
     }
 
     pub fn percentile(&self, subject: Subject) -> f64 {
+        self.scores.get(&subject).unwrap().percentile()
+    }
+
+    pub fn standard_score_typed(&self, subject: Subject) -> StandardScore {
+        self.scores.get(&subject).unwrap().standard_score
+    }
+
+    pub fn percentile_typed(&self, subject: Subject) -> Percentile {
         self.scores.get(&subject).unwrap().percentile
     }
 
-    pub fn rank(&self, subject: Subject) -> usize {
+    pub fn rank(&self, subject: Subject) -> Grade {
         self.scores.get(&subject).unwrap().rank
     }
 
-    pub fn to_dataframe(&self) -> DataFrame {
-        let mut df = DataFrame::new(vec![]);
-        df.push(
-            "Korean",
-            Series::new(vec![
-                self.korean().standard_score(),
-                self.korean().percentile(),
-                self.korean().rank() as f64,
-            ]),
-        );
-        df.push(
-            "Math",
-            Series::new(vec![
-                self.math().standard_score(),
-                self.math().percentile(),
-                self.math().rank() as f64,
-            ]),
-        );
-        df.push(
-            "English",
-            Series::new(vec![0f64, 0f64, self.english().rank() as f64]),
-        );
-        df.push(
-            "Chemistry",
-            Series::new(vec![
-                self.chemistry().standard_score(),
-                self.chemistry().percentile(),
-                self.chemistry().rank() as f64,
-            ]),
-        );
-        df.push(
-            "EarthScience",
-            Series::new(vec![
-                self.earth_science().standard_score(),
-                self.earth_science().percentile(),
-                self.earth_science().rank() as f64,
-            ]),
-        );
+    #[cfg(feature = "binary")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        crate::binary::encode(self)
+    }
+
+    #[cfg(feature = "binary")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        crate::binary::decode(bytes)
+    }
 
-        df
+    /// Record in its serde form as TOML, e.g. a file written by hand for
+    /// `suneung record --from-toml`. The text is not normalized, so names
+    /// keep every character, but it is limited to `input::MAX_INPUT_BYTES`
+    /// and the record must pass `validate`.
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(text: &str) -> crate::error::Result<Self> {
+        crate::input::check_len("record TOML", text)?;
+        let record: Record = toml::from_str(text)
+            .map_err(|e| SuneungError::InvalidInput(format!("record TOML: {}", e.message())))?;
+        record.validate()?;
+        Ok(record)
     }
 
-    pub fn write_parquet(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let df = self.to_dataframe();
-        let path = format!("data/{}", self.name());
-        if !std::path::Path::new(&path).exists() {
-            std::fs::create_dir(&path)?;
+    /// Check every recorded score against its valid range (표준점수 0-200,
+    /// 백분위 0-100, 등급 1-9) and the name with `input::check_name`.
+    pub fn validate(&self) -> crate::error::Result<()> {
+        crate::input::check_name(&self.name)?;
+        for (subject, score) in self.iter() {
+            check_score(subject, score)?;
         }
-        df.write_parquet(
-            &format!("data/{}/record.parquet", self.name()),
-            CompressionOptions::Uncompressed,
-        )?;
         Ok(())
     }
 
-    pub fn read_parquet(name: &str) -> Self {
-        let df = DataFrame::read_parquet(&format!("data/{}/record.parquet", name)).unwrap();
-        let korean: Vec<f64> = df["Korean"].to_vec();
-        let math: Vec<f64> = df["Math"].to_vec();
-        let english: Vec<f64> = df["English"].to_vec();
-        let chemistry: Vec<f64> = df["Chemistry"].to_vec();
-        let earth_science: Vec<f64> = df["EarthScience"].to_vec();
+    pub(crate) fn require(&self, subject: Subject) -> crate::error::Result<Score> {
+        self.try_score(subject)
+            .ok_or(SuneungError::MissingSubject(subject))
+    }
 
-        let mut record = Record::new(name);
+    /// Converted score at `university` for `year` together with the
+    /// contribution of each subject.
+    pub fn calc_breakdown(
+        &self,
+        university: University,
+        year: usize,
+    ) -> crate::error::Result<CalcBreakdown> {
+        let weight = UniversityWeight::load_cached(university, year)
+            .ok_or(SuneungError::UnsupportedYear { university, year })?;
+        weight.convert(self)
+    }
 
-        record.record(Subject::Korean, korean[0], korean[1], korean[2] as usize);
-        record.record(Subject::Math, math[0], math[1], math[2] as usize);
-        record.record(Subject::English, 0f64, 0f64, english[2] as usize);
-        record.record(
-            Subject::Chemistry,
-            chemistry[0],
-            chemistry[1],
-            chemistry[2] as usize,
-        );
-        record.record(
-            Subject::EarthScience,
-            earth_science[0],
-            earth_science[1],
-            earth_science[2] as usize,
-        );
+    /// `calc_breakdown` with weights from `source` instead of the embedded data
+    pub fn calc_breakdown_with(
+        &self,
+        source: &dyn WeightSource,
+        university: University,
+        year: usize,
+    ) -> crate::error::Result<CalcBreakdown> {
+        let weight = source.get(university, year)?;
+        weight.convert(self)
+    }
 
-        record
+    /// Converted score with an explicit weight, e.g. one from
+    /// `UniversityWeightBuilder`
+    pub fn calc_with_weight(&self, weight: &UniversityWeight) -> crate::error::Result<f64> {
+        weight.convert(self).map(|b| b.total)
     }
 
+    /// Converted score with weights from `source` instead of the embedded data
+    pub fn calc_with_source(
+        &self,
+        source: &dyn WeightSource,
+        university: University,
+        year: usize,
+    ) -> crate::error::Result<f64> {
+        self.calc_breakdown_with(source, university, year)
+            .map(|b| b.total)
+    }
+
+    pub fn try_calc_with_university(
+        &self,
+        university: University,
+        year: usize,
+    ) -> crate::error::Result<f64> {
+        self.calc_breakdown(university, year).map(|b| b.total)
+    }
+
+    /// Panics where `try_calc_with_university` would return an error.
     pub fn calc_with_university(&self, university: University, year: usize) -> f64 {
-        let weight = UniversityWeight::load(university, year);
-        let weight_sum_except_eng = weight.korean + weight.math + weight.science;
-        let weight_eng = weight.english;
-        let weight_sum = weight_sum_except_eng + weight_eng;
-
-        let korean = self.korean().standard_score() * weight.korean / weight_sum_except_eng;
-        let math = self.math().standard_score() * weight.math / weight_sum_except_eng;
-        let science_required = weight.science_required();
-        let science_cand = match science_required {
-            1 => {
-                self.chemistry()
-                    .standard_score()
-                    .max(self.earth_science().standard_score())
-                    * 2f64
+        self.try_calc_with_university(university, year)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Converted scores at every university with data for `year`, best
+    /// first: `calc_all_opts` with the default `CalcOptions`.
+    pub fn calc_all(&self, year: usize) -> crate::error::Result<Vec<(University, f64)>> {
+        let scores = self.calc_all_opts(year, &CalcOptions::default())?;
+        Ok(scores
+            .into_iter()
+            .map(|(univ, b)| (univ, b.total))
+            .collect())
+    }
+
+    /// `calc_all` with the universities scored on the rayon thread pool.
+    /// The result is the same as that of `calc_all`, whatever the
+    /// scheduling.
+    ///
+    /// ```
+    /// use suneung_calc::score::Record;
+    ///
+    /// let record = Record::sample();
+    /// assert_eq!(record.calc_all_par(2024).unwrap(), record.calc_all(2024).unwrap());
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn calc_all_par(&self, year: usize) -> crate::error::Result<Vec<(University, f64)>> {
+        use rayon::prelude::*;
+
+        let results: Vec<_> = University::all()
+            .par_iter()
+            .map(|&univ| {
+                let options = CalcOptions::default();
+                (univ, self.calc_with_university_opts(univ, year, &options))
+            })
+            .collect();
+        let scores = rank_scores(results)?;
+        Ok(scores
+            .into_iter()
+            .map(|(univ, b)| (univ, b.total))
+            .collect())
+    }
+
+    /// Ordering for sorting records best-first by their converted score at
+    /// `university`. Records whose calculation fails sort after every record
+    /// with a score.
+    pub fn cmp_by_university(
+        &self,
+        other: &Record,
+        university: University,
+        year: usize,
+    ) -> std::cmp::Ordering {
+        let a = self.try_calc_with_university(university, year).ok();
+        let b = other.try_calc_with_university(university, year).ok();
+        match (a, b) {
+            (Some(a), Some(b)) => b.total_cmp(&a),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }
+    }
+}
+
+// Breakdowns of the universities with data, best first; the sort is
+// stable, so ties keep the order of `results`
+fn rank_scores(
+    results: impl IntoIterator<Item = (University, crate::error::Result<CalcBreakdown>)>,
+) -> crate::error::Result<Vec<(University, CalcBreakdown)>> {
+    let mut scores = vec![];
+    for (univ, result) in results {
+        match result {
+            Ok(breakdown) => scores.push((univ, breakdown)),
+            Err(SuneungError::UnsupportedYear { .. }) => (),
+            Err(e) => return Err(e),
+        }
+    }
+    scores.sort_by(|a, b| b.1.total.total_cmp(&a.1.total));
+    Ok(scores)
+}
+
+fn check_score(subject: Subject, score: &Score) -> crate::error::Result<()> {
+    match range_problem(score, subject == Subject::English) {
+        Some(reason) => Err(SuneungError::InvalidScore { subject, reason }),
+        None => Ok(()),
+    }
+}
+
+// Why `score` is out of range, if it is. The grade is valid by construction,
+// and grade-only scores skip the standard score and percentile checks.
+fn range_problem(score: &Score, grade_only: bool) -> Option<String> {
+    if grade_only {
+        return None;
+    }
+    if let Err(SuneungError::ScoreOutOfRange(reason)) = StandardScore::new(score.standard_score()) {
+        return Some(reason);
+    }
+    if let Err(SuneungError::ScoreOutOfRange(reason)) = Percentile::new(score.percentile()) {
+        return Some(reason);
+    }
+    None
+}
+
+/// Per-subject contributions to a converted score.
+///
+/// `korean`, `math` and `science` are already scaled the way they enter
+/// `total`; `english` is the adjustment relative to the default grade.
+/// `total` is kept within 0 and `UniversityWeight::max_score`; `warnings`
+/// lists that and anything else `CalcOptions` let through.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CalcBreakdown {
+    pub korean: f64,
+    pub math: f64,
+    pub science: f64,
+    pub english: f64,
+    pub total: f64,
+    /// How the 탐구 scores were combined into `science`
+    #[serde(default)]
+    pub science_aggregation: ScienceAggregation,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<CalcWarning>,
+}
+
+/// Something questionable a calculation went past; `Strictness::Strict`
+/// refuses instead
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CalcWarning {
+    /// The contributions summed to `unclamped`, below 0
+    ClampedToZero { unclamped: f64 },
+    /// The contributions summed to `unclamped`, above the university's
+    /// maximum `max`
+    ClampedToMax { unclamped: f64, max: f64 },
+    /// A score outside the range `Record::validate` accepts, e.g. read
+    /// from a file of an earlier release, used as it is
+    ScoreOutOfRange {
+        subject: Subject,
+        standard_score: f64,
+        percentile: f64,
+    },
+    /// The record has no English; `grade` was assumed
+    EnglishAssumed { grade: Grade },
+    /// The university doubles the better 탐구, and `subject` is the only
+    /// one recorded
+    SingleScience { subject: Subject },
+    /// No weights for `requested`, those of `used` were taken
+    FallbackYear { requested: usize, used: usize },
+}
+
+impl CalcWarning {
+    // English description for `SuneungError::Strict`
+    pub(crate) fn reason(&self) -> String {
+        match self {
+            CalcWarning::ClampedToZero { unclamped } => {
+                format!("converted score {:.2} is below 0", unclamped)
             }
-            2 => self.chemistry().standard_score() + self.earth_science().standard_score(),
-            _ => unreachable!(),
-        };
-        let science = science_cand * weight.science / weight_sum_except_eng;
+            CalcWarning::ClampedToMax { unclamped, max } => {
+                format!(
+                    "converted score {:.2} is above the maximum {:.2}",
+                    unclamped, max
+                )
+            }
+            CalcWarning::ScoreOutOfRange {
+                subject,
+                standard_score,
+                percentile,
+            } => format!(
+                "{} score {}/{} is out of range",
+                subject.name(),
+                standard_score,
+                percentile
+            ),
+            CalcWarning::EnglishAssumed { grade } => {
+                format!("record has no English score, grade {} assumed", grade)
+            }
+            CalcWarning::SingleScience { subject } => {
+                format!("{} is the only science recorded", subject.name())
+            }
+            CalcWarning::FallbackYear { requested, used } => {
+                format!("no weight data for {}, {} used instead", requested, used)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for CalcWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CalcWarning::ClampedToZero { unclamped } => {
+                write!(
+                    f,
+                    "환산 점수 {:.2}점이 0점 미만이라 0점으로 맞춤",
+                    unclamped
+                )
+            }
+            CalcWarning::ClampedToMax { unclamped, max } => write!(
+                f,
+                "환산 점수 {:.2}점이 만점 {:.2}점을 넘어 만점으로 맞춤",
+                unclamped, max
+            ),
+            CalcWarning::ScoreOutOfRange {
+                subject,
+                standard_score,
+                percentile,
+            } => write!(
+                f,
+                "{} 표준점수 {} / 백분위 {}가 범위를 벗어났지만 그대로 반영",
+                crate::display::label(*subject),
+                standard_score,
+                percentile
+            ),
+            CalcWarning::EnglishAssumed { grade } => {
+                write!(f, "영어 성적이 없어 {}등급으로 계산", grade)
+            }
+            CalcWarning::SingleScience { subject } => write!(
+                f,
+                "탐구가 {} 1과목뿐이라 그 점수를 2배로 반영",
+                crate::display::label(*subject)
+            ),
+            CalcWarning::FallbackYear { requested, used } => write!(
+                f,
+                "{}학년도 반영 비율이 없어 {}학년도 것으로 계산",
+                requested, used
+            ),
+        }
+    }
+}
 
-        let total = (korean + math + science) * 3f64;
+/// `anon-xxxxxxxx` from a 64-bit FNV-1a hash of salt and name.
+///
+/// FNV is spelled out here instead of using `DefaultHasher` because the std
+/// hasher is not guaranteed to be stable across Rust releases.
+pub(crate) fn pseudonym(salt: &str, name: &str) -> String {
+    let mut hash = 0xcbf29ce484222325u64;
+    for b in salt.bytes().chain(std::iter::once(0)).chain(name.bytes()) {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("anon-{:08x}", (hash >> 32) as u32 ^ hash as u32)
+}
 
-        let eng_rank = self.english().rank();
-        let eng_required_rank = weight.english_required();
-        let eng_table = weight.english_table();
+// Declares `University` together with `University::all()`, `name()` and
+// `name_en()` from a single list, so a new variant can never be missing from
+// `all()` or lack a name.
+macro_rules! universities {
+    ($($univ:ident => ($name:expr, $name_en:expr, $region:ident, $kind:ident, [$($alias:expr),*])),* $(,)?) => {
+        #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+        #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+        pub enum University {
+            $($univ),*
+        }
 
-        let eng_default_score = eng_table[eng_required_rank];
-        let eng_score = eng_table[eng_rank];
+        impl University {
+            /// Every variant in declaration order
+            pub fn all() -> &'static [University] {
+                &[$(University::$univ),*]
+            }
 
-        if weight_eng > 0f64 {
-            total + (eng_score - eng_default_score) * weight_eng / weight_sum
-        } else {
-            total + (eng_score - eng_default_score) / 4f64
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(University::$univ => $name),*
+                }
+            }
+
+            /// Romanized name for English-language reports
+            pub fn name_en(&self) -> &'static str {
+                match self {
+                    $(University::$univ => $name_en),*
+                }
+            }
+
+            /// Where the campus the weight data describes is located
+            pub fn region(&self) -> Region {
+                match self {
+                    $(University::$univ => Region::$region),*
+                }
+            }
+
+            pub fn institution_type(&self) -> InstitutionType {
+                match self {
+                    $(University::$univ => InstitutionType::$kind),*
+                }
+            }
+
+            /// Other spellings accepted by `FromStr`, e.g. `건국대학교`, `건대`
+            pub fn aliases(&self) -> &'static [&'static str] {
+                match self {
+                    $(University::$univ => &[$($alias),*]),*
+                }
+            }
+        }
+    };
+}
+
+universities! {
+    KYUNGHEE => ("경희대(서울)", "Kyung Hee University (Seoul)", Seoul, Private, ["경희대", "경희대학교", "경희"]),
+    DONGGUK => ("동국대", "Dongguk University", Seoul, Private, ["동국대학교", "동국"]),
+    SEOULSCITECH => ("서울과기대", "Seoul National University of Science and Technology", Seoul, National, ["서울과학기술대", "서울과학기술대학교", "과기대"]),
+    KWANGWOON => ("광운대", "Kwangwoon University", Seoul, Private, ["광운대학교", "광운"]),
+    INHA => ("인하대", "Inha University", GyeonggiIncheon, Private, ["인하대학교", "인하"]),
+    ERICA => ("한양대(ERICA)", "Hanyang University ERICA", GyeonggiIncheon, Private, ["한양대ERICA", "한양대에리카", "에리카"]),
+    SEJONG => ("세종대", "Sejong University", Seoul, Private, ["세종대학교", "세종"]),
+    KOOKMIN => ("국민대", "Kookmin University", Seoul, Private, ["국민대학교", "국민"]),
+    AJU => ("아주대", "Ajou University", GyeonggiIncheon, Private, ["아주대학교", "아주"]),
+    SOONGSIL => ("숭실대", "Soongsil University", Seoul, Private, ["숭실대학교", "숭실"]),
+    KONKUK => ("건국대", "Konkuk University", Seoul, Private, ["건국대학교", "건국", "건대"]),
+    CATHOLIC => ("가톨릭대", "The Catholic University of Korea", GyeonggiIncheon, Private, ["가톨릭대학교", "가톨릭"]),
+    CHUNGANG => ("중앙대", "Chung-Ang University", Seoul, Private, ["중앙대학교", "중앙", "중대"]),
+    SEOUL => ("서울시립대", "University of Seoul", Seoul, National, ["서울시립대학교", "시립대"]),
+    SOGANG => ("서강대", "Sogang University", Seoul, Private, ["서강대학교", "서강"]),
+}
+
+/// 권역 of a university campus
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Region {
+    Seoul,
+    /// 경기·인천
+    GyeonggiIncheon,
+    Other,
+}
+
+impl Region {
+    pub fn all() -> &'static [Region] {
+        &[Region::Seoul, Region::GyeonggiIncheon, Region::Other]
+    }
+
+    /// Identifier used on the command line
+    pub fn id(&self) -> &'static str {
+        match self {
+            Region::Seoul => "seoul",
+            Region::GyeonggiIncheon => "gyeonggi-incheon",
+            Region::Other => "other",
         }
     }
 }
 
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
-pub enum University {
-    KYUNGHEE,
-    DONGGUK,
-    SEOULSCITECH,
-    KWANGWOON,
-    INHA,
-    ERICA,
-    SEJONG,
-    KOOKMIN,
-    AJU,
-    SOONGSIL,
-    KONKUK,
-    CATHOLIC,
-    CHUNGANG,
-    SEOUL,
-    SOGANG,
+impl std::str::FromStr for Region {
+    type Err = SuneungError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Region::all()
+            .iter()
+            .copied()
+            .find(|r| r.id().eq_ignore_ascii_case(s))
+            .ok_or_else(|| SuneungError::UnknownName {
+                kind: "region",
+                name: s.to_string(),
+            })
+    }
 }
 
-impl University {
-    pub fn name(&self) -> &'static str {
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum InstitutionType {
+    Private,
+    /// 국공립, including 시립 universities
+    National,
+    /// 과학기술원 (KAIST, POSTECH, ...)
+    ScienceInstitute,
+    WomensUniversity,
+}
+
+/// Language of the names and labels of generated reports
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    Korean,
+    English,
+}
+
+#[deprecated(note = "renamed to `Locale`, which now covers every label")]
+pub type NameLocale = Locale;
+
+impl Locale {
+    // `korean` or `english`, whichever is in this locale
+    pub(crate) fn pick<'a>(self, korean: &'a str, english: &'a str) -> &'a str {
         match self {
-            University::KYUNGHEE => "경희대(서울)",
-            University::DONGGUK => "동국대",
-            University::SEOULSCITECH => "서울과기대",
-            University::KWANGWOON => "광운대",
-            University::INHA => "인하대",
-            University::ERICA => "한양대(ERICA)",
-            University::SEJONG => "세종대",
-            University::KOOKMIN => "국민대",
-            University::AJU => "아주대",
-            University::SOONGSIL => "숭실대",
-            University::KONKUK => "건국대",
-            University::CATHOLIC => "가톨릭대",
-            University::CHUNGANG => "중앙대",
-            University::SEOUL => "서울시립대",
-            University::SOGANG => "서강대",
-        }
-    }
-}
-
-#[derive(Debug, Clone)]
+            Locale::Korean => korean,
+            Locale::English => english,
+        }
+    }
+}
+
+/// `ko`/`korean` or `en`/`english`, case-insensitive
+impl std::str::FromStr for Locale {
+    type Err = SuneungError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "ko" | "korean" => Ok(Locale::Korean),
+            "en" | "english" => Ok(Locale::English),
+            _ => Err(SuneungError::UnknownName {
+                kind: "locale",
+                name: s.to_string(),
+            }),
+        }
+    }
+}
+
+/// Accepts the identifier (`KONKUK`, case-insensitive), the Korean or
+/// English name, or one of the `aliases`. Spaces are ignored.
+impl std::str::FromStr for University {
+    type Err = SuneungError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalize = |s: &str| s.split_whitespace().collect::<String>().to_lowercase();
+        let key = normalize(s);
+        University::iter()
+            .find(|u| {
+                normalize(&format!("{:?}", u)) == key
+                    || normalize(u.name()) == key
+                    || normalize(u.name_en()) == key
+                    || u.aliases().iter().any(|a| normalize(a) == key)
+            })
+            .ok_or_else(|| SuneungError::UnknownName {
+                kind: "university",
+                name: s.to_string(),
+            })
+    }
+}
+
+impl std::fmt::Display for University {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// Years with embedded weight data
+pub(crate) const YEARS: [usize; 4] = [2022, 2023, 2024, 2025];
+
+impl University {
+    pub fn iter() -> impl Iterator<Item = University> {
+        Self::all().iter().copied()
+    }
+
+    /// Universities with embedded weight data for `year`
+    pub fn supported(year: usize) -> Vec<University> {
+        Self::iter()
+            .filter(|&u| EMBEDDED.contains_key(&(u, year)))
+            .collect()
+    }
+
+    /// Years with embedded weight data, oldest first
+    pub fn supported_years(&self) -> Vec<usize> {
+        YEARS
+            .into_iter()
+            .filter(|&year| EMBEDDED.contains_key(&(*self, year)))
+            .collect()
+    }
+
+    /// Inverse of the `Debug` form used as an identifier (`"KONKUK"`)
+    pub fn from_id(id: &str) -> Option<University> {
+        Self::iter().find(|u| format!("{:?}", u) == id)
+    }
+
+    pub fn localized_name(&self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::Korean => self.name(),
+            Locale::English => self.name_en(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UniversityWeight {
-    korean: f64,
-    math: f64,
-    english: f64,
-    science: f64,
-    science_required: usize, // Number of required subjects
-    english_required: usize, // Default rank
-    english_table: Vec<f64>,
+    pub(crate) korean: f64,
+    pub(crate) math: f64,
+    pub(crate) english: f64,
+    pub(crate) science: f64,
+    pub(crate) science_required: usize, // Number of required subjects
+    // Left out by weights written before it existed, see
+    // `science_aggregation()`
+    #[serde(default)]
+    pub(crate) science_aggregation: Option<ScienceAggregation>,
+    pub(crate) english_required: usize, // Default rank
+    pub(crate) english_table: EnglishTable,
+    #[serde(default)]
+    pub(crate) formula: FormulaKind,
+    #[serde(default)]
+    pub(crate) provenance: Option<Provenance>,
+    // `max_score`, kept from its first success; the fields above don't
+    // change after construction
+    #[serde(skip)]
+    pub(crate) max_score: OnceLock<f64>,
+}
+
+/// Where the numbers of a `UniversityWeight` come from, for answering
+/// "which document says so?"
+///
+/// ```
+/// use suneung_calc::score::{Provenance, University, UniversityWeight};
+///
+/// let weight = UniversityWeight::try_load(University::SOGANG, 2024).unwrap();
+/// assert_eq!(weight.provenance().unwrap().document, "2024학년도 정시 모집요강");
+///
+/// let source = Provenance::new("2026학년도 정시 모집요강")
+///     .section("p. 12")
+///     .entered("2025-09-01");
+/// assert_eq!(source.to_string(), "2026학년도 정시 모집요강, p. 12 (2025-09-01 입력)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Provenance {
+    /// Title of the source document, e.g. `2024학년도 정시 모집요강`
+    pub document: Cow<'static, str>,
+    /// Page or section within the document
+    #[serde(default)]
+    pub section: Option<Cow<'static, str>>,
+    /// Date the numbers were entered, `YYYY-MM-DD`
+    #[serde(default)]
+    pub entered: Option<Cow<'static, str>>,
+}
+
+impl Provenance {
+    pub fn new(document: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            document: document.into(),
+            section: None,
+            entered: None,
+        }
+    }
+
+    pub fn section(mut self, section: impl Into<Cow<'static, str>>) -> Self {
+        self.section = Some(section.into());
+        self
+    }
+
+    pub fn entered(mut self, date: impl Into<Cow<'static, str>>) -> Self {
+        self.entered = Some(date.into());
+        self
+    }
+}
+
+impl std::fmt::Display for Provenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.document)?;
+        if let Some(section) = &self.section {
+            write!(f, ", {}", section)?;
+        }
+        if let Some(entered) = &self.entered {
+            write!(f, " ({} 입력)", entered)?;
+        }
+        Ok(())
+    }
 }
 
-macro_rules! make_university_weight {
+// The embedded English tables as f64, evaluated at compile time
+const fn table_to_f64<const N: usize>(table: [usize; N]) -> [f64; N] {
+    let mut out = [0f64; N];
+    let mut i = 0;
+    while i < N {
+        out[i] = table[i] as f64;
+        i += 1;
+    }
+    out
+}
+
+/// Weight of one university for one year as embedded in the crate: the
+/// constants of `university_weight.rs`, with the English table already in
+/// f64
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct UniversityWeightData {
+    pub(crate) university: University,
+    pub(crate) year: usize,
+    /// 국어, 수학, 영어, 탐구
+    pub(crate) weight: [usize; 4],
+    pub(crate) science_required: usize,
+    pub(crate) science_aggregation: ScienceAggregation,
+    pub(crate) english_required: usize,
+    pub(crate) english_table: &'static [f64],
+    /// Title of the source document, if recorded
+    pub(crate) source: Option<&'static str>,
+    /// Page or section of `source` and the date the row was entered
+    pub(crate) section: Option<&'static str>,
+    pub(crate) entered: Option<&'static str>,
+}
+
+// `sourced` also takes the `_SOURCE`, `_SECTION` and `_ENTERED` constants
+// for the weight's `Provenance`
+macro_rules! weight_data {
     ($univ:ident, $year:expr) => {
-        {
-            paste! {
-                let weight = [<$univ _ $year _WEIGHT>].to_vec();
-                let korean = weight[0];
-                let math = weight[1];
-                let english = weight[2];
-                let science = weight[3];
-                let science_required = [<$univ _ $year _SCI_REQ>];
-                let english_required = [<$univ _ $year _ENG_REQ>];
-                let english_table = [<$univ _$year _ENG>].to_vec().iter().map(|x| *x as f64).collect::<Vec<f64>>();
-
-                UniversityWeight {
-                    korean: korean as f64,
-                    math: math as f64,
-                    english: english as f64,
-                    science: science as f64,
-                    science_required,
-                    english_required,
-                    english_table,
+        weight_data!(@data $univ, $year, None, None, None)
+    };
+    ($univ:ident, $year:expr, sourced) => {
+        paste! {
+            weight_data!(
+                @data $univ,
+                $year,
+                Some([<$univ _ $year _SOURCE>]),
+                [<$univ _ $year _SECTION>],
+                [<$univ _ $year _ENTERED>]
+            )
+        }
+    };
+    (@data $univ:ident, $year:expr, $source:expr, $section:expr, $entered:expr) => {
+        paste! {
+            {
+                const ENGLISH_TABLE: [f64; [<$univ _ $year _ENG>].len()] =
+                    table_to_f64([<$univ _ $year _ENG>]);
+                UniversityWeightData {
+                    university: University::$univ,
+                    year: $year,
+                    weight: [<$univ _ $year _WEIGHT>],
+                    science_required: [<$univ _ $year _SCI_REQ>],
+                    science_aggregation: [<$univ _ $year _SCI_AGG>],
+                    english_required: [<$univ _ $year _ENG_REQ>],
+                    english_table: &ENGLISH_TABLE,
+                    source: $source,
+                    section: $section,
+                    entered: $entered,
                 }
             }
         }
+    };
+}
+
+// Every embedded weight, evaluated at compile time. The list follows
+// `data_src/weights.csv` and is generated by `build.rs`.
+static EMBEDDED_DATA: &[UniversityWeightData] =
+    &include!(concat!(env!("OUT_DIR"), "/embedded_weights.rs"));
+
+impl From<&UniversityWeightData> for UniversityWeight {
+    fn from(data: &UniversityWeightData) -> Self {
+        let [korean, math, english, science] = data.weight;
+        UniversityWeight {
+            korean: korean as f64,
+            math: math as f64,
+            english: english as f64,
+            science: science as f64,
+            science_required: data.science_required,
+            science_aggregation: Some(data.science_aggregation),
+            english_required: data.english_required,
+            english_table: EnglishTable::from_legacy(data.english_table).unwrap_or_else(|e| {
+                panic!(
+                    "embedded English table of {} for {} is invalid: {}",
+                    data.university.name(),
+                    data.year,
+                    e
+                )
+            }),
+            formula: FormulaKind::Standard,
+            provenance: data.source.map(|document| {
+                let mut provenance = Provenance::new(document);
+                provenance.section = data.section.map(Cow::Borrowed);
+                provenance.entered = data.entered.map(Cow::Borrowed);
+                provenance
+            }),
+            max_score: OnceLock::new(),
+        }
     }
 }
 
+static EMBEDDED: LazyLock<HashMap<(University, usize), Arc<UniversityWeight>>> =
+    LazyLock::new(|| {
+        EMBEDDED_DATA
+            .iter()
+            .map(|data| {
+                let weight = UniversityWeight::from_embedded(data);
+                ((data.university, data.year), Arc::new(weight))
+            })
+            .collect()
+    });
+
 impl UniversityWeight {
+    /// Panics where `try_load` would return `None`.
+    #[deprecated(note = "panics for a year without data; use `try_load` or `load_cached`")]
     pub fn load(univ: University, year: usize) -> Self {
-        match (univ, year) {
-            // 2022
-            (University::KYUNGHEE, 2022) => make_university_weight!(KYUNGHEE, 2022),
-            (University::DONGGUK, 2022) => make_university_weight!(DONGGUK, 2022),
-            (University::SEOULSCITECH, 2022) => make_university_weight!(SEOULSCITECH, 2022),
-            (University::KWANGWOON, 2022) => make_university_weight!(KWANGWOON, 2022),
-            (University::INHA, 2022) => make_university_weight!(INHA, 2022),
-            (University::ERICA, 2022) => make_university_weight!(ERICA, 2022),
-            (University::SEJONG, 2022) => make_university_weight!(SEJONG, 2022),
-            (University::KOOKMIN, 2022) => make_university_weight!(KOOKMIN, 2022),
-            (University::AJU, 2022) => make_university_weight!(AJU, 2022),
-            (University::SOONGSIL, 2022) => make_university_weight!(SOONGSIL, 2022),
-            (University::CATHOLIC, 2022) => make_university_weight!(CATHOLIC, 2022),
-            // 2023
-            (University::KYUNGHEE, 2023) => make_university_weight!(KYUNGHEE, 2023),
-            (University::DONGGUK, 2023) => make_university_weight!(DONGGUK, 2023),
-            (University::SEOULSCITECH, 2023) => make_university_weight!(SEOULSCITECH, 2023),
-            (University::KWANGWOON, 2023) => make_university_weight!(KWANGWOON, 2023),
-            (University::INHA, 2023) => make_university_weight!(INHA, 2023),
-            (University::ERICA, 2023) => make_university_weight!(ERICA, 2023),
-            (University::SEJONG, 2023) => make_university_weight!(SEJONG, 2023),
-            (University::KOOKMIN, 2023) => make_university_weight!(KOOKMIN, 2023),
-            (University::AJU, 2023) => make_university_weight!(AJU, 2023),
-            (University::SOONGSIL, 2023) => make_university_weight!(SOONGSIL, 2023),
-            (University::CATHOLIC, 2023) => make_university_weight!(CATHOLIC, 2023),
-            // 2024
-            (University::SOGANG, 2024) => make_university_weight!(SOGANG, 2024),
-            (University::CHUNGANG, 2024) => make_university_weight!(CHUNGANG, 2024),
-            (University::KYUNGHEE, 2024) => make_university_weight!(KYUNGHEE, 2024),
-            (University::SEOUL, 2024) => make_university_weight!(SEOUL, 2024),
-            (University::DONGGUK, 2024) => make_university_weight!(DONGGUK, 2024),
-            (University::SEOULSCITECH, 2024) => make_university_weight!(SEOULSCITECH, 2024),
-            (University::KWANGWOON, 2024) => make_university_weight!(KWANGWOON, 2024),
-            (University::INHA, 2024) => make_university_weight!(INHA, 2024),
-            (University::ERICA, 2024) => make_university_weight!(ERICA, 2024),
-            (University::SEJONG, 2024) => make_university_weight!(SEJONG, 2024),
-            (University::KOOKMIN, 2024) => make_university_weight!(KOOKMIN, 2024),
-            (University::AJU, 2024) => make_university_weight!(AJU, 2024),
-            (University::SOONGSIL, 2024) => make_university_weight!(SOONGSIL, 2024),
-            (University::KONKUK, 2024) => make_university_weight!(KONKUK, 2024),
-            (University::CATHOLIC, 2024) => make_university_weight!(CATHOLIC, 2024),
-            // 2025
-            (University::SOGANG, 2025) => make_university_weight!(SOGANG, 2025),
-            (University::CHUNGANG, 2025) => make_university_weight!(CHUNGANG, 2025),
-            (University::KYUNGHEE, 2025) => make_university_weight!(KYUNGHEE, 2025),
-            (University::SEOUL, 2025) => make_university_weight!(SEOUL, 2025),
-            (University::KONKUK, 2025) => make_university_weight!(KONKUK, 2025),
-            (University::DONGGUK, 2025) => make_university_weight!(DONGGUK, 2025),
-            _ => unimplemented!(),
+        Self::try_load(univ, year)
+            .unwrap_or_else(|| panic!("{} has no weight data for {}", univ.name(), year))
+    }
+
+    /// Copy of the embedded weight of `univ` for `year`, taken from the
+    /// `load_cached` weights
+    ///
+    /// ```
+    /// use suneung_calc::score::{University, UniversityWeight};
+    ///
+    /// let konkuk = UniversityWeight::try_load(University::KONKUK, 2024).unwrap();
+    /// let cached = UniversityWeight::load_cached(University::KONKUK, 2024).unwrap();
+    /// assert_eq!(konkuk.korean(), cached.korean());
+    /// assert!(UniversityWeight::try_load(University::KONKUK, 2022).is_none());
+    /// ```
+    pub fn try_load(univ: University, year: usize) -> Option<Self> {
+        Self::load_cached(univ, year).map(|weight| (*weight).clone())
+    }
+
+    // `build.rs` already checks the data, so an invalid embedded weight is
+    // a bug in the crate rather than something callers can handle
+    fn from_embedded(data: &UniversityWeightData) -> Self {
+        let weight = Self::from(data);
+        if let Err(e) = weight.validate() {
+            panic!(
+                "embedded weight of {} for {} is invalid: {}",
+                data.university.name(),
+                data.year,
+                e
+            );
+        }
+        weight
+    }
+
+    /// Check that the weight can be used for calculation: finite,
+    /// non-negative weights with some weight on 국어, 수학 or 탐구, 1 or 2
+    /// required sciences as the 탐구 aggregation needs, a default
+    /// English grade from 1 to 9 and an English table of the kind the
+    /// formula reads: points deducted for `FormulaKind::DeductionEnglish`,
+    /// points awarded otherwise (see `EnglishTable`).
+    /// Embedded, TOML and built weights are all checked on load.
+    ///
+    /// ```
+    /// use suneung_calc::score::{University, UniversityWeight};
+    ///
+    /// for year in 2015..2030 {
+    ///     for univ in University::supported(year) {
+    ///         assert_eq!(UniversityWeight::try_load(univ, year).unwrap().validate(), Ok(()));
+    ///     }
+    /// }
+    /// ```
+    pub fn validate(&self) -> std::result::Result<(), WeightValidationError> {
+        for (subject, weight) in [
+            ("korean", self.korean),
+            ("math", self.math),
+            ("english", self.english),
+            ("science", self.science),
+        ] {
+            if !weight.is_finite() || weight < 0f64 {
+                return Err(WeightValidationError::NegativeWeight { subject, weight });
+            }
+        }
+        if self.korean + self.math + self.science <= 0f64 {
+            return Err(WeightValidationError::NoWeight);
+        }
+        if !(1..=2).contains(&self.science_required) {
+            return Err(WeightValidationError::ScienceRequired(
+                self.science_required,
+            ));
+        }
+        let aggregation = self.science_aggregation();
+        if aggregation.subjects() != self.science_required {
+            return Err(WeightValidationError::ScienceAggregation {
+                aggregation,
+                science_required: self.science_required,
+            });
         }
+        if !(1..=9).contains(&self.english_required) {
+            return Err(WeightValidationError::EnglishRequired(
+                self.english_required,
+            ));
+        }
+        let points = self.english_table.points();
+        match self.formula {
+            FormulaKind::DeductionEnglish => EnglishTable::deductions_of(points)?,
+            _ => EnglishTable::points_of(points)?,
+        };
+        Ok(())
+    }
+
+    /// Shared copy of the embedded weight of `univ` for `year`. Every weight
+    /// is built once, on the first call from any thread, so repeated
+    /// calculations don't rebuild or copy it.
+    pub fn load_cached(univ: University, year: usize) -> Option<Arc<Self>> {
+        EMBEDDED.get(&(univ, year)).cloned()
     }
 
     pub fn korean(&self) -> f64 {
@@ -382,11 +1471,41 @@ impl UniversityWeight {
         self.science_required
     }
 
+    /// How the 탐구 scores are combined. Weights that don't say, such as
+    /// TOML files from earlier releases, take the meaning
+    /// `science_required` had: `ScienceAggregation::for_required`.
+    pub fn science_aggregation(&self) -> ScienceAggregation {
+        self.science_aggregation
+            .unwrap_or_else(|| ScienceAggregation::for_required(self.science_required))
+    }
+
     pub fn english_required(&self) -> usize {
         self.english_required
     }
 
-    pub fn english_table(&self) -> &Vec<f64> {
+    pub fn formula(&self) -> FormulaKind {
+        self.formula
+    }
+
+    /// Table value for an English grade
+    #[deprecated(note = "use `english_table().get(grade)`")]
+    pub fn english_score(&self, grade: Grade) -> Option<f64> {
+        self.english_table.get(grade)
+    }
+
+    pub fn english_table(&self) -> &EnglishTable {
         &self.english_table
     }
+
+    /// Points of the covered English grades, 1등급 at index 0. Earlier releases
+    /// returned the table indexed by the grade itself from `english_table`.
+    #[deprecated(note = "use `english_table()`, which is looked up by `Grade`")]
+    pub fn english_table_slice(&self) -> &[f64] {
+        self.english_table.points()
+    }
+
+    /// Source of the numbers, if recorded; see `Provenance`
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
 }
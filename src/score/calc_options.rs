@@ -0,0 +1,297 @@
+//! What a calculation does with records and requests the plain
+//! `Record::calc_breakdown` takes at face value or refuses: missing
+//! English, scores out of range, a single 탐구 to double, years without
+//! weights.
+
+use super::{
+    range_problem, rank_scores, CalcBreakdown, CalcWarning, Grade, Record, Score, Subject,
+    University,
+};
+use crate::error::{Result, SuneungError};
+use crate::formula::ScienceAggregation;
+use crate::score::UniversityWeight;
+use std::borrow::Cow;
+
+/// Whether a questionable calculation goes ahead
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Strictness {
+    /// Fail with `SuneungError::Strict` on the first `CalcWarning`
+    Strict,
+    /// Go ahead as the other options say, listing each `CalcWarning` on the
+    /// breakdown
+    #[default]
+    Lenient,
+}
+
+/// How a record without English is scored
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum AbsentEnglish {
+    /// `SuneungError::MissingSubject`
+    #[default]
+    Error,
+    /// As the university's default grade, so English adds nothing
+    DefaultGrade,
+    /// As the given grade
+    Grade(Grade),
+}
+
+/// Which 탐구 a university doubling the better one takes
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum ScienceSelection {
+    /// The better of those recorded, by `Score::max_by` with what the
+    /// formula reflects 탐구 by
+    #[default]
+    Best,
+    /// This subject, whatever the other scored; `MissingSubject` without it
+    Only(Subject),
+}
+
+/// Options of `Record::calc_with_university_opts` and
+/// `Record::calc_all_opts`. The default is what `calc_breakdown` does,
+/// with warnings added for what it lets through silently.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct CalcOptions {
+    pub strictness: Strictness,
+    pub absent_english: AbsentEnglish,
+    pub science_selection: ScienceSelection,
+    /// Year whose weights stand in for a university without data for the
+    /// requested one
+    pub fallback_year: Option<usize>,
+    /// Decimal places the total is rounded to, after clamping
+    pub rounding_override: Option<u32>,
+}
+
+impl Record {
+    /// `calc_breakdown` under `options`. Warnings come in the order the
+    /// calculation ran into them: the year, the scores, English, 탐구, then
+    /// clamping.
+    ///
+    /// ```
+    /// use suneung_calc::error::SuneungError;
+    /// use suneung_calc::score::{
+    ///     AbsentEnglish, CalcOptions, CalcWarning, Grade, Record, Strictness, Subject, University,
+    /// };
+    ///
+    /// // No English, and a 백분위 of 101 as files of earlier releases could
+    /// // hold, which only the deprecated raw API still records
+    /// let mut record = Record::sample();
+    /// record.remove_subject(Subject::English);
+    /// let math = record.try_score(Subject::Math).unwrap();
+    /// #[allow(deprecated)]
+    /// record.record(Subject::Math, math.standard_score(), 101.0, math.rank());
+    /// assert!(record.calc_breakdown(University::SOGANG, 2024).is_err());
+    ///
+    /// let lenient = CalcOptions {
+    ///     absent_english: AbsentEnglish::DefaultGrade,
+    ///     ..CalcOptions::default()
+    /// };
+    /// let breakdown = record
+    ///     .calc_with_university_opts(University::SOGANG, 2024, &lenient)
+    ///     .unwrap();
+    /// assert_eq!(breakdown.english, 0.0);
+    /// assert_eq!(
+    ///     breakdown.warnings,
+    ///     [
+    ///         CalcWarning::ScoreOutOfRange {
+    ///             subject: Subject::Math,
+    ///             standard_score: math.standard_score(),
+    ///             percentile: 101.0,
+    ///         },
+    ///         CalcWarning::EnglishAssumed { grade: Grade::try_from(2u8).unwrap() },
+    ///     ]
+    /// );
+    ///
+    /// // Strict refuses at the first of them
+    /// let strict = CalcOptions {
+    ///     strictness: Strictness::Strict,
+    ///     ..lenient
+    /// };
+    /// match record.calc_with_university_opts(University::SOGANG, 2024, &strict) {
+    ///     Err(SuneungError::Strict(warning)) => assert_eq!(warning, breakdown.warnings[0]),
+    ///     other => panic!("{:?}", other),
+    /// }
+    ///
+    /// // A year without weights, with and without a fallback
+    /// let record = Record::sample();
+    /// assert!(record
+    ///     .calc_with_university_opts(University::SOGANG, 2030, &CalcOptions::default())
+    ///     .is_err());
+    /// let fallback = CalcOptions {
+    ///     fallback_year: Some(2024),
+    ///     rounding_override: Some(1),
+    ///     ..CalcOptions::default()
+    /// };
+    /// let breakdown = record
+    ///     .calc_with_university_opts(University::SOGANG, 2030, &fallback)
+    ///     .unwrap();
+    /// assert_eq!(breakdown.total, 388.5);
+    /// assert_eq!(
+    ///     breakdown.warnings,
+    ///     [CalcWarning::FallbackYear { requested: 2030, used: 2024 }]
+    /// );
+    /// ```
+    pub fn calc_with_university_opts(
+        &self,
+        university: University,
+        year: usize,
+        options: &CalcOptions,
+    ) -> Result<CalcBreakdown> {
+        if let Some(weight) = UniversityWeight::load_cached(university, year) {
+            return calc(self, &weight, options, vec![]);
+        }
+        let fallback = options
+            .fallback_year
+            .and_then(|used| Some((used, UniversityWeight::load_cached(university, used)?)));
+        let Some((used, weight)) = fallback else {
+            return Err(SuneungError::UnsupportedYear { university, year });
+        };
+        let warning = CalcWarning::FallbackYear {
+            requested: year,
+            used,
+        };
+        calc(self, &weight, options, vec![warning])
+    }
+
+    /// `calc_with_university_opts` with an explicit weight, e.g. one from
+    /// `UniversityWeightBuilder`
+    ///
+    /// ```
+    /// use suneung_calc::builder::UniversityWeightBuilder;
+    /// use suneung_calc::formula::ScienceAggregation;
+    /// use suneung_calc::score::{
+    ///     CalcOptions, CalcWarning, Record, ScienceSelection, Strictness, Subject, University,
+    ///     UniversityWeight,
+    /// };
+    ///
+    /// let sogang = UniversityWeight::try_load(University::SOGANG, 2024).unwrap();
+    /// let weight = UniversityWeightBuilder::from_weight(&sogang)
+    ///     .science_aggregation(ScienceAggregation::BestDoubled)
+    ///     .build()
+    ///     .unwrap();
+    /// let mut record = Record::sample();
+    /// record.remove_subject(Subject::EarthScience);
+    ///
+    /// // The one 탐구 recorded is doubled, which may not be what was meant
+    /// let lenient = record.calc_with_weight_opts(&weight, &CalcOptions::default()).unwrap();
+    /// assert_eq!(lenient.warnings, [CalcWarning::SingleScience { subject: Subject::Chemistry }]);
+    /// let strict = CalcOptions {
+    ///     strictness: Strictness::Strict,
+    ///     ..CalcOptions::default()
+    /// };
+    /// assert!(record.calc_with_weight_opts(&weight, &strict).is_err());
+    ///
+    /// // Unless it is chosen outright
+    /// let chosen = CalcOptions {
+    ///     science_selection: ScienceSelection::Only(Subject::Chemistry),
+    ///     ..strict
+    /// };
+    /// let breakdown = record.calc_with_weight_opts(&weight, &chosen).unwrap();
+    /// assert_eq!(breakdown.total, lenient.total);
+    /// ```
+    pub fn calc_with_weight_opts(
+        &self,
+        weight: &UniversityWeight,
+        options: &CalcOptions,
+    ) -> Result<CalcBreakdown> {
+        calc(self, weight, options, vec![])
+    }
+
+    /// `calc_all` under `options`, with the breakdown of each university.
+    /// With `Strictness::Strict` any warning fails the whole ranking, as any
+    /// error other than `UnsupportedYear` does.
+    pub fn calc_all_opts(
+        &self,
+        year: usize,
+        options: &CalcOptions,
+    ) -> Result<Vec<(University, CalcBreakdown)>> {
+        rank_scores(
+            University::iter()
+                .map(|univ| (univ, self.calc_with_university_opts(univ, year, options))),
+        )
+    }
+}
+
+// `weight.convert` of the record as `prepare` leaves it, after `warnings`
+// from choosing the weight
+fn calc(
+    record: &Record,
+    weight: &UniversityWeight,
+    options: &CalcOptions,
+    mut warnings: Vec<CalcWarning>,
+) -> Result<CalcBreakdown> {
+    let record = prepare(record, weight, options, &mut warnings)?;
+    let mut breakdown = weight.convert(&record)?;
+    warnings.append(&mut breakdown.warnings);
+    breakdown.warnings = warnings;
+    if let Some(places) = options.rounding_override {
+        let scale = 10f64.powi(places as i32);
+        breakdown.total = (breakdown.total * scale).round() / scale;
+    }
+
+    match (options.strictness, breakdown.warnings.first()) {
+        (Strictness::Strict, Some(&warning)) => Err(SuneungError::Strict(warning)),
+        _ => Ok(breakdown),
+    }
+}
+
+// `record` as `options` have the weight score it, with a warning for each
+// score out of range, English assumed and single 탐구 doubled
+fn prepare<'a>(
+    record: &'a Record,
+    weight: &UniversityWeight,
+    options: &CalcOptions,
+    warnings: &mut Vec<CalcWarning>,
+) -> Result<Cow<'a, Record>> {
+    let mut record = Cow::Borrowed(record);
+    for (subject, score) in record.iter() {
+        if range_problem(score, subject == Subject::English).is_some() {
+            warnings.push(CalcWarning::ScoreOutOfRange {
+                subject,
+                standard_score: score.standard_score(),
+                percentile: score.percentile(),
+            });
+        }
+    }
+
+    if !record.contains(Subject::English) {
+        let grade = match options.absent_english {
+            AbsentEnglish::Error => None,
+            AbsentEnglish::DefaultGrade => Some(Grade::try_from(weight.english_required())?),
+            AbsentEnglish::Grade(grade) => Some(grade),
+        };
+        if let Some(grade) = grade {
+            record
+                .to_mut()
+                .record_score(Subject::English, Score::grade_only(grade));
+            warnings.push(CalcWarning::EnglishAssumed { grade });
+        }
+    }
+
+    if weight.science_aggregation() == ScienceAggregation::BestDoubled {
+        let sciences = [Subject::Chemistry, Subject::EarthScience];
+        match options.science_selection {
+            ScienceSelection::Best => {
+                let recorded: Vec<Subject> = sciences
+                    .into_iter()
+                    .filter(|&s| record.contains(s))
+                    .collect();
+                if let [subject] = recorded[..] {
+                    warnings.push(CalcWarning::SingleScience { subject });
+                }
+            }
+            ScienceSelection::Only(subject) => {
+                if !sciences.contains(&subject) {
+                    return Err(SuneungError::InvalidInput(format!(
+                        "{} is not a science subject",
+                        subject.name()
+                    )));
+                }
+                record.require(subject)?;
+                for other in sciences.into_iter().filter(|&s| s != subject) {
+                    record.to_mut().remove_subject(other);
+                }
+            }
+        }
+    }
+    Ok(record)
+}
@@ -0,0 +1,81 @@
+//! 선택과목 of the 국어 and 수학 scores of a `Record`, printed next to the
+//! subject the way the 성적통지표 does: `국어(언어와매체)`, `수학(미적분)`
+
+use super::{Locale, Record, Subject};
+use crate::convert::Track;
+use crate::error::Result;
+
+impl Record {
+    /// Mark the score of `elective.subject()` as taken in `elective`,
+    /// replacing any elective it had. `MissingSubject` when the subject is
+    /// not recorded.
+    pub fn set_elective(&mut self, elective: Track) -> Result<()> {
+        self.update_score(elective.subject(), |s| s.elective = Some(elective))
+    }
+
+    /// Forget the elective of `subject`
+    pub fn clear_elective(&mut self, subject: Subject) -> Result<()> {
+        self.update_score(subject, |s| s.elective = None)
+    }
+
+    /// Elective of `subject`, when recorded and known
+    pub fn elective(&self, subject: Subject) -> Option<Track> {
+        self.try_score(subject)?.elective()
+    }
+
+    /// `subject` as the 성적통지표 prints it: 탐구 subjects under their area,
+    /// 국어 and 수학 with their elective when it is known
+    ///
+    /// ```
+    /// use suneung_calc::convert::{MathTrack, Track};
+    /// use suneung_calc::score::{Record, Subject};
+    ///
+    /// let mut record = Record::sample();
+    /// assert_eq!(record.display_subject(Subject::Math), "수학");
+    /// assert_eq!(record.display_subject(Subject::Chemistry), "탐구(화학Ⅰ)");
+    /// assert!(!record.to_string().contains("수학("));
+    ///
+    /// record.set_elective(Track::Math(MathTrack::Calculus)).unwrap();
+    /// assert_eq!(record.display_subject(Subject::Math), "수학(미적분)");
+    /// assert_eq!(record.display_subject(Subject::Korean), "국어");
+    /// assert_eq!(record.display_subject(Subject::English), "영어");
+    /// assert!(record.to_string().contains("수학(미적분)"));
+    ///
+    /// // Pasted 성적통지표 keep the electives they name
+    /// let text = "국어(언어와매체) 131 93 2 / 수학 128 89 2 / 탐구(화학Ⅰ) 64 92 2";
+    /// let pasted = Record::parse_report_text("홍길동", text).unwrap();
+    /// assert_eq!(pasted.display_subject(Subject::Korean), "국어(언어와매체)");
+    /// assert_eq!(pasted.display_subject(Subject::Math), "수학");
+    /// assert_eq!(pasted.elective(Subject::Math), None);
+    ///
+    /// // Only the scores of 국어 and 수학 have an elective
+    /// let mut english = Record::new("홍길동");
+    /// assert!(english.set_elective(Track::Math(MathTrack::Geometry)).is_err());
+    /// ```
+    pub fn display_subject(&self, subject: Subject) -> String {
+        self.localized_subject(subject, Locale::Korean)
+    }
+
+    // `display_subject` in `locale`, `Math (Calculus)` in English
+    pub(crate) fn localized_subject(&self, subject: Subject, locale: Locale) -> String {
+        let name = subject.localized_name(locale);
+        match (subject, locale) {
+            (Subject::Chemistry | Subject::EarthScience, Locale::Korean) => {
+                format!("탐구({})", name)
+            }
+            (Subject::Chemistry | Subject::EarthScience, Locale::English) => {
+                format!("Science ({})", name)
+            }
+            _ => match (self.elective(subject), locale) {
+                (None, _) => name.to_string(),
+                // The 성적통지표 writes the elective without spaces
+                (Some(track), Locale::Korean) => {
+                    format!("{}({})", name, track.label().replace(' ', ""))
+                }
+                (Some(track), Locale::English) => {
+                    format!("{} ({})", name, track.localized_label(locale))
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Points per English grade of a `UniversityWeight`, looked up by `Grade`
+//! so that no caller depends on how the table is laid out.
+
+use super::Grade;
+use crate::error::{SuneungError, WeightValidationError};
+use serde::{Deserialize, Serialize};
+
+const GRADES: usize = 9;
+
+/// Points for English grades 1 to 9, finite and never more for a worse
+/// grade, or, for `FormulaKind::DeductionEnglish`, the points deducted,
+/// non-negative and never fewer for a worse grade (see `deductions`). A
+/// table read by `from_legacy` may cover only the grades from 1등급 to
+/// some grade; `get` has no points for the grades after it. The serde form
+/// is the list of the covered values, 1등급 first, and reads either kind;
+/// lists are laid out as by `from_legacy`. `UniversityWeight::validate`
+/// checks that the kind suits the formula.
+///
+/// ```
+/// use suneung_calc::score::{EnglishTable, Grade};
+///
+/// let grade = |g: u8| Grade::try_from(g).unwrap();
+/// let table =
+///     EnglishTable::new([200.0, 196.0, 188.0, 160.0, 120.0, 80.0, 40.0, 20.0, 0.0]).unwrap();
+/// assert_eq!(table.get(grade(2)), Some(196.0));
+/// assert_eq!(table.delta(grade(2), grade(4)), Some(-36.0));
+/// assert_eq!(table.max_penalty(), 200.0);
+///
+/// // Nine grades, or a padding slot and nine grades, give the same table
+/// let padded = [200.0, 200.0, 196.0, 188.0, 160.0, 120.0, 80.0, 40.0, 20.0, 0.0];
+/// assert_eq!(EnglishTable::from_legacy(&padded).unwrap(), table);
+/// assert_eq!(EnglishTable::from_legacy(&padded[1..]).unwrap(), table);
+///
+/// // Six grades cover 1등급 to 6등급 only
+/// let short = EnglishTable::from_legacy(&padded[1..7]).unwrap();
+/// assert_eq!(short.get(grade(6)), Some(80.0));
+/// assert_eq!(short.get(grade(7)), None);
+/// assert_eq!(short.last_grade(), grade(6));
+///
+/// assert!(EnglishTable::new([90.0, 100.0, 80.0, 70.0, 60.0, 50.0, 40.0, 30.0, 20.0]).is_err());
+///
+/// // Points deducted, nothing for 1등급
+/// let deductions =
+///     EnglishTable::deductions([0.0, 0.5, 2.0, 4.0, 6.0, 8.0, 10.0, 12.0, 14.0]).unwrap();
+/// assert_eq!(deductions.get(grade(3)), Some(2.0));
+/// assert_eq!(deductions.max_penalty(), 14.0);
+/// assert!(EnglishTable::deductions([200.0, 196.0, 188.0, 160.0, 120.0, 80.0, 40.0, 20.0, 0.0]).is_err());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<f64>", into = "Vec<f64>")]
+pub struct EnglishTable {
+    // Points of the covered grades, then 0 for the others
+    points: [f64; GRADES],
+    // Grades covered, from 1등급
+    grades: usize,
+}
+
+impl EnglishTable {
+    /// Table of the points of grades 1 to 9, in that order
+    pub fn new(points: [f64; GRADES]) -> Result<Self, WeightValidationError> {
+        Self::points_of(&points)
+    }
+
+    /// Table of the points deducted for grades 1 to 9, in that order, as
+    /// `FormulaKind::DeductionEnglish` reads it: non-negative and never
+    /// fewer for a worse grade
+    pub fn deductions(points: [f64; GRADES]) -> Result<Self, WeightValidationError> {
+        Self::deductions_of(&points)
+    }
+
+    /// Table from the lists of earlier releases. Ten values are a padding
+    /// slot followed by grades 1 to 9, as `UniversityWeightBuilder` used to
+    /// store them. Up to nine values list the grades from 1등급 on, as in
+    /// `data_src/english_tables.csv`, and the table covers only those.
+    pub fn from_legacy(values: &[f64]) -> Result<Self, WeightValidationError> {
+        Self::points_of(layout(values)?)
+    }
+
+    /// Points of `grade`; `None` for a grade after `last_grade`
+    pub fn get(&self, grade: Grade) -> Option<f64> {
+        self.points().get(usize::from(grade) - 1).copied()
+    }
+
+    /// `get`, or `SuneungError::InsufficientData` for a grade the table
+    /// does not cover
+    pub(crate) fn require(&self, grade: Grade) -> crate::error::Result<f64> {
+        self.get(grade).ok_or_else(|| {
+            SuneungError::InsufficientData(format!(
+                "the English table has no points for {}등급, only 1등급 to {}등급",
+                grade,
+                self.last_grade()
+            ))
+        })
+    }
+
+    /// Worst grade the table has points for
+    pub fn last_grade(&self) -> Grade {
+        Grade::try_from(self.grades as u8).expect("tables cover 1 to 9 grades")
+    }
+
+    /// Points lost from 1등급 to `last_grade`, for either kind of table
+    pub fn max_penalty(&self) -> f64 {
+        (self.points[0] - self.points[self.grades - 1]).abs()
+    }
+
+    /// Change in points from the grade `from` to the grade `to`, negative
+    /// when `to` is worse; `None` when either is not covered
+    pub fn delta(&self, from: Grade, to: Grade) -> Option<f64> {
+        Some(self.get(to)? - self.get(from)?)
+    }
+
+    /// Points of the covered grades, 1등급 first
+    pub fn points(&self) -> &[f64] {
+        &self.points[..self.grades]
+    }
+
+    // `new` of the first `points.len()` grades
+    pub(crate) fn points_of(points: &[f64]) -> Result<Self, WeightValidationError> {
+        if let Some(&x) = points.iter().find(|x| !x.is_finite()) {
+            return Err(WeightValidationError::EnglishTableValue(x));
+        }
+        if let Some(i) = points.windows(2).position(|pair| pair[0] < pair[1]) {
+            return Err(WeightValidationError::EnglishTableRises {
+                better: i + 1,
+                worse: i + 2,
+            });
+        }
+        Self::covering(points)
+    }
+
+    // `deductions` of the first `points.len()` grades
+    pub(crate) fn deductions_of(points: &[f64]) -> Result<Self, WeightValidationError> {
+        if let Some(&x) = points.iter().find(|x| !(x.is_finite() && **x >= 0f64)) {
+            return Err(WeightValidationError::EnglishTableValue(x));
+        }
+        if let Some(i) = points.windows(2).position(|pair| pair[0] > pair[1]) {
+            return Err(WeightValidationError::EnglishDeductionFalls {
+                better: i + 1,
+                worse: i + 2,
+            });
+        }
+        Self::covering(points)
+    }
+
+    fn covering(points: &[f64]) -> Result<Self, WeightValidationError> {
+        if !(1..=GRADES).contains(&points.len()) {
+            return Err(WeightValidationError::EnglishTableLength(points.len()));
+        }
+        let mut table = [0f64; GRADES];
+        table[..points.len()].copy_from_slice(points);
+        Ok(Self {
+            points: table,
+            grades: points.len(),
+        })
+    }
+}
+
+// The covered values of a list read by `from_legacy`
+fn layout(values: &[f64]) -> Result<&[f64], WeightValidationError> {
+    match values.len() {
+        0 => Err(WeightValidationError::EnglishTableLength(0)),
+        n if n <= GRADES => Ok(values),
+        n if n == GRADES + 1 => Ok(&values[1..]),
+        n => Err(WeightValidationError::EnglishTableLength(n)),
+    }
+}
+
+impl TryFrom<Vec<f64>> for EnglishTable {
+    type Error = WeightValidationError;
+
+    fn try_from(values: Vec<f64>) -> Result<Self, Self::Error> {
+        let points = layout(&values)?;
+        Self::points_of(points).or_else(|e| Self::deductions_of(points).map_err(|_| e))
+    }
+}
+
+impl From<EnglishTable> for Vec<f64> {
+    fn from(table: EnglishTable) -> Self {
+        table.points().to_vec()
+    }
+}
@@ -0,0 +1,139 @@
+//! One record from partial records of the same student, e.g. 국어/수학/영어
+//! from the school and 탐구 from the student
+
+use super::{Record, Subject};
+use crate::error::MergeConflict;
+use crate::exam::ExamMeta;
+use std::cmp::Ordering;
+
+/// Which score `Record::merge` keeps for a subject both records have with
+/// different values
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+    PreferSelf,
+    PreferOther,
+    /// The score of the later exam, by `ExamId` and then `noted_at`. When
+    /// neither record is later, e.g. one has no exam, as `Error`.
+    PreferNewer,
+    /// `MergeConflict::Subjects`
+    #[default]
+    Error,
+}
+
+impl Record {
+    /// Every subject and tag of `self` and `other`. A subject both have with
+    /// different scores, counting their sources and electives, is settled by
+    /// `policy`, or the merge fails with every such subject. Scores keep
+    /// their sources and electives. The exam is that of the record whose scores win,
+    /// or either record's when only one has one. `MergeConflict::NameMismatch`
+    /// unless both records have the same name; see `merge_ignoring_name`.
+    ///
+    /// ```
+    /// use suneung_calc::error::MergeConflict;
+    /// use suneung_calc::exam::{ExamKind, ExamMeta};
+    /// use suneung_calc::score::{MergePolicy, Record, StandardScore, Subject};
+    ///
+    /// let sample = Record::sample();
+    /// let part = |subjects: &[Subject]| {
+    ///     let mut record = Record::new(sample.name());
+    ///     for &s in subjects {
+    ///         record.record_score(s, sample.try_score(s).unwrap());
+    ///     }
+    ///     record
+    /// };
+    /// let school = part(&[Subject::Korean, Subject::Math, Subject::English]);
+    /// let student = part(&[Subject::Chemistry, Subject::EarthScience]);
+    ///
+    /// // Disjoint parts merge under any policy
+    /// let merged = school.clone().merge(student, MergePolicy::Error).unwrap();
+    /// assert_eq!(merged.len(), 5);
+    /// assert_eq!(merged.try_score(Subject::Chemistry), sample.try_score(Subject::Chemistry));
+    ///
+    /// // The student remembers 수학 differently, from a later exam
+    /// let mut student = part(&[Subject::Math, Subject::Chemistry]);
+    /// student
+    ///     .set_standard_score_typed(Subject::Math, StandardScore::new(131.0).unwrap())
+    ///     .unwrap();
+    /// student.set_exam(Some(ExamMeta::new(ExamKind::Csat, 2024)));
+    /// let mut school = school;
+    /// school.set_exam(Some(ExamMeta::new(ExamKind::Mock6, 2024)));
+    /// let math = |student: &Record, policy| {
+    ///     let merged = school.clone().merge(student.clone(), policy)?;
+    ///     Ok::<_, MergeConflict>(merged.try_score(Subject::Math).unwrap().standard_score())
+    /// };
+    /// assert_eq!(math(&student, MergePolicy::PreferSelf), Ok(128.0));
+    /// assert_eq!(math(&student, MergePolicy::PreferOther), Ok(131.0));
+    /// assert_eq!(math(&student, MergePolicy::PreferNewer), Ok(131.0));
+    /// assert_eq!(math(&student, MergePolicy::Error), Err(MergeConflict::Subjects(vec![Subject::Math])));
+    ///
+    /// // Without an exam to tell which is newer
+    /// student.set_exam(None);
+    /// assert!(math(&student, MergePolicy::PreferNewer).is_err());
+    ///
+    /// // Records of different students
+    /// let other = Record::new("김철수");
+    /// assert!(matches!(
+    ///     school.clone().merge(other.clone(), MergePolicy::PreferSelf),
+    ///     Err(MergeConflict::NameMismatch { .. })
+    /// ));
+    /// assert!(school.merge_ignoring_name(other, MergePolicy::PreferSelf).is_ok());
+    /// ```
+    pub fn merge(self, other: Record, policy: MergePolicy) -> Result<Record, MergeConflict> {
+        if self.name != other.name {
+            return Err(MergeConflict::NameMismatch {
+                ours: self.name,
+                theirs: other.name,
+            });
+        }
+        self.merge_ignoring_name(other, policy)
+    }
+
+    /// `merge` of records that may have different names, keeping the name
+    /// of `self`
+    pub fn merge_ignoring_name(
+        mut self,
+        other: Record,
+        policy: MergePolicy,
+    ) -> Result<Record, MergeConflict> {
+        let conflicts: Vec<Subject> = other
+            .iter()
+            .filter(|&(subject, score)| self.try_score(subject).is_some_and(|s| s != *score))
+            .map(|(subject, _)| subject)
+            .collect();
+        let prefer_other = match policy {
+            MergePolicy::PreferSelf => false,
+            MergePolicy::PreferOther => true,
+            MergePolicy::PreferNewer => match newer(self.exam.as_ref(), other.exam.as_ref()) {
+                Some(ordering) => ordering == Ordering::Less,
+                None if conflicts.is_empty() => false,
+                None => return Err(MergeConflict::Subjects(conflicts)),
+            },
+            MergePolicy::Error if conflicts.is_empty() => false,
+            MergePolicy::Error => return Err(MergeConflict::Subjects(conflicts)),
+        };
+
+        for (subject, &score) in other.iter() {
+            let taken = prefer_other || !self.contains(subject);
+            if taken && self.try_score(subject) != Some(score) {
+                self.record_score(subject, score);
+            }
+        }
+        if (prefer_other && other.exam.is_some()) || self.exam.is_none() {
+            self.exam = other.exam;
+        }
+        for tag in &other.tags {
+            self.add_tag(tag);
+        }
+        Ok(self)
+    }
+}
+
+// How the exam of `ours` compares with that of `theirs`, `Greater` when
+// `ours` is newer; `None` when neither is
+fn newer(ours: Option<&ExamMeta>, theirs: Option<&ExamMeta>) -> Option<Ordering> {
+    let key = |exam: &ExamMeta| (exam.id(), exam.noted_at.clone());
+    match key(ours?).cmp(&key(theirs?)) {
+        Ordering::Equal => None,
+        ordering => Some(ordering),
+    }
+}
@@ -0,0 +1,131 @@
+//! Which of two scores is better, in one place for everything that picks a
+//! score, such as the 탐구 subject `ScienceAggregation::BestDoubled` doubles.
+//!
+//! Scores are ordered by a tie-break chain: first the metric asked for,
+//! then the other of 표준점수 and 백분위, then the lower (better) grade.
+//! `PartialOrd` for `Score` is the chain starting from 표준점수.
+
+use super::Score;
+use std::cmp::Ordering;
+
+/// What a score is judged by first
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum ScoreMetric {
+    /// 표준점수, then 백분위, then grade
+    #[default]
+    StandardScore,
+    /// 백분위, then 표준점수, then grade
+    Percentile,
+}
+
+impl ScoreMetric {
+    /// `score` by this metric alone
+    pub fn value(&self, score: &Score) -> f64 {
+        match self {
+            ScoreMetric::StandardScore => score.standard_score(),
+            ScoreMetric::Percentile => score.percentile(),
+        }
+    }
+
+    fn other(&self) -> ScoreMetric {
+        match self {
+            ScoreMetric::StandardScore => ScoreMetric::Percentile,
+            ScoreMetric::Percentile => ScoreMetric::StandardScore,
+        }
+    }
+
+    // The tie-break chain from this metric, `Greater` when `a` is better;
+    // `None` when a value is NaN
+    fn chain(&self, a: &Score, b: &Score) -> Option<Ordering> {
+        let first = self.value(a).partial_cmp(&self.value(b))?;
+        let second = self.other().value(a).partial_cmp(&self.other().value(b))?;
+        Some(first.then(second).then(b.rank.cmp(&a.rank)))
+    }
+}
+
+impl Score {
+    /// How `a` compares with `b` by the tie-break chain from 표준점수,
+    /// `Greater` when `a` is better. Scores with a NaN value, which
+    /// `Record::validate` reports, tie.
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use suneung_calc::exam::{ExamId, ExamKind};
+    /// use suneung_calc::score::{Grade, Percentile, Score, StandardScore};
+    ///
+    /// let score = |s: f64, p: f64, g: u8| {
+    ///     Score::new_typed(
+    ///         StandardScore::new(s).unwrap(),
+    ///         Percentile::new(p).unwrap(),
+    ///         Grade::try_from(g).unwrap(),
+    ///     )
+    /// };
+    /// let a = score(64.0, 92.0, 2);
+    ///
+    /// // 표준점수 first
+    /// let higher = score(65.0, 90.0, 3);
+    /// assert_eq!(Score::better_by_standard(&higher, &a), Ordering::Greater);
+    /// assert!(higher > a);
+    ///
+    /// // then 백분위
+    /// let same_standard = score(64.0, 93.0, 3);
+    /// assert_eq!(Score::better_by_standard(&same_standard, &a), Ordering::Greater);
+    /// // which comes first when judging by 백분위
+    /// assert_eq!(Score::better_by_percentile(&same_standard, &higher), Ordering::Greater);
+    ///
+    /// // then the lower grade
+    /// let same_values = score(64.0, 92.0, 1);
+    /// assert_eq!(Score::better_by_standard(&same_values, &a), Ordering::Greater);
+    /// assert!(same_values > a);
+    ///
+    /// // Scores alike in all three are equal only when `==`
+    /// assert_eq!(a.partial_cmp(&a), Some(Ordering::Equal));
+    /// let csat = a.with_source(ExamId::new(ExamKind::Csat, 2024));
+    /// assert_eq!(Score::better_by_standard(&csat, &a), Ordering::Equal);
+    /// assert_eq!(csat.partial_cmp(&a), None);
+    /// ```
+    pub fn better_by_standard(a: &Score, b: &Score) -> Ordering {
+        ScoreMetric::StandardScore
+            .chain(a, b)
+            .unwrap_or(Ordering::Equal)
+    }
+
+    /// `better_by_standard` judging by 백분위 first
+    pub fn better_by_percentile(a: &Score, b: &Score) -> Ordering {
+        ScoreMetric::Percentile
+            .chain(a, b)
+            .unwrap_or(Ordering::Equal)
+    }
+
+    /// The best of `scores` by the tie-break chain from `metric`, the
+    /// earlier winning a full tie; `None` when there are none
+    ///
+    /// ```
+    /// use suneung_calc::score::{Record, Score, ScoreMetric, Subject};
+    ///
+    /// let record = Record::sample();
+    /// let sciences = [Subject::Chemistry, Subject::EarthScience].map(|s| record.try_score(s).unwrap());
+    /// let best = Score::max_by(sciences, ScoreMetric::StandardScore).unwrap();
+    /// assert_eq!(Some(best), record.try_score(Subject::EarthScience));
+    /// assert!(Score::max_by([], ScoreMetric::Percentile).is_none());
+    /// ```
+    pub fn max_by<I: IntoIterator<Item = Score>>(scores: I, metric: ScoreMetric) -> Option<Score> {
+        scores
+            .into_iter()
+            .reduce(|best, score| match metric.chain(&score, &best) {
+                Some(Ordering::Greater) => score,
+                _ => best,
+            })
+    }
+}
+
+impl PartialOrd for Score {
+    /// `Score::better_by_standard`, but `None` rather than `Equal` for
+    /// scores that tie without being equal, e.g. differing only in source
+    fn partial_cmp(&self, other: &Score) -> Option<Ordering> {
+        match ScoreMetric::StandardScore.chain(self, other)? {
+            Ordering::Equal if self != other => None,
+            ordering => Some(ordering),
+        }
+    }
+}
@@ -0,0 +1,498 @@
+use super::{Grade, Percentile, Record, Score, StandardScore, Subject};
+use crate::atomic::write_all_atomic_with;
+use crate::checksum::{dataframe_checksum, verify_dataframe};
+use crate::convert::Track;
+use crate::error::SuneungError;
+use crate::exam::{ExamId, ExamMeta};
+use arrow2::array::{PrimitiveArray, Utf8Array};
+use arrow2::io::parquet::read::{infer_schema, read_metadata, FileReader};
+use peroxide::fuga::*;
+use std::path::Path;
+use std::time::SystemTime;
+
+const METRIC_COLUMN: &str = "Metric";
+const METRIC_ROWS: [&str; 3] = ["standard_score", "percentile", "grade"];
+// Optional column holding `[kind, year, noted_at]` of the exam
+const EXAM_COLUMN: &str = "Exam";
+// Optional column holding the tags, one per line, in its first row
+const TAGS_COLUMN: &str = "Tags";
+// Optional column holding `subject year kind` of each score with a source,
+// one per line, in its first row
+const SOURCES_COLUMN: &str = "Sources";
+// Optional column holding `subject track` of each score with an elective,
+// e.g. `Math calculus`, one per line, in its first row
+const ELECTIVES_COLUMN: &str = "Electives";
+/// Data root used by `write_parquet`/`read_parquet`, relative to the working
+/// directory
+pub const DATA_DIR: &str = "data";
+
+fn read_dataframe(dir: &Path, name: &str) -> crate::error::Result<DataFrame> {
+    crate::input::check_name(name)?;
+    let path = dir.join(name).join("record.parquet");
+    DataFrame::read_parquet(&path.to_string_lossy())
+        .map_err(|e| SuneungError::Parquet(e.to_string()))
+}
+
+// Only the columns accepted by `keep`; the others are never decoded
+fn read_columns<F: Fn(&str) -> bool>(
+    dir: &Path,
+    name: &str,
+    keep: F,
+) -> crate::error::Result<DataFrame> {
+    crate::input::check_name(name)?;
+    let parquet = |e: arrow2::error::Error| SuneungError::Parquet(e.to_string());
+    let mut reader = std::fs::File::open(dir.join(name).join("record.parquet"))?;
+    let metadata = read_metadata(&mut reader).map_err(parquet)?;
+    let schema = infer_schema(&metadata)
+        .map_err(parquet)?
+        .filter(|_, field| keep(&field.name));
+    let fields = schema.fields.clone();
+
+    let mut df = DataFrame::new(vec![]);
+    for chunk in FileReader::new(reader, metadata.row_groups, schema, None, None, None) {
+        for (field, array) in fields.iter().zip(chunk.map_err(parquet)?.into_arrays()) {
+            let array = array.as_any();
+            let series = if let Some(values) = array.downcast_ref::<PrimitiveArray<f64>>() {
+                Series::new(values.values().to_vec())
+            } else if let Some(values) = array.downcast_ref::<Utf8Array<i32>>() {
+                Series::new(values.values_iter().map(str::to_string).collect::<Vec<_>>())
+            } else {
+                return Err(SuneungError::Parquet(format!(
+                    "{}: unexpected type of column {}",
+                    name, field.name
+                )));
+            };
+            df.push(&field.name, series);
+        }
+    }
+    Ok(df)
+}
+
+// `Math 2024 Csat`, a line of the sources column
+fn parse_source(line: &str) -> Option<(Subject, ExamId)> {
+    let mut fields = line.split(' ');
+    let subject = fields.next()?;
+    let subject = Subject::all()
+        .iter()
+        .copied()
+        .find(|s| s.name() == subject)?;
+    let year = fields.next()?.parse().ok()?;
+    let kind = fields.next()?.parse().ok()?;
+    fields
+        .next()
+        .is_none()
+        .then(|| (subject, ExamId::new(kind, year)))
+}
+
+// `Math calculus`, a line of the electives column
+fn parse_elective(line: &str) -> Option<(Subject, Track)> {
+    let (subject, track) = line.split_once(' ')?;
+    let subject = Subject::all()
+        .iter()
+        .copied()
+        .find(|s| s.name() == subject)?;
+    let track: Track = track.parse().ok()?;
+    (track.subject() == subject).then_some((subject, track))
+}
+
+// The numbers of `column`; `Parquet` when it holds anything else
+fn f64_column<'a>(df: &'a DataFrame, name: &str, column: &str) -> crate::error::Result<&'a [f64]> {
+    match &df[column].values {
+        DTypeArray::F64(values) => Ok(values),
+        _ => Err(SuneungError::Parquet(format!(
+            "{}: column {} does not hold numbers",
+            name, column
+        ))),
+    }
+}
+
+// The strings of `column`; `Parquet` when it holds anything else
+fn str_column<'a>(
+    df: &'a DataFrame,
+    name: &str,
+    column: &str,
+) -> crate::error::Result<&'a [String]> {
+    match &df[column].values {
+        DTypeArray::Str(values) => Ok(values),
+        _ => Err(SuneungError::Parquet(format!(
+            "{}: column {} does not hold text",
+            name, column
+        ))),
+    }
+}
+
+fn has_metric_column(df: &DataFrame) -> bool {
+    df.header().iter().any(|h| h == METRIC_COLUMN)
+}
+
+/// A student directory found by `list_records`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredRecord {
+    pub name: String,
+    /// `None` when the record has no exam meta or cannot be read
+    pub exam: Option<ExamMeta>,
+    /// Modification time of `record.parquet`, if the platform reports one
+    pub modified: Option<SystemTime>,
+}
+
+/// Every student stored under `dir`, sorted by name. Only the exam column
+/// of each record is read, without checksum verification.
+pub fn list_records<P: AsRef<Path>>(dir: P) -> crate::error::Result<Vec<StoredRecord>> {
+    let dir = dir.as_ref();
+    let mut records = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let file = path.join("record.parquet");
+        if !file.exists() {
+            continue;
+        }
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        records.push(StoredRecord {
+            name: name.to_string(),
+            exam: Record::read_parquet_subjects_in(dir, name, &[])
+                .ok()
+                .and_then(|r| r.exam),
+            modified: std::fs::metadata(&file).and_then(|m| m.modified()).ok(),
+        });
+    }
+    records.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(records)
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ReadOptions {
+    /// Load the record even if it does not match its `record.sha256` sidecar
+    pub skip_verify: bool,
+}
+
+impl Record {
+    /// A leading `Metric` column labeling the rows (`METRIC_ROWS`), then one
+    /// column per recorded subject. Absent subjects have no column at all.
+    pub fn to_dataframe(&self) -> DataFrame {
+        // Metric names, one column per recorded subject, the exam, tags,
+        // sources and electives
+        let columns = 5 + self.len();
+        let mut header = Vec::with_capacity(columns);
+        let mut data = Vec::with_capacity(columns);
+        header.push(METRIC_COLUMN.to_string());
+        data.push(Series::new(
+            METRIC_ROWS
+                .iter()
+                .map(|m| m.to_string())
+                .collect::<Vec<_>>(),
+        ));
+        for (subject, score) in self.iter() {
+            let values = match subject {
+                Subject::English => [0f64, 0f64, score.rank().into()],
+                _ => [
+                    score.standard_score(),
+                    score.percentile(),
+                    score.rank().into(),
+                ],
+            };
+            header.push(subject.name().to_string());
+            data.push(Series::new(values.to_vec()));
+        }
+        if let Some(exam) = &self.exam {
+            header.push(EXAM_COLUMN.to_string());
+            data.push(Series::new(vec![
+                exam.kind.name().to_string(),
+                exam.year.to_string(),
+                exam.noted_at.clone().unwrap_or_default(),
+            ]));
+        }
+        // Tags never contain control characters, see `Record::add_tag`
+        if !self.tags.is_empty() {
+            header.push(TAGS_COLUMN.to_string());
+            data.push(Series::new(vec![
+                self.tags.join("\n"),
+                String::new(),
+                String::new(),
+            ]));
+        }
+        let sources: Vec<String> = self
+            .iter()
+            .filter_map(|(subject, score)| {
+                let source = score.source()?;
+                Some(format!(
+                    "{} {} {}",
+                    subject.name(),
+                    source.year,
+                    source.kind.name()
+                ))
+            })
+            .collect();
+        if !sources.is_empty() {
+            header.push(SOURCES_COLUMN.to_string());
+            data.push(Series::new(vec![
+                sources.join("\n"),
+                String::new(),
+                String::new(),
+            ]));
+        }
+        let electives: Vec<String> = self
+            .iter()
+            .filter_map(|(subject, score)| {
+                Some(format!("{} {}", subject.name(), score.elective()?.id()))
+            })
+            .collect();
+        if !electives.is_empty() {
+            header.push(ELECTIVES_COLUMN.to_string());
+            data.push(Series::new(vec![
+                electives.join("\n"),
+                String::new(),
+                String::new(),
+            ]));
+        }
+
+        let mut df = DataFrame::new(data);
+        *df.header_mut() = header;
+        df
+    }
+
+    pub fn write_parquet(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.write_parquet_in(DATA_DIR)
+    }
+
+    /// `write_parquet` under `dir` instead of `data`
+    pub fn write_parquet_in<P: AsRef<Path>>(
+        &self,
+        dir: P,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        crate::input::check_name(self.name())?;
+        let df = self.to_dataframe();
+        let path = dir.as_ref().join(self.name());
+        if !path.exists() {
+            std::fs::create_dir_all(&path)?;
+        }
+        // The sidecar is replaced together with the file it checks, so a
+        // failed write can't leave one of them stale
+        let checksum = dataframe_checksum(&df);
+        let targets = [path.join("record.parquet"), path.join("record.sha256")];
+        write_all_atomic_with(&[&targets[0], &targets[1]], |i, tmp| {
+            if i == 1 {
+                std::fs::write(tmp, &checksum)?;
+                return Ok(());
+            }
+            let tmp = tmp
+                .to_str()
+                .ok_or_else(|| format!("{} is not a UTF-8 path", tmp.display()))?;
+            df.write_parquet(tmp, CompressionOptions::Uncompressed)
+        })
+    }
+
+    pub fn read_parquet(name: &str) -> crate::error::Result<Self> {
+        Self::read_parquet_with(name, &ReadOptions::default())
+    }
+
+    pub fn read_parquet_with(name: &str, options: &ReadOptions) -> crate::error::Result<Self> {
+        Self::read_parquet_in(DATA_DIR, name, options)
+    }
+
+    /// `read_parquet_with` under `dir` instead of `data`
+    pub fn read_parquet_in<P: AsRef<Path>>(
+        dir: P,
+        name: &str,
+        options: &ReadOptions,
+    ) -> crate::error::Result<Self> {
+        let df = read_dataframe(dir.as_ref(), name)?;
+
+        if !options.skip_verify {
+            // Files written before checksums were introduced have no sidecar
+            match std::fs::read_to_string(dir.as_ref().join(name).join("record.sha256")) {
+                Ok(sidecar) => {
+                    if let Err((expected, found)) = verify_dataframe(&sidecar, &df) {
+                        return Err(SuneungError::ChecksumMismatch {
+                            name: name.to_string(),
+                            expected,
+                            found,
+                        });
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => (),
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Self::from_dataframe(&df, name)
+    }
+
+    /// `read_parquet_subjects_in` under `data`
+    pub fn read_parquet_subjects(name: &str, subjects: &[Subject]) -> crate::error::Result<Self> {
+        Self::read_parquet_subjects_in(DATA_DIR, name, subjects)
+    }
+
+    /// The record stored under `dir`, reading only `subjects` and the exam,
+    /// tag, source and elective columns. Other subjects are left out, as
+    /// are requested ones the file lacks. The checksum covers columns that
+    /// are never decoded, so it is not verified.
+    pub fn read_parquet_subjects_in<P: AsRef<Path>>(
+        dir: P,
+        name: &str,
+        subjects: &[Subject],
+    ) -> crate::error::Result<Self> {
+        let df = read_columns(dir.as_ref(), name, |column| {
+            column == METRIC_COLUMN
+                || column == EXAM_COLUMN
+                || column == TAGS_COLUMN
+                || column == SOURCES_COLUMN
+                || column == ELECTIVES_COLUMN
+                || subjects.iter().any(|s| s.name() == column)
+        })?;
+        Self::from_dataframe(&df, name)
+    }
+
+    fn from_dataframe(df: &DataFrame, name: &str) -> crate::error::Result<Self> {
+        // Legacy files have no Metric column and rely on METRIC_ROWS order
+        let rows = if has_metric_column(df) {
+            let labels = str_column(df, name, METRIC_COLUMN)?;
+            let mut rows = [0usize; 3];
+            for (row, metric) in rows.iter_mut().zip(METRIC_ROWS) {
+                *row = labels.iter().position(|l| l == metric).ok_or_else(|| {
+                    SuneungError::Parquet(format!("{}: missing {} row", name, metric))
+                })?;
+            }
+            rows
+        } else {
+            [0, 1, 2]
+        };
+        let [standard_score, percentile, grade] = rows;
+
+        let mut record = Record::new(name);
+        for &subject in Subject::all() {
+            if !df.header().iter().any(|h| h == subject.name()) {
+                continue;
+            }
+            let values = f64_column(df, name, subject.name())?;
+            let value = |row: usize| {
+                values.get(row).copied().ok_or_else(|| {
+                    SuneungError::Parquet(format!(
+                        "{}: column {} has {} rows, expected {}",
+                        name,
+                        subject.name(),
+                        values.len(),
+                        METRIC_ROWS.len()
+                    ))
+                })
+            };
+            let rank = Grade::try_from(value(grade)?).map_err(|e| SuneungError::InvalidScore {
+                subject,
+                reason: format!("{}: {}", name, e),
+            })?;
+            match subject {
+                Subject::English => record.record_score(subject, Score::grade_only(rank)),
+                // Files of earlier releases may hold scores out of range
+                _ => record.record_typed(
+                    subject,
+                    StandardScore::unchecked(value(standard_score)?),
+                    Percentile::unchecked(value(percentile)?),
+                    rank,
+                ),
+            }
+        }
+
+        if df.header().iter().any(|h| h == EXAM_COLUMN) {
+            let fields = str_column(df, name, EXAM_COLUMN)?;
+            let [kind, year, noted_at] = fields else {
+                return Err(SuneungError::Parquet(format!(
+                    "{}: malformed {} column",
+                    name, EXAM_COLUMN
+                )));
+            };
+            let year = year.parse().map_err(|_| {
+                SuneungError::Parquet(format!("{}: invalid exam year {:?}", name, year))
+            })?;
+            record.exam = Some(ExamMeta {
+                kind: kind.parse()?,
+                year,
+                noted_at: (!noted_at.is_empty()).then(|| noted_at.clone()),
+            });
+        }
+        if df.header().iter().any(|h| h == TAGS_COLUMN) {
+            let rows = str_column(df, name, TAGS_COLUMN)?;
+            for tag in rows.first().into_iter().flat_map(|tags| tags.lines()) {
+                record.add_tag(tag);
+            }
+        }
+        if df.header().iter().any(|h| h == SOURCES_COLUMN) {
+            let rows = str_column(df, name, SOURCES_COLUMN)?;
+            for line in rows.first().into_iter().flat_map(|sources| sources.lines()) {
+                let (subject, source) = parse_source(line).ok_or_else(|| {
+                    SuneungError::Parquet(format!("{}: malformed source {:?}", name, line))
+                })?;
+                // A source of a subject not read is dropped with it
+                if let Some(score) = record.try_score(subject) {
+                    record.scores.insert(subject, score.with_source(source));
+                }
+            }
+        }
+
+        if df.header().iter().any(|h| h == ELECTIVES_COLUMN) {
+            let rows = str_column(df, name, ELECTIVES_COLUMN)?;
+            for line in rows
+                .first()
+                .into_iter()
+                .flat_map(|electives| electives.lines())
+            {
+                let (subject, elective) = parse_elective(line).ok_or_else(|| {
+                    SuneungError::Parquet(format!("{}: malformed elective {:?}", name, line))
+                })?;
+                // An elective of a subject not read is dropped with it
+                if let Some(score) = record.try_score(subject) {
+                    record.scores.insert(subject, score.with_elective(elective));
+                }
+            }
+        }
+
+        Ok(record)
+    }
+
+    /// Whether the stored file predates the Metric column. Such files (English
+    /// stored as `[0, 0, grade]`, rows identified by position) still load.
+    pub fn is_legacy(name: &str) -> crate::error::Result<bool> {
+        Self::is_legacy_in(DATA_DIR, name)
+    }
+
+    /// `is_legacy` under `dir` instead of `data`
+    pub fn is_legacy_in<P: AsRef<Path>>(dir: P, name: &str) -> crate::error::Result<bool> {
+        Ok(!has_metric_column(&read_dataframe(dir.as_ref(), name)?))
+    }
+
+    /// Rewrite a legacy file in the current layout. Returns whether anything
+    /// was rewritten.
+    pub fn upgrade(name: &str) -> crate::error::Result<bool> {
+        Self::upgrade_in(DATA_DIR, name)
+    }
+
+    /// `upgrade` under `dir` instead of `data`
+    pub fn upgrade_in<P: AsRef<Path>>(dir: P, name: &str) -> crate::error::Result<bool> {
+        let dir = dir.as_ref();
+        if !Self::is_legacy_in(dir, name)? {
+            return Ok(false);
+        }
+        Self::read_parquet_in(dir, name, &ReadOptions::default())?
+            .write_parquet_in(dir)
+            .map_err(|e| SuneungError::Parquet(e.to_string()))?;
+        Ok(true)
+    }
+
+    /// `upgrade` every record under `data`, returning the upgraded names.
+    pub fn upgrade_all() -> crate::error::Result<Vec<String>> {
+        let mut upgraded = vec![];
+        for entry in std::fs::read_dir(DATA_DIR)? {
+            let path = entry?.path();
+            if !path.join("record.parquet").exists() {
+                continue;
+            }
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                if Self::upgrade(name)? {
+                    upgraded.push(name.to_string());
+                }
+            }
+        }
+        upgraded.sort();
+        Ok(upgraded)
+    }
+}
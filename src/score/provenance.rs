@@ -0,0 +1,104 @@
+//! Which exam each score came from, for records put together from several
+//! exams such as `Record::best_of` of a student's 모의평가 and 수능
+
+use super::{Grade, Percentile, Record, Score, StandardScore, Subject};
+use crate::error::{Result, SuneungError};
+use crate::exam::ExamId;
+
+impl Record {
+    /// `record_typed` of a score taken from `source`, which reports mark
+    /// next to the subject
+    pub fn record_with_source(
+        &mut self,
+        subject: Subject,
+        standard_score: StandardScore,
+        percentile: Percentile,
+        rank: Grade,
+        source: ExamId,
+    ) {
+        let score = Score::new_typed(standard_score, percentile, rank).with_source(source);
+        self.record_score(subject, score);
+    }
+
+    /// The best score of each subject over `history`, records of one
+    /// student from different exams: the highest 표준점수, or for English
+    /// the best grade, the earlier record winning ties. Each score keeps
+    /// the source it was recorded with, or else gets the exam of its record,
+    /// so the result has no exam of its own. Tags are those of every record.
+    /// `InvalidInput` when `history` is empty or the names differ.
+    ///
+    /// ```
+    /// use suneung_calc::exam::{ExamId, ExamKind, ExamMeta};
+    /// use suneung_calc::score::{Record, StandardScore, Subject};
+    ///
+    /// let mut june = Record::sample();
+    /// june.set_exam(Some(ExamMeta::new(ExamKind::Mock6, 2024)));
+    /// let mut csat = Record::sample();
+    /// csat.set_exam(Some(ExamMeta::new(ExamKind::Csat, 2024)));
+    /// let standard = |x| StandardScore::new(x).unwrap();
+    /// csat.set_standard_score_typed(Subject::Math, standard(135.0)).unwrap();
+    /// csat.set_standard_score_typed(Subject::Korean, standard(120.0)).unwrap();
+    ///
+    /// let best = Record::best_of(&[june, csat]).unwrap();
+    /// let source = |s| best.try_score(s).unwrap().source();
+    /// assert_eq!(best.standard_score(Subject::Math), 135.0);
+    /// assert_eq!(source(Subject::Math), Some(ExamId::new(ExamKind::Csat, 2024)));
+    /// assert_eq!(source(Subject::Korean), Some(ExamId::new(ExamKind::Mock6, 2024)));
+    /// assert!(best.exam().is_none());
+    ///
+    /// // Reports mark each score with the exam it came from
+    /// let report = best.to_string();
+    /// assert!(report.contains("국어¹"));
+    /// assert!(report.contains("수학²"));
+    /// assert!(report.contains("¹ 2024 6월 모의평가"));
+    /// assert!(report.contains("² 2024 수능"));
+    ///
+    /// // and so do merges of the result
+    /// let mut extra = Record::new(best.name());
+    /// extra.record_score(Subject::English, best.try_score(Subject::English).unwrap());
+    /// let merged = extra.merge(best.clone(), Default::default()).unwrap();
+    /// assert_eq!(merged.try_score(Subject::Math), best.try_score(Subject::Math));
+    ///
+    /// assert!(Record::best_of(&[]).is_err());
+    /// ```
+    pub fn best_of(history: &[Record]) -> Result<Record> {
+        let Some(first) = history.first() else {
+            return Err(SuneungError::InvalidInput("best of no records".to_string()));
+        };
+        if let Some(other) = history.iter().find(|r| r.name != first.name) {
+            return Err(SuneungError::InvalidInput(format!(
+                "best of records of {} and {}",
+                first.name, other.name
+            )));
+        }
+
+        let mut best = Record::new(&first.name);
+        for record in history {
+            let exam = record.exam().map(|e| e.id());
+            for (subject, &score) in record.iter() {
+                let score = Score {
+                    source: score.source.or(exam),
+                    ..score
+                };
+                if best
+                    .try_score(subject)
+                    .is_none_or(|b| better(subject, &score, &b))
+                {
+                    best.scores.insert(subject, score);
+                }
+            }
+            for tag in &record.tags {
+                best.add_tag(tag);
+            }
+        }
+        Ok(best)
+    }
+}
+
+// Whether `score` beats `best` of `subject`
+fn better(subject: Subject, score: &Score, best: &Score) -> bool {
+    match subject {
+        Subject::English => score.rank < best.rank,
+        _ => score.standard_score > best.standard_score,
+    }
+}
@@ -0,0 +1,329 @@
+//! Optional log of the changes to the scores of a `Record`, so that a
+//! correction made by mistake can be undone. Off until
+//! `Record::enable_history`; the log is kept in memory and written next to
+//! the record as `<dir>/<name>/history.csv` only by `write_history_in`.
+//!
+//! Each side of a revision takes six columns: 표준점수, 백분위, 등급,
+//! whether the 백분위 was estimated, the source exam (`2024 Csat`) and the
+//! 선택과목 (`calculus`), all empty for a subject that was not recorded.
+//! Files written before the last two columns existed are still read, with
+//! neither source nor 선택과목.
+
+#[cfg(feature = "csv")]
+use super::{Grade, Percentile, StandardScore};
+use super::{Record, Score, Subject};
+use crate::atomic::write_atomic;
+#[cfg(feature = "csv")]
+use crate::convert::Track;
+use crate::error::{Result, SuneungError};
+#[cfg(feature = "csv")]
+use crate::exam::ExamId;
+use std::path::Path;
+#[cfg(feature = "csv")]
+use std::time::Duration;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Revisions `Record::enable_history` keeps before dropping the oldest
+pub const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+const HISTORY_FILE: &str = "history.csv";
+const HISTORY_HEADER: &str = "at_unix_ms,subject,\
+    before_standard_score,before_percentile,before_grade,before_estimated,before_source,before_elective,\
+    after_standard_score,after_percentile,after_grade,after_estimated,after_source,after_elective";
+// Header of the files written before the source and elective columns
+#[cfg(feature = "csv")]
+const HISTORY_HEADER_V1: &str = "at_unix_ms,subject,\
+    before_standard_score,before_percentile,before_grade,before_estimated,\
+    after_standard_score,after_percentile,after_grade,after_estimated";
+
+/// One change to the score of `subject`; `None` for a subject that was not
+/// recorded before or is not after
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Revision {
+    pub subject: Subject,
+    pub before: Option<Score>,
+    pub after: Option<Score>,
+    pub at: SystemTime,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct History {
+    limit: usize,
+    revisions: Vec<Revision>,
+}
+
+impl History {
+    fn push(&mut self, revision: Revision) {
+        self.revisions.push(revision);
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        let excess = self.revisions.len().saturating_sub(self.limit);
+        self.revisions.drain(..excess);
+    }
+}
+
+impl Record {
+    /// Start logging every change made by `record`, `record_score`, the
+    /// `set_*` methods and `remove_subject`, keeping the last
+    /// `DEFAULT_HISTORY_LIMIT`
+    ///
+    /// ```
+    /// use suneung_calc::score::{Grade, Percentile, Record, StandardScore, Subject};
+    ///
+    /// let mut record = Record::sample();
+    /// let before = record.try_score(Subject::Math).unwrap();
+    /// record.enable_history();
+    ///
+    /// // A typo, fixed and then found to have been right
+    /// let typo = StandardScore::new(182.0).unwrap();
+    /// record.set_standard_score_typed(Subject::Math, typo).unwrap();
+    /// record.record_typed(
+    ///     Subject::Math,
+    ///     StandardScore::new(128.0).unwrap(),
+    ///     Percentile::new(89.0).unwrap(),
+    ///     Grade::try_from(2u8).unwrap(),
+    /// );
+    /// assert_eq!(record.history().len(), 2);
+    /// record.undo().unwrap();
+    /// record.undo().unwrap();
+    /// assert_eq!(record.try_score(Subject::Math), Some(before));
+    /// assert!(record.history().is_empty());
+    /// assert!(record.undo().is_err());
+    ///
+    /// // Removing a subject is undone too
+    /// record.remove_subject(Subject::English);
+    /// record.undo().unwrap();
+    /// assert!(record.contains(Subject::English));
+    /// ```
+    pub fn enable_history(&mut self) {
+        self.enable_history_with_limit(DEFAULT_HISTORY_LIMIT);
+    }
+
+    /// `enable_history` keeping the last `limit` revisions. On a record
+    /// already logging, only the limit changes, dropping the oldest
+    /// revisions beyond it.
+    pub fn enable_history_with_limit(&mut self, limit: usize) {
+        let history = self.history.get_or_insert_with(|| {
+            Box::new(History {
+                limit,
+                revisions: vec![],
+            })
+        });
+        history.limit = limit;
+        history.trim();
+    }
+
+    /// Stop logging and drop the revisions logged so far
+    pub fn disable_history(&mut self) {
+        self.history = None;
+    }
+
+    /// Logged revisions, oldest first; empty while history is off
+    pub fn history(&self) -> &[Revision] {
+        self.history
+            .as_ref()
+            .map_or(&[], |history| history.revisions.as_slice())
+    }
+
+    /// Revert the latest logged revision, restoring the exact score before
+    /// it. `InvalidInput` when there is none.
+    pub fn undo(&mut self) -> Result<()> {
+        let revision = self
+            .history
+            .as_mut()
+            .and_then(|history| history.revisions.pop())
+            .ok_or_else(|| SuneungError::InvalidInput(format!("{}: nothing to undo", self.name)))?;
+        match revision.before {
+            Some(score) => self.scores.insert(revision.subject, score),
+            None => self.scores.remove(&revision.subject),
+        };
+        Ok(())
+    }
+
+    // Log a change of `subject` made by an edit, if history is on
+    pub(super) fn log_revision(
+        &mut self,
+        subject: Subject,
+        before: Option<Score>,
+        after: Option<Score>,
+    ) {
+        if let Some(history) = self.history.as_mut() {
+            history.push(Revision {
+                subject,
+                before,
+                after,
+                at: SystemTime::now(),
+            });
+        }
+    }
+
+    /// Replace the `history.csv` of the record under `dir` with its logged
+    /// revisions
+    pub fn write_history_in<P: AsRef<Path>>(&self, dir: P) -> Result<()> {
+        crate::input::check_name(self.name())?;
+        let path = dir.as_ref().join(self.name());
+        std::fs::create_dir_all(&path)?;
+        let mut text = format!("{}\n", HISTORY_HEADER);
+        for revision in self.history() {
+            let at = revision
+                .at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis();
+            let fields = [score_fields(revision.before), score_fields(revision.after)];
+            text.push_str(&format!(
+                "{},{},{}\n",
+                at,
+                revision.subject.name(),
+                fields.join(",")
+            ));
+        }
+        write_atomic(&path.join(HISTORY_FILE), |tmp| {
+            std::fs::write(tmp, text)?;
+            Ok(())
+        })
+        .map_err(|e| SuneungError::Io(std::io::Error::other(e.to_string())))
+    }
+
+    /// Turn history on, replacing the logged revisions with those of the
+    /// `history.csv` of the record under `dir`; none when it was never
+    /// written. Revisions beyond the limit are dropped, oldest first, so
+    /// call `enable_history_with_limit` first for another limit.
+    #[cfg(feature = "csv")]
+    pub fn load_history_in<P: AsRef<Path>>(&mut self, dir: P) -> Result<()> {
+        crate::input::check_name(self.name())?;
+        let path = dir.as_ref().join(self.name()).join(HISTORY_FILE);
+        let revisions = match crate::input::read_to_string(&path) {
+            Ok(text) => parse_history(&text)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e.into()),
+        };
+        if self.history.is_none() {
+            self.enable_history();
+        }
+        if let Some(history) = self.history.as_mut() {
+            history.revisions = revisions;
+            history.trim();
+        }
+        Ok(())
+    }
+}
+
+// Six CSV fields of a score, all empty for `None`
+fn score_fields(score: Option<Score>) -> String {
+    match score {
+        Some(s) => format!(
+            "{},{},{},{},{},{}",
+            s.standard_score,
+            s.percentile,
+            s.rank,
+            s.percentile_estimated,
+            s.source
+                .map(|id| format!("{} {}", id.year, id.kind.name()))
+                .unwrap_or_default(),
+            s.elective.map(|track| track.id()).unwrap_or_default()
+        ),
+        None => ",,,,,".to_string(),
+    }
+}
+
+#[cfg(feature = "csv")]
+fn parse_history(text: &str) -> Result<Vec<Revision>> {
+    crate::input::check_len("history", text)?;
+    let invalid = |line: usize, reason: String| {
+        SuneungError::InvalidInput(format!("history line {}: {}", line, reason))
+    };
+    let mut rdr = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(text.as_bytes());
+    // Fields per score: 6, or 4 in files without source and elective
+    let header = rdr
+        .headers()
+        .map_err(|e| invalid(1, e.to_string()))?
+        .iter()
+        .collect::<Vec<_>>()
+        .join(",");
+    let width = match header.as_str() {
+        HISTORY_HEADER => 6,
+        HISTORY_HEADER_V1 => 4,
+        _ => return Err(invalid(1, format!("unknown header {:?}", header))),
+    };
+    let mut revisions = vec![];
+    for (i, row) in rdr.records().enumerate() {
+        // +2: 1-based, after the header
+        let line = i + 2;
+        let row = row.map_err(|e| invalid(line, e.to_string()))?;
+        let field = |j: usize| row.get(j).unwrap_or_default();
+        let at = field(0)
+            .parse()
+            .map_err(|_| invalid(line, format!("time {:?} is not a number", field(0))))?;
+        let subject = Subject::all()
+            .iter()
+            .copied()
+            .find(|s| s.name() == field(1))
+            .ok_or_else(|| invalid(line, format!("unknown subject {:?}", field(1))))?;
+        let score = |start: usize| {
+            let fields = [0, 1, 2, 3, 4, 5].map(|k| if k < width { field(start + k) } else { "" });
+            let score = parse_score(fields).map_err(|reason| invalid(line, reason))?;
+            match score.and_then(|s| s.elective) {
+                Some(track) if track.subject() != subject => Err(invalid(
+                    line,
+                    format!("{} is not a 선택과목 of {}", track, subject.name()),
+                )),
+                _ => Ok(score),
+            }
+        };
+        revisions.push(Revision {
+            subject,
+            before: score(2)?,
+            after: score(2 + width)?,
+            at: UNIX_EPOCH + Duration::from_millis(at),
+        });
+    }
+    Ok(revisions)
+}
+
+#[cfg(feature = "csv")]
+fn parse_score(fields: [&str; 6]) -> std::result::Result<Option<Score>, String> {
+    if fields.iter().all(|f| f.is_empty()) {
+        return Ok(None);
+    }
+    let number = |f: &str| {
+        f.parse::<f64>()
+            .map_err(|_| format!("score {:?} is not a number", f))
+    };
+    let [standard_score, percentile, grade, estimated, source, elective] = fields;
+    let rank = grade
+        .parse::<Grade>()
+        .map_err(|_| format!("invalid grade {:?}", grade))?;
+    let percentile_estimated = estimated
+        .parse()
+        .map_err(|_| format!("estimated flag {:?} is not true or false", estimated))?;
+    let source = match source.split_once(' ') {
+        _ if source.is_empty() => None,
+        Some((year, kind)) => Some(ExamId::new(
+            kind.parse()
+                .map_err(|_| format!("unknown exam kind {:?}", kind))?,
+            year.parse()
+                .map_err(|_| format!("exam year {:?} is not a number", year))?,
+        )),
+        None => return Err(format!("source {:?} is not a year and exam", source)),
+    };
+    let elective = match elective {
+        "" => None,
+        id => Some(
+            id.parse::<Track>()
+                .map_err(|_| format!("unknown 선택과목 {:?}", id))?,
+        ),
+    };
+    Ok(Some(Score {
+        standard_score: StandardScore::unchecked(number(standard_score)?),
+        percentile: Percentile::unchecked(number(percentile)?),
+        rank,
+        percentile_estimated,
+        source,
+        elective,
+    }))
+}
@@ -0,0 +1,111 @@
+//! Made-up records for doctests, examples and downstream snapshot tests.
+//! None of them belongs to a real student, and their scores are kept the
+//! same from release to release.
+
+use super::{Grade, Percentile, Record, Score, StandardScore, Subject};
+
+// (표준점수, 백분위, 등급) per subject; English takes only the grade
+type Scores = [Option<(f64, f64, u8)>; 5];
+
+fn synthetic(name: &str, scores: Scores) -> Record {
+    let mut record = Record::new(name);
+    for (&subject, score) in Subject::all().iter().zip(scores) {
+        let Some((standard, percentile, grade)) = score else {
+            continue;
+        };
+        let grade = Grade::try_from(grade).expect("sample grades are 1-9");
+        match subject {
+            Subject::English => record.record_score(subject, Score::grade_only(grade)),
+            _ => record.record_typed(
+                subject,
+                StandardScore::new(standard).expect("sample scores are in range"),
+                Percentile::new(percentile).expect("sample scores are in range"),
+                grade,
+            ),
+        }
+    }
+    record
+}
+
+impl Record {
+    /// Synthetic science-track record in the style of the 2024 수능: 국어
+    /// 131/93/2, 수학 128/89/2, 영어 2등급, 화학Ⅰ 64/92/2, 지구과학Ⅰ
+    /// 66/94/1 (표준점수/백분위/등급).
+    ///
+    /// ```
+    /// use suneung_calc::score::{Record, Subject, University};
+    ///
+    /// let record = Record::sample();
+    /// assert_eq!(record.len(), Subject::all().len());
+    /// assert!(record.try_calc_with_university(University::KONKUK, 2024).is_ok());
+    /// ```
+    pub fn sample() -> Record {
+        synthetic(
+            "샘플",
+            [
+                Some((131f64, 93f64, 2)),
+                Some((128f64, 89f64, 2)),
+                Some((0f64, 0f64, 2)),
+                Some((64f64, 92f64, 2)),
+                Some((66f64, 94f64, 1)),
+            ],
+        )
+    }
+
+    /// Synthetic records, best first: an upper band one, `sample`, a middle
+    /// and a lower band one, then a record without 지구과학Ⅰ for the
+    /// `MissingSubject` path of universities requiring two sciences.
+    ///
+    /// ```
+    /// use suneung_calc::score::{Record, Subject};
+    ///
+    /// let samples = Record::samples();
+    /// assert_eq!(samples.len(), 5);
+    /// assert!(!samples[4].contains(Subject::EarthScience));
+    /// ```
+    pub fn samples() -> Vec<Record> {
+        vec![
+            synthetic(
+                "샘플 상위권",
+                [
+                    Some((139f64, 99f64, 1)),
+                    Some((145f64, 99f64, 1)),
+                    Some((0f64, 0f64, 1)),
+                    Some((70f64, 98f64, 1)),
+                    Some((71f64, 99f64, 1)),
+                ],
+            ),
+            Record::sample(),
+            synthetic(
+                "샘플 중위권",
+                [
+                    Some((120f64, 80f64, 3)),
+                    Some((118f64, 78f64, 3)),
+                    Some((0f64, 0f64, 3)),
+                    Some((58f64, 80f64, 3)),
+                    Some((60f64, 82f64, 3)),
+                ],
+            ),
+            synthetic(
+                "샘플 하위권",
+                [
+                    Some((105f64, 55f64, 5)),
+                    Some((98f64, 48f64, 5)),
+                    Some((0f64, 0f64, 5)),
+                    Some((48f64, 50f64, 5)),
+                    Some((50f64, 56f64, 5)),
+                ],
+            ),
+            synthetic(
+                "샘플 과탐 1과목",
+                [
+                    Some((124f64, 86f64, 3)),
+                    Some((121f64, 82f64, 3)),
+                    Some((0f64, 0f64, 2)),
+                    Some((61f64, 85f64, 3)),
+                    None,
+                ],
+            ),
+        ]
+    }
+}
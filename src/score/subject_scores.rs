@@ -0,0 +1,101 @@
+//! Storage of the scores of a `Record`: one slot per subject, so lookups are
+//! an index, nothing is allocated and every iteration, `Debug` output and
+//! serialization follows `Subject::all()` whatever the recording order.
+
+use super::{Score, Subject};
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::collections::HashMap;
+
+// `Subject` is declared in `Subject::all()` order, the last one last
+const SUBJECTS: usize = Subject::EarthScience as usize + 1;
+
+#[derive(Copy, Clone, Default, PartialEq)]
+pub(super) struct SubjectScores([Option<Score>; SUBJECTS]);
+
+// Position of `subject` in `Subject::all()`
+fn slot(subject: Subject) -> usize {
+    subject as usize
+}
+
+impl SubjectScores {
+    pub(super) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(super) fn get(&self, subject: &Subject) -> Option<&Score> {
+        self.0[slot(*subject)].as_ref()
+    }
+
+    pub(super) fn insert(&mut self, subject: Subject, score: Score) -> Option<Score> {
+        self.0[slot(subject)].replace(score)
+    }
+
+    pub(super) fn remove(&mut self, subject: &Subject) -> Option<Score> {
+        self.0[slot(*subject)].take()
+    }
+
+    pub(super) fn contains_key(&self, subject: &Subject) -> bool {
+        self.get(subject).is_some()
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.0.iter().flatten().count()
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.0.iter().all(Option::is_none)
+    }
+
+    /// Recorded scores in `Subject::all()` order
+    pub(super) fn iter(&self) -> impl Iterator<Item = (Subject, &Score)> + '_ {
+        Subject::all()
+            .iter()
+            .zip(&self.0)
+            .filter_map(|(&subject, score)| score.as_ref().map(|s| (subject, s)))
+    }
+}
+
+impl std::fmt::Debug for SubjectScores {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// The same map of subject names to scores the former `HashMap` produced,
+// so stored JSON and bincode data keep loading
+impl Serialize for SubjectScores {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (subject, score) in self.iter() {
+            map.serialize_entry(&subject, score)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for SubjectScores {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let map = HashMap::<Subject, Score>::deserialize(deserializer)?;
+        let mut scores = Self::new();
+        for (subject, score) in map {
+            scores.insert(subject, score);
+        }
+        Ok(scores)
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for SubjectScores {
+    fn schema_name() -> String {
+        HashMap::<Subject, Score>::schema_name()
+    }
+
+    fn is_referenceable() -> bool {
+        HashMap::<Subject, Score>::is_referenceable()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        HashMap::<Subject, Score>::json_schema(gen)
+    }
+}
@@ -0,0 +1,75 @@
+//! Free-form labels on a `Record` ("재수생", "A반", ...) for slicing a
+//! cohort with `Cohort::filter_tag`
+
+use super::Record;
+use serde::{Deserialize, Deserializer};
+
+// `tag` as stored: `input::normalize`d, control characters as spaces and
+// trimmed; `None` when nothing is left
+fn normalize_tag(tag: &str) -> Option<String> {
+    let tag: String = crate::input::normalize(tag)
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    let tag = tag.trim();
+    (!tag.is_empty()).then(|| tag.to_string())
+}
+
+// Tags as `add_tag` would have left them, for serde forms edited by hand
+pub(super) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<String>, D::Error> {
+    let mut record = Record::new("");
+    for tag in Vec::<String>::deserialize(d)? {
+        record.add_tag(&tag);
+    }
+    Ok(record.tags)
+}
+
+impl Record {
+    /// Add `tag`, trimmed and with full-width forms and control characters
+    /// replaced. Blank tags and tags the record already has are ignored.
+    ///
+    /// ```
+    /// use suneung_calc::score::Record;
+    ///
+    /// let mut record = Record::sample();
+    /// record.add_tag(" 재수생 ");
+    /// record.add_tag("Ａ반");
+    /// record.add_tag("재수생");
+    /// record.add_tag("  ");
+    /// assert_eq!(record.tags(), ["재수생", "A반"]);
+    /// assert!(record.has_tag("A반"));
+    ///
+    /// // Every serde form keeps them
+    /// let json = serde_json::to_string(&record).unwrap();
+    /// let back: suneung_calc::score::Record = serde_json::from_str(&json).unwrap();
+    /// assert_eq!(back.tags(), record.tags());
+    /// ```
+    pub fn add_tag(&mut self, tag: &str) {
+        if let Some(tag) = normalize_tag(tag) {
+            if !self.tags.contains(&tag) {
+                self.tags.push(tag);
+            }
+        }
+    }
+
+    /// Remove `tag`, normalized as by `add_tag`. Returns whether the record
+    /// had it.
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let Some(tag) = normalize_tag(tag) else {
+            return false;
+        };
+        let before = self.tags.len();
+        self.tags.retain(|t| *t != tag);
+        self.tags.len() < before
+    }
+
+    /// Tags in the order they were added
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Whether the record has `tag`, normalized as by `add_tag`
+    pub fn has_tag(&self, tag: &str) -> bool {
+        normalize_tag(tag).is_some_and(|tag| self.tags.contains(&tag))
+    }
+}
@@ -0,0 +1,130 @@
+//! 표준점수 and 백분위 as their own types, so that one cannot be passed for
+//! the other: `Record::record_typed` takes them in a fixed order and
+//! rejects `(93, 131)` at compile time.
+//!
+//! Both are validated by `new` and `FromStr` only. Serde takes stored values
+//! as they are, so records written by earlier releases with a 백분위 of 101
+//! still load and are reported by `Record::validate`.
+
+use crate::error::{Result, SuneungError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::{Add, Sub};
+
+/// 표준점수, 0 to 200. Sums and differences are plain numbers, since 표점합
+/// and gaps are not standard scores themselves.
+///
+/// ```
+/// use suneung_calc::score::{Percentile, StandardScore};
+///
+/// let korean = StandardScore::new(131.0).unwrap();
+/// let math: StandardScore = "137".parse().unwrap();
+/// assert_eq!(korean + math, 268.0);
+/// assert_eq!(math - korean, 6.0);
+/// assert_eq!([korean, math].into_iter().sum::<f64>(), 268.0);
+/// assert!(StandardScore::new(201.0).is_err());
+/// assert!(Percentile::new(131.0).is_err());
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct StandardScore(f64);
+
+/// 백분위, 0 to 100
+#[derive(Debug, Copy, Clone, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct Percentile(f64);
+
+// `new`, `get`, conversions and arithmetic of a bounded score type
+macro_rules! bounded {
+    ($ty:ident, $what:literal, $max:literal) => {
+        impl $ty {
+            pub const MIN: $ty = $ty(0f64);
+            pub const MAX: $ty = $ty($max as f64);
+
+            #[doc = concat!("`ScoreOutOfRange` unless `value` is within 0 and ", stringify!($max))]
+            pub fn new(value: f64) -> Result<Self> {
+                if (Self::MIN.0..=Self::MAX.0).contains(&value) {
+                    Ok(Self(value))
+                } else {
+                    Err(SuneungError::ScoreOutOfRange(format!(
+                        concat!($what, " must be 0-", stringify!($max), ", got {}"),
+                        value
+                    )))
+                }
+            }
+
+            /// Value as it is stored, for arithmetic and output
+            pub fn get(&self) -> f64 {
+                self.0
+            }
+
+            // Values of files and callers that predate validation, checked
+            // later by `Record::validate` if at all
+            pub(crate) fn unchecked(value: f64) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$ty> for f64 {
+            fn from(value: $ty) -> Self {
+                value.0
+            }
+        }
+
+        impl TryFrom<f64> for $ty {
+            type Error = SuneungError;
+
+            fn try_from(value: f64) -> Result<Self> {
+                Self::new(value)
+            }
+        }
+
+        impl fmt::Display for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl std::str::FromStr for $ty {
+            type Err = SuneungError;
+
+            /// Full-width digits are accepted, see `input::normalize`
+            fn from_str(s: &str) -> Result<Self> {
+                let value: f64 = crate::input::normalize(s).trim().parse().map_err(|_| {
+                    SuneungError::ScoreOutOfRange(format!(
+                        concat!($what, " must be 0-", stringify!($max), ", got {:?}"),
+                        s
+                    ))
+                })?;
+                Self::new(value)
+            }
+        }
+
+        impl Add for $ty {
+            type Output = f64;
+
+            fn add(self, other: Self) -> f64 {
+                self.0 + other.0
+            }
+        }
+
+        impl Sub for $ty {
+            type Output = f64;
+
+            fn sub(self, other: Self) -> f64 {
+                self.0 - other.0
+            }
+        }
+
+        impl std::iter::Sum<$ty> for f64 {
+            fn sum<I: Iterator<Item = $ty>>(iter: I) -> f64 {
+                iter.map(|x| x.0).sum()
+            }
+        }
+    };
+}
+
+bounded!(StandardScore, "standard score", 200);
+bounded!(Percentile, "percentile", 100);
@@ -0,0 +1,229 @@
+use crate::api::compare;
+pub use crate::api::{
+    CalcAllRequest, CalcRequest, CalcResponse, CalculatorForm, RankedScore, UniversityInfo,
+    YearQuery,
+};
+use crate::error::SuneungError;
+use crate::report::{html, ReportOptions};
+use crate::score::{Locale, University};
+use axum::extract::Query;
+use axum::http::StatusCode;
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Form, Json, Router};
+use serde::Serialize;
+use std::net::SocketAddr;
+
+#[derive(Debug, Clone, Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+/// Every handler error is a client error: the inputs are the only thing that
+/// can make a calculation fail.
+#[derive(Debug)]
+pub struct ApiError(pub SuneungError);
+
+impl From<SuneungError> for ApiError {
+    fn from(e: SuneungError) -> Self {
+        Self(e)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ErrorBody {
+            error: self.0.to_string(),
+        };
+        (StatusCode::BAD_REQUEST, Json(body)).into_response()
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Handlers (plain functions over the library API)
+// └──────────────────────────────────────────────────────────┘
+pub fn calc(req: CalcRequest) -> Result<CalcResponse, SuneungError> {
+    req.record.validate()?;
+    let breakdown = req.record.calc_breakdown(req.university, req.year)?;
+    Ok(CalcResponse {
+        name: req.record.name().to_string(),
+        university: req.university,
+        university_name: req.university.name().to_string(),
+        year: req.year,
+        score: breakdown.total,
+        breakdown,
+    })
+}
+
+/// Every university with data for the year, best first. A university the
+/// record cannot be scored at keeps its row, unranked and marked
+/// `Ineligible`, after the ranked ones; only an invalid record fails the
+/// request.
+///
+/// ```
+/// use suneung_calc::api::Eligibility;
+/// use suneung_calc::score::{Record, Subject};
+/// use suneung_calc::server::{calc_all, CalcAllRequest};
+///
+/// let record = Record::sample();
+/// let rows = calc_all(CalcAllRequest { record: record.clone(), year: 2024 }).unwrap();
+/// assert_eq!(rows[0].rank, Some(1));
+///
+/// // Every 2024 university requires two sciences
+/// let mut one_science = record;
+/// one_science.remove_subject(Subject::EarthScience);
+/// let unranked = calc_all(CalcAllRequest { record: one_science, year: 2024 }).unwrap();
+/// assert_eq!(unranked.len(), rows.len());
+/// assert!(unranked.iter().all(|row| row.rank.is_none()
+///     && matches!(
+///         &row.eligibility,
+///         Eligibility::Ineligible { code, subject: Some(Subject::EarthScience), .. }
+///             if code == "missing_subject"
+///     )));
+/// ```
+pub fn calc_all(req: CalcAllRequest) -> Result<Vec<RankedScore>, SuneungError> {
+    req.record.validate()?;
+    let universities = University::supported(req.year);
+    let ranked = compare(&req.record, &universities, req.year, true)
+        .into_iter()
+        .map(|row| RankedScore {
+            rank: row.rank,
+            university: row.university,
+            university_name: row.university_name,
+            admission_group: row.university.admission_group(req.year),
+            score: row.score,
+            eligibility: row.eligibility,
+        })
+        .collect();
+    Ok(ranked)
+}
+
+pub fn universities(year: usize) -> Vec<UniversityInfo> {
+    University::supported(year)
+        .into_iter()
+        .map(|u| UniversityInfo {
+            id: u,
+            name: u.name().to_string(),
+        })
+        .collect()
+}
+
+/// The calculator page for a submitted `form`, or the blank page for
+/// `None`. `Err` is the page showing why the form does not make a record.
+///
+/// ```
+/// use suneung_calc::score::Locale;
+/// use suneung_calc::server::{calculator_page, CalculatorForm};
+///
+/// let field = |x: &str| x.to_string();
+/// let mut form = CalculatorForm {
+///     year: field("2024"),
+///     korean_standard: field("131"),
+///     korean_percentile: field("93"),
+///     korean_grade: field("2"),
+///     math_standard: field("128"),
+///     math_percentile: field("89"),
+///     math_grade: field("2"),
+///     english_grade: field("2"),
+///     chemistry_standard: field("64"),
+///     chemistry_percentile: field("92"),
+///     chemistry_grade: field("2"),
+///     earth_science_standard: field("66"),
+///     earth_science_percentile: field("94"),
+///     earth_science_grade: field("1"),
+///     ..Default::default()
+/// };
+///
+/// // Every university ranked, under the form filled in as it was typed
+/// let page = calculator_page(Some(&form), Locale::Korean).unwrap();
+/// assert!(page.contains("2024학년도 대학별 환산 점수"));
+/// assert!(page.contains("<td class=\"num\">1</td>"));
+/// assert!(!page.contains("불가"));
+/// assert!(page.contains("name=\"korean_standard\" value=\"131\""));
+/// assert!(page.contains("<option selected>2024</option>"));
+///
+/// // A subject left blank makes universities requiring it ineligible
+/// let one_science = CalculatorForm {
+///     earth_science_standard: String::new(),
+///     earth_science_percentile: String::new(),
+///     earth_science_grade: String::new(),
+///     ..form.clone()
+/// };
+/// let page = calculator_page(Some(&one_science), Locale::Korean).unwrap();
+/// assert!(page.contains("불가 (지구과학Ⅰ 성적 없음)"));
+///
+/// form.chemistry_percentile = field("130");
+/// let page = calculator_page(Some(&form), Locale::Korean).unwrap_err();
+/// assert!(page.contains("Invalid Chemistry score: percentile must be 0-100, got 130"));
+/// assert!(!page.contains("대학별 환산 점수"));
+/// assert!(page.contains("name=\"chemistry_percentile\" value=\"130\""));
+///
+/// let blank = calculator_page(None, Locale::English).unwrap();
+/// assert!(blank.contains("CSAT converted score calculator"));
+/// assert!(!blank.contains("role=\"alert\""));
+/// ```
+pub fn calculator_page(form: Option<&CalculatorForm>, locale: Locale) -> Result<String, String> {
+    let opts = ReportOptions {
+        locale,
+        ..ReportOptions::default()
+    };
+    let Some(form) = form else {
+        return Ok(html::calculator(&CalculatorForm::default(), None, &opts));
+    };
+    let outcome = form.record();
+    let page = html::calculator(form, Some(&outcome), &opts);
+    match outcome {
+        Ok(_) => Ok(page),
+        Err(_) => Err(page),
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  HTTP wiring
+// └──────────────────────────────────────────────────────────┘
+pub fn router() -> Router {
+    router_in(Locale::default())
+}
+
+/// `router` with the calculator page in `locale`
+pub fn router_in(locale: Locale) -> Router {
+    let page = |result: Result<String, String>| match result {
+        Ok(page) => (StatusCode::OK, Html(page)),
+        Err(page) => (StatusCode::BAD_REQUEST, Html(page)),
+    };
+    Router::new()
+        .route(
+            "/",
+            get(move || async move { page(calculator_page(None, locale)) }).post(
+                move |Form(form): Form<CalculatorForm>| async move {
+                    page(calculator_page(Some(&form), locale))
+                },
+            ),
+        )
+        .route(
+            "/calc",
+            post(
+                |Json(req): Json<CalcRequest>| async move { calc(req).map(Json).map_err(ApiError) },
+            ),
+        )
+        .route(
+            "/calc_all",
+            post(|Json(req): Json<CalcAllRequest>| async move {
+                calc_all(req).map(Json).map_err(ApiError)
+            }),
+        )
+        .route(
+            "/universities",
+            get(|Query(q): Query<YearQuery>| async move { Json(universities(q.year)) }),
+        )
+}
+
+pub async fn serve(addr: SocketAddr) -> std::io::Result<()> {
+    serve_in(addr, Locale::default()).await
+}
+
+/// `serve` with the calculator page in `locale`
+pub async fn serve_in(addr: SocketAddr, locale: Locale) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, router_in(locale)).await
+}
@@ -0,0 +1,210 @@
+//! Target converted scores of a student and progress towards them across
+//! exams.
+//!
+//! Targets are stored next to the student's record as
+//! `<dir>/<name>/targets.csv`, one `university,year,target` row per target
+//! (university as its identifier, e.g. `KONKUK`).
+
+use crate::atomic::write_atomic;
+use crate::error::{Result, SuneungError};
+use crate::exam::ExamId;
+use crate::score::{Record, University};
+use serde::Serialize;
+use std::path::Path;
+
+const TARGETS_FILE: &str = "targets.csv";
+
+/// Converted scores a student aims for: university, 학년도 of its weight
+/// data and the target score
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Targets {
+    pub entries: Vec<(University, u16, f64)>,
+}
+
+impl Targets {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, university: University, year: u16, target: f64) -> Self {
+        self.entries.push((university, year, target));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Targets of the student `name` under `dir`; empty when none were
+    /// written
+    #[cfg(feature = "csv")]
+    pub fn read_in<P: AsRef<Path>>(dir: P, name: &str) -> Result<Self> {
+        crate::input::check_name(name)?;
+        let path = dir.as_ref().join(name).join(TARGETS_FILE);
+        match crate::input::read_to_string(&path) {
+            Ok(text) => Self::from_csv_str(&text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Replace the targets of the student `name` under `dir`
+    pub fn write_in<P: AsRef<Path>>(&self, dir: P, name: &str) -> Result<()> {
+        crate::input::check_name(name)?;
+        let path = dir.as_ref().join(name);
+        std::fs::create_dir_all(&path)?;
+        let text = self.to_csv_string();
+        write_atomic(&path.join(TARGETS_FILE), |tmp| {
+            std::fs::write(tmp, text)?;
+            Ok(())
+        })
+        .map_err(|e| SuneungError::Io(std::io::Error::other(e.to_string())))
+    }
+
+    /// Parse the contents of a `targets.csv`
+    #[cfg(feature = "csv")]
+    pub fn from_csv_str(text: &str) -> Result<Self> {
+        crate::input::check_len("targets", text)?;
+        let invalid = |line: usize, reason: String| {
+            SuneungError::InvalidInput(format!("targets line {}: {}", line, reason))
+        };
+        let mut rdr = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .flexible(true)
+            .from_reader(text.as_bytes());
+        let mut entries = vec![];
+        for (i, row) in rdr.records().enumerate() {
+            // +2: 1-based, after the header
+            let line = i + 2;
+            let row = row.map_err(|e| invalid(line, e.to_string()))?;
+            let [Some(university), Some(year), Some(target)] = [0, 1, 2].map(|j| row.get(j)) else {
+                return Err(invalid(line, "expected university,year,target".to_string()));
+            };
+            let university: University = university
+                .parse()
+                .map_err(|e| invalid(line, format!("{}", e)))?;
+            let year = year
+                .parse()
+                .map_err(|_| invalid(line, format!("year {:?} is not a number", year)))?;
+            let target = target
+                .parse::<f64>()
+                .ok()
+                .filter(|t| t.is_finite())
+                .ok_or_else(|| invalid(line, format!("target {:?} is not a number", target)))?;
+            entries.push((university, year, target));
+        }
+        Ok(Self { entries })
+    }
+
+    pub fn to_csv_string(&self) -> String {
+        let mut out = String::from("university,year,target\n");
+        for (university, year, target) in &self.entries {
+            out.push_str(&format!("{:?},{},{}\n", university, year, target));
+        }
+        out
+    }
+}
+
+/// Change of a converted score since the previous exam
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Trend {
+    Up,
+    Down,
+    Flat,
+}
+
+impl Trend {
+    /// `▲`, `▼` or `-`
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Trend::Up => "▲",
+            Trend::Down => "▼",
+            Trend::Flat => "-",
+        }
+    }
+}
+
+/// One exam measured against one target
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ProgressRow {
+    /// `None` for a record without exam information
+    pub exam: Option<ExamId>,
+    pub university: University,
+    pub year: u16,
+    pub target: f64,
+    /// `None` when the exam cannot be scored at the university
+    pub score: Option<f64>,
+    /// `score - target`, negative below the target
+    pub gap: Option<f64>,
+    /// Change of `score` since the previous exam, when both have one
+    pub change: Option<f64>,
+    pub trend: Option<Trend>,
+}
+
+/// Every exam of `history` (one student's records in chronological order,
+/// e.g. 3월 → 6월 → 9월 → 수능) against every target, exam by exam and in
+/// the order of `targets` within an exam
+///
+/// ```
+/// use suneung_calc::score::{Grade, Percentile, Record, StandardScore, Subject, University};
+/// use suneung_calc::targets::{progress, Targets, Trend};
+///
+/// let exam = |korean: f64| {
+///     let mut record = Record::sample();
+///     record.record_typed(
+///         Subject::Korean,
+///         StandardScore::new(korean).unwrap(),
+///         Percentile::new(90f64).unwrap(),
+///         Grade::try_from(2u8).unwrap(),
+///     );
+///     record
+/// };
+/// let targets = Targets::new().with(University::KONKUK, 2024, 400f64);
+///
+/// // Improving: every row is above the previous one and closer to the target
+/// let rows = progress(&[exam(120f64), exam(125f64), exam(131f64)], &targets);
+/// assert_eq!(rows[0].trend, None);
+/// assert!(rows[1..].iter().all(|r| r.trend == Some(Trend::Up)));
+/// assert!(rows[2].gap.unwrap() > rows[0].gap.unwrap());
+///
+/// // Regressing, then holding
+/// let rows = progress(&[exam(131f64), exam(125f64), exam(125f64)], &targets);
+/// assert_eq!(rows[1].trend, Some(Trend::Down));
+/// assert!(rows[1].change.unwrap() < 0f64);
+/// assert_eq!(rows[2].trend, Some(Trend::Flat));
+/// ```
+pub fn progress(history: &[Record], targets: &Targets) -> Vec<ProgressRow> {
+    let mut rows = vec![];
+    let mut previous: Vec<Option<f64>> = vec![None; targets.entries.len()];
+    for record in history {
+        for (k, &(university, year, target)) in targets.entries.iter().enumerate() {
+            let score = record
+                .try_calc_with_university(university, year as usize)
+                .ok();
+            let change = score.zip(previous[k]).map(|(now, before)| now - before);
+            rows.push(ProgressRow {
+                exam: record.exam().map(|e| e.id()),
+                university,
+                year,
+                target,
+                score,
+                gap: score.map(|s| s - target),
+                change,
+                trend: change.map(|c| {
+                    if c > 0f64 {
+                        Trend::Up
+                    } else if c < 0f64 {
+                        Trend::Down
+                    } else {
+                        Trend::Flat
+                    }
+                }),
+            });
+            previous[k] = score;
+        }
+    }
+    rows
+}
@@ -1,226 +1,7 @@
-// ┌──────────────────────────────────────────────────────────┐
-//  2025
-// └──────────────────────────────────────────────────────────┘
-pub const SOGANG_2025_WEIGHT: [usize; 4] = [367, 433, 0, 200];
-pub const SOGANG_2025_ENG: [usize; 6] = [100, 99, 98, 97, 96, 95];
-pub const SOGANG_2025_SCI_REQ: usize = 2;
-pub const SOGANG_2025_ENG_REQ: usize = 2;
+//! Embedded weight constants, one set per university and year. Generated
+//! by `build.rs` from `data_src/weights.csv` and
+//! `data_src/english_tables.csv`; edit those instead.
 
-pub const CHUNGANG_2025_WEIGHT: [usize; 4] = [30, 35, 0, 35];
-pub const CHUNGANG_2025_ENG: [usize; 6] = [100, 98, 95, 92, 86, 75];
-pub const CHUNGANG_2025_SCI_REQ: usize = 2;
-pub const CHUNGANG_2025_ENG_REQ: usize = 2;
+use crate::formula::ScienceAggregation;
 
-pub const KYUNGHEE_2025_WEIGHT: [usize; 4] = [20, 35, 15, 30];
-pub const KYUNGHEE_2025_ENG: [usize; 6] = [200, 196, 188, 160, 120, 80];
-pub const KYUNGHEE_2025_SCI_REQ: usize = 2;
-pub const KYUNGHEE_2025_ENG_REQ: usize = 2;
-
-pub const SEOUL_2025_WEIGHT: [usize; 4] = [20, 40, 10, 30];
-pub const SEOUL_2025_ENG: [usize; 6] = [100, 98, 94, 90, 86, 82];
-pub const SEOUL_2025_SCI_REQ: usize = 2;
-pub const SEOUL_2025_ENG_REQ: usize = 2;
-
-pub const KONKUK_2025_WEIGHT: [usize; 4] = [30, 40, 10, 20];
-pub const KONKUK_2025_ENG: [usize; 6] = [200, 197, 190, 185, 180, 170];
-pub const KONKUK_2025_SCI_REQ: usize = 2;
-pub const KONKUK_2025_ENG_REQ: usize = 2;
-
-pub const DONGGUK_2025_WEIGHT: [usize; 4] = [25, 30, 15, 25];
-pub const DONGGUK_2025_ENG: [usize; 6] = [200, 199, 197, 190, 180, 140];
-pub const DONGGUK_2025_SCI_REQ: usize = 2;
-pub const DONGGUK_2025_ENG_REQ: usize = 2;
-
-// ┌──────────────────────────────────────────────────────────┐
-//  2024
-// └──────────────────────────────────────────────────────────┘
-pub const SOGANG_2024_WEIGHT: [usize; 4] = [367, 433, 0, 200];
-pub const SOGANG_2024_ENG: [usize; 6] = [100, 99, 98, 97, 96, 95];
-pub const SOGANG_2024_SCI_REQ: usize = 2;
-pub const SOGANG_2024_ENG_REQ: usize = 2;
-
-pub const CHUNGANG_2024_WEIGHT: [usize; 4] = [25, 40, 0, 35];
-pub const CHUNGANG_2024_ENG: [usize; 6] = [100, 98, 95, 92, 86, 75];
-pub const CHUNGANG_2024_SCI_REQ: usize = 2;
-pub const CHUNGANG_2024_ENG_REQ: usize = 2;
-
-pub const KYUNGHEE_2024_WEIGHT: [usize; 4] = [20, 35, 15, 30];
-pub const KYUNGHEE_2024_ENG: [usize; 6] = [200, 196, 188, 160, 120, 80];
-pub const KYUNGHEE_2024_SCI_REQ: usize = 2;
-pub const KYUNGHEE_2024_ENG_REQ: usize = 2;
-
-pub const SEOUL_2024_WEIGHT: [usize; 4] = [20, 35, 10, 35];
-pub const SEOUL_2024_ENG: [usize; 6] = [100, 98, 94, 90, 86, 82];
-pub const SEOUL_2024_SCI_REQ: usize = 2;
-pub const SEOUL_2024_ENG_REQ: usize = 2;
-
-pub const DONGGUK_2024_WEIGHT: [usize; 4] = [25, 35, 15, 25];
-pub const DONGGUK_2024_ENG: [usize; 6] = [200, 199, 197, 190, 180, 140];
-pub const DONGGUK_2024_SCI_REQ: usize = 2;
-pub const DONGGUK_2024_ENG_REQ: usize = 2;
-
-pub const SEOULSCITECH_2024_WEIGHT: [usize; 4] = [20, 35, 20, 25];
-pub const SEOULSCITECH_2024_ENG: [usize; 6] = [135, 132, 128, 120, 100, 80];
-pub const SEOULSCITECH_2024_SCI_REQ: usize = 2;
-pub const SEOULSCITECH_2024_ENG_REQ: usize = 2;
-
-pub const KWANGWOON_2024_WEIGHT: [usize; 4] = [20, 35, 20, 25];
-pub const KWANGWOON_2024_ENG: [usize; 6] = [200, 198, 195, 190, 182, 170];
-pub const KWANGWOON_2024_SCI_REQ: usize = 2;
-pub const KWANGWOON_2024_ENG_REQ: usize = 2;
-
-pub const INHA_2024_WEIGHT: [usize; 4] = [20, 30, 20, 25];
-pub const INHA_2024_ENG: [usize; 6] = [200, 198, 196, 190, 180, 160];
-pub const INHA_2024_SCI_REQ: usize = 2;
-pub const INHA_2024_ENG_REQ: usize = 2;
-
-pub const ERICA_2024_WEIGHT: [usize; 4] = [25, 30, 20, 25];
-pub const ERICA_2024_ENG: [usize; 6] = [200, 199, 198, 196, 193, 190];
-pub const ERICA_2024_SCI_REQ: usize = 2;
-pub const ERICA_2024_ENG_REQ: usize = 2;
-
-pub const SEJONG_2024_WEIGHT: [usize; 4] = [20, 35, 20, 25];
-pub const SEJONG_2024_ENG: [usize; 6] = [200, 198, 196, 194, 170, 150];
-pub const SEJONG_2024_SCI_REQ: usize = 2;
-pub const SEJONG_2024_ENG_REQ: usize = 2;
-
-pub const KOOKMIN_2024_WEIGHT: [usize; 4] = [20, 30, 20, 30];
-pub const KOOKMIN_2024_ENG: [usize; 6] = [200, 196, 190, 180, 170, 160];
-pub const KOOKMIN_2024_SCI_REQ: usize = 2;
-pub const KOOKMIN_2024_ENG_REQ: usize = 2;
-
-pub const AJU_2024_WEIGHT: [usize; 4] = [20, 35, 15, 30];
-pub const AJU_2024_ENG: [usize; 6] = [150, 144, 138, 126, 90, 60];
-pub const AJU_2024_SCI_REQ: usize = 2;
-pub const AJU_2024_ENG_REQ: usize = 2;
-
-pub const SOONGSIL_2024_WEIGHT: [usize; 4] = [20, 35, 20, 25];
-pub const SOONGSIL_2024_ENG: [usize; 6] = [200, 194, 186, 173, 144, 116];
-pub const SOONGSIL_2024_SCI_REQ: usize = 2;
-pub const SOONGSIL_2024_ENG_REQ: usize = 2;
-
-pub const KONKUK_2024_WEIGHT: [usize; 4] = [25, 40, 10, 25];
-pub const KONKUK_2024_ENG: [usize; 6] = [200, 198, 196, 193, 188, 183];
-pub const KONKUK_2024_SCI_REQ: usize = 2;
-pub const KONKUK_2024_ENG_REQ: usize = 2;
-
-pub const CATHOLIC_2024_WEIGHT: [usize; 4] = [30, 30, 20, 20];
-pub const CATHOLIC_2024_ENG: [usize; 6] = [200, 195, 190, 185, 180, 175];
-pub const CATHOLIC_2024_SCI_REQ: usize = 2;
-pub const CATHOLIC_2024_ENG_REQ: usize = 2;
-
-// ┌──────────────────────────────────────────────────────────┐
-//  2023
-// └──────────────────────────────────────────────────────────┘
-pub const KYUNGHEE_2023_WEIGHT: [usize; 4] = [20, 35, 15, 30];
-pub const KYUNGHEE_2023_ENG: [usize; 6] = [200, 196, 188, 160, 120, 80];
-pub const KYUNGHEE_2023_SCI_REQ: usize = 2;
-pub const KYUNGHEE_2023_ENG_REQ: usize = 2;
-
-pub const DONGGUK_2023_WEIGHT: [usize; 4] = [25, 30, 20, 20];
-pub const DONGGUK_2023_ENG: [usize; 6] = [200, 199, 197, 190, 180, 140];
-pub const DONGGUK_2023_SCI_REQ: usize = 2;
-pub const DONGGUK_2023_ENG_REQ: usize = 2;
-
-pub const SEOULSCITECH_2023_WEIGHT: [usize; 4] = [20, 35, 20, 25];
-pub const SEOULSCITECH_2023_ENG: [usize; 6] = [135, 130, 125, 115, 100, 80];
-pub const SEOULSCITECH_2023_SCI_REQ: usize = 2;
-pub const SEOULSCITECH_2023_ENG_REQ: usize = 2;
-
-pub const KWANGWOON_2023_WEIGHT: [usize; 4] = [20, 35, 20, 25];
-pub const KWANGWOON_2023_ENG: [usize; 6] = [200, 198, 195, 190, 182, 170];
-pub const KWANGWOON_2023_SCI_REQ: usize = 2;
-pub const KWANGWOON_2023_ENG_REQ: usize = 2;
-
-pub const INHA_2023_WEIGHT: [usize; 4] = [20, 30, 20, 25];
-pub const INHA_2023_ENG: [usize; 6] = [200, 198, 194, 185, 170, 150];
-pub const INHA_2023_SCI_REQ: usize = 2;
-pub const INHA_2023_ENG_REQ: usize = 2;
-
-pub const ERICA_2023_WEIGHT: [usize; 4] = [25, 30, 20, 25];
-pub const ERICA_2023_ENG: [usize; 6] = [200, 199, 198, 196, 193, 190];
-pub const ERICA_2023_SCI_REQ: usize = 2;
-pub const ERICA_2023_ENG_REQ: usize = 2;
-
-pub const SEJONG_2023_WEIGHT: [usize; 4] = [20, 35, 20, 25];
-pub const SEJONG_2023_ENG: [usize; 6] = [200, 198, 196, 194, 170, 150];
-pub const SEJONG_2023_SCI_REQ: usize = 2;
-pub const SEJONG_2023_ENG_REQ: usize = 2;
-
-pub const KOOKMIN_2023_WEIGHT: [usize; 4] = [20, 30, 20, 30];
-pub const KOOKMIN_2023_ENG: [usize; 6] = [200, 196, 190, 180, 170, 160];
-pub const KOOKMIN_2023_SCI_REQ: usize = 2;
-pub const KOOKMIN_2023_ENG_REQ: usize = 2;
-
-pub const AJU_2023_WEIGHT: [usize; 4] = [20, 35, 15, 30];
-pub const AJU_2023_ENG: [usize; 6] = [150, 144, 138, 126, 90, 60];
-pub const AJU_2023_SCI_REQ: usize = 2;
-pub const AJU_2023_ENG_REQ: usize = 2;
-
-pub const SOONGSIL_2023_WEIGHT: [usize; 4] = [20, 35, 20, 25];
-pub const SOONGSIL_2023_ENG: [usize; 6] = [200, 194, 186, 173, 144, 116];
-pub const SOONGSIL_2023_SCI_REQ: usize = 2;
-pub const SOONGSIL_2023_ENG_REQ: usize = 2;
-
-pub const CATHOLIC_2023_WEIGHT: [usize; 4] = [30, 30, 20, 20];
-pub const CATHOLIC_2023_ENG: [usize; 6] = [140, 138, 136, 134, 130, 126];
-pub const CATHOLIC_2023_SCI_REQ: usize = 2;
-pub const CATHOLIC_2023_ENG_REQ: usize = 2;
-
-// ┌──────────────────────────────────────────────────────────┐
-//  2022
-// └──────────────────────────────────────────────────────────┘
-pub const KYUNGHEE_2022_WEIGHT: [usize; 4] = [20, 35, 15, 25];
-pub const KYUNGHEE_2022_ENG: [usize; 6] = [200, 192, 178, 154, 120, 80];
-pub const KYUNGHEE_2022_SCI_REQ: usize = 2;
-pub const KYUNGHEE_2022_ENG_REQ: usize = 2;
-
-pub const DONGGUK_2022_WEIGHT: [usize; 4] = [25, 30, 20, 20];
-pub const DONGGUK_2022_ENG: [usize; 6] = [200, 199, 197, 190, 180, 140];
-pub const DONGGUK_2022_SCI_REQ: usize = 2;
-pub const DONGGUK_2022_ENG_REQ: usize = 2;
-
-pub const SEOULSCITECH_2022_WEIGHT: [usize; 4] = [20, 35, 20, 25];
-pub const SEOULSCITECH_2022_ENG: [usize; 6] = [135, 130, 125, 115, 100, 80];
-pub const SEOULSCITECH_2022_SCI_REQ: usize = 2;
-pub const SEOULSCITECH_2022_ENG_REQ: usize = 2;
-
-pub const KWANGWOON_2022_WEIGHT: [usize; 4] = [20, 35, 20, 25];
-pub const KWANGWOON_2022_ENG: [usize; 6] = [200, 197, 192, 184, 172, 160];
-pub const KWANGWOON_2022_SCI_REQ: usize = 2;
-pub const KWANGWOON_2022_ENG_REQ: usize = 2;
-
-pub const INHA_2022_WEIGHT: [usize; 4] = [20, 30, 20, 25];
-pub const INHA_2022_ENG: [usize; 6] = [200, 198, 194, 185, 170, 150];
-pub const INHA_2022_SCI_REQ: usize = 2;
-pub const INHA_2022_ENG_REQ: usize = 2;
-
-pub const ERICA_2022_WEIGHT: [usize; 4] = [25, 30, 20, 25];
-pub const ERICA_2022_ENG: [usize; 6] = [200, 199, 198, 196, 193, 190];
-pub const ERICA_2022_SCI_REQ: usize = 2;
-pub const ERICA_2022_ENG_REQ: usize = 2;
-
-pub const SEJONG_2022_WEIGHT: [usize; 4] = [20, 35, 20, 25];
-pub const SEJONG_2022_ENG: [usize; 6] = [200, 195, 190, 180, 160, 140];
-pub const SEJONG_2022_SCI_REQ: usize = 2;
-pub const SEJONG_2022_ENG_REQ: usize = 2;
-
-pub const KOOKMIN_2022_WEIGHT: [usize; 4] = [20, 30, 20, 30];
-pub const KOOKMIN_2022_ENG: [usize; 6] = [200, 196, 190, 180, 170, 160];
-pub const KOOKMIN_2022_SCI_REQ: usize = 2;
-pub const KOOKMIN_2022_ENG_REQ: usize = 2;
-
-pub const AJU_2022_WEIGHT: [usize; 4] = [20, 35, 20, 25];
-pub const AJU_2022_ENG: [usize; 6] = [200, 192, 184, 168, 120, 80];
-pub const AJU_2022_SCI_REQ: usize = 2;
-pub const AJU_2022_ENG_REQ: usize = 2;
-
-pub const SOONGSIL_2022_WEIGHT: [usize; 4] = [20, 35, 20, 25];
-pub const SOONGSIL_2022_ENG: [usize; 6] = [200, 194, 186, 173, 144, 116];
-pub const SOONGSIL_2022_SCI_REQ: usize = 2;
-pub const SOONGSIL_2022_ENG_REQ: usize = 2;
-
-pub const CATHOLIC_2022_WEIGHT: [usize; 4] = [30, 30, 20, 20];
-pub const CATHOLIC_2022_ENG: [usize; 6] = [200, 196, 192, 188, 180, 170];
-pub const CATHOLIC_2022_SCI_REQ: usize = 2;
-pub const CATHOLIC_2022_ENG_REQ: usize = 2;
+include!(concat!(env!("OUT_DIR"), "/university_weight.rs"));
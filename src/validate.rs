@@ -0,0 +1,199 @@
+//! Sanity checks for weight data: properties every conversion should have,
+//! tested on synthetic probe records. Meant to be run after adding or
+//! editing a university's weights.
+
+use crate::formula::perfect_record;
+use crate::score::{
+    Grade, Percentile, Record, Score, StandardScore, Subject, University, UniversityWeight, YEARS,
+};
+use std::fmt;
+
+/// Points added to a standard score or percentile by the monotonicity probes
+const STEP: f64 = 5f64;
+
+/// Property a converted score is expected to have
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Invariant {
+    /// A higher standard score never lowers the converted score
+    StandardScore,
+    /// A higher percentile never lowers the converted score
+    Percentile,
+    /// A better English grade never lowers the converted score
+    EnglishGrade,
+    /// No record scores above the perfect one (표준점수 200, 백분위 100 and
+    /// 1등급 everywhere)
+    PerfectIsMaximum,
+}
+
+/// One probe that broke an `Invariant`
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvariantViolation {
+    pub university: University,
+    pub year: usize,
+    pub invariant: Invariant,
+    /// Subject that was changed; `None` for `PerfectIsMaximum`
+    pub subject: Option<Subject>,
+    /// What was changed, e.g. `standard score 131 -> 136`
+    pub perturbation: String,
+    /// Converted score before the change, or of the perfect record
+    pub before: f64,
+    /// Converted score after the change, or of the probe above the perfect
+    /// record
+    pub after: f64,
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} ({:?}",
+            self.university.name(),
+            self.year,
+            self.invariant
+        )?;
+        if let Some(subject) = self.subject {
+            write!(f, ", {}", subject.name())?;
+        }
+        write!(
+            f,
+            "): {}: {:.3} -> {:.3}",
+            self.perturbation, self.before, self.after
+        )
+    }
+}
+
+/// Every `Invariant` of the embedded weight of `univ` for `year`, probed
+/// with `Record::samples` and small changes to them. Probes the university
+/// cannot score (a missing subject, an English grade outside its table)
+/// are skipped, as is a university without data for `year`.
+///
+/// ```
+/// use suneung_calc::score::University;
+/// use suneung_calc::validate::check_weight;
+///
+/// assert!(check_weight(University::KONKUK, 2024).is_empty());
+/// ```
+pub fn check_weight(univ: University, year: usize) -> Vec<InvariantViolation> {
+    let score = |record: &Record| univ_score(record, univ, year);
+    let violation = |invariant, subject, perturbation: String, before, after| InvariantViolation {
+        university: univ,
+        year,
+        invariant,
+        subject,
+        perturbation,
+        before,
+        after,
+    };
+    let mut violations = vec![];
+    let mut probes = vec![];
+
+    for base in Record::samples() {
+        let Some(before) = score(&base) else {
+            continue;
+        };
+        probes.push(before);
+        for (subject, s) in base.iter() {
+            let changes = match subject {
+                Subject::English => english_change(s),
+                _ => score_changes(s),
+            };
+            for (invariant, changed, perturbation) in changes {
+                let mut probe = base.clone();
+                probe.record_score(subject, changed);
+                let Some(after) = score(&probe) else {
+                    continue;
+                };
+                probes.push(after);
+                if after < before {
+                    violations.push(violation(
+                        invariant,
+                        Some(subject),
+                        perturbation,
+                        before,
+                        after,
+                    ));
+                }
+            }
+        }
+    }
+
+    if let Some(perfect) = score(&perfect_record()) {
+        if let Some(&best) = probes.iter().max_by(|a, b| a.total_cmp(b)) {
+            if best > perfect {
+                violations.push(violation(
+                    Invariant::PerfectIsMaximum,
+                    None,
+                    "perfect record".to_string(),
+                    perfect,
+                    best,
+                ));
+            }
+        }
+    }
+    violations
+}
+
+/// `check_weight` for every university and year with embedded data
+///
+/// ```
+/// assert_eq!(suneung_calc::validate::check_all(), vec![]);
+/// ```
+pub fn check_all() -> Vec<InvariantViolation> {
+    YEARS
+        .into_iter()
+        .flat_map(|year| {
+            University::supported(year)
+                .into_iter()
+                .flat_map(move |univ| check_weight(univ, year))
+        })
+        .collect()
+}
+
+// The formula's own total, before `UniversityWeight::convert` clamps it
+// to the very range `Invariant::PerfectIsMaximum` checks
+fn univ_score(record: &Record, univ: University, year: usize) -> Option<f64> {
+    let weight = UniversityWeight::load_cached(univ, year)?;
+    let breakdown = weight.formula().formula().convert(record, &weight).ok()?;
+    Some(breakdown.total)
+}
+
+// Standard score and percentile each raised by `STEP`, within their ranges
+fn score_changes(s: &Score) -> Vec<(Invariant, Score, String)> {
+    let mut changes = vec![];
+    let standard = (s.standard_score() + STEP).min(200f64);
+    if standard > s.standard_score() {
+        if let (Ok(standard), Ok(percentile)) = (
+            StandardScore::new(standard),
+            Percentile::new(s.percentile()),
+        ) {
+            let changed = Score::new_typed(standard, percentile, s.rank());
+            let perturbation = format!("standard score {} -> {}", s.standard_score(), standard);
+            changes.push((Invariant::StandardScore, changed, perturbation));
+        }
+    }
+    let percentile = (s.percentile() + STEP).min(100f64);
+    if percentile > s.percentile() {
+        if let (Ok(standard), Ok(percentile)) = (
+            StandardScore::new(s.standard_score()),
+            Percentile::new(percentile),
+        ) {
+            let changed = Score::new_typed(standard, percentile, s.rank());
+            let perturbation = format!("percentile {} -> {}", s.percentile(), percentile);
+            changes.push((Invariant::Percentile, changed, perturbation));
+        }
+    }
+    changes
+}
+
+// One grade better, unless already 1등급
+fn english_change(s: &Score) -> Vec<(Invariant, Score, String)> {
+    let grade = s.rank().get();
+    match Grade::try_from(grade - 1) {
+        Ok(better) => vec![(
+            Invariant::EnglishGrade,
+            Score::grade_only(better),
+            format!("grade {} -> {}", grade, grade - 1),
+        )],
+        Err(_) => vec![],
+    }
+}
@@ -0,0 +1,59 @@
+//! `wasm-bindgen` entry points for a browser calculator.
+//!
+//! Everything goes in and out as JSON strings so the JS side needs no
+//! generated bindings for the Rust types. Weight tables are the embedded
+//! ones; nothing here touches the filesystem.
+
+use crate::score::{CalcBreakdown, Record, University, UniversityWeight};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+#[derive(Serialize)]
+struct CalcResult {
+    university: University,
+    university_name: &'static str,
+    year: usize,
+    score: f64,
+    breakdown: CalcBreakdown,
+}
+
+#[derive(Serialize)]
+struct UniversityEntry {
+    id: University,
+    name: &'static str,
+}
+
+fn js_error<E: std::fmt::Display>(e: E) -> JsValue {
+    JsValue::from_str(&e.to_string())
+}
+
+/// Converted score of a JSON `Record` at `university` (`"KONKUK"`, ...).
+#[wasm_bindgen]
+pub fn calc(record_json: &str, university: &str, year: usize) -> Result<String, JsValue> {
+    let record: Record = serde_json::from_str(record_json).map_err(js_error)?;
+    let university = University::from_id(university)
+        .ok_or_else(|| js_error(format!("Unknown university {:?}", university)))?;
+    record.validate().map_err(js_error)?;
+    let breakdown = record.calc_breakdown(university, year).map_err(js_error)?;
+    serde_json::to_string(&CalcResult {
+        university,
+        university_name: university.name(),
+        year,
+        score: breakdown.total,
+        breakdown,
+    })
+    .map_err(js_error)
+}
+
+/// JSON array of `{id, name}` for universities with weight data for `year`
+#[wasm_bindgen]
+pub fn supported_universities(year: usize) -> String {
+    let entries: Vec<UniversityEntry> = University::iter()
+        .filter(|&u| UniversityWeight::try_load(u, year).is_some())
+        .map(|u| UniversityEntry {
+            id: u,
+            name: u.name(),
+        })
+        .collect();
+    serde_json::to_string(&entries).unwrap()
+}
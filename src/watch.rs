@@ -0,0 +1,176 @@
+//! Converted scores of everyone under a data root, kept current by
+//! re-reading only the students whose files changed. This is the reload
+//! logic behind `suneung watch`; noticing that something changed is left to
+//! the caller.
+
+use crate::calculator::ScoreCalculator;
+use crate::error::Result;
+use crate::score::{ReadOptions, Record, University};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+// Files of a student directory whose change means the record must be read
+// again. The sidecar is renamed into place after the record, so a reader
+// that lands in between sees a checksum mismatch and retries once the
+// sidecar changes.
+const WATCHED_FILES: [&str; 2] = ["record.parquet", "record.sha256"];
+
+/// Modification time and length of each of `WATCHED_FILES`
+type Fingerprint = [Option<(SystemTime, u64)>; 2];
+
+#[derive(Debug, Clone)]
+struct Student {
+    // `None` after a failed read, so the next refresh tries again
+    fingerprint: Option<Fingerprint>,
+    record: Record,
+    scores: Vec<(University, f64)>,
+}
+
+/// What a `WatchedCohort::refresh` did, student names in sorted order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Changes {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+    /// Students whose files could not be read, with the error. A student
+    /// already loaded keeps their previous scores.
+    pub failed: Vec<(String, String)>,
+}
+
+impl Changes {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.updated.is_empty()
+            && self.removed.is_empty()
+            && self.failed.is_empty()
+    }
+}
+
+/// Students under a data root with their converted scores for one year.
+///
+/// Starts empty; the first `refresh` reports everyone as added.
+#[derive(Debug, Clone)]
+pub struct WatchedCohort {
+    dir: PathBuf,
+    calculator: ScoreCalculator,
+    students: BTreeMap<String, Student>,
+}
+
+impl WatchedCohort {
+    pub fn new<P: AsRef<Path>>(dir: P, year: usize) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            calculator: ScoreCalculator::new(year),
+            students: BTreeMap::new(),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn year(&self) -> usize {
+        self.calculator.year()
+    }
+
+    /// Re-read and rescore the students whose files were added or changed
+    /// since the last call, and drop the ones that are gone. Fails only if
+    /// the data root itself cannot be listed.
+    pub fn refresh(&mut self) -> Result<Changes> {
+        let mut changes = Changes::default();
+        let current = self.scan()?;
+
+        let gone: Vec<String> = self
+            .students
+            .keys()
+            .filter(|name| !current.contains_key(*name))
+            .cloned()
+            .collect();
+        for name in gone {
+            self.students.remove(&name);
+            changes.removed.push(name);
+        }
+
+        for (name, fingerprint) in current {
+            let known = self.students.get(&name);
+            if known.is_some_and(|s| s.fingerprint == Some(fingerprint)) {
+                continue;
+            }
+            let is_new = known.is_none();
+            match Record::read_parquet_in(&self.dir, &name, &ReadOptions::default()) {
+                Ok(record) => {
+                    let scores = self.score(&record);
+                    self.students.insert(
+                        name.clone(),
+                        Student {
+                            fingerprint: Some(fingerprint),
+                            record,
+                            scores,
+                        },
+                    );
+                    if is_new {
+                        changes.added.push(name);
+                    } else {
+                        changes.updated.push(name);
+                    }
+                }
+                Err(e) => {
+                    if let Some(student) = self.students.get_mut(&name) {
+                        student.fingerprint = None;
+                    }
+                    changes.failed.push((name, e.to_string()));
+                }
+            }
+        }
+        Ok(changes)
+    }
+
+    /// Loaded records, sorted by name
+    pub fn records(&self) -> Vec<Record> {
+        self.students.values().map(|s| s.record.clone()).collect()
+    }
+
+    /// Scores of every loaded record in the order of `records`, leaving out
+    /// the universities a record cannot be scored at, as `render_comparison`
+    /// expects
+    pub fn results(&self) -> Vec<Vec<(University, f64)>> {
+        self.students.values().map(|s| s.scores.clone()).collect()
+    }
+
+    /// Scores of one student, best first
+    pub fn scores(&self, name: &str) -> Option<&[(University, f64)]> {
+        self.students.get(name).map(|s| s.scores.as_slice())
+    }
+
+    fn score(&self, record: &Record) -> Vec<(University, f64)> {
+        let mut scores: Vec<(University, f64)> = self
+            .calculator
+            .universities()
+            .filter_map(|univ| self.calculator.score(record, univ).ok().map(|s| (univ, s)))
+            .collect();
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scores
+    }
+
+    // Student directories holding a record, with their fingerprints
+    fn scan(&self) -> Result<BTreeMap<String, Fingerprint>> {
+        let mut found = BTreeMap::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let stat = |file: &str| {
+                std::fs::metadata(path.join(file))
+                    .ok()
+                    .and_then(|m| Some((m.modified().ok()?, m.len())))
+            };
+            let fingerprint = WATCHED_FILES.map(stat);
+            if fingerprint[0].is_some() {
+                found.insert(name.to_string(), fingerprint);
+            }
+        }
+        Ok(found)
+    }
+}
@@ -0,0 +1,168 @@
+//! A university's formula in a form that can be explained: reflection
+//! ratios in percent, how 탐구 and English count, and what English is
+//! worth at each grade.
+
+use crate::display::fmt_number;
+use crate::formula::{english_relative, FormulaKind, ScienceAggregation};
+use crate::score::{Grade, Provenance, UniversityWeight};
+use serde::Serialize;
+use std::fmt;
+
+/// How English enters the converted score
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum EnglishPolicy {
+    /// Part of the reflection ratio, relative to the default grade
+    Ratio,
+    /// Outside the ratio, a quarter of the table points relative to the
+    /// default grade added or taken off
+    Bonus,
+    /// The table lists points deducted per grade
+    Deduction,
+}
+
+impl EnglishPolicy {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EnglishPolicy::Ratio => "반영 비율에 포함",
+            EnglishPolicy::Bonus => "가감점",
+            EnglishPolicy::Deduction => "등급별 감점",
+        }
+    }
+}
+
+/// `UniversityWeight::describe`. Displays as a one-paragraph summary.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WeightInfo {
+    /// Reflection ratios in percent of their sum, to 0.1
+    pub korean: f64,
+    pub math: f64,
+    pub english: f64,
+    pub science: f64,
+    pub science_required: usize,
+    pub science_aggregation: ScienceAggregation,
+    /// 탐구 is reflected by 백분위 instead of 표준점수
+    pub science_by_percentile: bool,
+    pub english_policy: EnglishPolicy,
+    pub english_default: usize,
+    /// Converted points of English at each grade the table covers, best
+    /// first: relative to the default grade for `Ratio` and `Bonus`,
+    /// negative for `Deduction`
+    pub english_points: Vec<(Grade, f64)>,
+    /// Document, section and entry date of the numbers, if recorded
+    pub provenance: Option<Provenance>,
+}
+
+impl UniversityWeight {
+    /// Explainable view of the weight, see `WeightInfo`
+    ///
+    /// ```
+    /// use suneung_calc::score::{University, UniversityWeight};
+    /// use suneung_calc::weight_info::EnglishPolicy;
+    ///
+    /// let info = UniversityWeight::try_load(University::KYUNGHEE, 2024).unwrap().describe();
+    /// assert_eq!(info.english_policy, EnglishPolicy::Ratio);
+    /// assert_eq!(
+    ///     info.to_string(),
+    ///     "국어 20%, 수학 35%, 영어 15%, 탐구 30% 반영, 탐구는 표준점수 2과목 합. \
+    ///      영어는 반영 비율에 포함, 2등급 기준 1등급 +0.6점, 6등급 -17.4점 \
+    ///      (6등급 아래는 환산표에 없음)."
+    /// );
+    ///
+    /// let info = UniversityWeight::try_load(University::SOGANG, 2024).unwrap().describe();
+    /// assert_eq!(info.english_policy, EnglishPolicy::Bonus);
+    /// assert_eq!(info.korean + info.math + info.science, 100.0);
+    /// assert_eq!(info.provenance.unwrap().document, "2024학년도 정시 모집요강");
+    /// ```
+    pub fn describe(&self) -> WeightInfo {
+        let sum = self.korean() + self.math() + self.english() + self.science();
+        let percent = |w: f64| (w / sum * 1000f64).round() / 10f64;
+        let english_policy = if self.formula() == FormulaKind::DeductionEnglish {
+            EnglishPolicy::Deduction
+        } else if self.english() > 0f64 {
+            EnglishPolicy::Ratio
+        } else {
+            EnglishPolicy::Bonus
+        };
+        let english_points = (1..=9u8)
+            .filter_map(|g| {
+                let grade = Grade::try_from(g).ok()?;
+                let points = match english_policy {
+                    EnglishPolicy::Deduction => -self.english_table().get(grade)?,
+                    _ => english_relative(self, grade).ok()?,
+                };
+                Some((grade, points))
+            })
+            .collect();
+        WeightInfo {
+            korean: percent(self.korean()),
+            math: percent(self.math()),
+            english: percent(self.english()),
+            science: percent(self.science()),
+            science_required: self.science_required(),
+            science_aggregation: self.science_aggregation(),
+            science_by_percentile: self.formula() == FormulaKind::Percentile,
+            english_policy,
+            english_default: self.english_required(),
+            english_points,
+            provenance: self.provenance().cloned(),
+        }
+    }
+}
+
+impl fmt::Display for WeightInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ratios: Vec<String> = [
+            ("국어", self.korean),
+            ("수학", self.math),
+            ("영어", self.english),
+            ("탐구", self.science),
+        ]
+        .iter()
+        .filter(|(_, ratio)| *ratio > 0f64)
+        .map(|(name, ratio)| format!("{} {}%", name, fmt_number(*ratio, None)))
+        .collect();
+        let metric = if self.science_by_percentile {
+            "백분위"
+        } else {
+            "표준점수"
+        };
+        write!(
+            f,
+            "{} 반영, 탐구는 {} {}. 영어는 {}",
+            ratios.join(", "),
+            metric,
+            self.science_aggregation.label(),
+            self.english_policy.label()
+        )?;
+
+        let points = |x: f64| fmt_number((x * 100f64).round() / 100f64, None);
+        if self.english_policy == EnglishPolicy::Deduction {
+            if let Some(&(grade, worst)) = self.english_points.last() {
+                write!(f, ", 감점 최대 {}점 ({}등급)", points(-worst), grade)?;
+            }
+        } else {
+            let mut ends: Vec<String> = [self.english_points.first(), self.english_points.last()]
+                .into_iter()
+                .flatten()
+                .filter(|(grade, _)| usize::from(*grade) != self.english_default)
+                .map(|&(grade, x)| {
+                    let sign = if x >= 0f64 { "+" } else { "" };
+                    format!("{}등급 {}{}점", grade, sign, points(x))
+                })
+                .collect();
+            ends.dedup();
+            if !ends.is_empty() {
+                write!(f, ", {}등급 기준 {}", self.english_default, ends.join(", "))?;
+            }
+        }
+        if let Some(&(last, _)) = self.english_points.last() {
+            if usize::from(last) < 9 {
+                write!(f, " ({}등급 아래는 환산표에 없음)", last)?;
+            }
+        }
+        write!(f, ".")
+    }
+}
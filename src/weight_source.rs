@@ -0,0 +1,83 @@
+use crate::error::{Result, SuneungError};
+use crate::score::{University, UniversityWeight};
+use std::collections::HashMap;
+#[cfg(feature = "toml")]
+use std::path::PathBuf;
+
+/// Where `Record::calc_with_source` gets university weights from
+pub trait WeightSource {
+    /// Weight of `university` for `year`, or `UnsupportedYear` if the source
+    /// has none.
+    fn get(&self, university: University, year: usize) -> Result<UniversityWeight>;
+}
+
+/// The weight data compiled into the crate (`university_weight.rs`)
+#[derive(Debug, Copy, Clone, Default)]
+pub struct EmbeddedWeights;
+
+impl WeightSource for EmbeddedWeights {
+    fn get(&self, university: University, year: usize) -> Result<UniversityWeight> {
+        UniversityWeight::try_load(university, year)
+            .ok_or(SuneungError::UnsupportedYear { university, year })
+    }
+}
+
+/// Weights kept in memory, e.g. hypothetical ratios for a counseling scenario
+#[derive(Debug, Clone, Default)]
+pub struct MapWeights {
+    weights: HashMap<(University, usize), UniversityWeight>,
+}
+
+impl MapWeights {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, university: University, year: usize, weight: UniversityWeight) {
+        self.weights.insert((university, year), weight);
+    }
+}
+
+impl WeightSource for MapWeights {
+    fn get(&self, university: University, year: usize) -> Result<UniversityWeight> {
+        self.weights
+            .get(&(university, year))
+            .cloned()
+            .ok_or(SuneungError::UnsupportedYear { university, year })
+    }
+}
+
+/// One `<UNIVERSITY>_<year>.toml` file per weight (e.g. `KONKUK_2024.toml`),
+/// using the field names of `UniversityWeight`.
+#[cfg(feature = "toml")]
+#[derive(Debug, Clone)]
+pub struct TomlDirWeights {
+    dir: PathBuf,
+}
+
+#[cfg(feature = "toml")]
+impl TomlDirWeights {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[cfg(feature = "toml")]
+impl WeightSource for TomlDirWeights {
+    fn get(&self, university: University, year: usize) -> Result<UniversityWeight> {
+        let path = self.dir.join(format!("{:?}_{}.toml", university, year));
+        let text = match crate::input::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(SuneungError::UnsupportedYear { university, year })
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let invalid = |e: &dyn std::fmt::Display| {
+            SuneungError::WeightData(format!("{}: {}", path.display(), e))
+        };
+        let weight: UniversityWeight = toml::from_str(&text).map_err(|e| invalid(&e))?;
+        weight.validate().map_err(|e| invalid(&e))?;
+        Ok(weight)
+    }
+}
@@ -0,0 +1,209 @@
+//! Export and import of whole data directories
+#![cfg(feature = "archive")]
+
+use std::io::{Read, Write};
+use std::path::Path;
+use suneung_calc::archive::{
+    export_archive, import_archive, ARCHIVE_FORMAT_VERSION, MAX_ENTRY_BYTES,
+};
+use suneung_calc::exam::{ExamKind, ExamMeta};
+use suneung_calc::score::{ReadOptions, Record, Subject};
+
+fn data_dir(root: &Path) {
+    let mut june = Record::sample();
+    june.set_exam(Some(ExamMeta::new(ExamKind::Mock6, 2024)));
+    june.write_parquet_in(root).unwrap();
+    let mut other = Record::samples().remove(0);
+    other.set_exam(None);
+    other.write_parquet_in(root).unwrap();
+}
+
+// `archive` with the bytes of the archive entry `name` passed through `edit`
+fn rewrite(archive: &Path, out: &Path, name: &str, edit: impl Fn(Vec<u8>) -> Vec<u8>) {
+    let mut zip = zip::ZipArchive::new(std::fs::File::open(archive).unwrap()).unwrap();
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(out).unwrap());
+    for i in 0..zip.len() {
+        let mut file = zip.by_index(i).unwrap();
+        let mut bytes = vec![];
+        file.read_to_end(&mut bytes).unwrap();
+        let file_name = file.name().to_string();
+        if file_name == name {
+            bytes = edit(bytes);
+        }
+        writer
+            .start_file(file_name, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(&bytes).unwrap();
+    }
+    writer.finish().unwrap();
+}
+
+#[test]
+fn manifest_lists_students_exams_and_format() {
+    let root = tempfile::tempdir().unwrap();
+    data_dir(root.path());
+    let out = tempfile::tempdir().unwrap();
+    let manifest = export_archive(root.path(), &out.path().join("season.zip")).unwrap();
+
+    assert_eq!(manifest.format_version, ARCHIVE_FORMAT_VERSION);
+    assert_eq!(manifest.crate_version, env!("CARGO_PKG_VERSION"));
+    let sample = Record::sample();
+    assert!(manifest.students.iter().any(|s| s == sample.name()));
+    assert_eq!(manifest.exams.len(), manifest.students.len());
+    let exam = |student: &str| {
+        manifest
+            .exams
+            .iter()
+            .find(|e| e.student == student)
+            .unwrap()
+            .exam
+            .clone()
+    };
+    assert_eq!(
+        exam(sample.name()),
+        Some(ExamMeta::new(ExamKind::Mock6, 2024))
+    );
+    assert_eq!(exam(Record::samples()[0].name()), None);
+    // record.parquet and its checksum sidecar for each student
+    assert_eq!(manifest.files.len(), 2 * manifest.students.len());
+}
+
+#[test]
+fn import_restores_the_records() {
+    let root = tempfile::tempdir().unwrap();
+    data_dir(root.path());
+    let out = tempfile::tempdir().unwrap();
+    let archive = out.path().join("season.zip");
+    export_archive(root.path(), &archive).unwrap();
+
+    let dest = tempfile::tempdir().unwrap();
+    let manifest = import_archive(&archive, dest.path()).unwrap();
+    for student in &manifest.students {
+        let restored = Record::read_parquet_in(dest.path(), student, &ReadOptions::default());
+        let original = Record::read_parquet_in(root.path(), student, &ReadOptions::default());
+        let score = |r: &Record| r.try_score(Subject::Math);
+        assert_eq!(score(&restored.unwrap()), score(&original.unwrap()));
+    }
+}
+
+#[test]
+fn corrupted_archive_leaves_the_destination_untouched() {
+    let root = tempfile::tempdir().unwrap();
+    data_dir(root.path());
+    let out = tempfile::tempdir().unwrap();
+    let archive = out.path().join("season.zip");
+    let manifest = export_archive(root.path(), &archive).unwrap();
+
+    // One record file flipped after the manifest was written; the first
+    // file in the manifest is still intact
+    let last = manifest.files.last().unwrap();
+    let corrupted = out.path().join("corrupted.zip");
+    rewrite(
+        &archive,
+        &corrupted,
+        &format!("records/{}", last.path),
+        |mut b| {
+            b[0] ^= 0xff;
+            b
+        },
+    );
+
+    let dest = tempfile::tempdir().unwrap();
+    let error = import_archive(&corrupted, dest.path()).unwrap_err();
+    assert!(error.to_string().contains("checksum mismatch"));
+    assert_eq!(std::fs::read_dir(dest.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn newer_format_is_refused() {
+    let root = tempfile::tempdir().unwrap();
+    data_dir(root.path());
+    let out = tempfile::tempdir().unwrap();
+    let archive = out.path().join("season.zip");
+    export_archive(root.path(), &archive).unwrap();
+
+    let newer = out.path().join("newer.zip");
+    rewrite(&archive, &newer, "manifest.json", |bytes| {
+        let mut manifest: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        manifest["format_version"] = (ARCHIVE_FORMAT_VERSION + 1).into();
+        serde_json::to_vec(&manifest).unwrap()
+    });
+    let dest = tempfile::tempdir().unwrap();
+    assert!(import_archive(&newer, dest.path()).is_err());
+
+    // Every archive carries its format
+    let unversioned = out.path().join("unversioned.zip");
+    rewrite(&archive, &unversioned, "manifest.json", |bytes| {
+        let mut manifest: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        manifest.as_object_mut().unwrap().remove("format_version");
+        serde_json::to_vec(&manifest).unwrap()
+    });
+    assert!(import_archive(&unversioned, dest.path()).is_err());
+    assert_eq!(std::fs::read_dir(dest.path()).unwrap().count(), 0);
+}
+
+// `archive` with the manifest size of its first file set to `size`
+fn with_first_size(archive: &Path, out: &Path, size: u64) {
+    rewrite(archive, out, "manifest.json", |bytes| {
+        let mut manifest: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        manifest["files"][0]["size"] = size.into();
+        serde_json::to_vec(&manifest).unwrap()
+    });
+}
+
+#[test]
+fn sizes_are_checked_before_and_while_reading() {
+    let root = tempfile::tempdir().unwrap();
+    data_dir(root.path());
+    let out = tempfile::tempdir().unwrap();
+    let archive = out.path().join("season.zip");
+    let manifest = export_archive(root.path(), &archive).unwrap();
+    let first = &manifest.files[0];
+    let dest = tempfile::tempdir().unwrap();
+    let refused = |size: u64| {
+        let edited = out.path().join("edited.zip");
+        with_first_size(&archive, &edited, size);
+        import_archive(&edited, dest.path())
+            .unwrap_err()
+            .to_string()
+    };
+
+    assert!(refused(MAX_ENTRY_BYTES + 1).contains("more than the limit"));
+    // A file longer than the manifest says is not read past its size
+    assert!(refused(first.size - 1).contains("larger than the limit"));
+    assert!(refused(first.size + 1).contains("the manifest says"));
+    assert_eq!(std::fs::read_dir(dest.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn names_that_are_not_one_directory_are_refused() {
+    let root = tempfile::tempdir().unwrap();
+    data_dir(root.path());
+    let out = tempfile::tempdir().unwrap();
+    let archive = out.path().join("season.zip");
+    export_archive(root.path(), &archive).unwrap();
+    let dest = tempfile::tempdir().unwrap();
+
+    let edited = out.path().join("edited.zip");
+    for (key, value) in [
+        ("students", "../밖"),
+        ("files", "샘플/../../record.parquet"),
+        ("files", "샘플/하위/record.parquet"),
+    ] {
+        rewrite(&archive, &edited, "manifest.json", |bytes| {
+            let mut manifest: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+            match key {
+                "students" => manifest["students"][0] = value.into(),
+                _ => manifest["files"][0]["path"] = value.into(),
+            }
+            serde_json::to_vec(&manifest).unwrap()
+        });
+        let error = import_archive(&edited, dest.path()).unwrap_err();
+        assert!(error.to_string().contains("unsafe"), "{}: {}", value, error);
+    }
+    assert_eq!(std::fs::read_dir(dest.path()).unwrap().count(), 0);
+
+    // A student directory export could not import again
+    std::fs::create_dir_all(root.path().join("줄\n바꿈")).unwrap();
+    assert!(export_archive(root.path(), &out.path().join("bad.zip")).is_err());
+}
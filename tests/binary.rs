@@ -0,0 +1,105 @@
+//! Round trips and corrupted input of the bincode snapshots
+#![cfg(feature = "binary")]
+
+use suneung_calc::convert::{KoreanTrack, Track};
+use suneung_calc::exam::{ExamId, ExamKind, ExamMeta};
+use suneung_calc::record_set::RecordSet;
+use suneung_calc::score::{Record, Subject};
+
+// Records compare by their serde model, which is what the snapshot keeps
+fn json<T: serde::Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).unwrap()
+}
+
+fn detailed() -> Record {
+    let mut record = Record::sample();
+    record.set_exam(Some(ExamMeta::new(ExamKind::Mock9, 2024)));
+    record.add_tag("3반");
+    record
+        .set_elective(Track::Korean(KoreanTrack::LanguageMedia))
+        .unwrap();
+    let math = record.try_score(Subject::Math).unwrap();
+    record.record_score(
+        Subject::Math,
+        math.with_source(ExamId::new(ExamKind::Mock6, 2024)),
+    );
+    record
+}
+
+#[test]
+fn record_round_trips() {
+    for record in [Record::sample(), detailed(), Record::new("빈 기록")] {
+        let bytes = record.to_bytes().unwrap();
+        assert_eq!(&bytes[..4], b"SNCR");
+        let back = Record::from_bytes(&bytes).unwrap();
+        assert_eq!(json(&back), json(&record));
+    }
+}
+
+#[test]
+fn partial_record_round_trips() {
+    let mut record = Record::sample();
+    record.remove_subject(Subject::EarthScience);
+    record.remove_subject(Subject::Chemistry);
+    let back = Record::from_bytes(&record.to_bytes().unwrap()).unwrap();
+    assert_eq!(back.len(), 3);
+    assert!(back.try_score(Subject::Chemistry).is_none());
+    assert_eq!(json(&back), json(&record));
+}
+
+#[test]
+fn record_set_round_trips() {
+    let mut set = RecordSet::new();
+    for record in Record::samples() {
+        set.push(record);
+    }
+    set.push(detailed());
+    let back = RecordSet::from_bytes(&set.to_bytes().unwrap()).unwrap();
+    assert_eq!(back.len(), set.len());
+    assert_eq!(json(&back), json(&set));
+}
+
+#[test]
+fn truncated_bytes_are_an_error() {
+    let bytes = detailed().to_bytes().unwrap();
+    for len in 0..bytes.len() {
+        assert!(
+            Record::from_bytes(&bytes[..len]).is_err(),
+            "{} of {} bytes decoded",
+            len,
+            bytes.len()
+        );
+    }
+}
+
+#[test]
+fn corrupted_bytes_do_not_panic() {
+    let bytes = detailed().to_bytes().unwrap();
+    for i in 0..bytes.len() {
+        for flip in [0x01, 0x80, 0xff] {
+            let mut corrupted = bytes.clone();
+            corrupted[i] ^= flip;
+            // Either an error or some record, never a panic
+            let _ = Record::from_bytes(&corrupted);
+            let _ = RecordSet::from_bytes(&corrupted);
+        }
+    }
+}
+
+#[test]
+fn wrong_header_is_an_error() {
+    let mut bytes = Record::sample().to_bytes().unwrap();
+    bytes[0] = b'X';
+    assert!(Record::from_bytes(&bytes).is_err());
+
+    let mut bytes = Record::sample().to_bytes().unwrap();
+    bytes[4] = bytes[4].wrapping_add(1);
+    let error = Record::from_bytes(&bytes).unwrap_err();
+    assert!(error.to_string().contains("version"));
+
+    // A huge length prefix is refused by the size limit, not allocated
+    let mut bytes = b"SNCR".to_vec();
+    bytes.push(Record::sample().to_bytes().unwrap()[4]);
+    bytes.extend([0xfc, 0xff, 0xff, 0xff, 0xff]);
+    assert!(Record::from_bytes(&bytes).is_err());
+}
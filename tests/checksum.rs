@@ -0,0 +1,179 @@
+//! Tamper and corruption detection of stored records
+#![cfg(feature = "parquet")]
+
+use peroxide::fuga::*;
+use std::path::Path;
+use suneung_calc::error::SuneungError;
+use suneung_calc::score::{ReadOptions, Record, StandardScore, Subject};
+
+fn record_path(root: &Path) -> std::path::PathBuf {
+    root.join(Record::sample().name()).join("record.parquet")
+}
+
+fn read(root: &Path) -> Result<Record, SuneungError> {
+    Record::read_parquet_in(root, Record::sample().name(), &ReadOptions::default())
+}
+
+fn read_unverified(root: &Path) -> Result<Record, SuneungError> {
+    let options = ReadOptions { skip_verify: true };
+    Record::read_parquet_in(root, Record::sample().name(), &options)
+}
+
+// Replace every occurrence of `from` in `bytes`, page statistics included,
+// with `to`
+fn replace(bytes: &mut [u8], from: &[u8], to: &[u8]) {
+    let mut found = false;
+    for at in 0..=bytes.len() - from.len() {
+        if &bytes[at..at + from.len()] == from {
+            bytes[at..at + to.len()].copy_from_slice(to);
+            found = true;
+        }
+    }
+    assert!(found, "value not found in file");
+}
+
+#[test]
+fn untouched_file_verifies() {
+    let root = tempfile::tempdir().unwrap();
+    Record::sample().write_parquet_in(root.path()).unwrap();
+    let sidecar = std::fs::read_to_string(root.path().join("샘플/record.sha256")).unwrap();
+    assert_eq!(sidecar.len(), 64);
+    assert_eq!(
+        read(root.path()).unwrap().standard_score(Subject::Korean),
+        131.0
+    );
+}
+
+#[test]
+fn flipped_value_in_the_file_is_detected() {
+    let root = tempfile::tempdir().unwrap();
+    Record::sample().write_parquet_in(root.path()).unwrap();
+
+    // Korean 131 becomes 141 in the uncompressed data page
+    let path = record_path(root.path());
+    let mut bytes = std::fs::read(&path).unwrap();
+    replace(&mut bytes, &131f64.to_le_bytes(), &141f64.to_le_bytes());
+    std::fs::write(&path, bytes).unwrap();
+
+    match read(root.path()) {
+        Err(SuneungError::ChecksumMismatch {
+            name,
+            expected,
+            found,
+        }) => {
+            assert_eq!(name, "샘플");
+            assert_ne!(expected, found);
+        }
+        other => panic!("expected a checksum mismatch, got {:?}", other),
+    }
+    let tampered = read_unverified(root.path()).unwrap();
+    assert_eq!(tampered.standard_score(Subject::Korean), 141.0);
+}
+
+#[test]
+fn rewritten_dataframe_is_detected() {
+    let root = tempfile::tempdir().unwrap();
+    let record = Record::sample();
+    record.write_parquet_in(root.path()).unwrap();
+
+    // A well-formed file whose content no longer matches the sidecar
+    let mut df = record.to_dataframe();
+    df[Subject::Math.name()] = Series::new(vec![138f64, 97.0, 1.0]);
+    let path = record_path(root.path());
+    df.write_parquet(path.to_str().unwrap(), CompressionOptions::Uncompressed)
+        .unwrap();
+
+    assert!(matches!(
+        read(root.path()),
+        Err(SuneungError::ChecksumMismatch { .. })
+    ));
+    assert_eq!(
+        read_unverified(root.path())
+            .unwrap()
+            .standard_score(Subject::Math),
+        138.0
+    );
+}
+
+#[test]
+fn edited_sidecar_is_detected() {
+    let root = tempfile::tempdir().unwrap();
+    Record::sample().write_parquet_in(root.path()).unwrap();
+    let sidecar = root.path().join("샘플/record.sha256");
+    std::fs::write(&sidecar, "0".repeat(64)).unwrap();
+    assert!(matches!(
+        read(root.path()),
+        Err(SuneungError::ChecksumMismatch { .. })
+    ));
+
+    // Files written before checksums were introduced have no sidecar
+    std::fs::remove_file(&sidecar).unwrap();
+    assert!(read(root.path()).is_ok());
+}
+
+#[test]
+fn failed_write_keeps_the_file_and_its_sidecar() {
+    let root = tempfile::tempdir().unwrap();
+    let mut record = Record::sample();
+    record.write_parquet_in(root.path()).unwrap();
+
+    // The sidecar can't be moved aside, so neither file is replaced
+    std::fs::create_dir_all(root.path().join("샘플/record.sha256.bak/inside")).unwrap();
+    record
+        .set_standard_score_typed(Subject::Korean, StandardScore::new(141.0).unwrap())
+        .unwrap();
+    assert!(record.write_parquet_in(root.path()).is_err());
+    assert_eq!(
+        read(root.path()).unwrap().standard_score(Subject::Korean),
+        131.0
+    );
+    for leftover in [
+        "record.parquet.tmp",
+        "record.sha256.tmp",
+        "record.parquet.bak",
+    ] {
+        assert!(
+            !root.path().join("샘플").join(leftover).exists(),
+            "{}",
+            leftover
+        );
+    }
+}
+
+// A file holding `df` and no sidecar
+fn write_unchecked(root: &Path, df: &DataFrame) {
+    let path = record_path(root);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    df.write_parquet(path.to_str().unwrap(), CompressionOptions::Uncompressed)
+        .unwrap();
+}
+
+#[test]
+fn short_column_is_an_error() {
+    let root = tempfile::tempdir().unwrap();
+    let mut df = DataFrame::new(vec![]);
+    df.push("Korean", Series::new(vec![131f64, 93.0]));
+    write_unchecked(root.path(), &df);
+
+    match read(root.path()) {
+        Err(SuneungError::Parquet(reason)) => assert!(reason.contains("2 rows"), "{}", reason),
+        other => panic!("expected a parquet error, got {:?}", other),
+    }
+}
+
+#[test]
+fn column_of_the_wrong_type_is_an_error() {
+    let root = tempfile::tempdir().unwrap();
+    let mut df = DataFrame::new(vec![]);
+    df.push(
+        "Korean",
+        Series::new(vec!["131".to_string(), "93".to_string(), "2".to_string()]),
+    );
+    write_unchecked(root.path(), &df);
+    assert!(matches!(read(root.path()), Err(SuneungError::Parquet(_))));
+
+    let mut df = Record::sample().to_dataframe();
+    df["Metric"] = Series::new(vec![1f64, 2.0, 3.0]);
+    write_unchecked(root.path(), &df);
+    assert!(matches!(read(root.path()), Err(SuneungError::Parquet(_))));
+}
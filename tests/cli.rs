@@ -0,0 +1,581 @@
+#![cfg(feature = "cli")]
+
+//! The `suneung` binary run against a data root in a temporary directory
+
+mod common;
+
+use assert_cmd::Command;
+use common::assert_snapshot;
+use predicates::prelude::*;
+use serde_json::Value;
+use std::path::Path;
+use suneung_calc::score::{Record, University};
+
+/// `suneung --data-dir <dir>`, with the config looked up under `dir` so the
+/// user's own config file is never read
+fn suneung(dir: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("suneung").unwrap();
+    cmd.env("XDG_CONFIG_HOME", dir.join("config"))
+        .env("NO_COLOR", "1")
+        .arg("--data-dir")
+        .arg(dir);
+    cmd
+}
+
+/// Store the scores of `Record::sample` as `name`
+fn record(dir: &Path, name: &str) {
+    suneung(dir)
+        .args(["record", "--name", name])
+        .args([
+            "--korean",
+            "131,93,2",
+            "--math",
+            "128,89,2",
+            "--english",
+            "2",
+        ])
+        .args(["--chemistry", "64,92,2", "--earth", "66,94,1"])
+        .assert()
+        .success();
+}
+
+fn stdout_json(cmd: &mut Command) -> Value {
+    let output = cmd.assert().success().get_output().stdout.clone();
+    serde_json::from_slice(&output).unwrap()
+}
+
+fn stderr_json(cmd: &mut Command) -> Value {
+    let output = cmd.assert().failure().get_output().stderr.clone();
+    serde_json::from_slice(&output).unwrap()
+}
+
+#[test]
+fn record_writes_the_student_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    suneung(dir.path())
+        .args(["record", "--name", "홍길동", "--korean", "131,93,2"])
+        .args(["--english", "2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("국어"))
+        .stderr(predicate::str::contains("saved to"));
+
+    let student = dir.path().join("홍길동");
+    assert!(student.join("record.parquet").is_file());
+    assert!(student.join("record.sha256").is_file());
+}
+
+#[test]
+fn record_updates_an_existing_student() {
+    let dir = tempfile::tempdir().unwrap();
+    record(dir.path(), "홍길동");
+    let json = stdout_json(suneung(dir.path()).args([
+        "--json",
+        "record",
+        "--name",
+        "홍길동",
+        "--math",
+        "135,96,1",
+    ]));
+
+    assert_eq!(json["name"], "홍길동");
+    assert_eq!(
+        json["path"].as_str().map(Path::new),
+        Some(dir.path().join("홍길동").as_path())
+    );
+    let scores = &json["record"]["scores"];
+    assert_eq!(scores["Math"]["standard_score"], 135.0);
+    assert_eq!(scores["Korean"]["standard_score"], 131.0);
+    assert_eq!(scores["EarthScience"]["rank"], 1);
+}
+
+#[test]
+fn record_rejects_bad_scores_as_usage_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    suneung(dir.path())
+        .args(["record", "--name", "홍길동", "--korean", "131,930,2"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("percentile must be 0-100"));
+
+    let json = stderr_json(suneung(dir.path()).args([
+        "--json",
+        "record",
+        "--name",
+        "홍길동",
+        "--korean",
+        "abc",
+    ]));
+    assert_eq!(json["error"]["code"], "usage");
+    assert!(!dir.path().join("홍길동").exists());
+}
+
+#[test]
+fn record_needs_a_name() {
+    let dir = tempfile::tempdir().unwrap();
+    suneung(dir.path())
+        .args(["record", "--korean", "131,93,2"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--name"));
+}
+
+#[test]
+fn calc_prints_the_converted_score() {
+    let dir = tempfile::tempdir().unwrap();
+    record(dir.path(), "홍길동");
+    suneung(dir.path())
+        .args(["calc", "--name", "홍길동", "--university", "건국대"])
+        .args(["--year", "2024"])
+        .assert()
+        .success()
+        .stdout("홍길동 건국대 2024: 388.17\n");
+}
+
+#[test]
+fn calc_accepts_ids_names_and_aliases() {
+    let dir = tempfile::tempdir().unwrap();
+    record(dir.path(), "홍길동");
+    for university in ["KYUNGHEE", "경희대", "경희"] {
+        let json = stdout_json(suneung(dir.path()).args([
+            "--json",
+            "calc",
+            "--name",
+            "홍길동",
+            "--university",
+            university,
+            "--year",
+            "2024",
+        ]));
+        assert_eq!(json["university"], "KYUNGHEE", "{university}");
+    }
+}
+
+#[test]
+fn calc_json_matches_the_library() {
+    let dir = tempfile::tempdir().unwrap();
+    record(dir.path(), "홍길동");
+    let json = stdout_json(suneung(dir.path()).args([
+        "--json",
+        "calc",
+        "--name",
+        "홍길동",
+        "--university",
+        "KONKUK",
+        "--year",
+        "2024",
+    ]));
+
+    let breakdown = Record::sample()
+        .calc_breakdown(University::KONKUK, 2024)
+        .unwrap();
+    assert_eq!(json["name"], "홍길동");
+    assert_eq!(json["university_name"], "건국대");
+    assert_eq!(json["year"], 2024);
+    assert_eq!(json["score"], breakdown.total);
+    assert_eq!(json["breakdown"], serde_json::to_value(&breakdown).unwrap());
+}
+
+#[test]
+fn calc_reports_unknown_universities_years_and_students() {
+    let dir = tempfile::tempdir().unwrap();
+    record(dir.path(), "홍길동");
+    let calc = |args: &[&str]| {
+        let mut cmd = suneung(dir.path());
+        cmd.args(["--json", "calc"]).args(args);
+        cmd
+    };
+
+    let json = stderr_json(&mut calc(&["--name", "홍길동", "--university", "없는대"]));
+    assert_eq!(json["error"]["code"], "usage");
+    let json = stderr_json(&mut calc(&[
+        "--name",
+        "홍길동",
+        "--university",
+        "KONKUK",
+        "--year",
+        "1999",
+    ]));
+    assert!(json["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("supported years: 2024, 2025"));
+    let json = stderr_json(&mut calc(&[
+        "--name",
+        "없음",
+        "--university",
+        "KONKUK",
+        "--year",
+        "2024",
+    ]));
+    assert!(json["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("no record for 없음"));
+}
+
+#[test]
+fn calc_takes_the_year_from_the_config() {
+    let dir = tempfile::tempdir().unwrap();
+    record(dir.path(), "홍길동");
+    let config = dir.path().join("suneung.toml");
+    std::fs::write(&config, "default_year = 2024\n").unwrap();
+    suneung(dir.path())
+        .arg("--config")
+        .arg(&config)
+        .args(["calc", "--name", "홍길동", "--university", "KONKUK"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2024: 388.17"));
+    suneung(dir.path())
+        .args(["calc", "--name", "홍길동", "--university", "KONKUK"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--year is required"));
+}
+
+#[test]
+fn compare_keeps_the_listed_order_unless_sorted() {
+    let dir = tempfile::tempdir().unwrap();
+    record(dir.path(), "홍길동");
+    let universities = |sort: bool| {
+        let mut cmd = suneung(dir.path());
+        cmd.args(["--json", "compare", "--name", "홍길동", "--year", "2024"])
+            .args(["--universities", "KONKUK,SOGANG"]);
+        if sort {
+            cmd.arg("--sort");
+        }
+        let rows = stdout_json(&mut cmd);
+        rows.as_array()
+            .unwrap()
+            .iter()
+            .map(|row| (row["university"].clone(), row["rank"].clone()))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        universities(false),
+        [("KONKUK".into(), 2.into()), ("SOGANG".into(), 1.into())]
+    );
+    assert_eq!(
+        universities(true),
+        [("SOGANG".into(), 1.into()), ("KONKUK".into(), 2.into())]
+    );
+}
+
+#[test]
+fn compare_csv_marks_missing_data_and_ineligible_rows() {
+    let dir = tempfile::tempdir().unwrap();
+    suneung(dir.path())
+        .args(["record", "--name", "홍길동", "--korean", "131,93,2"])
+        .args(["--math", "128,89,2", "--english", "2"])
+        .args(["--chemistry", "64,92,2"])
+        .assert()
+        .success();
+
+    suneung(dir.path())
+        .args(["compare", "--name", "홍길동", "--year", "2022"])
+        .args(["--universities", "KONKUK,KYUNGHEE", "--output", "csv"])
+        .assert()
+        .success()
+        .stdout(
+            "rank,university,university_name,score,percent_of_max,status,reason\n\
+             ,KONKUK,건국대,,,no_data,\n\
+             ,KYUNGHEE,경희대(서울),,,ineligible,Record has no EarthScience score\n",
+        );
+}
+
+#[test]
+fn compare_table_explains_missing_data() {
+    let dir = tempfile::tempdir().unwrap();
+    record(dir.path(), "홍길동");
+    suneung(dir.path())
+        .args(["compare", "--name", "홍길동", "--year", "2022"])
+        .args(["--universities", "KONKUK,KYUNGHEE"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("388.12").and(predicate::str::contains(
+                "건국대: 2022학년도 반영 비율 없음",
+            )),
+        );
+}
+
+#[test]
+fn list_universities_matches_the_embedded_data() {
+    let dir = tempfile::tempdir().unwrap();
+    let ids = |year: Option<&str>| {
+        let mut cmd = suneung(dir.path());
+        cmd.args(["--json", "list", "universities"]);
+        if let Some(year) = year {
+            cmd.args(["--year", year]);
+        }
+        let entries = stdout_json(&mut cmd);
+        for entry in entries.as_array().unwrap() {
+            let university: University = serde_json::from_value(entry["id"].clone()).unwrap();
+            assert_eq!(entry["name"], university.to_string());
+            assert_eq!(
+                entry["years"],
+                serde_json::to_value(university.supported_years()).unwrap()
+            );
+        }
+        serde_json::from_value::<Vec<University>>(
+            entries
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|e| e["id"].clone())
+                .collect(),
+        )
+        .unwrap()
+    };
+
+    assert_eq!(ids(None), University::all());
+    assert_eq!(ids(Some("2022")), University::supported(2022));
+}
+
+#[test]
+fn list_universities_in_english() {
+    let dir = tempfile::tempdir().unwrap();
+    suneung(dir.path())
+        .args(["--locale", "en", "list", "universities"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Konkuk").and(predicate::str::contains("건국대").not()));
+}
+
+#[test]
+fn list_records_shows_names_and_exams() {
+    let dir = tempfile::tempdir().unwrap();
+    let empty = stdout_json(suneung(dir.path()).args(["--json", "list", "records"]));
+    assert_eq!(empty, Value::Array(vec![]));
+
+    record(dir.path(), "홍길동");
+    suneung(dir.path())
+        .args(["record", "--name", "김철수", "--korean", "124,85,3"])
+        .args(["--exam", "2024-09"])
+        .assert()
+        .success();
+
+    let entries = stdout_json(suneung(dir.path()).args(["--json", "list", "records"]));
+    let mut entries: Vec<_> = entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| (e["name"].clone(), e["exam"].clone(), e["modified"].is_u64()))
+        .collect();
+    entries.sort_by_key(|e| e.0.to_string());
+    assert_eq!(
+        entries,
+        [
+            ("김철수".into(), "2025 9월 모의평가".into(), true),
+            ("홍길동".into(), Value::Null, true),
+        ]
+    );
+}
+
+fn headings(markdown: &str) -> Vec<&str> {
+    markdown.lines().filter(|l| l.starts_with('#')).collect()
+}
+
+#[test]
+fn report_prints_the_student_sections() {
+    let dir = tempfile::tempdir().unwrap();
+    record(dir.path(), "홍길동");
+    let report = |skip: Option<&str>| {
+        let mut cmd = suneung(dir.path());
+        cmd.args(["report", "--name", "홍길동", "--year", "2024"]);
+        if let Some(skip) = skip {
+            cmd.args(["--skip", skip]);
+        }
+        String::from_utf8(cmd.assert().success().get_output().stdout.clone()).unwrap()
+    };
+
+    let full = report(None);
+    assert_eq!(
+        headings(&full),
+        [
+            "# 홍길동",
+            "## 성적",
+            "## 2024학년도 대학별 환산 점수",
+            "## 영어 등급별 환산 점수"
+        ]
+    );
+    assert!(full.contains("| 국어 | 131 | 93 | 2등급 |"));
+    assert!(full.contains("| 11 | 건국대 | 388.17 |"));
+    assert_eq!(
+        headings(&report(Some("comparison,english-sweep"))),
+        ["# 홍길동", "## 성적"]
+    );
+}
+
+#[test]
+fn report_out_writes_the_file_and_names_it() {
+    let dir = tempfile::tempdir().unwrap();
+    record(dir.path(), "홍길동");
+    let out = dir.path().join("report.md");
+    let json = stdout_json(
+        suneung(dir.path())
+            .args(["--json", "report", "--name", "홍길동", "--year", "2024"])
+            .arg("--out")
+            .arg(&out),
+    );
+
+    assert_eq!(json["path"].as_str().map(Path::new), Some(out.as_path()));
+    assert_eq!(
+        json["markdown"].as_str().unwrap(),
+        std::fs::read_to_string(&out).unwrap()
+    );
+    let html = stdout_json(suneung(dir.path()).args([
+        "--json",
+        "report",
+        "--name",
+        "홍길동",
+        "--year",
+        "2024",
+        "--format",
+        "html",
+    ]));
+    assert_eq!(html["path"], Value::Null);
+    assert!(html["html"].as_str().unwrap().contains("홍길동"));
+}
+
+#[test]
+fn report_cohort_covers_every_student() {
+    let dir = tempfile::tempdir().unwrap();
+    record(dir.path(), "홍길동");
+    record(dir.path(), "김철수");
+    let output = suneung(dir.path())
+        .args(["report", "--cohort", "--year", "2024"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let markdown = String::from_utf8(output).unwrap();
+
+    let headings = headings(&markdown);
+    assert_eq!(headings[0], "# 2024학년도 환산 점수 (2명)");
+    assert!(headings.contains(&"## 학생별 환산 점수"));
+    let tops = headings.iter().filter(|h| h.ends_with("상위 10명")).count();
+    assert_eq!(tops, University::supported(2024).len());
+    assert!(markdown.contains("홍길동") && markdown.contains("김철수"));
+}
+
+#[test]
+fn report_needs_a_student_or_the_cohort() {
+    let dir = tempfile::tempdir().unwrap();
+    suneung(dir.path())
+        .args(["report", "--year", "2024"])
+        .assert()
+        .code(2);
+    suneung(dir.path())
+        .args(["report", "--name", "홍길동", "--cohort", "--year", "2024"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+/// `value` with every scalar replaced by its JSON type name and every
+/// array by the distinct shapes of its items, so that a snapshot pins the
+/// keys and types of the `--json` output rather than its numbers
+fn shape(value: &Value) -> Value {
+    match value {
+        Value::Null => "null".into(),
+        Value::Bool(_) => "bool".into(),
+        Value::Number(_) => "number".into(),
+        Value::String(_) => "string".into(),
+        Value::Array(items) => {
+            let mut shapes: Vec<Value> = vec![];
+            for item in items.iter().map(shape) {
+                if !shapes.contains(&item) {
+                    shapes.push(item);
+                }
+            }
+            Value::Array(shapes)
+        }
+        Value::Object(map) => {
+            Value::Object(map.iter().map(|(k, v)| (k.clone(), shape(v))).collect())
+        }
+    }
+}
+
+#[test]
+fn json_shapes_are_stable() {
+    let dir = tempfile::tempdir().unwrap();
+    record(dir.path(), "홍길동");
+    let commands: &[&[&str]] = &[
+        &["record", "--name", "홍길동", "--english", "2"],
+        &[
+            "calc",
+            "--name",
+            "홍길동",
+            "--university",
+            "KONKUK",
+            "--year",
+            "2024",
+        ],
+        &[
+            "compare",
+            "--name",
+            "홍길동",
+            "--year",
+            "2022",
+            "--universities",
+            "KONKUK,KYUNGHEE",
+        ],
+        &["list", "universities"],
+        &["list", "records"],
+        &["report", "--name", "홍길동", "--year", "2024"],
+        &[
+            "report",
+            "--name",
+            "홍길동",
+            "--year",
+            "2024",
+            "--format",
+            "html",
+        ],
+        &[
+            "report",
+            "--name",
+            "홍길동",
+            "--year",
+            "2024",
+            "--format",
+            "latex",
+        ],
+        &["radar", "--name", "홍길동"],
+        &["batch", "--year", "2024"],
+    ];
+
+    let mut snapshot = String::new();
+    for args in commands {
+        let json = stdout_json(suneung(dir.path()).arg("--json").args(*args));
+        snapshot += &format!(
+            "{}\n{}\n\n",
+            args.join(" "),
+            serde_json::to_string_pretty(&shape(&json)).unwrap()
+        );
+    }
+    for args in [
+        &[
+            "calc",
+            "--name",
+            "없음",
+            "--university",
+            "KONKUK",
+            "--year",
+            "2024",
+        ][..],
+        &["calc", "--bogus"],
+    ] {
+        let json = stderr_json(suneung(dir.path()).arg("--json").args(args));
+        snapshot += &format!(
+            "{} (stderr)\n{}\n\n",
+            args.join(" "),
+            serde_json::to_string_pretty(&shape(&json)).unwrap()
+        );
+    }
+    assert_snapshot("cli_json_shapes.txt", &snapshot);
+}
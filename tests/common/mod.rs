@@ -0,0 +1,26 @@
+//! Helpers shared by the integration tests
+
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+
+/// Compare `actual` with `tests/snapshots/<name>`. With `UPDATE_SNAPSHOTS`
+/// set the snapshot is written instead, to be reviewed in the diff.
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(name);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("{}: {} (run with UPDATE_SNAPSHOTS=1)", path.display(), e));
+    assert!(
+        expected == actual,
+        "{} differs\n--- expected\n{}\n--- actual\n{}",
+        name,
+        expected,
+        actual
+    );
+}
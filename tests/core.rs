@@ -0,0 +1,82 @@
+//! The calculation core, which builds with `--no-default-features`; CI runs
+//! this file that way to keep the optional dependencies out of it.
+
+use suneung_calc::error::SuneungError;
+use suneung_calc::score::{
+    Grade, Percentile, Record, Score, StandardScore, Subject, University, UniversityWeight,
+};
+
+fn sample(english: u8) -> Record {
+    let grade = |g: u8| Grade::try_from(g).unwrap();
+    let score = |s: f64, p: f64, g: u8| {
+        Score::new_typed(
+            StandardScore::new(s).unwrap(),
+            Percentile::new(p).unwrap(),
+            grade(g),
+        )
+    };
+    let mut record = Record::new("sample");
+    record.record_score(Subject::Korean, score(131.0, 93.0, 2));
+    record.record_score(Subject::Math, score(128.0, 89.0, 2));
+    record.record_score(Subject::English, Score::grade_only(grade(english)));
+    record.record_score(Subject::Chemistry, score(64.0, 92.0, 2));
+    record.record_score(Subject::EarthScience, score(66.0, 94.0, 1));
+    record
+}
+
+#[test]
+fn calculates_without_optional_features() {
+    // Values of tests/fixtures/calc_outputs.txt
+    let record = sample(2);
+    assert_eq!(
+        record.calc_with_university(University::KONKUK, 2024),
+        388.16666666666663
+    );
+    assert_eq!(
+        sample(1).calc_with_university(University::KYUNGHEE, 2022),
+        389.3881578947368
+    );
+}
+
+#[test]
+fn every_supported_pair_calculates() {
+    let record = sample(3);
+    for year in [2022, 2023, 2024, 2025] {
+        let supported = University::supported(year);
+        assert!(!supported.is_empty(), "{}", year);
+        for university in supported {
+            let weight = UniversityWeight::try_load(university, year).unwrap();
+            let score = record.try_calc_with_university(university, year).unwrap();
+            assert_eq!(score, record.calc_with_weight(&weight).unwrap());
+            assert!(
+                score.is_finite() && score > 0.0,
+                "{:?} {}",
+                university,
+                year
+            );
+        }
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+#[should_panic(expected = "건국대 has no weight data for 2022")]
+fn load_panics_without_data() {
+    UniversityWeight::load(University::KONKUK, 2022);
+}
+
+#[test]
+fn english_grades_outside_the_table_are_errors() {
+    // The embedded tables list 1등급 to 6등급; nothing is assumed for the rest
+    assert!(sample(6)
+        .try_calc_with_university(University::KYUNGHEE, 2024)
+        .is_ok());
+    for english in 7..=9 {
+        match sample(english).try_calc_with_university(University::KYUNGHEE, 2024) {
+            Err(SuneungError::InsufficientData(reason)) => {
+                assert!(reason.contains(&format!("{}등급", english)), "{}", reason)
+            }
+            other => panic!("{}등급: {:?}", english, other),
+        }
+    }
+}
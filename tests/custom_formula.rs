@@ -0,0 +1,63 @@
+//! Formulas too deep or too long are parse errors, not stack overflows
+
+use suneung_calc::custom_formula::{CustomFormula, MAX_DEPTH};
+use suneung_calc::error::SuneungError;
+use suneung_calc::input::MAX_INPUT_BYTES;
+use suneung_calc::score::{Record, University, UniversityWeight};
+
+fn depth_error(expr: &str) -> usize {
+    match CustomFormula::parse(expr) {
+        Err(SuneungError::FormulaParse { position, reason }) => {
+            assert!(reason.contains("nests deeper"), "{}", reason);
+            position
+        }
+        other => panic!("{:?}", other.map(|f| f.to_string())),
+    }
+}
+
+#[test]
+fn deep_parentheses_are_rejected() {
+    depth_error(&"(".repeat(200_000));
+    // with the position of the first parenthesis past the limit
+    let expr = format!(
+        "{}1{}",
+        "(".repeat(MAX_DEPTH + 1),
+        ")".repeat(MAX_DEPTH + 1)
+    );
+    assert_eq!(depth_error(&expr), MAX_DEPTH + 1);
+}
+
+#[test]
+fn long_chains_and_unary_minus_are_rejected() {
+    depth_error(&"-".repeat(200_000));
+    depth_error(&format!("{}1", "1+".repeat(200_000)));
+    depth_error(&format!("{}1", "2*".repeat(200_000)));
+}
+
+#[test]
+fn nesting_up_to_the_limit_is_accepted() {
+    let record = Record::sample();
+    let table = *UniversityWeight::try_load(University::KYUNGHEE, 2024)
+        .unwrap()
+        .english_table();
+    let eval = |expr: &str| {
+        record
+            .calc_with_formula(&CustomFormula::parse(expr).unwrap(), &table)
+            .unwrap()
+    };
+
+    let parens = format!("{}KOR{}", "(".repeat(MAX_DEPTH), ")".repeat(MAX_DEPTH));
+    assert_eq!(eval(&parens), 131.0);
+    let chain = format!("{}1", "1+".repeat(MAX_DEPTH));
+    assert_eq!(eval(&chain), (MAX_DEPTH + 1) as f64);
+    assert_eq!(eval(&format!("{}KOR", "-".repeat(MAX_DEPTH))), 131.0);
+}
+
+#[test]
+fn oversized_formulas_are_rejected_before_parsing() {
+    let expr = "1".repeat(MAX_INPUT_BYTES + 1);
+    assert!(matches!(
+        CustomFormula::parse(&expr),
+        Err(SuneungError::InvalidInput(_))
+    ));
+}
@@ -0,0 +1,52 @@
+//! Snapshots of `Display for Record`
+
+mod common;
+
+use common::assert_snapshot;
+use suneung_calc::convert::{KoreanTrack, MathTrack, Track};
+use suneung_calc::exam::{ExamId, ExamKind, ExamMeta};
+use suneung_calc::score::{Record, Subject};
+
+#[test]
+fn sample() {
+    assert_snapshot("display_sample.txt", &Record::sample().to_string());
+}
+
+#[test]
+fn with_exam_tags_electives_and_sources() {
+    let mut record = Record::sample();
+    record.set_exam(Some(ExamMeta::new(ExamKind::Csat, 2024)));
+    record.add_tag("3반");
+    record.add_tag("재수");
+    record
+        .set_elective(Track::Korean(KoreanTrack::LanguageMedia))
+        .unwrap();
+    record
+        .set_elective(Track::Math(MathTrack::Calculus))
+        .unwrap();
+    let math = record.try_score(Subject::Math).unwrap();
+    record.record_score(
+        Subject::Math,
+        math.with_source(ExamId::new(ExamKind::Mock9, 2024)),
+    );
+    let chemistry = record.try_score(Subject::Chemistry).unwrap();
+    record.record_score(
+        Subject::Chemistry,
+        chemistry.with_estimated_percentile(91.5),
+    );
+    assert_snapshot("display_detailed.txt", &record.to_string());
+}
+
+#[test]
+fn partial_record() {
+    let mut record = Record::sample();
+    record.rename("세 과목");
+    record.remove_subject(Subject::Chemistry);
+    record.remove_subject(Subject::EarthScience);
+    assert_snapshot("display_partial.txt", &record.to_string());
+}
+
+#[test]
+fn empty_record() {
+    assert_snapshot("display_empty.txt", &Record::new("빈 기록").to_string());
+}
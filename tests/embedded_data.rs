@@ -0,0 +1,136 @@
+#![cfg(feature = "csv")]
+
+//! The embedded weights against `data_src/`, read here independently of
+//! `build.rs`, so a change to the generator that drops or garbles a value
+//! fails a test rather than a calculation.
+
+use std::collections::HashMap;
+use suneung_calc::score::{University, UniversityWeight};
+
+// Rows of a file of `data_src/`, header excluded, keyed by university and
+// year
+fn rows(file: &str) -> HashMap<(University, usize), Vec<String>> {
+    let path = format!("{}/data_src/{}", env!("CARGO_MANIFEST_DIR"), file);
+    let mut rdr = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_path(&path)
+        .unwrap();
+    let mut rows = HashMap::new();
+    for record in rdr.records() {
+        let record = record.unwrap();
+        let fields: Vec<String> = record.iter().map(str::to_string).collect();
+        let university: University = fields[0].parse().unwrap();
+        let year: usize = fields[1].parse().unwrap();
+        assert!(
+            rows.insert((university, year), fields[2..].to_vec())
+                .is_none(),
+            "{} {} twice in {}",
+            university,
+            year,
+            file
+        );
+    }
+    rows
+}
+
+fn number(field: &str) -> f64 {
+    field.parse().unwrap()
+}
+
+#[test]
+fn every_embedded_weight_has_a_row_and_every_row_a_weight() {
+    let weights = rows("weights.csv");
+    let embedded: Vec<(University, usize)> = (2022..=2025)
+        .flat_map(|year| {
+            University::supported(year)
+                .into_iter()
+                .map(move |u| (u, year))
+        })
+        .collect();
+    assert_eq!(embedded.len(), weights.len());
+    for key in &embedded {
+        assert!(weights.contains_key(key), "{:?} has no row", key);
+    }
+    for &(university, year) in weights.keys() {
+        assert!(UniversityWeight::load_cached(university, year).is_some());
+    }
+}
+
+#[test]
+fn embedded_weights_match_weights_csv() {
+    for ((university, year), fields) in rows("weights.csv") {
+        let weight = UniversityWeight::load_cached(university, year).unwrap();
+        let at = format!("{} {}", university, year);
+        assert_eq!(
+            [
+                weight.korean(),
+                weight.math(),
+                weight.english(),
+                weight.science()
+            ],
+            [
+                number(&fields[0]),
+                number(&fields[1]),
+                number(&fields[2]),
+                number(&fields[3])
+            ],
+            "{}",
+            at
+        );
+        assert_eq!(
+            weight.science_required(),
+            fields[4].parse::<usize>().unwrap(),
+            "{}",
+            at
+        );
+        assert_eq!(
+            format!("{:?}", weight.science_aggregation()),
+            fields[5],
+            "{}",
+            at
+        );
+        assert_eq!(
+            weight.english_required(),
+            fields[6].parse::<usize>().unwrap(),
+            "{}",
+            at
+        );
+        let optional = |i: usize| fields.get(i).filter(|s| !s.is_empty()).cloned();
+        let provenance = weight.provenance();
+        assert_eq!(
+            provenance.map(|p| p.document.to_string()),
+            optional(7),
+            "{}",
+            at
+        );
+        assert_eq!(
+            provenance.and_then(|p| p.section.as_deref().map(str::to_string)),
+            optional(8),
+            "{}",
+            at
+        );
+        assert_eq!(
+            provenance.and_then(|p| p.entered.as_deref().map(str::to_string)),
+            optional(9),
+            "{}",
+            at
+        );
+    }
+}
+
+#[test]
+fn embedded_english_tables_match_english_tables_csv() {
+    for ((university, year), fields) in rows("english_tables.csv") {
+        let weight = UniversityWeight::load_cached(university, year).unwrap();
+        let listed: Vec<f64> = fields.iter().map(|f| number(f)).collect();
+        // Grades past the end of the row are not covered
+        assert_eq!(
+            weight.english_table().points(),
+            &listed[..],
+            "{} {}",
+            university,
+            year
+        );
+    }
+}
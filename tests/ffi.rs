@@ -0,0 +1,166 @@
+//! The C ABI called the way a C# or C host would, with every kind of bad
+//! argument; none of them may abort the process.
+#![cfg(feature = "ffi")]
+
+use std::ffi::{CStr, CString};
+use std::ptr;
+use suneung_calc::ffi::*;
+use suneung_calc::score::{Record, Subject, University};
+
+fn last_error() -> String {
+    let message = suneung_last_error_message();
+    assert!(!message.is_null());
+    unsafe { CStr::from_ptr(message) }
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+fn code(subject: Subject) -> u32 {
+    Subject::all().iter().position(|&s| s == subject).unwrap() as u32
+}
+
+fn university_code(university: University) -> u32 {
+    University::all()
+        .iter()
+        .position(|&u| u == university)
+        .unwrap() as u32
+}
+
+// `Record::sample` through the C ABI
+unsafe fn sample() -> *mut SuneungRecord {
+    let name = CString::new("샘플").unwrap();
+    let record = suneung_record_new(name.as_ptr());
+    assert!(!record.is_null());
+    let sample = Record::sample();
+    for (subject, score) in sample.iter() {
+        let result = suneung_record_set(
+            record,
+            code(subject),
+            score.standard_score(),
+            score.percentile(),
+            score.rank().get() as u32,
+        );
+        assert_eq!(result, SUNEUNG_OK, "{:?}", subject);
+    }
+    record
+}
+
+#[test]
+fn calculates_like_the_library() {
+    unsafe {
+        let record = sample();
+        let mut score = 0f64;
+        let result = suneung_calc(
+            record,
+            university_code(University::KONKUK),
+            2024,
+            &mut score,
+        );
+        assert_eq!(result, SUNEUNG_OK);
+        assert_eq!(
+            score,
+            Record::sample().calc_with_university(University::KONKUK, 2024)
+        );
+        suneung_record_free(record);
+    }
+}
+
+#[test]
+fn null_pointers_are_errors() {
+    unsafe {
+        assert!(suneung_record_new(ptr::null()).is_null());
+        assert_eq!(last_error(), "name is NULL");
+
+        let result = suneung_record_set(ptr::null_mut(), 0, 131.0, 93.0, 2);
+        assert_eq!(result, SUNEUNG_ERR_INVALID_ARGUMENT);
+
+        let mut score = 0f64;
+        assert_eq!(
+            suneung_calc(ptr::null(), 0, 2024, &mut score),
+            SUNEUNG_ERR_INVALID_ARGUMENT
+        );
+
+        let record = sample();
+        assert_eq!(
+            suneung_calc(record, 0, 2024, ptr::null_mut()),
+            SUNEUNG_ERR_INVALID_ARGUMENT
+        );
+        assert_eq!(last_error(), "out_score is NULL");
+        suneung_record_free(record);
+        suneung_record_free(ptr::null_mut());
+    }
+}
+
+#[test]
+fn invalid_names_are_errors() {
+    unsafe {
+        let bytes = [0xffu8, 0xfe, 0];
+        assert!(suneung_record_new(bytes.as_ptr().cast()).is_null());
+        assert_eq!(last_error(), "name is not valid UTF-8");
+    }
+}
+
+#[test]
+fn bad_scores_and_codes_are_errors() {
+    unsafe {
+        let record = sample();
+        let korean = code(Subject::Korean);
+        for (standard_score, percentile, grade) in [
+            (f64::NAN, 93.0, 2),
+            (131.0, f64::INFINITY, 2),
+            (-1.0, 93.0, 2),
+            (131.0, 101.0, 2),
+        ] {
+            let result = suneung_record_set(record, korean, standard_score, percentile, grade);
+            assert_eq!(result, SUNEUNG_ERR_INVALID_SCORE);
+        }
+        for grade in [0, 10, u32::MAX] {
+            assert_ne!(
+                suneung_record_set(record, korean, 131.0, 93.0, grade),
+                SUNEUNG_OK
+            );
+        }
+        assert_eq!(
+            suneung_record_set(record, 99, 131.0, 93.0, 2),
+            SUNEUNG_ERR_INVALID_ARGUMENT
+        );
+
+        let mut score = -1f64;
+        assert_eq!(
+            suneung_calc(record, u32::MAX, 2024, &mut score),
+            SUNEUNG_ERR_INVALID_ARGUMENT
+        );
+        assert_eq!(
+            suneung_calc(
+                record,
+                university_code(University::KONKUK),
+                1999,
+                &mut score
+            ),
+            SUNEUNG_ERR_UNSUPPORTED_YEAR
+        );
+        // Failed calls leave the output alone
+        assert_eq!(score, -1.0);
+        suneung_record_free(record);
+    }
+}
+
+#[test]
+fn missing_subjects_are_errors() {
+    unsafe {
+        let name = CString::new("빈 기록").unwrap();
+        let record = suneung_record_new(name.as_ptr());
+        let mut score = 0f64;
+        assert_eq!(
+            suneung_calc(
+                record,
+                university_code(University::KONKUK),
+                2024,
+                &mut score
+            ),
+            SUNEUNG_ERR_MISSING_SUBJECT
+        );
+        suneung_record_free(record);
+    }
+}
@@ -0,0 +1,83 @@
+//! Limits on text and names from outside the crate
+
+use suneung_calc::error::SuneungError;
+use suneung_calc::input::{check_name, MAX_NAME_CHARS};
+use suneung_calc::score::Record;
+
+#[test]
+fn plain_names_are_accepted() {
+    for name in [
+        "홍길동",
+        "김철수 (재수)",
+        "a.b",
+        "...",
+        "3반-12",
+        &"가".repeat(MAX_NAME_CHARS),
+    ] {
+        assert_eq!(check_name(name).ok(), Some(()), "{:?}", name);
+    }
+}
+
+#[test]
+fn names_that_are_not_one_directory_are_rejected() {
+    for name in [
+        "",
+        ".",
+        "..",
+        "../홍길동",
+        "홍길동/..",
+        "/etc",
+        "a\\b",
+        "\\\\server\\share",
+        "C:",
+        "c:홍길동",
+        "홍길동\n",
+        "\0",
+        &"가".repeat(MAX_NAME_CHARS + 1),
+    ] {
+        assert!(
+            matches!(check_name(name), Err(SuneungError::InvalidInput(_))),
+            "{:?}",
+            name
+        );
+    }
+}
+
+#[test]
+fn records_with_such_names_do_not_validate() {
+    assert!(Record::sample().validate().is_ok());
+    let mut record = Record::sample();
+    record.rename("../../etc");
+    assert!(matches!(
+        record.validate(),
+        Err(SuneungError::InvalidInput(m)) if m.contains("path separator")
+    ));
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn stored_records_are_refused_such_names() {
+    use suneung_calc::score::ReadOptions;
+
+    let root = tempfile::tempdir().unwrap();
+    let dir = root.path().join("data");
+    let mut record = Record::sample();
+    record.rename("../밖");
+    assert!(matches!(
+        record.write_parquet_in(&dir).unwrap_err().downcast::<SuneungError>(),
+        Ok(e) if matches!(*e, SuneungError::InvalidInput(_))
+    ));
+    assert!(!root.path().join("밖").exists());
+
+    Record::sample().write_parquet_in(root.path()).unwrap();
+    for name in ["../샘플", ".."] {
+        assert!(matches!(
+            Record::read_parquet_in(&dir, name, &ReadOptions::default()),
+            Err(SuneungError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            Record::read_parquet_subjects_in(&dir, name, &[]),
+            Err(SuneungError::InvalidInput(_))
+        ));
+    }
+}
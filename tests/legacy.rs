@@ -0,0 +1,84 @@
+//! Files written before the Metric column, see `Record::is_legacy`.
+//!
+//! `fixtures/legacy/legacy/record.parquet` was written by the first release
+//! (`Record::write_parquet` with all five subjects, English as
+//! `[0, 0, grade]`) and must keep loading.
+#![cfg(feature = "parquet")]
+
+use std::path::{Path, PathBuf};
+use suneung_calc::score::{Grade, ReadOptions, Record, Subject};
+
+const NAME: &str = "legacy";
+
+fn fixtures() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/legacy")
+}
+
+// A copy of the fixture to upgrade
+fn copy() -> tempfile::TempDir {
+    let root = tempfile::tempdir().unwrap();
+    std::fs::create_dir(root.path().join(NAME)).unwrap();
+    std::fs::copy(
+        fixtures().join(NAME).join("record.parquet"),
+        root.path().join(NAME).join("record.parquet"),
+    )
+    .unwrap();
+    root
+}
+
+fn assert_fixture_scores(record: &Record) {
+    let expected = [
+        (Subject::Korean, 131.0, 93.0, 2),
+        (Subject::Math, 128.0, 89.0, 2),
+        (Subject::Chemistry, 64.0, 92.0, 2),
+        (Subject::EarthScience, 66.0, 94.0, 1),
+    ];
+    assert_eq!(record.len(), 5);
+    for (subject, standard_score, percentile, grade) in expected {
+        assert_eq!(
+            record.standard_score(subject),
+            standard_score,
+            "{:?}",
+            subject
+        );
+        assert_eq!(record.percentile(subject), percentile, "{:?}", subject);
+        assert_eq!(record.rank(subject).get(), grade, "{:?}", subject);
+    }
+    assert_eq!(record.rank(Subject::English), Grade::try_from(3u8).unwrap());
+}
+
+#[test]
+fn legacy_fixture_loads() {
+    let record = Record::read_parquet_in(fixtures(), NAME, &ReadOptions::default()).unwrap();
+    assert_eq!(record.name(), NAME);
+    assert_fixture_scores(&record);
+    assert!(record.exam().is_none());
+    assert!(Record::is_legacy_in(fixtures(), NAME).unwrap());
+}
+
+#[test]
+fn legacy_fixture_loads_subjects() {
+    let record =
+        Record::read_parquet_subjects_in(fixtures(), NAME, &[Subject::Math, Subject::English])
+            .unwrap();
+    assert_eq!(
+        record.subjects().collect::<Vec<_>>(),
+        [Subject::Math, Subject::English]
+    );
+    assert_eq!(record.standard_score(Subject::Math), 128.0);
+    assert_eq!(record.rank(Subject::English), Grade::try_from(3u8).unwrap());
+}
+
+#[test]
+fn upgrade_rewrites_the_fixture_forward() {
+    let root = copy();
+    assert!(Record::upgrade_in(root.path(), NAME).unwrap());
+    assert!(!Record::is_legacy_in(root.path(), NAME).unwrap());
+    assert!(root.path().join(NAME).join("record.sha256").exists());
+
+    let upgraded = Record::read_parquet_in(root.path(), NAME, &ReadOptions::default()).unwrap();
+    assert_fixture_scores(&upgraded);
+
+    // Current files are left alone
+    assert!(!Record::upgrade_in(root.path(), NAME).unwrap());
+}
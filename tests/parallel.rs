@@ -0,0 +1,62 @@
+#![cfg(all(feature = "parallel", feature = "stats"))]
+
+//! The rayon paths give what the serial ones do, for every year and on
+//! pools of different sizes.
+
+use suneung_calc::cohort::Cohort;
+use suneung_calc::report::matrix;
+use suneung_calc::score::{Record, Subject, University};
+
+const YEARS: [usize; 4] = [2022, 2023, 2024, 2025];
+
+// The samples, one of them without 지구과학, and one with no scores at all
+fn records() -> Vec<Record> {
+    let mut records = Record::samples();
+    let mut one_science = Record::sample();
+    one_science.rename("탐구 하나");
+    one_science.remove_subject(Subject::EarthScience);
+    records.push(one_science);
+    records.push(Record::new("빈 기록"));
+    records
+}
+
+fn on_threads<T: Send>(threads: usize, f: impl FnOnce() -> T + Send) -> T {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .unwrap()
+        .install(f)
+}
+
+#[test]
+fn calc_all_par_matches_calc_all() {
+    for year in YEARS {
+        for record in records() {
+            let serial = record.calc_all(year).map_err(|e| e.to_string());
+            for threads in [1, 2, 8] {
+                let parallel = on_threads(threads, || record.calc_all_par(year));
+                assert_eq!(
+                    parallel.map_err(|e| e.to_string()),
+                    serial,
+                    "{} {} on {} threads",
+                    record.name(),
+                    year,
+                    threads
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn calc_matrix_par_matches_matrix() {
+    let records = records();
+    let cohort = Cohort::new(records.clone());
+    for year in YEARS {
+        let serial = matrix(&records, &University::supported(year), year);
+        for threads in [1, 2, 8] {
+            let parallel = on_threads(threads, || cohort.calc_matrix_par(year));
+            assert_eq!(parallel, serial, "{} on {} threads", year, threads);
+        }
+    }
+}
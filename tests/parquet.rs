@@ -0,0 +1,115 @@
+//! Round trips of records through `record.parquet`
+#![cfg(feature = "parquet")]
+
+use peroxide::fuga::*;
+use std::path::Path;
+use suneung_calc::score::{Grade, ReadOptions, Record, Score, Subject};
+
+// Records compare by their serde model
+fn json(record: &Record) -> serde_json::Value {
+    serde_json::to_value(record).unwrap()
+}
+
+fn round_trip(root: &Path, record: &Record) -> Record {
+    record.write_parquet_in(root).unwrap();
+    Record::read_parquet_in(root, record.name(), &ReadOptions::default()).unwrap()
+}
+
+// Three of the five subjects, the second science not taken yet
+fn three_of_five(present: [Subject; 3]) -> Record {
+    let sample = Record::sample();
+    let mut record = Record::new("세 과목");
+    for subject in present {
+        record.record_score(subject, sample.try_score(subject).unwrap());
+    }
+    record
+}
+
+#[test]
+fn three_of_five_subjects_round_trip() {
+    let root = tempfile::tempdir().unwrap();
+    let present = [Subject::Korean, Subject::Math, Subject::Chemistry];
+    let record = three_of_five(present);
+
+    let back = round_trip(root.path(), &record);
+    assert_eq!(back.len(), 3);
+    assert_eq!(back.subjects().collect::<Vec<_>>(), present);
+    for subject in [Subject::English, Subject::EarthScience] {
+        assert!(back.try_score(subject).is_none(), "{:?}", subject);
+    }
+    assert_eq!(json(&back), json(&record));
+}
+
+#[test]
+fn every_three_of_five_round_trips() {
+    let root = tempfile::tempdir().unwrap();
+    let all = Subject::all();
+    for (i, a) in all.iter().enumerate() {
+        for (j, b) in all.iter().enumerate().skip(i + 1) {
+            for c in all.iter().skip(j + 1) {
+                let record = three_of_five([*a, *b, *c]);
+                let back = round_trip(root.path(), &record);
+                assert_eq!(json(&back), json(&record), "{:?}", [a, b, c]);
+            }
+        }
+    }
+}
+
+#[test]
+fn absent_subjects_have_no_column() {
+    let record = three_of_five([Subject::Korean, Subject::English, Subject::EarthScience]);
+    let df = record.to_dataframe();
+    let header = df.header();
+    assert!(header.iter().any(|h| h == "English"));
+    assert!(!header.iter().any(|h| h == "Math" || h == "Chemistry"));
+}
+
+#[test]
+fn english_alone_keeps_its_grade() {
+    let root = tempfile::tempdir().unwrap();
+    let mut record = Record::new("영어만");
+    record.record_score(
+        Subject::English,
+        Score::grade_only(Grade::try_from(3u8).unwrap()),
+    );
+    let back = round_trip(root.path(), &record);
+    assert_eq!(back.len(), 1);
+    assert_eq!(back.rank(Subject::English), Grade::try_from(3u8).unwrap());
+}
+
+#[test]
+fn reading_subjects_of_a_partial_record() {
+    let root = tempfile::tempdir().unwrap();
+    let record = three_of_five([Subject::Korean, Subject::Math, Subject::Chemistry]);
+    record.write_parquet_in(root.path()).unwrap();
+
+    let some = Record::read_parquet_subjects_in(
+        root.path(),
+        record.name(),
+        &[Subject::Math, Subject::EarthScience],
+    )
+    .unwrap();
+    assert_eq!(some.subjects().collect::<Vec<_>>(), [Subject::Math]);
+}
+
+#[test]
+fn written_file_labels_its_metric_rows() {
+    let root = tempfile::tempdir().unwrap();
+    let record = Record::sample();
+    record.write_parquet_in(root.path()).unwrap();
+
+    // Read the file as an external tool would, without `Record`
+    let path = root.path().join(record.name()).join("record.parquet");
+    let df = DataFrame::read_parquet(path.to_str().unwrap()).unwrap();
+    let header = df.header();
+    assert_eq!(header[0], "Metric");
+    let metrics: Vec<String> = df["Metric"].to_vec();
+    assert_eq!(metrics, ["standard_score", "percentile", "grade"]);
+
+    let subjects: Vec<&str> = Subject::all().iter().map(|s| s.name()).collect();
+    assert_eq!(&header[1..6], subjects.as_slice());
+    let korean: Vec<f64> = df["Korean"].to_vec();
+    assert_eq!(korean, [131.0, 93.0, 2.0]);
+    let english: Vec<f64> = df["English"].to_vec();
+    assert_eq!(english[2], 2.0);
+}
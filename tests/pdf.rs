@@ -0,0 +1,81 @@
+#![cfg(feature = "pdf")]
+
+//! PDF student reports, checked by their header and page tree.
+//!
+//! Typesetting needs a font with Hangul: the directory of
+//! `SUNEUNG_TEST_FONT` if set, else the system fonts. A test that finds
+//! none says so on stderr and returns early; the CI job has fonts-nanum.
+
+use std::path::PathBuf;
+use suneung_calc::error::SuneungError;
+use suneung_calc::report::{pdf, ReportOptions};
+use suneung_calc::score::Record;
+
+fn options() -> ReportOptions {
+    let font_dirs = std::env::var_os("SUNEUNG_TEST_FONT")
+        .map(PathBuf::from)
+        .and_then(|font| font.parent().map(PathBuf::from))
+        .into_iter()
+        .collect();
+    ReportOptions {
+        font_dirs,
+        ..ReportOptions::default()
+    }
+}
+
+// `None` when no font covers Hangul
+fn report(record: &Record, opts: &ReportOptions) -> Option<Vec<u8>> {
+    match pdf::student_report_bytes(record, 2024, opts) {
+        Err(SuneungError::Pdf(m)) if m.contains("no font with Hangul") => {
+            eprintln!("{}; set SUNEUNG_TEST_FONT to run this test", m);
+            None
+        }
+        other => Some(other.unwrap()),
+    }
+}
+
+// Page objects, `/Type /Page` but not `/Type /Pages`
+fn pages(pdf: &[u8]) -> usize {
+    let needle = b"/Type /Page";
+    pdf.windows(needle.len() + 1)
+        .filter(|w| w.starts_with(needle) && w[needle.len()] != b's')
+        .count()
+}
+
+#[test]
+fn report_is_a_pdf_with_pages() {
+    let Some(bytes) = report(&Record::sample(), &options()) else {
+        return;
+    };
+    assert!(bytes.starts_with(b"%PDF-1."));
+    assert!(bytes.trim_ascii_end().ends_with(b"%%EOF"));
+    assert!(pages(&bytes) >= 1);
+}
+
+#[test]
+fn scores_alone_fit_on_one_page() {
+    let mut opts = options();
+    opts.sections.comparison = false;
+    opts.sections.english_sweep = false;
+    let Some(short) = report(&Record::sample(), &opts) else {
+        return;
+    };
+    assert_eq!(pages(&short), 1);
+
+    let full = report(&Record::sample(), &options()).unwrap();
+    assert!(pages(&full) >= pages(&short));
+    assert!(full.len() > short.len());
+}
+
+#[test]
+fn student_report_writes_the_bytes() {
+    let opts = options();
+    let Some(bytes) = report(&Record::sample(), &opts) else {
+        return;
+    };
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("report.pdf");
+    pdf::student_report(&Record::sample(), 2024, &opts, &out).unwrap();
+    // Typst output is deterministic for the same input
+    assert_eq!(std::fs::read(out).unwrap(), bytes);
+}
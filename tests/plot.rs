@@ -0,0 +1,322 @@
+#![cfg(feature = "plot")]
+
+//! Bars and trend charts, drawn to files in a temporary directory.
+//!
+//! Drawing needs a font: `SUNEUNG_TEST_FONT` if set, else the first of
+//! `plot::FONT_CANDIDATES` that is installed. Without either the drawing
+//! tests print why and pass; CI installs a Korean font so they run there.
+
+use std::path::{Path, PathBuf};
+use suneung_calc::admission::Chance;
+use suneung_calc::api::{compare, ComparisonRow};
+use suneung_calc::error::SuneungError;
+use suneung_calc::exam::{ExamKind, ExamMeta};
+use suneung_calc::normalize::ExamStatsTable;
+use suneung_calc::plot::{
+    bar_data, trend, trend_data, university_bars, PlotOptions, FONT_CANDIDATES,
+};
+use suneung_calc::score::{Record, Subject, University};
+
+fn font() -> Option<PathBuf> {
+    let font = std::env::var_os("SUNEUNG_TEST_FONT")
+        .map(PathBuf::from)
+        .or_else(|| {
+            FONT_CANDIDATES
+                .iter()
+                .map(PathBuf::from)
+                .find(|p| p.is_file())
+        });
+    if font.is_none() {
+        eprintln!("no font for drawing, set SUNEUNG_TEST_FONT to run this test");
+    }
+    font
+}
+
+fn rows() -> Vec<ComparisonRow> {
+    compare(&Record::sample(), &University::supported(2024), 2024, true)
+}
+
+// How many `<rect` elements of the SVG at `path` are filled with `color`
+fn rects_filled(path: &Path, color: &str) -> usize {
+    let fill = format!("fill=\"{}\"", color);
+    std::fs::read_to_string(path)
+        .unwrap()
+        .split("<rect")
+        .skip(1)
+        .filter(|rect| rect[..rect.find('>').unwrap()].contains(&fill))
+        .count()
+}
+
+#[test]
+fn bar_data_sorts_scores_and_leaves_out_unscored_rows() {
+    let mut rows = rows();
+    rows[0].score = None;
+    let bars = bar_data(&rows, &PlotOptions::default());
+
+    assert_eq!(bars.len(), rows.len() - 1);
+    assert!(bars.windows(2).all(|w| w[0].value >= w[1].value));
+    assert!(bars.iter().all(|b| b.university != rows[0].university));
+    assert!(bars.iter().all(|b| b.chance.is_none()));
+}
+
+#[test]
+fn bar_data_percent_of_max_and_chances() {
+    let rows = rows();
+    let best = rows.iter().filter_map(|r| r.score).fold(0f64, f64::max);
+    let mut opts = PlotOptions {
+        percent_of_max: true,
+        ..PlotOptions::default()
+    };
+    let konkuk = rows
+        .iter()
+        .find(|r| r.university == University::KONKUK)
+        .and_then(|r| r.score)
+        .unwrap();
+    opts.cuts.insert(University::KONKUK, konkuk + 1.0);
+    opts.cuts.insert(University::SOGANG, 300.0);
+    let bars = bar_data(&rows, &opts);
+
+    assert_eq!(bars[0].value, 100.0);
+    let bar = |u: University| bars.iter().find(|b| b.university == u).unwrap();
+    assert_eq!(bar(University::KONKUK).value, konkuk / best * 100.0);
+    assert_eq!(bar(University::KONKUK).chance, Some(Chance::Reach));
+    assert_eq!(bar(University::SOGANG).chance, Some(Chance::Safe));
+    assert_eq!(bar(University::DONGGUK).chance, None);
+}
+
+#[test]
+fn bars_are_drawn_one_per_university() {
+    let Some(font) = font() else { return };
+    let dir = tempfile::tempdir().unwrap();
+    let opts = PlotOptions {
+        font: Some(font),
+        ..PlotOptions::default()
+    };
+    let rows = rows();
+    let path =
+        university_bars(&Record::sample(), &rows, dir.path().join("bars.svg"), &opts).unwrap();
+
+    let svg = std::fs::read_to_string(&path).unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("샘플 대학별 환산 점수"));
+    // BLUE.mix(0.6), with no legend without cut lines
+    assert_eq!(rects_filled(&path, "#0000FF"), rows.len());
+    for row in &rows {
+        assert!(svg.contains(&format!(">\n{:.2}\n</text>", row.score.unwrap())));
+    }
+}
+
+#[test]
+fn bars_with_cuts_are_colored_and_get_a_legend() {
+    let Some(font) = font() else { return };
+    let dir = tempfile::tempdir().unwrap();
+    let mut opts = PlotOptions {
+        font: Some(font),
+        ..PlotOptions::default()
+    };
+    opts.cuts.insert(University::KONKUK, 300.0);
+    let rows = rows();
+    let path =
+        university_bars(&Record::sample(), &rows, dir.path().join("bars.svg"), &opts).unwrap();
+
+    // Each color once per bar and once in the legend
+    assert_eq!(rects_filled(&path, "#2EA043"), 1 + 1);
+    assert_eq!(rects_filled(&path, "#969696"), rows.len() - 1 + 1);
+    assert!(std::fs::read_to_string(&path).unwrap().contains("안정"));
+}
+
+#[test]
+fn bars_are_written_as_png() {
+    let Some(font) = font() else { return };
+    let dir = tempfile::tempdir().unwrap();
+    let opts = PlotOptions {
+        font: Some(font),
+        width: 800,
+        height: 500,
+        ..PlotOptions::default()
+    };
+    let path = university_bars(
+        &Record::sample(),
+        &rows(),
+        dir.path().join("bars.png"),
+        &opts,
+    )
+    .unwrap();
+
+    let png = std::fs::read(path).unwrap();
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    // IHDR: width and height, big-endian, after the chunk length and type
+    assert_eq!(u32::from_be_bytes(png[16..20].try_into().unwrap()), 800);
+    assert_eq!(u32::from_be_bytes(png[20..24].try_into().unwrap()), 500);
+}
+
+#[test]
+fn nothing_to_draw_and_unknown_formats_are_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut unscored = rows();
+    for row in &mut unscored {
+        row.score = None;
+    }
+    let err = university_bars(
+        &Record::sample(),
+        &unscored,
+        dir.path().join("bars.svg"),
+        &PlotOptions::default(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, SuneungError::Plot(m) if m.contains("no converted score")));
+
+    let Some(font) = font() else { return };
+    let opts = PlotOptions {
+        font: Some(font),
+        ..PlotOptions::default()
+    };
+    let err = university_bars(
+        &Record::sample(),
+        &rows(),
+        dir.path().join("bars.gif"),
+        &opts,
+    )
+    .unwrap_err();
+    assert!(matches!(err, SuneungError::Plot(m) if m.contains(".png or .svg")));
+}
+
+// 6월, 9월 and 수능 of the 2024학년도; 수학 is missing from 9월
+fn exams() -> Vec<Record> {
+    [ExamKind::Mock6, ExamKind::Mock9, ExamKind::Csat]
+        .into_iter()
+        .enumerate()
+        .map(|(i, kind)| {
+            let mut record = Record::sample();
+            record.set_exam(Some(ExamMeta::new(kind, 2024)));
+            let korean = record.try_score(Subject::Korean).unwrap();
+            record
+                .set_standard_score_typed(
+                    Subject::Korean,
+                    (korean.standard_score() + i as f64).try_into().unwrap(),
+                )
+                .unwrap();
+            if kind == ExamKind::Mock9 {
+                record.remove_subject(Subject::Math);
+            }
+            record
+        })
+        .collect()
+}
+
+#[test]
+fn trend_data_follows_each_subject_across_exams() {
+    let records = exams();
+    let data = trend_data(
+        &records,
+        &[Subject::Korean, Subject::Math, Subject::English],
+        &PlotOptions::default(),
+    );
+
+    let labels: Vec<String> = records
+        .iter()
+        .map(|r| r.exam().unwrap().to_string())
+        .collect();
+    assert_eq!(data.labels, labels);
+    assert_eq!(
+        data.lines,
+        vec![
+            (Subject::Korean, vec![Some(131.0), Some(132.0), Some(133.0)]),
+            (Subject::Math, vec![Some(128.0), None, Some(128.0)]),
+        ]
+    );
+    assert_eq!(data.overlay, None);
+}
+
+#[test]
+fn trend_data_percentiles_z_scores_and_overlay() {
+    let records = exams();
+    let percentile = PlotOptions {
+        percentile: true,
+        ..PlotOptions::default()
+    };
+    let data = trend_data(&records, &[Subject::Chemistry], &percentile);
+    assert_eq!(data.lines, vec![(Subject::Chemistry, vec![Some(92.0); 3])]);
+
+    // Mock exams have no embedded statistics and fall back to the nominal
+    // scale of 50 ± 10
+    let normalized = PlotOptions {
+        normalized: Some(ExamStatsTable::embedded()),
+        ..PlotOptions::default()
+    };
+    let data = trend_data(&records, &[Subject::Chemistry], &normalized);
+    assert_eq!(data.lines[0].1[0], Some(1.4));
+
+    let overlay = PlotOptions {
+        overlay: Some(University::KONKUK),
+        ..PlotOptions::default()
+    };
+    let data = trend_data(&records, &[Subject::Korean], &overlay);
+    let converted = data.overlay.unwrap();
+    assert_eq!(
+        converted[0],
+        records[0]
+            .try_calc_with_university(University::KONKUK, 2024)
+            .ok()
+    );
+    assert_eq!(converted[1], None);
+    assert!(converted[2] > converted[0]);
+}
+
+#[test]
+fn trend_is_drawn_with_a_line_per_subject() {
+    let Some(font) = font() else { return };
+    let dir = tempfile::tempdir().unwrap();
+    let opts = PlotOptions {
+        font: Some(font),
+        overlay: Some(University::KONKUK),
+        ..PlotOptions::default()
+    };
+    let path = trend(
+        &exams(),
+        &[Subject::Korean, Subject::Math],
+        dir.path().join("trend.svg"),
+        &opts,
+    )
+    .unwrap();
+
+    let svg = std::fs::read_to_string(path).unwrap();
+    assert!(svg.contains("샘플 표준점수 추이"));
+    assert!(svg.contains("건국대 환산 점수"));
+    // Lines in the `Palette99` colors of 국어 and 수학: 국어 in one run,
+    // 수학 split in two around 9월
+    let lines = |color: &str| {
+        svg.split("<polyline")
+            .skip(1)
+            .filter(|l| l.contains(&format!("stroke=\"{}\" stroke-width=\"2\"", color)))
+            .count()
+    };
+    assert_eq!(lines("#E6194B"), 1);
+    assert_eq!(lines("#3CB44B"), 2);
+}
+
+#[test]
+fn trend_without_anything_to_draw_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("trend.svg");
+    let err = trend(&[], &[Subject::Korean], &path, &PlotOptions::default()).unwrap_err();
+    assert!(matches!(err, SuneungError::Plot(m) if m.contains("no records")));
+
+    let err = trend(
+        &exams(),
+        &[Subject::English],
+        &path,
+        &PlotOptions::default(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, SuneungError::Plot(m) if m.contains("no scores")));
+
+    let mut undated = Record::sample();
+    undated.set_exam(None);
+    let opts = PlotOptions {
+        overlay: Some(University::KONKUK),
+        ..PlotOptions::default()
+    };
+    let err = trend(&[undated], &[Subject::Korean], &path, &opts).unwrap_err();
+    assert!(matches!(err, SuneungError::Plot(m) if m.contains("no exam has a converted score")));
+}
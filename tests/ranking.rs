@@ -0,0 +1,99 @@
+//! `rank_records` and `Record::cmp_by_university`, ties and failures included
+
+use std::cmp::Ordering;
+use suneung_calc::record_set::{rank_records, RecordSet};
+use suneung_calc::score::{Record, StandardScore, Subject, University};
+
+const UNIVERSITY: University = University::KONKUK;
+const YEAR: usize = 2024;
+
+fn named(name: &str, korean: f64) -> Record {
+    let mut record = Record::sample();
+    record.rename(name);
+    record
+        .set_standard_score_typed(Subject::Korean, StandardScore::new(korean).unwrap())
+        .unwrap();
+    record
+}
+
+// Two sciences are required in 2024, so this record cannot be calculated
+fn one_science(name: &str) -> Record {
+    let mut record = named(name, 140.0);
+    record.remove_subject(Subject::EarthScience);
+    record
+}
+
+fn names(records: &[Record]) -> Vec<&str> {
+    records.iter().map(|r| r.name()).collect()
+}
+
+#[test]
+fn ties_share_a_rank_and_leave_a_gap() {
+    let mut records = vec![
+        named("다", 120.0),
+        named("가", 131.0),
+        named("나", 131.0),
+        named("라", 135.0),
+    ];
+    let ranked = rank_records(&mut records, UNIVERSITY, YEAR);
+    let ranks: Vec<(usize, &str)> = ranked.iter().map(|(r, n, _)| (*r, n.as_str())).collect();
+    // The sort is stable, so tied records keep their order
+    assert_eq!(ranks, [(1, "라"), (2, "가"), (2, "나"), (4, "다")]);
+    assert_eq!(ranked[1].2, ranked[2].2);
+    assert!(ranked[0].2 > ranked[1].2 && ranked[2].2 > ranked[3].2);
+    assert_eq!(names(&records), ["라", "가", "나", "다"]);
+}
+
+#[test]
+fn every_record_tied() {
+    let mut records = vec![named("가", 131.0), named("나", 131.0), named("다", 131.0)];
+    let ranks: Vec<usize> = rank_records(&mut records, UNIVERSITY, YEAR)
+        .iter()
+        .map(|(r, ..)| *r)
+        .collect();
+    assert_eq!(ranks, [1, 1, 1]);
+}
+
+#[test]
+fn failures_sort_last_and_are_left_out() {
+    let mut records = vec![
+        one_science("실패1"),
+        named("가", 131.0),
+        one_science("실패2"),
+        named("나", 125.0),
+    ];
+    let ranked = rank_records(&mut records, UNIVERSITY, YEAR);
+    let ranks: Vec<(usize, &str)> = ranked.iter().map(|(r, n, _)| (*r, n.as_str())).collect();
+    assert_eq!(ranks, [(1, "가"), (2, "나")]);
+    assert_eq!(names(&records), ["가", "나", "실패1", "실패2"]);
+}
+
+#[test]
+fn nothing_ranked_in_an_unsupported_year() {
+    let mut records = vec![named("가", 131.0), named("나", 125.0)];
+    assert!(rank_records(&mut records, UNIVERSITY, 1999).is_empty());
+    assert!(rank_records(&mut [], UNIVERSITY, YEAR).is_empty());
+}
+
+#[test]
+fn cmp_by_university_orders_best_first() {
+    let (high, low, failed) = (named("가", 135.0), named("나", 120.0), one_science("다"));
+    let cmp = |a: &Record, b: &Record| a.cmp_by_university(b, UNIVERSITY, YEAR);
+    assert_eq!(cmp(&high, &low), Ordering::Less);
+    assert_eq!(cmp(&low, &high), Ordering::Greater);
+    assert_eq!(cmp(&high, &high.clone()), Ordering::Equal);
+    assert_eq!(cmp(&low, &failed), Ordering::Less);
+    assert_eq!(cmp(&failed, &high), Ordering::Greater);
+    assert_eq!(cmp(&failed, &one_science("라")), Ordering::Equal);
+}
+
+#[test]
+fn record_set_sorts_like_rank_records() {
+    let mut set = RecordSet::from(vec![
+        one_science("실패"),
+        named("나", 120.0),
+        named("가", 135.0),
+    ]);
+    set.sort_by_university(UNIVERSITY, YEAR);
+    assert_eq!(names(set.records()), ["가", "나", "실패"]);
+}
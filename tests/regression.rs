@@ -0,0 +1,188 @@
+//! Pins the embedded weights and the scores calculated from them for every
+//! supported university and year, so a change to either is explicit.
+//!
+//! - `fixtures/embedded_weights.txt` was written by the first release and
+//!   must stay bit-identical (f64 `Debug` output round-trips exactly).
+//! - `fixtures/calc_outputs.txt` pins today's scores. They differ from
+//!   `fixtures/calc_outputs_first_release.txt` for English grades other than
+//!   the default 2등급 of every weight, because the first release read the
+//!   English table one grade off; changing a table or formula means
+//!   regenerating the file and reviewing the diff.
+
+use std::path::PathBuf;
+use suneung_calc::score::{
+    Grade, Percentile, Record, Score, StandardScore, Subject, University, UniversityWeight,
+};
+
+const YEARS: [usize; 4] = [2022, 2023, 2024, 2025];
+
+fn fixture(name: &str) -> Vec<String> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name);
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+fn pairs() -> Vec<(University, usize, UniversityWeight)> {
+    let mut pairs = vec![];
+    for year in YEARS {
+        for &university in University::all() {
+            if let Some(weight) = UniversityWeight::try_load(university, year) {
+                pairs.push((university, year, weight));
+            }
+        }
+    }
+    pairs
+}
+
+fn record(
+    name: &str,
+    korean: (f64, f64, u8),
+    math: (f64, f64, u8),
+    english: u8,
+    sciences: [(f64, f64, u8); 2],
+) -> Record {
+    let grade = |g: u8| Grade::try_from(g).unwrap();
+    let score = |(s, p, g): (f64, f64, u8)| {
+        Score::new_typed(
+            StandardScore::new(s).unwrap(),
+            Percentile::new(p).unwrap(),
+            grade(g),
+        )
+    };
+    let mut record = Record::new(name);
+    record.record_score(Subject::Korean, score(korean));
+    record.record_score(Subject::Math, score(math));
+    record.record_score(Subject::English, Score::grade_only(grade(english)));
+    record.record_score(Subject::Chemistry, score(sciences[0]));
+    record.record_score(Subject::EarthScience, score(sciences[1]));
+    record
+}
+
+// `Record::sample` with English grades 1 to 5, and a stronger record with
+// the default and a worse English grade
+fn records() -> Vec<Record> {
+    let mut records = vec![];
+    for english in 1..=5 {
+        records.push(record(
+            &format!("sample/english{}", english),
+            (131.0, 93.0, 2),
+            (128.0, 89.0, 2),
+            english,
+            [(64.0, 92.0, 2), (66.0, 94.0, 1)],
+        ));
+    }
+    for english in [2, 4] {
+        records.push(record(
+            &format!("top/english{}", english),
+            (140.0, 99.0, 1),
+            (145.0, 100.0, 1),
+            english,
+            [(70.0, 99.0, 1), (60.0, 80.0, 3)],
+        ));
+    }
+    records
+}
+
+fn calc_outputs() -> Vec<String> {
+    let records = records();
+    let mut lines = vec![];
+    for (university, year, _) in pairs() {
+        for record in &records {
+            let score = record.try_calc_with_university(university, year).unwrap();
+            lines.push(format!(
+                "{:?} {} {} {:?}",
+                university,
+                year,
+                record.name(),
+                score
+            ));
+        }
+    }
+    lines
+}
+
+fn assert_lines(actual: &[String], expected: &[String]) {
+    for (actual, expected) in actual.iter().zip(expected) {
+        assert_eq!(actual, expected);
+    }
+    assert_eq!(actual.len(), expected.len());
+}
+
+#[test]
+fn embedded_weights_match_the_first_release() {
+    let lines: Vec<String> = pairs()
+        .iter()
+        .map(|(university, year, w)| {
+            let table = w.english_table().points();
+            // The tables cover the first release's six grades and no more
+            assert_eq!(table.len(), 6, "{:?}", university);
+            format!(
+                "{:?} {} {:?} {:?} {:?} {:?} {} {} {:?}",
+                university,
+                year,
+                w.korean(),
+                w.math(),
+                w.english(),
+                w.science(),
+                w.science_required(),
+                w.english_required(),
+                &table[..6]
+            )
+        })
+        .collect();
+    assert_lines(&lines, &fixture("embedded_weights.txt"));
+}
+
+// FNV-1a, so the hash does not depend on any crate
+fn fnv1a(hash: &mut u64, bytes: &[u8]) {
+    for &b in bytes {
+        *hash ^= b as u64;
+        *hash = hash.wrapping_mul(0x100000001b3);
+    }
+}
+
+#[test]
+fn embedded_weights_hash() {
+    let mut hash = 0xcbf29ce484222325u64;
+    for (university, year, w) in pairs() {
+        fnv1a(&mut hash, university.name().as_bytes());
+        fnv1a(&mut hash, &(year as u64).to_le_bytes());
+        for x in [w.korean(), w.math(), w.english(), w.science()] {
+            fnv1a(&mut hash, &x.to_bits().to_le_bytes());
+        }
+        fnv1a(&mut hash, &(w.science_required() as u64).to_le_bytes());
+        fnv1a(&mut hash, &(w.english_required() as u64).to_le_bytes());
+        for x in w.english_table().points() {
+            fnv1a(&mut hash, &x.to_bits().to_le_bytes());
+        }
+    }
+    assert_eq!(format!("{:016x}", hash), "df0ef448222bfbde");
+}
+
+#[test]
+fn calc_outputs_are_pinned() {
+    assert_lines(&calc_outputs(), &fixture("calc_outputs.txt"));
+}
+
+#[test]
+fn default_english_grade_scores_match_the_first_release() {
+    let first_release = fixture("calc_outputs_first_release.txt");
+    let outputs = calc_outputs();
+    assert_eq!(outputs.len(), first_release.len());
+    let mut changed = 0;
+    for (now, then) in outputs.iter().zip(&first_release) {
+        if now.contains("english2 ") {
+            assert_eq!(now, then);
+        } else if now != then {
+            changed += 1;
+        }
+    }
+    // The English fix moved the scores of the other grades
+    assert!(changed > 0);
+}
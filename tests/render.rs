@@ -0,0 +1,54 @@
+#![cfg(feature = "table")]
+
+//! Snapshots of the comfy-table output for a two-student fixture
+
+mod common;
+
+use common::assert_snapshot;
+use suneung_calc::render::{render_comparison, render_record};
+use suneung_calc::score::{Record, Subject};
+
+fn students() -> Vec<Record> {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/render/students.json"
+    );
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn comparison_of_two_students() {
+    let records = students();
+    let results: Vec<_> = records.iter().map(|r| r.calc_all(2024).unwrap()).collect();
+    assert_snapshot(
+        "render_comparison.txt",
+        &render_comparison(&records, &results),
+    );
+}
+
+#[test]
+fn record_of_each_student() {
+    let records = students();
+    assert_snapshot("render_record_sample.txt", &render_record(&records[0]));
+    assert_snapshot("render_record_second.txt", &render_record(&records[1]));
+}
+
+#[test]
+fn universities_missing_for_a_student_show_ineligible() {
+    let records = students();
+    let mut results: Vec<_> = records.iter().map(|r| r.calc_all(2024).unwrap()).collect();
+    results[1].truncate(1);
+
+    let table = render_comparison(&records, &results);
+    // One row per university, each but the second student's best marked 불가
+    assert_eq!(table.matches("불가").count(), results[0].len() - 1);
+}
+
+#[test]
+fn record_without_a_subject_lists_the_rest() {
+    let mut record = students().remove(1);
+    record.remove_subject(Subject::EarthScience);
+    let table = render_record(&record);
+    assert!(table.contains("화학"));
+    assert!(!table.contains("지구과학"));
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "stats")]
+
+//! The HTML reports parsed back, rather than matched as text
+
+use scraper::{Html, Selector};
+use suneung_calc::report::{html, ReportOptions};
+use suneung_calc::score::{Record, Subject, University};
+
+fn select<'a>(document: &'a Html, selector: &str) -> Vec<scraper::ElementRef<'a>> {
+    document
+        .select(&Selector::parse(selector).unwrap())
+        .collect()
+}
+
+fn section_ids(document: &Html) -> Vec<String> {
+    select(document, "body > section")
+        .iter()
+        .map(|s| s.value().attr("id").unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn student_report_has_its_sections() {
+    let document = Html::parse_document(&html::student_report(
+        &Record::sample(),
+        2024,
+        &ReportOptions::default(),
+    ));
+    assert!(document.errors.is_empty(), "{:?}", document.errors);
+
+    assert_eq!(
+        section_ids(&document),
+        ["scores", "comparison", "english-sweep"]
+    );
+    let title = select(&document, "title")[0].text().collect::<String>();
+    assert_eq!(title, "샘플");
+    // Header row and one row per subject
+    assert_eq!(select(&document, "#scores tr").len(), 1 + 5);
+}
+
+#[test]
+fn comparison_chart_has_one_bar_per_university() {
+    let document = Html::parse_document(&html::student_report(
+        &Record::sample(),
+        2024,
+        &ReportOptions::default(),
+    ));
+
+    let bars = select(&document, "#comparison svg rect.bar");
+    let supported = University::supported(2024);
+    assert_eq!(bars.len(), supported.len());
+    let mut named: Vec<String> = bars
+        .iter()
+        .map(|bar| {
+            let title = bar.text().collect::<String>();
+            title.split(": ").next().unwrap().to_string()
+        })
+        .collect();
+    let mut expected: Vec<String> = supported.iter().map(|u| u.to_string()).collect();
+    named.sort();
+    expected.sort();
+    assert_eq!(named, expected);
+
+    // One data row per university under the chart
+    assert_eq!(
+        select(&document, "#comparison tbody tr").len(),
+        supported.len()
+    );
+}
+
+#[test]
+fn no_chart_when_no_university_can_be_scored() {
+    // Every university of 2024 takes two 탐구 subjects
+    let mut record = Record::sample();
+    record.remove_subject(Subject::EarthScience);
+    let document = Html::parse_document(&html::student_report(
+        &record,
+        2024,
+        &ReportOptions::default(),
+    ));
+
+    assert_eq!(select(&document, "#comparison").len(), 1);
+    assert!(select(&document, "#comparison svg").is_empty());
+}
+
+#[test]
+fn skipped_sections_are_left_out() {
+    let mut opts = ReportOptions::default();
+    opts.sections.comparison = false;
+    opts.sections.english_sweep = false;
+    let document = Html::parse_document(&html::student_report(&Record::sample(), 2024, &opts));
+
+    assert_eq!(section_ids(&document), ["scores"]);
+    assert!(select(&document, "svg").is_empty());
+}
+
+#[test]
+fn cohort_report_has_its_sections() {
+    let mut other = Record::sample();
+    other.rename("김철수");
+    let records = [Record::sample(), other];
+    let document = Html::parse_document(&html::cohort_report(
+        &records,
+        2024,
+        &ReportOptions::default(),
+    ));
+    assert!(document.errors.is_empty(), "{:?}", document.errors);
+
+    let ids = section_ids(&document);
+    assert_eq!(
+        ids[..5],
+        [
+            "matrix",
+            "statistics",
+            "correlation",
+            "grade-cuts",
+            "ranking"
+        ]
+    );
+    let tops: Vec<&String> = ids[5..].iter().collect();
+    assert_eq!(tops.len(), University::supported(2024).len());
+    assert!(tops.iter().all(|id| id.starts_with("top-")));
+    let students: Vec<String> = select(&document, "#matrix tbody tr td:first-child")
+        .iter()
+        .map(|td| td.text().collect())
+        .collect();
+    assert!(students.contains(&"샘플".to_string()));
+    assert!(students.contains(&"김철수".to_string()));
+}
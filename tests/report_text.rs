@@ -0,0 +1,126 @@
+//! `Record::parse_report_text` on report text shaped like real pastes
+
+use suneung_calc::convert::{KoreanTrack, MathTrack, Track};
+use suneung_calc::error::SuneungError;
+use suneung_calc::score::{Grade, Record, Subject};
+
+fn fixture(name: &str) -> String {
+    let path = format!(
+        "{}/tests/fixtures/report_text/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    );
+    std::fs::read_to_string(path).unwrap()
+}
+
+fn parse(name: &str) -> Record {
+    Record::parse_report_text("학생", &fixture(name)).unwrap()
+}
+
+fn grade(g: u8) -> Grade {
+    Grade::try_from(g).unwrap()
+}
+
+// The scores of `Record::sample`, which every complete fixture holds
+fn assert_sample_scores(record: &Record) {
+    let sample = Record::sample();
+    for subject in Subject::all() {
+        let (parsed, expected) = (record.try_score(*subject), sample.try_score(*subject));
+        let parsed = parsed.unwrap_or_else(|| panic!("{:?} missing", subject));
+        let expected = expected.unwrap();
+        assert_eq!(
+            parsed.standard_score(),
+            expected.standard_score(),
+            "{:?}",
+            subject
+        );
+        assert_eq!(parsed.percentile(), expected.percentile(), "{:?}", subject);
+        assert_eq!(parsed.rank(), expected.rank(), "{:?}", subject);
+    }
+}
+
+#[test]
+fn one_line_with_slashes() {
+    let record = parse("one_line.txt");
+    assert_eq!(record.len(), 5);
+    assert_sample_scores(&record);
+    assert_eq!(
+        record.try_score(Subject::Korean).unwrap().elective(),
+        Some(Track::Korean(KoreanTrack::LanguageMedia))
+    );
+    assert_eq!(
+        record.try_score(Subject::Math).unwrap().elective(),
+        Some(Track::Math(MathTrack::Calculus))
+    );
+}
+
+#[test]
+fn table_with_windows_line_endings() {
+    let text = fixture("table_crlf.txt");
+    assert!(text.contains("\r\n"), "fixture lost its CRLF line endings");
+    let record = Record::parse_report_text("학생", &text).unwrap();
+    assert_eq!(record.len(), 5);
+    assert_sample_scores(&record);
+    assert_eq!(
+        record.try_score(Subject::Korean).unwrap().elective(),
+        Some(Track::Korean(KoreanTrack::SpeechWriting))
+    );
+    assert_eq!(
+        record.try_score(Subject::Math).unwrap().elective(),
+        Some(Track::Math(MathTrack::Statistics))
+    );
+}
+
+#[test]
+fn abbreviated_names_and_units() {
+    let record = parse("abbreviated.txt");
+    assert_eq!(record.len(), 5);
+    assert_sample_scores(&record);
+    assert_eq!(
+        record.try_score(Subject::Korean).unwrap().elective(),
+        Some(Track::Korean(KoreanTrack::LanguageMedia))
+    );
+}
+
+#[test]
+fn full_width_digits_and_spaces() {
+    let record = parse("full_width.txt");
+    assert_eq!(record.len(), 4);
+    assert!(record.try_score(Subject::EarthScience).is_none());
+    assert_eq!(record.standard_score(Subject::Korean), 131.0);
+    assert_eq!(record.percentile(Subject::Math), 89.0);
+    assert_eq!(record.rank(Subject::English), grade(2));
+    assert_eq!(record.standard_score(Subject::Chemistry), 64.0);
+}
+
+#[test]
+fn every_bad_line_is_reported() {
+    let Err(SuneungError::ReportParse(errors)) =
+        Record::parse_report_text("학생", &fixture("errors.txt"))
+    else {
+        panic!("expected a report parse error");
+    };
+    let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+    assert_eq!(lines, [2, 3, 4, 5]);
+    assert!(
+        errors[0].reason.contains("got 2 numbers"),
+        "{}",
+        errors[0].reason
+    );
+    assert!(
+        errors[1].reason.contains("only a grade"),
+        "{}",
+        errors[1].reason
+    );
+    assert!(
+        errors[2].reason.contains("unknown subject"),
+        "{}",
+        errors[2].reason
+    );
+    assert!(
+        errors[3].reason.contains("grade must be 1-9"),
+        "{}",
+        errors[3].reason
+    );
+    assert_eq!(errors[2].text, "물리학Ⅰ 60 80 3");
+}
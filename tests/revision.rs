@@ -0,0 +1,119 @@
+#![cfg(feature = "csv")]
+
+//! `history.csv` written and read back, in the current layout and in the
+//! one without source and elective columns
+
+use suneung_calc::convert::{MathTrack, Track};
+use suneung_calc::error::SuneungError;
+use suneung_calc::exam::{ExamId, ExamKind};
+use suneung_calc::score::{Grade, Percentile, Record, Score, StandardScore, Subject};
+
+fn score(standard: f64, percentile: f64, grade: u8) -> Score {
+    Score::new_typed(
+        StandardScore::new(standard).unwrap(),
+        Percentile::new(percentile).unwrap(),
+        Grade::try_from(grade).unwrap(),
+    )
+}
+
+// Subject, before and after of every revision; the times are only kept to
+// the millisecond
+fn changes(record: &Record) -> Vec<(Subject, Option<Score>, Option<Score>)> {
+    record
+        .history()
+        .iter()
+        .map(|r| (r.subject, r.before, r.after))
+        .collect()
+}
+
+#[test]
+fn sources_and_electives_survive_the_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut record = Record::sample();
+    record.enable_history();
+    let mock = ExamId::new(ExamKind::Mock9, 2024);
+    record.record_score(
+        Subject::Math,
+        score(131.0, 95.0, 1)
+            .with_source(mock)
+            .with_elective(Track::Math(MathTrack::Calculus)),
+    );
+    record
+        .set_elective(Track::Math(MathTrack::Geometry))
+        .unwrap();
+    record.remove_subject(Subject::Chemistry);
+    record.write_history_in(dir.path()).unwrap();
+
+    let mut loaded = Record::sample();
+    loaded.load_history_in(dir.path()).unwrap();
+    assert_eq!(changes(&loaded), changes(&record));
+    let last_math = loaded.history()[1].after.unwrap();
+    assert_eq!(last_math.source(), Some(mock));
+    assert_eq!(last_math.elective(), Some(Track::Math(MathTrack::Geometry)));
+
+    // Undoing the loaded revisions restores the sourced score
+    loaded = record.clone();
+    loaded.load_history_in(dir.path()).unwrap();
+    loaded.undo().unwrap();
+    loaded.undo().unwrap();
+    assert_eq!(
+        loaded.try_score(Subject::Math).unwrap().elective(),
+        Some(Track::Math(MathTrack::Calculus))
+    );
+}
+
+#[test]
+fn files_without_source_and_elective_still_load() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("샘플")).unwrap();
+    std::fs::write(
+        dir.path().join("샘플").join("history.csv"),
+        "at_unix_ms,subject,\
+         before_standard_score,before_percentile,before_grade,before_estimated,\
+         after_standard_score,after_percentile,after_grade,after_estimated\n\
+         1700000000000,Math,128,89,2,false,131,95,1,false\n\
+         1700000000001,Chemistry,64,92,2,false,,,,\n",
+    )
+    .unwrap();
+
+    let mut record = Record::sample();
+    record.load_history_in(dir.path()).unwrap();
+    assert_eq!(
+        changes(&record),
+        [
+            (
+                Subject::Math,
+                Some(score(128.0, 89.0, 2)),
+                Some(score(131.0, 95.0, 1))
+            ),
+            (Subject::Chemistry, Some(score(64.0, 92.0, 2)), None),
+        ]
+    );
+}
+
+#[test]
+fn unknown_headers_and_mismatched_electives_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("샘플").join("history.csv");
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let load = |text: &str| {
+        std::fs::write(&path, text).unwrap();
+        Record::sample().load_history_in(dir.path())
+    };
+
+    let err = load("time,subject\n1700000000000,Math\n").unwrap_err();
+    assert!(matches!(err, SuneungError::InvalidInput(m) if m.contains("unknown header")));
+
+    let mut record = Record::sample();
+    record.enable_history();
+    record.record_score(
+        Subject::Math,
+        score(131.0, 95.0, 1).with_elective(Track::Math(MathTrack::Calculus)),
+    );
+    record.write_history_in(dir.path()).unwrap();
+    let text = std::fs::read_to_string(&path)
+        .unwrap()
+        .replace(",Math,", ",Korean,");
+    let err = load(&text).unwrap_err();
+    assert!(matches!(err, SuneungError::InvalidInput(m) if m.contains("line 2")));
+}
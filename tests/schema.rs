@@ -0,0 +1,64 @@
+#![cfg(feature = "schema")]
+
+use jsonschema::JSONSchema;
+use serde_json::{json, Value};
+use suneung_calc::api::CalcRequest;
+use suneung_calc::schema::{
+    all_schemas, calc_breakdown_schema, calc_request_schema, dump_schemas, record_schema,
+};
+use suneung_calc::score::{Record, University};
+
+fn compile(schema: &Value) -> JSONSchema {
+    JSONSchema::compile(schema).expect("the emitted schema is valid JSON Schema")
+}
+
+#[test]
+fn sample_record_validates() {
+    let schema = compile(&record_schema());
+    let record = serde_json::to_value(Record::sample()).unwrap();
+    assert!(schema.is_valid(&record), "{record}");
+}
+
+#[test]
+fn string_score_is_rejected() {
+    let schema = compile(&record_schema());
+    let mut record = serde_json::to_value(Record::sample()).unwrap();
+    record["scores"]["Korean"]["standard_score"] = json!("131");
+    assert!(!schema.is_valid(&record));
+}
+
+#[test]
+fn missing_name_is_rejected() {
+    let schema = compile(&record_schema());
+    let mut record = serde_json::to_value(Record::sample()).unwrap();
+    record.as_object_mut().unwrap().remove("name");
+    assert!(!schema.is_valid(&record));
+}
+
+#[test]
+fn calc_request_and_breakdown_validate() {
+    let request = CalcRequest {
+        record: Record::sample(),
+        university: University::KONKUK,
+        year: 2024,
+    };
+    let breakdown = request
+        .record
+        .calc_breakdown(request.university, request.year)
+        .unwrap();
+
+    assert!(compile(&calc_request_schema()).is_valid(&serde_json::to_value(&request).unwrap()));
+    assert!(compile(&calc_breakdown_schema()).is_valid(&serde_json::to_value(&breakdown).unwrap()));
+}
+
+#[test]
+fn dump_writes_every_schema() {
+    let dir = tempfile::tempdir().unwrap();
+    dump_schemas(dir.path()).unwrap();
+    for (name, schema) in all_schemas() {
+        let path = dir.path().join(name);
+        let written: Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written, schema, "{}", path.display());
+    }
+}
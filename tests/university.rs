@@ -0,0 +1,57 @@
+//! `University::all()` against the variants of the enum
+
+use std::collections::HashSet;
+use suneung_calc::score::University;
+
+// One arm per variant and no wildcard, so a new variant fails to compile
+// here until it is counted
+fn arm(university: University) -> usize {
+    match university {
+        University::KYUNGHEE => 0,
+        University::DONGGUK => 1,
+        University::SEOULSCITECH => 2,
+        University::KWANGWOON => 3,
+        University::INHA => 4,
+        University::ERICA => 5,
+        University::SEJONG => 6,
+        University::KOOKMIN => 7,
+        University::AJU => 8,
+        University::SOONGSIL => 9,
+        University::KONKUK => 10,
+        University::CATHOLIC => 11,
+        University::CHUNGANG => 12,
+        University::SEOUL => 13,
+        University::SOGANG => 14,
+    }
+}
+const ARMS: usize = 15;
+
+#[test]
+fn all_has_every_variant_once() {
+    assert_eq!(University::all().len(), ARMS);
+    let arms: HashSet<usize> = University::all().iter().map(|u| arm(*u)).collect();
+    assert_eq!(arms.len(), ARMS);
+    assert_eq!(University::iter().count(), ARMS);
+}
+
+#[test]
+fn names_are_distinct_and_parse_back() {
+    let names: HashSet<&str> = University::all().iter().map(|u| u.name()).collect();
+    assert_eq!(names.len(), ARMS);
+    let names_en: HashSet<&str> = University::all().iter().map(|u| u.name_en()).collect();
+    assert_eq!(names_en.len(), ARMS);
+    for university in University::all() {
+        assert_eq!(
+            university.name().parse::<University>().unwrap(),
+            *university
+        );
+        for alias in university.aliases() {
+            assert_eq!(
+                alias.parse::<University>().unwrap(),
+                *university,
+                "{}",
+                alias
+            );
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//! The browser entry points, run in a JS engine by
+//! `wasm-pack test --node -- --no-default-features --features wasm`
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use suneung_calc::score::{Record, University};
+use suneung_calc::wasm::{calc, supported_universities};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn calculates_one_record() {
+    let record = Record::sample();
+    let json = serde_json::to_string(&record).unwrap();
+    let result: serde_json::Value =
+        serde_json::from_str(&calc(&json, "KONKUK", 2024).unwrap()).unwrap();
+
+    assert_eq!(result["university"], "KONKUK");
+    assert_eq!(result["year"], 2024);
+    let expected = record.calc_with_university(University::KONKUK, 2024);
+    assert_eq!(result["score"].as_f64().unwrap(), expected);
+    assert_eq!(result["breakdown"]["total"].as_f64().unwrap(), expected);
+}
+
+#[wasm_bindgen_test]
+fn rejects_unknown_universities_and_years() {
+    let json = serde_json::to_string(&Record::sample()).unwrap();
+    assert!(calc(&json, "NOWHERE", 2024).is_err());
+    assert!(calc(&json, "KONKUK", 1999).is_err());
+    assert!(calc("{", "KONKUK", 2024).is_err());
+}
+
+#[wasm_bindgen_test]
+fn lists_the_embedded_universities() {
+    let entries: Vec<serde_json::Value> =
+        serde_json::from_str(&supported_universities(2024)).unwrap();
+    assert_eq!(entries.len(), University::supported(2024).len());
+    assert!(entries.iter().any(|e| e["id"] == "KONKUK"));
+    assert_eq!(supported_universities(1999), "[]");
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "watch")]
+
+//! Reloading a data root with `WatchedCohort`
+
+use suneung_calc::score::Record;
+use suneung_calc::watch::WatchedCohort;
+
+#[test]
+fn directories_with_unsafe_names_are_reported_not_read() {
+    let root = tempfile::tempdir().unwrap();
+    Record::sample().write_parquet_in(root.path()).unwrap();
+    // A record copied under a name `check_name` refuses
+    let odd = root.path().join("줄\n바꿈");
+    std::fs::create_dir_all(&odd).unwrap();
+    std::fs::copy(
+        root.path().join("샘플/record.parquet"),
+        odd.join("record.parquet"),
+    )
+    .unwrap();
+
+    let mut cohort = WatchedCohort::new(root.path(), 2024);
+    let changes = cohort.refresh().unwrap();
+    assert_eq!(changes.added, ["샘플"]);
+    assert_eq!(changes.failed.len(), 1);
+    assert_eq!(changes.failed[0].0, "줄\n바꿈");
+    assert!(
+        changes.failed[0].1.contains("control character"),
+        "{}",
+        changes.failed[0].1
+    );
+}
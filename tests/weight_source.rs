@@ -0,0 +1,72 @@
+//! Weights supplied through `WeightSource` instead of the embedded data
+
+use suneung_calc::builder::UniversityWeightBuilder;
+use suneung_calc::error::SuneungError;
+use suneung_calc::score::{Record, University, UniversityWeight};
+use suneung_calc::weight_source::{EmbeddedWeights, MapWeights};
+
+const UNIVERSITY: University = University::KONKUK;
+const YEAR: usize = 2024;
+
+fn halved_math() -> (UniversityWeight, UniversityWeight) {
+    let embedded = UniversityWeight::try_load(UNIVERSITY, YEAR).unwrap();
+    let halved = UniversityWeightBuilder::from_weight(&embedded)
+        .math(embedded.math() / 2.0)
+        .build()
+        .unwrap();
+    (embedded, halved)
+}
+
+#[test]
+fn halving_the_math_weight() {
+    let record = Record::sample();
+    let (embedded, halved) = halved_math();
+    let mut source = MapWeights::new();
+    source.insert(UNIVERSITY, YEAR, halved.clone());
+
+    let before = record.calc_breakdown(UNIVERSITY, YEAR).unwrap();
+    let after = record
+        .calc_breakdown_with(&source, UNIVERSITY, YEAR)
+        .unwrap();
+
+    // Weights are shares of 국어 + 수학 + 탐구, so halving 수학 shrinks its
+    // contribution by this factor and grows the others by the same sum
+    let sum = |w: &UniversityWeight| w.korean() + w.math() + w.science();
+    let factor = sum(&embedded) / (2.0 * sum(&halved));
+    assert!((after.math - before.math * factor).abs() < 1e-9);
+    assert!(after.korean > before.korean);
+    assert!(after.science > before.science);
+    assert_eq!(after.english, before.english);
+
+    let total = record.calc_with_source(&source, UNIVERSITY, YEAR).unwrap();
+    assert_eq!(total, after.total);
+    assert_eq!(total, record.calc_with_weight(&halved).unwrap());
+    assert_ne!(total, before.total);
+}
+
+#[test]
+fn embedded_weights_match_the_default() {
+    let record = Record::sample();
+    for university in University::supported(YEAR) {
+        assert_eq!(
+            record
+                .calc_with_source(&EmbeddedWeights, university, YEAR)
+                .unwrap(),
+            record.try_calc_with_university(university, YEAR).unwrap()
+        );
+    }
+}
+
+#[test]
+fn map_weights_only_have_what_was_inserted() {
+    let (_, halved) = halved_math();
+    let mut source = MapWeights::new();
+    source.insert(UNIVERSITY, YEAR, halved);
+    let record = Record::sample();
+    for (university, year) in [(University::SOGANG, YEAR), (UNIVERSITY, 2025)] {
+        assert!(matches!(
+            record.calc_with_source(&source, university, year),
+            Err(SuneungError::UnsupportedYear { .. })
+        ));
+    }
+}